@@ -35,3 +35,9 @@ impl crate::battery::Battery {
         99
     }
 }
+
+impl Default for Battery {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -31,13 +31,15 @@
 //! - `HAS_AI_BUTTON`: Indicates if the board has an AI (Artificial Intelligence) button.
 //! - `AI_BUTTON`: The pin for the AI button.
 //! - `AI_BUTTON_PULL_UP`: Indicates if the AI button has a pull-up resistor.
-//! - `CONSOLE_TX`, `CONSOLE_RX`: Pins for the console serial TX and RX.
+//! - `HAS_KEYBOARD`: Indicates if the board has the Q10 keyboard.
+//! - `KEYBOARD_COLS`, `KEYBOARD_ROWS`: Pins for the columns and rows of the keyboard matrix.
+//! - `BusPins`, `bus_pins`: The typed pins of the console serial and the display
+//!   SPI bus, taken from the pins handed out by `gpio::take`.
+//! - `DispSpi`, `DISP_SPI`, `DISP_SPI_MODE`: The SPI bus, as a type and as a value, and mode used for the display.
+//! - `PARK_PINS`: Pins set to analog mode while in low power modes.
 //!
 
-#[cfg(not(any(
-    feature = "board-hactar12",
-    feature = "board-sim"
-)))]
+#[cfg(not(any(feature = "board-hactar12", feature = "board-sim")))]
 compile_error!(
     "Must specify a board as compile feature. Try --features=hal/board-sim,bsp/std,app/std"
 );
@@ -47,15 +49,17 @@ compile_error!(
     "Must specify only a single board as a feature. Try --no-default-features --features=board-sim"
 );
 
-
 #[cfg(feature = "board-hactar12")]
 pub mod info {
     use hal::cpu;
     use hal::gpio;
+    use hal::spi;
 
     pub const CLOCK_HSE_FREQ: u32 = 24_000_000; // set to 0 for simulation
 
     pub const NUM_DEBUG_PINS: usize = 1;
+    // PA11 is also USB DM, so the pin is left to the USB console once it is
+    // started
     pub const DEBUG1_PIN: gpio::Pin = gpio::Pin(cpu::GPIOA, 11);
 
     pub const LEDS_INVERTED: bool = true;
@@ -71,31 +75,71 @@ pub mod info {
     pub const AI_BUTTON: gpio::Pin = gpio::Pin(cpu::GPIOC, 1);
     pub const AI_BUTTON_PULL_UP: bool = true;
 
-    pub const CONSOLE_TX: gpio::Pin = gpio::Pin(cpu::GPIOA, 9);
-    pub const CONSOLE_RX: gpio::Pin = gpio::Pin(cpu::GPIOA, 10);
+    // The matrix pins are not yet checked against the hactar12 schematic, so
+    // the keyboard stays off rather than driving unknown pins. Set this once
+    // the pins below are confirmed.
+    pub const HAS_KEYBOARD: bool = false;
+    pub const KEYBOARD_COLS: [gpio::Pin; 5] = [
+        gpio::Pin(cpu::GPIOB, 12),
+        gpio::Pin(cpu::GPIOB, 13),
+        gpio::Pin(cpu::GPIOB, 14),
+        gpio::Pin(cpu::GPIOB, 15),
+        gpio::Pin(cpu::GPIOC, 6),
+    ];
+    pub const KEYBOARD_ROWS: [gpio::Pin; 7] = [
+        gpio::Pin(cpu::GPIOB, 0),
+        gpio::Pin(cpu::GPIOB, 1),
+        gpio::Pin(cpu::GPIOB, 2),
+        gpio::Pin(cpu::GPIOB, 5),
+        gpio::Pin(cpu::GPIOB, 6),
+        gpio::Pin(cpu::GPIOB, 7),
+        gpio::Pin(cpu::GPIOB, 10),
+    ];
+
+    /// The typed pins of the console serial and the display SPI bus.
+    pub struct BusPins {
+        pub console_tx: gpio::PA9,
+        pub console_rx: gpio::PA10,
+        pub disp_scl: gpio::PA5, // Uses SPI1 CLK
+        pub disp_sda: gpio::PA7, // Uses SPI1 MOSI
+    }
+
+    pub fn bus_pins(pins: gpio::Pins) -> BusPins {
+        BusPins {
+            console_tx: pins.pa9,
+            console_rx: pins.pa10,
+            disp_scl: pins.pa5,
+            disp_sda: pins.pa7,
+        }
+    }
 
     pub const HAS_DISP: bool = true;
     pub const DISP_CS: gpio::Pin = gpio::Pin(cpu::GPIOB, 8); // Chip Select - low to select
     pub const DISP_DC: gpio::Pin = gpio::Pin(cpu::GPIOB, 9); // Data/Command - 0 command, 1 data
     pub const DISP_NRST: gpio::Pin = gpio::Pin(cpu::GPIOC, 13); // Reset - low to reset
     pub const DISP_BL: gpio::Pin = gpio::Pin(cpu::GPIOC, 14); // Backlight - 1 = on
-    pub const DISP_SCL: gpio::Pin = gpio::Pin(cpu::GPIOA, 5); // Uses SPI1 CLK
-    pub const DISP_SDA: gpio::Pin = gpio::Pin(cpu::GPIOA, 7); // Uses SPI1 MOSI
+    pub type DispSpi = spi::Spi1;
+    pub const DISP_SPI: spi::Bus = <DispSpi as spi::Instance>::BUS;
+    pub const DISP_SPI_MODE: spi::Mode = spi::Mode::Mode0;
     pub const DISP_NUM_ROWS: usize = 320;
     pub const DISP_NUM_COLS: usize = 240;
     pub const DISP_SPI_FREQ: u32 = 10_000_000; // must be slower if reading
-}
-
 
+    // outputs set to analog while in low power modes
+    pub const PARK_PINS: [gpio::Pin; 4] = [LED_RED_PIN, LED_GREEN_PIN, LED_BLUE_PIN, DEBUG1_PIN];
+}
 
 #[cfg(feature = "board-sim")]
 pub mod info {
     use hal::cpu;
     use hal::gpio;
+    use hal::spi;
 
     pub const CLOCK_HSE_FREQ: u32 = 0_000_000; // set to 0 for simulation
 
     pub const NUM_DEBUG_PINS: usize = 0;
+    // PA11 is also USB DM, so the pin is left to the USB console once it is
+    // started
     pub const DEBUG1_PIN: gpio::Pin = gpio::Pin(cpu::GPIOA, 11);
 
     pub const LEDS_INVERTED: bool = false;
@@ -111,19 +155,55 @@ pub mod info {
     pub const AI_BUTTON: gpio::Pin = gpio::Pin(cpu::GPIOC, 1);
     pub const AI_BUTTON_PULL_UP: bool = false;
 
-    pub const CONSOLE_TX: gpio::Pin = gpio::Pin(cpu::GPIOA, 9);
-    pub const CONSOLE_RX: gpio::Pin = gpio::Pin(cpu::GPIOA, 10);
+    pub const HAS_KEYBOARD: bool = true;
+    pub const KEYBOARD_COLS: [gpio::Pin; 5] = [
+        gpio::Pin(cpu::GPIOB, 12),
+        gpio::Pin(cpu::GPIOB, 13),
+        gpio::Pin(cpu::GPIOB, 14),
+        gpio::Pin(cpu::GPIOB, 15),
+        gpio::Pin(cpu::GPIOC, 6),
+    ];
+    pub const KEYBOARD_ROWS: [gpio::Pin; 7] = [
+        gpio::Pin(cpu::GPIOB, 0),
+        gpio::Pin(cpu::GPIOB, 1),
+        gpio::Pin(cpu::GPIOB, 2),
+        gpio::Pin(cpu::GPIOB, 5),
+        gpio::Pin(cpu::GPIOB, 6),
+        gpio::Pin(cpu::GPIOB, 7),
+        gpio::Pin(cpu::GPIOB, 10),
+    ];
+
+    /// The typed pins of the console serial and the display SPI bus.
+    pub struct BusPins {
+        pub console_tx: gpio::PA9,
+        pub console_rx: gpio::PA10,
+        pub disp_scl: gpio::PA5, // Uses SPI1 CLK
+        pub disp_sda: gpio::PA7, // Uses SPI1 MOSI
+    }
+
+    pub fn bus_pins(pins: gpio::Pins) -> BusPins {
+        BusPins {
+            console_tx: pins.pa9,
+            console_rx: pins.pa10,
+            disp_scl: pins.pa5,
+            disp_sda: pins.pa7,
+        }
+    }
 
     pub const HAS_DISP: bool = false;
     pub const DISP_CS: gpio::Pin = gpio::Pin(cpu::GPIOB, 8); // Chip Select - low to select
     pub const DISP_DC: gpio::Pin = gpio::Pin(cpu::GPIOB, 9); // Data/Command - 0 command, 1 data
     pub const DISP_NRST: gpio::Pin = gpio::Pin(cpu::GPIOC, 13); // Reset - low to reset
     pub const DISP_BL: gpio::Pin = gpio::Pin(cpu::GPIOC, 14); // Backlight - 1 = on
-    pub const DISP_SCL: gpio::Pin = gpio::Pin(cpu::GPIOA, 5); // Uses SPI1 CLK
-    pub const DISP_SDA: gpio::Pin = gpio::Pin(cpu::GPIOA, 7); // Uses SPI1 MOSI
+    pub type DispSpi = spi::Spi1;
+    pub const DISP_SPI: spi::Bus = <DispSpi as spi::Instance>::BUS;
+    pub const DISP_SPI_MODE: spi::Mode = spi::Mode::Mode0;
     pub const DISP_NUM_ROWS: usize = 320;
     pub const DISP_NUM_COLS: usize = 240;
     pub const DISP_SPI_FREQ: u32 = 10_000_000; // must be slower if reading
+
+    // outputs set to analog while in low power modes
+    pub const PARK_PINS: [gpio::Pin; 4] = [LED_RED_PIN, LED_GREEN_PIN, LED_BLUE_PIN, DEBUG1_PIN];
 }
 
 #[cfg(test)]
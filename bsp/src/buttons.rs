@@ -7,10 +7,59 @@
 //! - `read_ptt`: Reads the state of the PTT (Push-To-Talk) button.
 //! - `read_ai`: Reads the state of the AI button.
 //!
+//! The buttons also raise an EXTI interrupt on every edge. The interrupt only
+//! counts the edges so that a press that is shorter than the polling interval
+//! is still reported by the next read.
+//!
 
 extern crate hal;
 
 use crate::board;
+use hal::cpu::{self, Global};
+use hal::gpio;
+
+static PTT_EDGES: Global<u32> = Global::new(0);
+static AI_EDGES: Global<u32> = Global::new(0);
+
+fn on_ptt_edge() {
+    unsafe {
+        let n = PTT_EDGES.get();
+        PTT_EDGES.set(n.wrapping_add(1));
+    }
+}
+
+fn on_ai_edge() {
+    unsafe {
+        let n = AI_EDGES.get();
+        AI_EDGES.set(n.wrapping_add(1));
+    }
+}
+
+/// Returns the number of edges counted since the last call and resets the
+/// count, with interrupts masked so an edge counted in between is not lost.
+fn take_edges(edges: &Global<u32>) -> u32 {
+    cpu::critical_section(|| unsafe {
+        let n = edges.get();
+        edges.set(0);
+        n
+    })
+}
+
+/// Works out the state to report from the current `state` of the button, the
+/// previously reported state, and the number of edges seen in between. If the
+/// button went through a full press and release (or release and press) between
+/// two reads, the missed state is reported first. Contact bounce is not
+/// filtered, so a bouncing press can be reported as a short press first.
+fn track_edges(state: bool, prev: &mut bool, edges: u32) -> (bool, bool) {
+    let reported = if state == *prev && edges >= 2 {
+        !*prev
+    } else {
+        state
+    };
+    let changed = reported != *prev;
+    *prev = reported;
+    (reported, changed)
+}
 
 pub struct Buttons {
     prev_ptt: bool,
@@ -36,6 +85,8 @@ impl Buttons {
             } else {
                 board::info::PTT_BUTTON.pulldown();
             }
+
+            board::info::PTT_BUTTON.enable_interrupt(gpio::Edge::Both, on_ptt_edge);
         }
 
         if board::info::HAS_AI_BUTTON {
@@ -46,6 +97,8 @@ impl Buttons {
             } else {
                 board::info::AI_BUTTON.pulldown();
             }
+
+            board::info::AI_BUTTON.enable_interrupt(gpio::Edge::Both, on_ai_edge);
         }
     }
 
@@ -85,9 +138,8 @@ impl Buttons {
     pub fn read_ptt(&mut self) -> (bool, bool) {
         if board::info::HAS_PTT_BUTTON {
             let state = board::info::PTT_BUTTON.read() != board::info::PTT_BUTTON_PULL_UP;
-            let changed = state != self.prev_ptt;
-            self.prev_ptt = state;
-            return (state, changed);
+            let edges = take_edges(&PTT_EDGES);
+            return track_edges(state, &mut self.prev_ptt, edges);
         }
         (false, false)
     }
@@ -125,10 +177,39 @@ impl Buttons {
     pub fn read_ai(&mut self) -> (bool, bool) {
         if board::info::HAS_AI_BUTTON {
             let state = board::info::AI_BUTTON.read() != board::info::AI_BUTTON_PULL_UP;
-            let changed = state != self.prev_ai;
-            self.prev_ai = state;
-            return (state, changed);
+            let edges = take_edges(&AI_EDGES);
+            return track_edges(state, &mut self.prev_ai, edges);
         }
         (false, false)
     }
 }
+
+impl Default for Buttons {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_track_edges_short_press() {
+        let mut prev = false;
+
+        // press and release both happened between two reads
+        assert_eq!(track_edges(false, &mut prev, 2), (true, true));
+        assert_eq!(track_edges(false, &mut prev, 0), (false, true));
+        assert_eq!(track_edges(false, &mut prev, 0), (false, false));
+    }
+
+    #[test]
+    fn test_track_edges_normal_press() {
+        let mut prev = false;
+
+        assert_eq!(track_edges(true, &mut prev, 1), (true, true));
+        assert_eq!(track_edges(true, &mut prev, 0), (true, false));
+        assert_eq!(track_edges(false, &mut prev, 1), (false, true));
+    }
+}
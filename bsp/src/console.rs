@@ -2,16 +2,25 @@
 //!
 //! This module provides functionality for printing debug information to the console.
 //!
+//! The console goes out USART1, which reaches the host through the USB-serial
+//! bridge, or out the USB CDC-ACM port of the CPU. When the USB backend is
+//! selected, output falls back to the UART until a program on the host opens
+//! the USB port.
+//!
+//! ## Enums
+//!
+//! - `Backend`: Selects the UART or USB console.
+//!
 //! ## Traits
 //!
 //! - `Print`: A trait for printing data to the console.
-//! Implemented for various data types such as `[u8]`, and `u64`
+//!   Implemented for various data types such as `[u8]`, and `u64`
 //!
 //! ## Example
 //!
 //! ```rust
 //!  use bsp::BSP;
-//!  use bsp::console::Print;
+//!  use bsp::console::{Backend, Print};
 //!  let mut bsp = BSP::new();
 //!  bsp.init();
 //!
@@ -20,15 +29,29 @@
 //!
 //!  let number: u64 = 42;
 //!  number.print_console();
+//!
+//!  // switch to the faster USB port
+//!  bsp.console.set_backend(Backend::Usb);
 //! ```
 
 extern crate hal;
 
+use hal::cpu;
+use hal::cpu::Global;
 use hal::uart;
+use hal::usb;
 
 #[cfg(feature = "std")]
 extern crate std;
 
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Backend {
+    Uart,
+    Usb,
+}
+
+static BACKEND: Global<Backend> = Global::new(Backend::Uart);
+
 pub struct Console {}
 
 impl Console {
@@ -39,6 +62,43 @@ impl Console {
 
     #[inline(never)]
     pub fn init(&self) {}
+
+    /// Selects where the console output goes. Selecting `Backend::Usb`
+    /// starts the USB device.
+    #[inline(never)]
+    pub fn set_backend(&self, backend: Backend) {
+        if backend == Backend::Usb && self.backend() != Backend::Usb {
+            usb::init();
+        }
+        unsafe { BACKEND.set(backend) };
+    }
+
+    pub fn backend(&self) -> Backend {
+        unsafe { BACKEND.get() }
+    }
+}
+
+impl Default for Console {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Sends `data` to the USB port, waiting for room in the transmit buffer.
+/// Returns false if the port is not open, or if called from an exception
+/// handler, as the buffer is emptied by the USB interrupt which may not be
+/// able to preempt it, so the output goes to the UART instead.
+fn write_usb(mut data: &[u8]) -> bool {
+    let backend = unsafe { BACKEND.get() };
+    if backend != Backend::Usb || cpu::ipsr() != 0 || !usb::port_open() {
+        return false;
+    }
+
+    while !data.is_empty() && usb::port_open() {
+        let sent = usb::write(data);
+        data = &data[sent..];
+    }
+    true
 }
 
 pub trait Print {
@@ -57,6 +117,9 @@ impl Print for [u8] {
             }
             return;
         }
+        if write_usb(s) {
+            return;
+        }
         for c in s {
             uart::write1(*c);
         }
@@ -108,6 +171,34 @@ impl Print for u32 {
     }
 }
 
+/// Prints spaces to the console to pad the given value to the specified width.
+///
+/// # Arguments
+///
+/// * `v` - The value to be printed.
+/// * `pad` - The total width to pad the value to.
+pub fn print_pad(v: u32, pad: u32) {
+    let mut digits = 1;
+    let mut threshold = 10;
+
+    // Calculate the number of digits in the value
+    while v > threshold {
+        digits += 1;
+        threshold *= 10;
+    }
+
+    // Calculate the number of spaces needed for padding
+    if pad > digits {
+        let mut spaces = pad - digits;
+
+        // Print the padding to the console
+        while spaces > 0 {
+            spaces -= 1;
+            b" ".print_console();
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -138,32 +229,34 @@ mod tests {
         let number: u32 = 123;
         number.print_console();
     }
-}
 
-/// Prints spaces to the console to pad the given value to the specified width.
-///
-/// # Arguments
-///
-/// * `v` - The value to be printed.
-/// * `pad` - The total width to pad the value to.
-pub fn print_pad(v: u32, pad: u32) {
-    let mut digits = 1;
-    let mut threshold = 10;
+    #[test]
+    fn test_backend() {
+        let console = Console::new();
+        assert_eq!(console.backend(), Backend::Uart);
 
-    // Calculate the number of digits in the value
-    while v > threshold {
-        digits += 1;
-        threshold *= 10;
+        // output still reaches the sim console before the port is opened
+        console.set_backend(Backend::Usb);
+        assert_eq!(console.backend(), Backend::Usb);
+        b"OK".print_console();
+
+        console.set_backend(Backend::Uart);
     }
 
-    // Calculate the number of spaces needed for padding
-    if pad > digits {
-        let mut spaces = pad - digits;
+    #[test]
+    fn test_usb_from_handler() {
+        let _sim = hal::sim::Guard::new();
+        let console = Console::new();
+        console.set_backend(Backend::Usb);
+        // set configuration, then DTR from the host opening the port
+        usb::sim_reset();
+        usb::sim_setup(&[0x00, 0x09, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00]);
+        usb::sim_setup(&[0x21, 0x22, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00]);
+        assert!(write_usb(b"OK"));
 
-        // Print the padding to the console
-        while spaces > 0 {
-            spaces -= 1;
-            b" ".print_console();
-        }
+        // the USB interrupt cannot empty the buffer under a hard fault
+        hal::sim::set_ipsr(3);
+        assert!(!write_usb(b"OK"));
+        hal::sim::set_ipsr(0);
     }
 }
@@ -23,6 +23,7 @@
 extern crate hal;
 
 use crate::board;
+use hal::usb;
 
 pub struct Debug {}
 
@@ -34,15 +35,25 @@ impl Debug {
 
     #[inline(never)]
     pub fn init(&self) {
+        if usb::owns_pin(&board::info::DEBUG1_PIN) {
+            return;
+        }
         board::info::DEBUG1_PIN.output();
         board::info::DEBUG1_PIN.low();
     }
 }
 
+impl Default for Debug {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// Sets the state of a specified debug pin.
 ///
 /// This function sets the state of the debug pin identified by the given channel number.
 /// The pin can be set to either high or low based on the boolean value provided.
+/// Nothing is driven while the pin is taken by the USB console.
 ///
 /// # Arguments
 ///
@@ -71,7 +82,7 @@ impl Debug {
 pub fn set(channel: u8, state: bool) {
     assert!(channel < 1); // TODO - implement up to 4
 
-    if board::info::NUM_DEBUG_PINS >= 1 {
+    if board::info::NUM_DEBUG_PINS != 0 && !usb::owns_pin(&board::info::DEBUG1_PIN) {
         if state {
             board::info::DEBUG1_PIN.high();
         } else {
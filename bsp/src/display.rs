@@ -6,6 +6,8 @@
 //!
 //! - `size`: Returns the width and height of the display.
 //! - `draw_bitmap`: Draws a bitmap at a specified position.
+//! - `sleep`: Blanks the display and puts the controller to sleep.
+//! - `wake`: Wakes the controller and turns the display back on.
 //!
 //! ## Example
 //!
@@ -54,18 +56,11 @@ impl Display {
 
         board::info::DISP_NRST.output();
         board::info::DISP_NRST.low(); // put into reset
-        let now = hal::timer::current_time();
-        while hal::timer::current_time().sub(now).as_u64() < 50_000 {} // TODO needed ?
+        hal::delay::delay_ms(50); // TODO needed ?
         board::info::DISP_NRST.high(); // take out of reset
-        let now = hal::timer::current_time();
-        while hal::timer::current_time().sub(now).as_u64() < 120_000 {} // TODO needed ?
-
-        hal::spi::init1(
-            board::info::DISP_SPI_FREQ,
-            board::info::DISP_SCL,
-            board::info::DISP_SDA,
-        );
+        hal::delay::delay_ms(120); // TODO needed ?
 
+        // the SPI bus is set up by BSP::init, which owns its pins
         ili9341::setup();
     }
 
@@ -77,15 +72,48 @@ impl Display {
         )
     }
 
+    /// Turns off the backlight and puts the display controller in sleep mode.
+    /// The controller keeps the frame memory while asleep.
+    #[inline(never)]
+    pub fn sleep(&self) {
+        if !board::info::HAS_DISP {
+            return;
+        }
+        board::info::DISP_BL.low(); // backlight off
+
+        ili9341::command(ili9341::Command::DisplayOff, &[]);
+        ili9341::command(ili9341::Command::SleepIn, &[]);
+        hal::spi::flush(board::info::DISP_SPI);
+    }
+
+    /// Wakes the display controller from `sleep` and turns the backlight on.
+    #[inline(never)]
+    pub fn wake(&self) {
+        if !board::info::HAS_DISP {
+            return;
+        }
+        ili9341::command(ili9341::Command::SleepOut, &[]);
+        hal::delay::delay_ms(5);
+
+        ili9341::command(ili9341::Command::DisplayOn, &[]);
+
+        board::info::DISP_BL.high(); // backlight on
+    }
+
     /// Returns whether the display is ready to accept the next draw_bitmap command.
     pub fn ready(&self) -> bool {
-        true
+        if !board::info::HAS_DISP {
+            return true;
+        }
+        !hal::spi::busy(board::info::DISP_SPI)
     }
 
     /// Draws a bitmap at a specified position.
     ///
     /// This is an async call that starts a DMA transfer to the display controller and
     /// ready will return false until the transfer is complete.
+    /// The bitmap must not be changed until ready returns true.
+    /// If a previous transfer is still in progress, this waits for it to finish first.
     pub fn draw_bitmap(&self, bitmap: &[u16], x: usize, y: usize, width: usize, height: usize) {
         if !board::info::HAS_DISP {
             return;
@@ -93,6 +121,7 @@ impl Display {
 
         assert!(x + width <= crate::board::info::DISP_NUM_COLS);
         assert!(y + height <= crate::board::info::DISP_NUM_ROWS);
+        assert!(bitmap.len() >= width * height);
 
         let start_row: usize = (board::info::DISP_NUM_ROWS) - 1 - y - (height - 1);
         let start_row_low: u8 = (start_row & 0xFF) as u8;
@@ -119,8 +148,14 @@ impl Display {
             &[start_row_high, start_row_low, end_row_high, end_row_low],
         );
 
-        ili9341::command_wide(ili9341::Command::MemoryWrite, &bitmap);
-        ili9341::command(ili9341::Command::NoOp, &[]);
+        // the next command sent to the display ends the memory write
+        ili9341::command_dma(ili9341::Command::MemoryWrite, &bitmap[..width * height]);
+    }
+}
+
+impl Default for Display {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -131,6 +166,7 @@ mod ili9341 {
     pub enum Command {
         NoOp = 0x00,       // No operation
         SwReset = 0x01,    // Software reset
+        SleepIn = 0x10,    // Sleep in
         SleepOut = 0x11,   // Sleep out
         NormalMode = 0x13, // Normal mode
 
@@ -174,29 +210,59 @@ mod ili9341 {
         // Send a command to the display
         let c: [u8; 1] = [cmd as u8];
 
+        // wait for any DMA data to be sent before changing DC
+        hal::spi::flush(board::info::DISP_SPI);
+
         board::info::DISP_DC.low(); // command
-        hal::spi::write1(&c);
+        hal::spi::write(board::info::DISP_SPI, &c);
 
         board::info::DISP_DC.high(); // data
-        hal::spi::write1(paramters);
+        hal::spi::write(board::info::DISP_SPI, paramters);
     }
 
     pub fn command_wide(cmd: Command, paramters: &[u16]) {
         // Send a command to the display
         let c: [u8; 1] = [cmd as u8];
 
+        // wait for any DMA data to be sent before changing DC
+        hal::spi::flush(board::info::DISP_SPI);
+
+        board::info::DISP_DC.low(); // command
+        hal::spi::write(board::info::DISP_SPI, &c);
+
+        board::info::DISP_DC.high(); // data
+        hal::spi::write_wide(board::info::DISP_SPI, paramters);
+    }
+
+    /// Sends a command and starts sending the parameters with DMA.
+    /// Returns before the parameters have been sent.
+    pub fn command_dma(cmd: Command, paramters: &[u16]) {
+        if paramters.len() > 0xFFFF {
+            // too large for a single DMA transfer
+            command_wide(cmd, paramters);
+            return;
+        }
+
+        // Send a command to the display
+        let c: [u8; 1] = [cmd as u8];
+
+        // wait for any DMA data to be sent before changing DC
+        hal::spi::flush(board::info::DISP_SPI);
+
         board::info::DISP_DC.low(); // command
-        hal::spi::write1(&c);
+        hal::spi::write(board::info::DISP_SPI, &c);
 
         board::info::DISP_DC.high(); // data
-        hal::spi::write1_wide(paramters);
+        unsafe {
+            // the caller of draw_bitmap keeps the bitmap unchanged until ready
+            hal::spi::write_wide_dma(board::info::DISP_SPI, paramters);
+        }
     }
 
     pub fn setup() {
         //LCD_2IN4_Write_Command(0x01); //Software reset
         command(Command::SwReset, &[]);
-        let now = hal::timer::current_time();
-        while hal::timer::current_time().sub(now).as_u64() < 5_000 {}
+        hal::delay::delay_ms(5);
 
         command(Command::PowerCtrlA, &[0x39, 0x2C, 0x00, 0x34, 0x02]);
 
@@ -267,8 +333,7 @@ mod ili9341 {
         command(Command::NormalMode, &[]);
 
         command(Command::SleepOut, &[]);
-        let now = hal::timer::current_time();
-        while hal::timer::current_time().sub(now).as_u64() < 120_000 {}
+        hal::delay::delay_ms(120);
 
         command(Command::DisplayOn, &[]);
 
@@ -310,3 +375,47 @@ mod ili9341 {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_setup_commands() {
+        let dr = unsafe { core::ptr::addr_of_mut!((*hal::cpu::SPI1).dr) };
+
+        hal::sim::start_trace();
+        ili9341::setup();
+        let bytes = hal::sim::stop_trace().writes(dr);
+
+        // each command byte followed by its parameters
+        let expected: &[u8] = &[
+            0x01, // software reset
+            0xCB, 0x39, 0x2C, 0x00, 0x34, 0x02, // power control A
+            0xCF, 0x00, 0xC1, 0x30, // power control B
+            0xC0, 0x23, // power control 1
+            0xC1, 0x10, // power control 2
+            0xED, 0x64, 0x03, 0x12, 0x81, // power on sequence control
+            0xF7, 0x20, // pump ratio control
+            0xE8, 0x85, 0x00, 0x78, // driver timing control A
+            0xEA, 0x00, 0x00, // driver timing control B
+            0xC5, 0x3E, 0x28, // VCOM control 1
+            0xC7, 0x86, // VCOM control 2
+            0x36, 0x48, // memory access control
+            0x3A, 0x55, // 16 bit pixels
+            0xB1, 0x00, 0x18, // frame rate control
+            0xB6, 0x08, 0x82, 0x27, 0x01, // display function control
+            0x44, 0x02, // tear scanline
+            0xF2, 0x00, // 3 gamma off
+            0x26, 0x01, // gamma curve 1
+        ];
+        let sent: std::vec::Vec<u8> = bytes.iter().map(|&b| b as u8).collect();
+        assert_eq!(&sent[..expected.len()], expected);
+
+        // after the gamma tables, normal mode, sleep out and display on
+        let rest = &sent[expected.len()..];
+        assert_eq!(rest[0], 0xE0);
+        assert_eq!(rest[16], 0xE1);
+        assert_eq!(&rest[32..35], &[0x13, 0x11, 0x29]);
+    }
+}
@@ -6,6 +6,9 @@
 //! It includes methods for  getting device ID, secret key, extra random value,
 //! and hardware revision, as well as setting the secret key.
 //!
+//! The device ID is not stored but is a 64 bit FNV-1a hash of the unique ID
+//! of the chip.
+//!
 //!
 //! ## Functions
 //!
@@ -33,12 +36,13 @@ impl crate::info::Info {
     #[inline(never)]
     pub fn init(&self) {}
 
-    /// Retrieves the device ID.
+    /// Retrieves the device ID, derived from the 96 bit unique ID of the
+    /// chip so it is the same on every boot and differs between devices.
     ///
     /// # Returns
     /// A `u64` representing the device ID.
     pub fn get_device_id(&self) -> u64 {
-        0x12345678
+        device_id(hal::device::uid())
     }
 
     /// Retrieves the secret key.
@@ -73,3 +77,38 @@ impl crate::info::Info {
         12
     }
 }
+
+impl Default for Info {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Hashes the words of `uid` with 64 bit FNV-1a, which spreads the wafer
+/// position and lot number over all the bits of the device ID.
+fn device_id(uid: [u32; 3]) -> u64 {
+    let mut hash: u64 = 0xCBF2_9CE4_8422_2325;
+    for word in uid {
+        for byte in word.to_le_bytes() {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(0x0000_0100_0000_01B3);
+        }
+    }
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_device_id() {
+        let info = Info::new();
+        assert_eq!(info.get_device_id(), device_id(hal::device::uid()));
+        assert_eq!(info.get_device_id(), info.get_device_id());
+
+        // one bit of the lot number gives another ID
+        assert_ne!(device_id([1, 2, 3]), device_id([1, 2, 3 | 1 << 31]));
+        assert_ne!(device_id([0; 3]), 0);
+    }
+}
@@ -57,3 +57,9 @@ impl crate::inject::Inject {
     //        set led,
     //        and sent to display.
 }
+
+impl Default for Inject {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -8,11 +8,27 @@
 //! - **Keyboard Columns**: These are configured as output pins with a default state of low (pulled low). There are 5 columns.
 //! - **Keyboard Rows**: These are configured as input pins with a default state of low (pulled down). There are 7 rows.
 //!
+//! A scan drives each column high in turn and reads which rows follow it.
+//! Each key is debounced on its own, so any number of keys can be held at
+//! once. The matrix has no diodes, so when three keys at the corners of a
+//! rectangle are held the fourth corner reads as held too. A scan with such
+//! a rectangle leaves the keys at its corners as they were until it is gone.
+//!
+//! The keys are reported as events with the character they type:
+//!
+//! - Letters are lower case, or upper case while a `SHF` key is held.
+//! - While `ALT` is held, the keys type the symbol printed on them.
+//! - `SYM` locks the symbol layer on, until it is pressed again.
+//!
+//! The last key pressed repeats while it is held, after a delay. The
+//! modifier keys `SYM`, `ALT`, and `SHF` are reported but do not repeat.
+//!
 //! ## Functions
 //!
 //! - `new`: Creates a new instance of the `Keyboard` struct.
 //! - `init`: Initializes the keyboard interface.
-//! - `get_key`: Retrieves the key that has been pressed.
+//! - `scan`: Scans the matrix and queues the key events.
+//! - `next_event`: Takes the oldest key event from the queue.
 //!
 
 extern crate hal;
@@ -20,40 +36,21 @@ extern crate hal;
 #[cfg(feature = "std")]
 extern crate std;
 
-pub struct Keyboard {}
-
-impl crate::keyboard::Keyboard {
-    #[inline(never)]
-    pub fn new() -> Self {
-        crate::keyboard::Keyboard {}
-    }
+use crate::board;
+use hal::{delay, timer};
 
-    #[inline(never)]
-    pub fn init(&self) {}
+pub const Q10_COLS: usize = 5;
+pub const Q10_ROWS: usize = 7;
 
-    /// Gets the key that has been pressed. This will return just one key at a time.
-    ///
-    /// # Returns
-    /// A `u8` representing the key pressed. Returns 0 if no key has been pressed.
-
-    pub fn get_key(&self) -> u8 {
-        // returns 0 if no key has been pressed
-        0
-    }
-}
-
-const Q10_COLS: usize = 5;
-const Q10_ROWS: usize = 7;
-
-const SYM: u8 = 1; // Replace with the actual byte value
-const ALT: u8 = 2; // Replace with the actual byte value
-const MIC: u8 = 3; // Replace with the actual byte value
-const SHF: u8 = 4; // Replace with the actual byte value
-const DLR: u8 = 5; // Replace with the actual byte value
-const SPK: u8 = 6; // Replace with the actual byte value
-const BAK: u8 = 0x08; // Replace with the actual byte value
-const ENT: u8 = 0x0D; // Replace with the actual byte value
-const SPC: u8 = b' '; // 32
+pub const SYM: u8 = 1;
+pub const ALT: u8 = 2;
+pub const MIC: u8 = 3;
+pub const SHF: u8 = 4;
+pub const DLR: u8 = 5;
+pub const SPK: u8 = 6;
+pub const BAK: u8 = 0x08;
+pub const ENT: u8 = 0x0D;
+pub const SPC: u8 = b' '; // 32
 
 const BASE_CHAR_MAP: [[u8; Q10_ROWS]; Q10_COLS] = [
     [b'Q', b'W', SYM, b'A', ALT, SPC, MIC],    // col1
@@ -71,21 +68,441 @@ const SYMB_CHAR_MAP: [[u8; Q10_ROWS]; Q10_COLS] = [
     [b'+', b'"', b'-', BAK, SPK, b'.', b'\''], // col5
 ];
 
-pub fn remove_me() {
-    // TODO - remove this functions
-    let _ = (
-        BASE_CHAR_MAP,
-        SYMB_CHAR_MAP,
-        Q10_COLS,
-        Q10_ROWS,
-        SYM,
-        ALT,
-        MIC,
-        SHF,
-        DLR,
-        SPK,
-        BAK,
-        ENT,
-        SPC,
-    );
+/// Time a key must read the same before a press or release is accepted.
+const DEBOUNCE_US: u64 = 5_000;
+
+/// Time a key is held before it starts to repeat.
+const REPEAT_DELAY_US: u64 = 500_000;
+
+/// Time between the repeats of a held key.
+const REPEAT_INTERVAL_US: u64 = 100_000;
+
+/// Time for the rows to follow a column after it is driven high.
+const SETTLE_US: u32 = 2;
+
+/// Number of events kept until they are taken with `next_event`.
+const MAX_EVENTS: usize = 16;
+
+/// A key event, with the character the key types.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Event {
+    /// The key was pressed.
+    Down(u8),
+    /// The key was released. It has the character of its `Down` event.
+    Up(u8),
+    /// The key is still held and repeats.
+    Repeat(u8),
+}
+
+/// Returns true for the keys that change the layer of the other keys.
+fn is_modifier(key: u8) -> bool {
+    key == SYM || key == ALT || key == SHF
+}
+
+/// Returns the bits of the keys in `raw` that may be ghosts, which are the
+/// corners of any rectangle of held keys, as the matrix can not tell which
+/// of them is not held.
+fn ghost_mask(raw: &[u8; Q10_COLS]) -> [u8; Q10_COLS] {
+    let mut mask = [0; Q10_COLS];
+    for a in 0..Q10_COLS {
+        for b in a + 1..Q10_COLS {
+            let common = raw[a] & raw[b];
+            if common.count_ones() >= 2 {
+                mask[a] |= common;
+                mask[b] |= common;
+            }
+        }
+    }
+    mask
+}
+
+#[derive(Copy, Clone)]
+struct Key {
+    down: bool,
+    /// When the key first read different from `down`, while debouncing.
+    changing_since: Option<u64>,
+    /// The character of the last `Down` event.
+    code: u8,
+}
+
+/// The debounced state of the matrix and the events not yet taken.
+struct Matrix {
+    keys: [[Key; Q10_ROWS]; Q10_COLS],
+    sym_lock: bool,
+    repeat: Option<(usize, usize)>,
+    next_repeat_us: u64,
+    events: [Event; MAX_EVENTS],
+    num_events: usize,
+}
+
+impl Matrix {
+    const fn new() -> Self {
+        Matrix {
+            keys: [[Key {
+                down: false,
+                changing_since: None,
+                code: 0,
+            }; Q10_ROWS]; Q10_COLS],
+            sym_lock: false,
+            repeat: None,
+            next_repeat_us: 0,
+            events: [Event::Up(0); MAX_EVENTS],
+            num_events: 0,
+        }
+    }
+
+    /// Returns true if a key with the base character `key` is held.
+    fn held(&self, key: u8) -> bool {
+        (0..Q10_COLS).any(|col| {
+            (0..Q10_ROWS).any(|row| self.keys[col][row].down && BASE_CHAR_MAP[col][row] == key)
+        })
+    }
+
+    /// Returns the character the key at `col`, `row` types with the layer
+    /// picked by the modifiers held now.
+    fn code(&self, col: usize, row: usize) -> u8 {
+        let base = BASE_CHAR_MAP[col][row];
+        if is_modifier(base) {
+            base
+        } else if self.sym_lock || self.held(ALT) {
+            SYMB_CHAR_MAP[col][row]
+        } else if self.held(SHF) {
+            base
+        } else {
+            base.to_ascii_lowercase()
+        }
+    }
+
+    fn push(&mut self, event: Event) {
+        // when full the newest events are dropped
+        if self.num_events < MAX_EVENTS {
+            self.events[self.num_events] = event;
+            self.num_events += 1;
+        }
+    }
+
+    fn pop(&mut self) -> Option<Event> {
+        if self.num_events == 0 {
+            return None;
+        }
+        let event = self.events[0];
+        self.events.copy_within(1..self.num_events, 0);
+        self.num_events -= 1;
+        Some(event)
+    }
+
+    /// Updates the keys from a scan of the matrix at `now_us`, where
+    /// `raw[col]` has a bit set for each row that read high. The modifiers
+    /// are updated first, so a key pressed in the same scan as a modifier
+    /// gets its layer.
+    fn update(&mut self, raw: [u8; Q10_COLS], now_us: u64) {
+        let ghosts = ghost_mask(&raw);
+
+        for modifiers in [true, false] {
+            for (col, keys) in BASE_CHAR_MAP.iter().enumerate() {
+                for (row, &key) in keys.iter().enumerate() {
+                    if is_modifier(key) != modifiers {
+                        continue;
+                    }
+                    let bit = 1 << row;
+                    if ghosts[col] & bit != 0 {
+                        self.keys[col][row].changing_since = None;
+                        continue;
+                    }
+                    self.debounce(col, row, raw[col] & bit != 0, now_us);
+                }
+            }
+        }
+
+        // a key that reads released, while it is debounced, does not repeat
+        if let Some((col, row)) = self.repeat {
+            let key = self.keys[col][row];
+            if now_us >= self.next_repeat_us && key.changing_since.is_none() {
+                self.push(Event::Repeat(key.code));
+                self.next_repeat_us = now_us + REPEAT_INTERVAL_US;
+            }
+        }
+    }
+
+    /// Accepts a press or release of the key at `col`, `row` once it has
+    /// read `pressed` for `DEBOUNCE_US`.
+    fn debounce(&mut self, col: usize, row: usize, pressed: bool, now_us: u64) {
+        let key = &mut self.keys[col][row];
+        if pressed == key.down {
+            key.changing_since = None;
+            return;
+        }
+        let since = *key.changing_since.get_or_insert(now_us);
+        if now_us - since < DEBOUNCE_US {
+            return;
+        }
+        key.changing_since = None;
+        key.down = pressed;
+
+        if pressed {
+            let code = self.code(col, row);
+            self.keys[col][row].code = code;
+            self.push(Event::Down(code));
+            if code == SYM {
+                self.sym_lock = !self.sym_lock;
+            }
+            if !is_modifier(code) {
+                self.repeat = Some((col, row));
+                self.next_repeat_us = now_us + REPEAT_DELAY_US;
+            }
+        } else {
+            let code = key.code;
+            self.push(Event::Up(code));
+            if self.repeat == Some((col, row)) {
+                self.repeat = None;
+            }
+        }
+    }
+}
+
+/// Drives each column high in turn and returns the rows that followed it,
+/// as a bit for each row.
+fn read_matrix() -> [u8; Q10_COLS] {
+    let mut raw = [0; Q10_COLS];
+    for (col, col_pin) in board::info::KEYBOARD_COLS.iter().enumerate() {
+        col_pin.high();
+        delay::delay_us(SETTLE_US);
+        for (row, row_pin) in board::info::KEYBOARD_ROWS.iter().enumerate() {
+            if row_pin.read() {
+                raw[col] |= 1 << row;
+            }
+        }
+        col_pin.low();
+    }
+    raw
+}
+
+pub struct Keyboard {
+    matrix: Matrix,
+}
+
+impl crate::keyboard::Keyboard {
+    #[inline(never)]
+    pub fn new() -> Self {
+        crate::keyboard::Keyboard {
+            matrix: Matrix::new(),
+        }
+    }
+
+    #[inline(never)]
+    pub fn init(&self) {
+        if board::info::HAS_KEYBOARD {
+            for pin in board::info::KEYBOARD_COLS {
+                pin.output();
+            }
+            for pin in board::info::KEYBOARD_ROWS {
+                pin.input();
+                pin.pulldown();
+            }
+        }
+    }
+
+    /// Scans the keyboard matrix and queues an event for each key pressed,
+    /// released, or repeated since the last scan. It should be called about
+    /// every 10 ms.
+    pub fn scan(&mut self) {
+        if board::info::HAS_KEYBOARD {
+            let raw = read_matrix();
+            self.matrix.update(raw, timer::now().as_micros());
+        }
+    }
+
+    /// Takes the oldest key event queued by `scan`.
+    ///
+    /// # Returns
+    /// The `Event`, or `None` if there are no more events.
+    pub fn next_event(&mut self) -> Option<Event> {
+        self.matrix.pop()
+    }
+}
+
+impl Default for Keyboard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const Q: (usize, usize) = (0, 0);
+    const W: (usize, usize) = (0, 1);
+    const SYM_KEY: (usize, usize) = (0, 2);
+    const A: (usize, usize) = (0, 3);
+    const ALT_KEY: (usize, usize) = (0, 4);
+    const E: (usize, usize) = (1, 0);
+    const S: (usize, usize) = (1, 1);
+    const SHF_KEY: (usize, usize) = (1, 6);
+
+    fn raw(keys: &[(usize, usize)]) -> [u8; Q10_COLS] {
+        let mut raw = [0; Q10_COLS];
+        for (col, row) in keys {
+            raw[*col] |= 1 << row;
+        }
+        raw
+    }
+
+    fn events(matrix: &mut Matrix) -> std::vec::Vec<Event> {
+        core::iter::from_fn(|| matrix.pop()).collect()
+    }
+
+    /// Scans `keys` held for long enough to pass the debounce.
+    fn settle(matrix: &mut Matrix, keys: &[(usize, usize)], now_us: &mut u64) {
+        matrix.update(raw(keys), *now_us);
+        *now_us += DEBOUNCE_US;
+        matrix.update(raw(keys), *now_us);
+        *now_us += 10_000;
+    }
+
+    #[test]
+    fn test_debounce() {
+        let mut matrix = Matrix::new();
+
+        // a bounce shorter than the debounce time is not reported
+        matrix.update(raw(&[Q]), 0);
+        matrix.update(raw(&[]), 1_000);
+        matrix.update(raw(&[Q]), 2_000);
+        matrix.update(raw(&[Q]), 2_000 + DEBOUNCE_US - 1);
+        assert!(events(&mut matrix).is_empty());
+
+        matrix.update(raw(&[Q]), 2_000 + DEBOUNCE_US);
+        assert_eq!(events(&mut matrix), [Event::Down(b'q')]);
+
+        let mut now = 20_000;
+        settle(&mut matrix, &[], &mut now);
+        assert_eq!(events(&mut matrix), [Event::Up(b'q')]);
+    }
+
+    #[test]
+    fn test_layers() {
+        let mut matrix = Matrix::new();
+        let mut now = 0;
+
+        // shift and the key in the same scan
+        settle(&mut matrix, &[SHF_KEY, E], &mut now);
+        assert_eq!(events(&mut matrix), [Event::Down(SHF), Event::Down(b'E')]);
+
+        // the release has the character of the press
+        settle(&mut matrix, &[E], &mut now);
+        settle(&mut matrix, &[], &mut now);
+        assert_eq!(events(&mut matrix), [Event::Up(SHF), Event::Up(b'E')]);
+
+        settle(&mut matrix, &[ALT_KEY], &mut now);
+        settle(&mut matrix, &[ALT_KEY, W], &mut now);
+        settle(&mut matrix, &[], &mut now);
+        assert_eq!(
+            events(&mut matrix),
+            [
+                Event::Down(ALT),
+                Event::Down(b'1'),
+                Event::Up(ALT),
+                Event::Up(b'1')
+            ]
+        );
+
+        // symbol lock stays on until SYM is pressed again
+        settle(&mut matrix, &[SYM_KEY], &mut now);
+        settle(&mut matrix, &[], &mut now);
+        settle(&mut matrix, &[A], &mut now);
+        settle(&mut matrix, &[], &mut now);
+        settle(&mut matrix, &[SYM_KEY], &mut now);
+        settle(&mut matrix, &[], &mut now);
+        settle(&mut matrix, &[A], &mut now);
+        assert_eq!(
+            events(&mut matrix),
+            [
+                Event::Down(SYM),
+                Event::Up(SYM),
+                Event::Down(b'*'),
+                Event::Up(b'*'),
+                Event::Down(SYM),
+                Event::Up(SYM),
+                Event::Down(b'a')
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rollover() {
+        let mut matrix = Matrix::new();
+        let mut now = 0;
+
+        settle(&mut matrix, &[Q], &mut now);
+        settle(&mut matrix, &[Q, S], &mut now);
+        settle(&mut matrix, &[Q, S, E], &mut now);
+        settle(&mut matrix, &[S, E], &mut now);
+        assert_eq!(
+            events(&mut matrix),
+            [
+                Event::Down(b'q'),
+                Event::Down(b's'),
+                Event::Down(b'e'),
+                Event::Up(b'q')
+            ]
+        );
+    }
+
+    #[test]
+    fn test_ghosting() {
+        let mut matrix = Matrix::new();
+        let mut now = 0;
+
+        settle(&mut matrix, &[Q, W], &mut now);
+        assert_eq!(events(&mut matrix), [Event::Down(b'q'), Event::Down(b'w')]);
+
+        // E at the third corner makes S read held too, so neither is taken
+        let with_ghost = [Q, W, E, S];
+        settle(&mut matrix, &with_ghost, &mut now);
+        settle(&mut matrix, &with_ghost, &mut now);
+        assert!(events(&mut matrix).is_empty());
+
+        // once W is released there is no rectangle and E is seen
+        settle(&mut matrix, &[Q, E], &mut now);
+        assert_eq!(events(&mut matrix), [Event::Up(b'w'), Event::Down(b'e')]);
+    }
+
+    #[test]
+    fn test_repeat() {
+        let mut matrix = Matrix::new();
+
+        matrix.update(raw(&[SHF_KEY, Q]), 0);
+        matrix.update(raw(&[SHF_KEY, Q]), DEBOUNCE_US);
+        assert_eq!(events(&mut matrix), [Event::Down(SHF), Event::Down(b'Q')]);
+
+        let start = DEBOUNCE_US;
+        matrix.update(raw(&[SHF_KEY, Q]), start + REPEAT_DELAY_US - 1);
+        assert!(events(&mut matrix).is_empty());
+        matrix.update(raw(&[SHF_KEY, Q]), start + REPEAT_DELAY_US);
+        assert_eq!(events(&mut matrix), [Event::Repeat(b'Q')]);
+        matrix.update(raw(&[SHF_KEY, Q]), start + REPEAT_DELAY_US + 50_000);
+        assert!(events(&mut matrix).is_empty());
+        matrix.update(
+            raw(&[SHF_KEY, Q]),
+            start + REPEAT_DELAY_US + REPEAT_INTERVAL_US,
+        );
+        assert_eq!(events(&mut matrix), [Event::Repeat(b'Q')]);
+
+        // the held modifier does not repeat once the key is released
+        let end = start + 2 * REPEAT_DELAY_US;
+        matrix.update(raw(&[SHF_KEY]), end);
+        matrix.update(raw(&[SHF_KEY]), end + DEBOUNCE_US);
+        matrix.update(raw(&[SHF_KEY]), end + 2 * REPEAT_DELAY_US);
+        assert_eq!(events(&mut matrix), [Event::Up(b'Q')]);
+    }
+
+    #[test]
+    fn test_scan() {
+        let mut keyboard = Keyboard::new();
+        keyboard.init();
+
+        // nothing drives the rows in the simulation
+        keyboard.scan();
+        assert_eq!(keyboard.next_event(), None);
+    }
 }
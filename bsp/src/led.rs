@@ -49,6 +49,12 @@ impl Led {
     }
 }
 
+impl Default for Led {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// Represents the possible colors for the LED.
 pub enum Color {
     Black, // probably should not use
@@ -19,7 +19,8 @@
 //! ## Structs
 //!
 //! - `BSP`: Represents the Board Support Package,
-//!    providing methods to initialize and validate the hardware components.
+//!   providing methods to initialize and validate the hardware components,
+//!   and to put the board into a low power mode.
 //!
 //! ## Usage
 //!
@@ -43,6 +44,7 @@
 
 extern crate hal;
 
+use hal::power;
 use hal::{spi, timer, watch_dog};
 
 pub mod battery;
@@ -91,18 +93,22 @@ impl BSP {
 
     pub fn init(&mut self) {
         hal::init(board::info::CLOCK_HSE_FREQ);
+        let pins = hal::gpio::take().expect("GPIO pins already taken");
+        let bus_pins = board::info::bus_pins(pins);
 
         // do soon after clock is up so we  can use console
-        hal::uart::init1(115_200, board::info::CONSOLE_TX, board::info::CONSOLE_RX);
+        hal::uart::init1(115_200, bus_pins.console_tx, bus_pins.console_rx);
 
         // do after uart is up
         watch_dog::init();
 
         // must be after GPIO is initialized
-        spi::init1(
+        spi::init::<board::info::DispSpi>(
             board::info::DISP_SPI_FREQ,
-            board::info::DISP_SCL,
-            board::info::DISP_SDA,
+            board::info::DISP_SPI_MODE,
+            bus_pins.disp_scl,
+            hal::gpio::NoPin,
+            bus_pins.disp_sda,
         );
 
         // Do last as this starts timer events
@@ -111,12 +117,19 @@ impl BSP {
         // setup console early so other modules can use it
         self.console.init();
 
+        if hal::clock::hse_failed() {
+            use console::Print;
+            b"WARNING: HSE crystal failed, clock is running from the HSI\r\n".print_console();
+        }
+
         self.led.init();
 
         self.debug.init();
 
         self.buttons.init();
 
+        self.keyboard.init();
+
         self.random.init();
 
         self.inject.init();
@@ -125,9 +138,38 @@ impl BSP {
         self.display.init();
     }
 
+    /// Blanks the display, parks the board outputs, and enters the low power
+    /// `mode` until woken. The outputs and display are restored on return,
+    /// with the LED off.
+    ///
+    /// Standby does not return on hardware as the chip resets when woken.
+    #[inline(never)]
+    pub fn sleep(&mut self, mode: power::Mode) {
+        self.display.sleep();
+
+        for pin in board::info::PARK_PINS.iter() {
+            // the USB pins keep their function so the device stays attached
+            if !hal::usb::owns_pin(pin) {
+                pin.analog();
+            }
+        }
+
+        power::enter(mode);
+
+        self.led.init();
+        self.debug.init();
+        self.display.wake();
+    }
+
     pub fn validate(&self) {
         hal::validate();
 
         self.buttons.validate();
     }
 }
+
+impl Default for BSP {
+    fn default() -> Self {
+        Self::new()
+    }
+}
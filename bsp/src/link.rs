@@ -60,3 +60,9 @@ impl crate::link::Link {
         LinkMessage::None
     }
 }
+
+impl Default for Link {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -20,3 +20,9 @@ impl crate::random::Random {
         todo!();
     }
 }
+
+impl Default for Random {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -16,13 +16,13 @@ mod tests {
 
         let (_state, _changed) = bsp.buttons.read_ptt();
         let (_state, _changed) = bsp.buttons.read_ptt();
-        assert_eq!(_state, false);
-        assert_eq!(_changed, false);
+        assert!(!_state);
+        assert!(!_changed);
 
         let (_state, _changed) = bsp.buttons.read_ai();
         let (_state, _changed) = bsp.buttons.read_ai();
-        assert_eq!(_state, false);
-        assert_eq!(_changed, false);
+        assert!(!_state);
+        assert!(!_changed);
 
         let message: &[u8] = b"test";
         message.print_console();
@@ -34,5 +34,10 @@ mod tests {
         bsp::debug::set(0, false);
 
         led::set(led::Color::Green);
+
+        // the LED is off and the debug pin is an output again after waking
+        bsp.sleep(hal::power::Mode::Sleep);
+        bsp::debug::set(0, true);
+        led::set(led::Color::Green);
     }
 }
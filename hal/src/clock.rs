@@ -3,16 +3,46 @@
 //! This module provides functions to initialize and validate the clock configuration for the system.
 //! It handles setting up the PLL (Phase-Locked Loop), external clock, and various clock dividers.
 //!
+//! ## Structures
+//!
+//! - `ClockConfig`: Builds a clock configuration for a target system clock (STM32F405).
+//! - `PllConfig`: The M, N, P, and Q dividers of the main PLL (STM32F405).
+//! - `Clocks`: The frequencies of the system, AHB, and APB clocks.
+//!
+//! ## Enums
+//!
+//! - `Source`: The HSE or HSI oscillator that the system clock runs from.
+//!
 //! ## Functions
 //!
 //! - `init`: Initializes the clock configuration based on the board-specific settings.
 //! - `validate`: Validates the clock configuration to ensure it is set up correctly.
+//! - `restore`: Applies the last clock configuration again, such as after the stop mode.
+//! - `clocks`: Returns the current clock frequencies, which drivers use for their timing.
+//! - `source`: Returns the oscillator the system clock runs from.
+//! - `hse_failed`: Checks if the clocks fell back to the HSI because the HSE failed.
+//! - `handle_css_nmi`: Handles the clock security system NMI (STM32F405).
 //!
 //! ## Usage
 //!
 //! The `init` function should be called during system startup to configure the clock. The `validate` function
 //! can be used to check if the clock configuration is correct.
 //!
+//! On the STM32F405, `ClockConfig` can set a system clock other than 168 MHz:
+//!
+//! ```rust
+//! # #[cfg(feature = "stm32f405")] {
+//! use hal::clock::{self, ClockConfig};
+//!
+//! hal::cpu::init();
+//!
+//! // 120 MHz from a 24 MHz crystal, with 48 MHz for the USB and RNG
+//! ClockConfig::new(24_000_000).sysclk(120_000_000).apply();
+//!
+//! assert_eq!(clock::clocks().pclk2, 60_000_000);
+//! # }
+//! ```
+//!
 
 use core::ptr;
 
@@ -21,252 +51,649 @@ use super::cpu::*;
 
 #[allow(unused_imports)]
 use super::gpio;
+#[cfg(feature = "stm32f072")]
+use super::gpio::{af, AltFn};
 
 #[cfg(all(feature = "stm32f072", feature = "stm32f405"))]
 compile_error!("Must specify only a single CPU type featre");
 
+/// Frequencies in Hz of the clocks that drivers derive their timing from.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Clocks {
+    /// System clock.
+    pub sysclk: u32,
+    /// AHB clock, used by the CPU, memory, and DMA.
+    pub hclk: u32,
+    /// APB1 peripheral clock.
+    pub pclk1: u32,
+    /// APB2 peripheral clock.
+    pub pclk2: u32,
+}
+
+impl Clocks {
+    /// Returns the kernel clock of the timers on APB1, which is twice `pclk1`
+    /// when APB1 is divided from the AHB clock.
+    pub fn timclk1(&self) -> u32 {
+        if self.pclk1 == self.hclk {
+            self.pclk1
+        } else {
+            2 * self.pclk1
+        }
+    }
+
+    /// Returns the kernel clock of the timers on APB2.
+    pub fn timclk2(&self) -> u32 {
+        if self.pclk2 == self.hclk {
+            self.pclk2
+        } else {
+            2 * self.pclk2
+        }
+    }
+}
+
+#[cfg(feature = "stm32f405")]
+const HSI_FREQ: u32 = 16_000_000;
+
+#[cfg(feature = "stm32f072")]
+const HSI_FREQ: u32 = 8_000_000;
+
+// everything runs from the HSI out of reset
+static CLOCKS: Global<Clocks> = Global::new(hsi_clocks());
+
+/// Returns the clocks when running directly from the HSI with no dividers.
+const fn hsi_clocks() -> Clocks {
+    Clocks {
+        sysclk: HSI_FREQ,
+        hclk: HSI_FREQ,
+        pclk1: HSI_FREQ,
+        pclk2: HSI_FREQ,
+    }
+}
+
+/// Returns the clock frequencies set by the last call to `init`.
+pub fn clocks() -> Clocks {
+    unsafe { CLOCKS.get() }
+}
+
+fn set_clocks(clocks: Clocks) {
+    unsafe { CLOCKS.set(clocks) };
+}
+
+/// The oscillator the system clock runs from, directly or through the PLL.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Source {
+    Hse,
+    Hsi,
+}
+
+static SOURCE: Global<Source> = Global::new(Source::Hsi);
+static HSE_FAILED: Global<bool> = Global::new(false);
+
+/// Returns the oscillator the system clock is running from.
+pub fn source() -> Source {
+    unsafe { SOURCE.get() }
+}
+
+/// Returns true if the HSE was asked for but did not start, or failed while
+/// in use, so the clocks fell back to the HSI.
+///
+/// The failure stays recorded through `restore`, so it is still reported
+/// after waking from stop, until the next `init` or `ClockConfig::apply`.
+pub fn hse_failed() -> bool {
+    unsafe { HSE_FAILED.get() }
+}
+
+fn set_source(source: Source) {
+    unsafe { SOURCE.set(source) };
+}
+
+fn set_hse_failed(failed: bool) {
+    unsafe { HSE_FAILED.set(failed) };
+}
+
+/// Number of times a ready flag is polled before giving up, which is well
+/// over the 100 ms that a crystal may take to start at the HSI clock.
+const READY_POLLS: u32 = 1_000_000;
+
+/// Polls `ready` until it returns true. Returns false if it timed out.
+fn wait_ready(ready: impl Fn() -> bool) -> bool {
+    (0..READY_POLLS).any(|_| ready())
+}
+
+/// Turns on the HSE and waits for it to be ready. Returns false, with the
+/// HSE turned off again, if it does not start.
+fn start_hse() -> bool {
+    cpu::write!(RCC.cr.HSEON, 1);
+
+    let ready = wait_ready(|| cpu::read!(RCC.cr.HSERDY) == 1);
+    if !ready {
+        cpu::write!(RCC.cr.HSEON, 0);
+    }
+    ready
+}
+
+#[cfg(feature = "stm32f072")]
+static HSE_CLK_FREQ: Global<u32> = Global::new(0);
+
+/// Restores the clock configuration set by the last call to `init`.
+///
+/// The stop mode switches the system clock to the HSI and turns off the HSE
+/// and PLL, so this must be called after waking from stop.
+#[cfg(feature = "stm32f072")]
+#[inline(never)]
+pub fn restore() {
+    configure(unsafe { HSE_CLK_FREQ.get() });
+}
+
 #[cfg(feature = "stm32f072")]
 #[inline(never)]
 /// Initializes the clock configuration based on the board-specific settings.
 pub fn init(hse_clk_freq: u32) {
-    let pll_m: u32;
-    match hse_clk_freq {
+    set_hse_failed(false);
+    configure(hse_clk_freq);
+}
+
+/// Sets up the clocks for `init` and `restore`, recording an HSE failure
+/// without clearing an earlier one.
+#[cfg(feature = "stm32f072")]
+fn configure(hse_clk_freq: u32) {
+    unsafe { HSE_CLK_FREQ.set(hse_clk_freq) };
+
+    let pll_m: u32 = match hse_clk_freq {
         16_000_000 => {
-            // pll_m = 0b0001; // this is a x3 pll mult for 48MHz
-            // pll_m = 0b1010; // hack x12 TODO - fails to boot
-            pll_m = 0b0100; // hack x6 TODO
+            // 0b0001 is a x3 pll mult for 48MHz
+            // 0b1010 is a hack x12 TODO - fails to boot
+            0b0100 // hack x6 TODO
         }
 
         _ => {
             panic!("HSE Clk Freq not supported");
         }
-    }
+    };
 
-    // Enable HSE
-    cpu::write!(RCC.cr[HSEON;1], 1);
-
-    // Wait for HSE to be ready
-    while cpu::read!(RCC.cr[HSERDY;1]) == 0 {}
+    // Enable HSE, which is not used by the PLL but is checked here so a
+    // missing crystal is reported
+    if !start_hse() {
+        set_hse_failed(true);
+    }
+    set_source(Source::Hsi);
 
     // Configure PLL
-    cpu::write!(RCC.cfgr[PLLSRC;2], 1); // HSE as PLL source
-    cpu::write!(RCC.cfgr[PLLMUL;4], pll_m); // PLL multiplier
-    cpu::write!(RCC.cfgr[PPRE;3], 0b000); // No AHB prescaler
+    cpu::write!(RCC.cfgr.PLLSRC, 1); // HSE as PLL source
+    cpu::write!(RCC.cfgr.PLLMUL, pll_m); // PLL multiplier
+    cpu::write!(RCC.cfgr.PPRE, 0b000); // No AHB prescaler
 
     // Enable PLL
-    cpu::write!(RCC.cr[PLLON;1], 1);
+    cpu::write!(RCC.cr.PLLON, 1);
 
-    // Wait for PLL to be ready
-    while cpu::read!(RCC.cr[PLLRDY;1]) == 0 {}
+    // Wait for PLL to be ready, otherwise stay on the HSI
+    if !wait_ready(|| cpu::read!(RCC.cr.PLLRDY) == 1) {
+        cpu::write!(RCC.cr.PLLON, 0);
+        set_clocks(hsi_clocks());
+        return;
+    }
 
     // Select PLL as system clock source
-    cpu::write!(RCC.cfgr[SW;2], 0b10); // PLL as system clock source
+    cpu::write!(RCC.cfgr.SW, 0b10); // PLL as system clock source
 
     // Wait for PLL to be used as system clock source
-    while cpu::read!(RCC.cfgr[SWS;2]) != 0b10 {}
+    if !wait_ready(|| cpu::read!(RCC.cfgr.SWS) == 0b10) {
+        cpu::write!(RCC.cfgr.SW, 0b00);
+        set_clocks(hsi_clocks());
+        return;
+    }
+
+    // the PLL runs from the HSI through PREDIV, and the APB is not divided
+    let sysclk = HSI_FREQ * (pll_m + 2);
+    set_clocks(Clocks {
+        sysclk,
+        hclk: sysclk,
+        pclk1: sysclk,
+        pclk2: sysclk,
+    });
 }
 
 // Configures MCO to output half the PLLCLK frequency.
 #[cfg(feature = "stm32f072")]
 #[inline(never)]
-pub fn configure_mco(pin: super::gpio::Pin, mco_freq: u32) {
-    // TODO
-    assert!(pin.0 == GPIOA as *mut cpu::GpioReg);
-    assert!(pin.1 >= 8);
-
-    let pn = pin.1;
-
+pub fn configure_mco<P: AltFn<af::Mco>>(pin: P, mco_freq: u32) {
     assert!(mco_freq == 24_000_000);
 
     // Enable GPIOA clock
-    cpu::write!(RCC.ahbenr[IOPAEN;1], 1);
+    cpu::write!(RCC.ahbenr.IOPAEN, 1);
 
     // Configure PA8 as alternate function (MCO)
-    cpu::write!(GPIOA.moder[pn*2;2], 0b10); // Set mode to alternate function
-    cpu::write!(GPIOA.afrh[(pn-8)*4;4], 0); // Set AF0 for MCO
+    pin.pin().alt_fun(P::AF, true);
 
     // Configure MCO to output PLLCLK/2
-    cpu::write!(RCC.cfgr[MCO;4], 0b0111); // Set MCO source to PLLCLK
-                                          //cpu::write!(RCC.cfgr[MCO;4], 0b0100); // Set MCO source to SYSCLK
+    cpu::write!(RCC.cfgr.MCO, 0b0111); // Set MCO source to PLLCLK
+                                       //cpu::write!(RCC.cfgr.MCO, 0b0100); // Set MCO source to SYSCLK
 
-    cpu::write!(RCC.cfgr[PLLNODIV;1], 1); // PLL is NOT devided by 2
+    cpu::write!(RCC.cfgr.PLLNODIV, 1); // PLL is NOT devided by 2
 
-    //cpu::write!(RCC.cfgr[MCOPRE;3], 0b000); // MCO No prescaler
-    cpu::write!(RCC.cfgr[MCOPRE;3], 0b001); // Set MCO prescaler to divide by 2
-                                            //cpu::write!(RCC.cfgr[MCOPRE;3], 0b011); // MCO prescaler set to divide by 8
+    //cpu::write!(RCC.cfgr.MCOPRE, 0b000); // MCO No prescaler
+    cpu::write!(RCC.cfgr.MCOPRE, 0b001); // Set MCO prescaler to divide by 2
+                                         //cpu::write!(RCC.cfgr.MCOPRE, 0b011); // MCO prescaler set to divide by 8
 }
 
+/// Validates the clock configuration to ensure it is set up correctly.
 #[cfg(feature = "stm32f072")]
 #[inline(never)]
-pub fn validate() {}
+pub fn validate() {
+    let clocks = clocks();
+
+    // Check if system clock mux is set to PLL
+    if cpu::read!(RCC.cfgr.SWS) != 0b10 {
+        panic!("System clock not set to PLL");
+    }
+
+    if cpu::read!(RCC.cfgr.PLLSRC) != 1 {
+        panic!("PLL source not set correctly");
+    }
+
+    if cpu::read!(RCC.cfgr.PLLMUL) != clocks.sysclk / HSI_FREQ - 2 {
+        panic!("PLL multiplier not set correctly");
+    }
+
+    // Check AHB and APB prescalers
+    if cpu::read!(RCC.cfgr.HPRE) != 0b0000 {
+        panic!("AHB prescaler not set to 1");
+    }
+
+    if cpu::read!(RCC.cfgr.PPRE) != 0b000 {
+        panic!("APB prescaler not set to 1");
+    }
+}
 
 #[cfg(feature = "stm32f405")]
-#[inline(never)]
-/// Initializes the clock configuration based on the board-specific settings.
-pub fn init(_hse_clk_freq: u32) {
-    #[cfg(feature = "std")]
-    let clk_freq: u32 = 0;
-    #[cfg(not(feature = "std"))]
-    let clk_freq: u32 = _hse_clk_freq;
-
-    let pll_m: u32;
-    match clk_freq {
-        0 => {
-            pll_m = 8;
+const SYSCLK_MAX: u32 = 168_000_000;
+#[cfg(feature = "stm32f405")]
+const PCLK1_MAX: u32 = 42_000_000;
+#[cfg(feature = "stm32f405")]
+const PCLK2_MAX: u32 = 84_000_000;
+#[cfg(feature = "stm32f405")]
+const USB_FREQ: u32 = 48_000_000;
+
+/// The dividers of the main PLL. The VCO runs at `src / m * n`, the system
+/// clock is `vco / p`, and the USB, SDIO, and RNG clock is `vco / q`.
+#[cfg(feature = "stm32f405")]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct PllConfig {
+    pub m: u32,
+    pub n: u32,
+    pub p: u32,
+    pub q: u32,
+}
+
+/// A clock configuration for the STM32F405, built from the HSE frequency and a
+/// target system clock. The AHB runs at the system clock and the APB buses at
+/// the fastest speed they allow.
+#[cfg(feature = "stm32f405")]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct ClockConfig {
+    hse_freq: u32,
+    sysclk: u32,
+}
+
+#[cfg(feature = "stm32f405")]
+impl ClockConfig {
+    /// Creates a configuration for a 168 MHz system clock from an HSE crystal
+    /// of `hse_freq` Hz, or from the 16 MHz HSI if `hse_freq` is 0.
+    pub const fn new(hse_freq: u32) -> Self {
+        ClockConfig {
+            hse_freq,
+            sysclk: SYSCLK_MAX,
         }
+    }
 
-        16_000_000 => {
-            pll_m = 8;
+    /// Sets the target system clock in Hz.
+    pub const fn sysclk(mut self, freq: u32) -> Self {
+        self.sysclk = freq;
+        self
+    }
+
+    fn pll_input(&self) -> u32 {
+        if self.hse_freq == 0 {
+            HSI_FREQ
+        } else {
+            self.hse_freq
         }
+    }
 
-        24_000_000 => {
-            pll_m = 12;
+    /// Works out the PLL dividers that give exactly the target system clock
+    /// and 48 MHz for the USB and RNG. Returns None if there are none.
+    pub fn pll(&self) -> Option<PllConfig> {
+        if self.sysclk == 0 || self.sysclk > SYSCLK_MAX {
+            return None;
         }
+        let input = self.pll_input();
+
+        // the VCO input must be 1 to 2 MHz, and 2 MHz has the least jitter,
+        // so try the smallest M first
+        for m in 2..=63 {
+            let vco_in = input / m;
+            if !input.is_multiple_of(m) || vco_in > 2_000_000 {
+                continue;
+            }
+            if vco_in < 1_000_000 {
+                break;
+            }
+
+            for p in [2, 4, 6, 8] {
+                let vco = self.sysclk as u64 * p as u64;
+                if !(100_000_000..=432_000_000).contains(&vco) {
+                    continue;
+                }
+                let vco = vco as u32;
+                if !vco.is_multiple_of(vco_in) || !vco.is_multiple_of(USB_FREQ) {
+                    continue;
+                }
+
+                let n = vco / vco_in;
+                let q = vco / USB_FREQ;
+                if (50..=432).contains(&n) && (2..=15).contains(&q) {
+                    return Some(PllConfig { m, n, p, q });
+                }
+            }
+        }
+        None
+    }
 
-        _ => {
-            panic!("HSE Clk Freq not supported");
+    /// Returns the clock frequencies this configuration gives.
+    pub fn clocks(&self) -> Clocks {
+        let hclk = self.sysclk;
+        Clocks {
+            sysclk: self.sysclk,
+            hclk,
+            pclk1: hclk / apb_divider(hclk, PCLK1_MAX),
+            pclk2: hclk / apb_divider(hclk, PCLK2_MAX),
         }
     }
 
-    if clk_freq != 0 {
-        cpu::write!(RCC.cfgr[SWS0;2], 0b10);
-        cpu::write!(RCC.cr[PLLRDY;1], 1);
-        cpu::write!(RCC.cr[HSERDY;1], 1);
-        cpu::write!(RCC.pllcfgr[PLLSRC;1], 1);
-        cpu::write!(RCC.pllcfgr[PLLM0;6], pll_m);
+    /// Switches the system clock to the PLL with this configuration, and
+    /// returns the oscillator it runs from.
+    ///
+    /// If the HSE does not start, the PLL runs from the HSI at the same system
+    /// clock. If the PLL does not lock, the system clock stays on the HSI at
+    /// 16 MHz. Every wait on a ready flag has a timeout, so this always
+    /// returns. The clock security system is turned on when the HSE is used.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `pll` finds no PLL settings for the configuration.
+    #[inline(never)]
+    pub fn apply(&self) -> Source {
+        set_hse_failed(false);
+        self.configure()
     }
 
-    // setup flash wait states and cache
-    {
-        // set latency to 5 wait states - NOTE, if voltage is changed, need to change this
-        cpu::write!(FLASH.acr[LATENCY;3], 5);
+    /// Switches the clocks to this configuration for `apply`, `restore` and
+    /// the clock security system, recording an HSE failure without clearing
+    /// an earlier one.
+    fn configure(&self) -> Source {
+        let mut config = *self;
+
+        // run from the HSI while the PLL is changed
+        cpu::write!(RCC.cr.HSION, 1);
+        wait_ready(|| cpu::read!(RCC.cr.HSIRDY) == 1);
+
+        cpu::write!(RCC.cr.CSSON, 0);
+        cpu::write!(RCC.cfgr.SW, 0b00);
+        wait_ready(|| cpu::read!(RCC.cfgr.SWS) == 0b00);
+
+        cpu::write!(RCC.cfgr.HPRE, 0b0000);
+        cpu::write!(RCC.cfgr.PPRE1, 0b000);
+        cpu::write!(RCC.cfgr.PPRE2, 0b000);
+        set_clocks(hsi_clocks());
+        set_source(Source::Hsi);
+
+        cpu::write!(RCC.cr.PLLON, 0);
+        wait_ready(|| cpu::read!(RCC.cr.PLLRDY) == 0);
+
+        if config.hse_freq != 0 && !start_hse() {
+            // fall back to the HSI
+            config.hse_freq = 0;
+            set_hse_failed(true);
+        }
+        if config.hse_freq == 0 {
+            cpu::write!(RCC.cr.HSEON, 0);
+        }
 
-        // enable data, instruction, prefetch cache
-        cpu::write!(FLASH.acr[PRFTEN;1], 1);
-        cpu::write!(FLASH.acr[ICEN;1], 1);
-        cpu::write!(FLASH.acr[DCEN;1], 1);
-    }
+        let pll = match config.pll() {
+            Some(pll) => pll,
+            None => panic!("Clock config not supported"),
+        };
+        let clocks = config.clocks();
+        let source = if config.hse_freq != 0 {
+            Source::Hse
+        } else {
+            Source::Hsi
+        };
+
+        // setup flash wait states and cache before the clock speeds up
+        {
+            cpu::write!(FLASH.acr.LATENCY, flash_latency(clocks.hclk));
+
+            // enable data, instruction, prefetch cache
+            cpu::write!(FLASH.acr.PRFTEN, 1);
+            cpu::write!(FLASH.acr.ICEN, 1);
+            cpu::write!(FLASH.acr.DCEN, 1);
+        }
+
+        cpu::write!(RCC.pllcfgr.PLLQ, pll.q);
+        cpu::write!(RCC.pllcfgr.PLLM, pll.m);
+        cpu::write!(RCC.pllcfgr.PLLN, pll.n);
+        cpu::write!(RCC.pllcfgr.PLLP, pll.p / 2 - 1);
+        cpu::write!(RCC.pllcfgr.PLLSRC, (source == Source::Hse) as u32);
 
-    // set up external clock and PLL
-    {
-        // enable HSE
-        cpu::write!(RCC.cr[HSEON;1], 1);
+        unsafe { CONFIG.set(config) };
+        set_source(Source::Hsi);
 
-        // wait for HSE to be ready
-        if clk_freq != 0 {
-            while (cpu::read!(RCC.cr[HSERDY;1]) != 1) {}
+        // enable PLL and wait for it to be ready, otherwise stay on the HSI
+        cpu::write!(RCC.cr.PLLON, 1);
+        if !wait_ready(|| cpu::read!(RCC.cr.PLLRDY) == 1) {
+            cpu::write!(RCC.cr.PLLON, 0);
+            return Source::Hsi;
         }
 
-        // setup main PLL timing for external HSE
-        let pll_m: u32 = pll_m;
+        // setup clock usage and dividers
+        cpu::write!(RCC.cfgr.HPRE, 0b0000);
+        cpu::write!(RCC.cfgr.PPRE1, ppre(clocks.hclk / clocks.pclk1));
+        cpu::write!(RCC.cfgr.PPRE2, ppre(clocks.hclk / clocks.pclk2));
+
+        // switch clock to PLL and wait for it to switch
+        cpu::write!(RCC.cfgr.SW, 0b10);
+        if !wait_ready(|| cpu::read!(RCC.cfgr.SWS) == 0b10) {
+            cpu::write!(RCC.cfgr.SW, 0b00);
+            cpu::write!(RCC.cfgr.PPRE1, 0b000);
+            cpu::write!(RCC.cfgr.PPRE2, 0b000);
+            return Source::Hsi;
+        }
 
-        let pll_n: u32 = 168;
-        let pll_q: u32 = 4;
+        if source == Source::Hse {
+            // raise an NMI if the HSE fails from now on
+            cpu::write!(RCC.cr.CSSON, 1);
+        }
 
-        assert!(pll_q >= 2);
-        assert!(pll_q <= 0xF);
-        assert!(pll_n >= 50);
-        assert!(pll_n <= 432);
-        assert!(pll_m >= 2);
-        assert!(pll_m <= 63);
+        set_clocks(clocks);
+        set_source(source);
+        source
+    }
+}
 
-        cpu::write!(RCC.pllcfgr[PLLQ0;4], pll_q );
-        cpu::write!(RCC.pllcfgr[PLLM0;5], pll_m );
-        cpu::write!(RCC.pllcfgr[PLLN0;9], pll_n );
+/// Returns the smallest APB divider that keeps the APB clock at most `max`.
+#[cfg(feature = "stm32f405")]
+fn apb_divider(hclk: u32, max: u32) -> u32 {
+    let mut div = 1;
+    while hclk / div > max {
+        div *= 2;
+    }
+    assert!(div <= 16);
+    div
+}
 
-        // set main division factor to 2
-        cpu::write!(RCC.pllcfgr[PLLP0;2], 0b00);
+/// Returns the PPRE register value for an APB divider.
+#[cfg(feature = "stm32f405")]
+fn ppre(div: u32) -> u32 {
+    match div {
+        1 => 0b000,
+        2 => 0b100,
+        4 => 0b101,
+        8 => 0b110,
+        _ => 0b111,
+    }
+}
 
-        // select HSE
-        cpu::write!(RCC.pllcfgr[PLLSRC;1], 0b1);
+/// Returns the flash wait states needed at `hclk` with a 2.7 to 3.6 V supply.
+#[cfg(feature = "stm32f405")]
+fn flash_latency(hclk: u32) -> u32 {
+    (hclk - 1) / 30_000_000
+}
 
-        // enable PLL
-        cpu::write!(RCC.cr[PLLON;1], 0b1);
-        // wait for PLL to be ready
-        if clk_freq != 0 {
-            while (cpu::read!(RCC.cr[PLLRDY;1]) != 1) {}
-        }
+#[cfg(feature = "stm32f405")]
+static CONFIG: Global<ClockConfig> = Global::new(ClockConfig::new(0));
 
-        // setup clock usage and dividers
-        // sys clock div 1
-        cpu::write!(RCC.cfgr[HPRE;4], 0b0000);
-        // APB1 Clk Div = 4
-        cpu::write!(RCC.cfgr[PPRE1;3], 0b101);
-        // APB2 Clk Div = 2
-        cpu::write!(RCC.cfgr[PPRE2;3], 0b100);
-
-        // switch clock to PLL
-        cpu::write!(RCC.cfgr[SW0;2], 0b10 );
-
-        // wait for clock to switch to PLL
-        if clk_freq != 0 {
-            while (cpu::read!(RCC.cfgr[SWS0;2]) != 0b10) {}
-        }
+#[cfg(feature = "stm32f405")]
+#[inline(never)]
+/// Initializes the clock configuration based on the board-specific settings,
+/// which is a 168 MHz system clock from the HSE.
+pub fn init(hse_clk_freq: u32) {
+    ClockConfig::new(hse_clk_freq).apply();
+}
+
+/// Handles the NMI raised by the clock security system when the HSE fails
+/// while in use. Call this from the NMI vector.
+///
+/// The hardware has already switched the system clock to the HSI and turned
+/// off the HSE and PLL, so this restarts the PLL from the HSI at the same
+/// system clock and records the failure for `hse_failed`.
+#[cfg(feature = "stm32f405")]
+#[inline(never)]
+pub fn handle_css_nmi() {
+    if cpu::read!(RCC.cir.CSSF) == 0 {
+        return;
     }
+    cpu::write!(RCC.cir.CSSC, 1);
+
+    let config = unsafe { CONFIG.get() };
+    set_hse_failed(true);
+    ClockConfig::new(0).sysclk(config.sysclk).configure();
+}
+
+/// Restores the clock configuration set by the last call to `init` or
+/// `ClockConfig::apply`.
+///
+/// The stop mode switches the system clock to the HSI and turns off the HSE
+/// and PLL, so this must be called after waking from stop.
+#[cfg(feature = "stm32f405")]
+#[inline(never)]
+pub fn restore() {
+    unsafe { CONFIG.get() }.configure();
 }
 
 #[cfg(feature = "stm32f405")]
 #[inline(never)]
 /// Validates the clock configuration to ensure it is set up correctly.
 pub fn validate() {
-    // Check if HSE is ready
-    //if board::info::HAS_RCC {
-    //    if cpu::read!(RCC.cr[HSERDY;1]) != 1 {
-    //        panic!("HSE not ready");
-    //    }
-    //}
-
-    // Check if PLL is ready
-    //if board::info::HAS_RCC {
-    //    if cpu::read!(RCC.cr[PLLRDY;1]) != 1 {
-    //        panic!("PLL not ready");
-    //    }
-    //}
-
-    // Check if PLL source is HSE
-    // seems like this can not be read after it is enabled
-    //if cpu::read!(RCC.pllcfgr[PLLSRC;1]) != 1 {
-    //    panic!("PLL source not set to HSE");
-    //}
-
-    // Check if PLL M is set correctly
-    // seems like this can not be read after it is enabled
-    //if read!(RCC.pllcfgr[PLLM0;6]) != board::info::CLOCK_PLL_M {
-    //    panic!("PLL M not set correctly");
-    //}
-
-    // Check if PLL N is set to 168
-    if read!(RCC.pllcfgr[PLLN0;9]) != 168 {
-        panic!("PLL N not set to 168");
+    let config = unsafe { CONFIG.get() };
+    let pll = match config.pll() {
+        Some(pll) => pll,
+        None => panic!("Clock config not supported"),
+    };
+    let clocks = config.clocks();
+
+    // PLL M and the PLL source can not be read after the PLL is enabled
+
+    if read!(RCC.pllcfgr.PLLN) != pll.n {
+        panic!("PLL N not set correctly");
     }
 
-    // Check if PLL P is set to 2
-    if read!(RCC.pllcfgr[PLLP0;2]) != 0 {
-        panic!("PLL P not set to 2");
+    if read!(RCC.pllcfgr.PLLP) != pll.p / 2 - 1 {
+        panic!("PLL P not set correctly");
     }
 
-    // Check if PLL Q is set to 4
-    if read!(RCC.pllcfgr[PLLQ0;4]) != 4 {
-        panic!("PLL Q not set to 4");
+    if read!(RCC.pllcfgr.PLLQ) != pll.q {
+        panic!("PLL Q not set correctly");
     }
 
     // Check if system clock mux is set to PLL
-    if cpu::read!(RCC.cfgr[SWS0;2]) != 0b10 {
+    if cpu::read!(RCC.cfgr.SWS) != 0b10 {
         panic!("System clock not set to PLL");
     }
 
     // Check AHB prescaler
-    if cpu::read!(RCC.cfgr[HPRE;4]) != 0b0000 {
+    if cpu::read!(RCC.cfgr.HPRE) != 0b0000 {
         panic!("AHB prescaler not set to 1");
     }
 
-    // Check APB1 prescaler
-    if cpu::read!(RCC.cfgr[PPRE1;3]) != 0b101 {
-        panic!("APB1 prescaler not set to 4");
+    if cpu::read!(RCC.cfgr.PPRE1) != ppre(clocks.hclk / clocks.pclk1) {
+        panic!("APB1 prescaler not set correctly");
     }
 
-    // Check APB2 prescaler
-    if cpu::read!(RCC.cfgr[PPRE2;3]) != 0b100 {
-        panic!("APB2 prescaler not set to 2");
+    if cpu::read!(RCC.cfgr.PPRE2) != ppre(clocks.hclk / clocks.pclk2) {
+        panic!("APB2 prescaler not set correctly");
+    }
+}
+
+#[cfg(all(test, feature = "stm32f405"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pll() {
+        // the board crystals give 168 MHz with 48 MHz for USB
+        let pll = ClockConfig::new(24_000_000).pll().unwrap();
+        assert_eq!(
+            pll,
+            PllConfig {
+                m: 12,
+                n: 168,
+                p: 2,
+                q: 7
+            }
+        );
+        let pll = ClockConfig::new(16_000_000).pll().unwrap();
+        assert_eq!(
+            pll,
+            PllConfig {
+                m: 8,
+                n: 168,
+                p: 2,
+                q: 7
+            }
+        );
+
+        // HSI and an odd crystal with a 1 MHz VCO input
+        assert!(ClockConfig::new(0).pll().is_some());
+        let pll = ClockConfig::new(25_000_000).pll().unwrap();
+        assert_eq!((pll.m, pll.n), (25, 336));
+
+        // slower clocks use a larger P
+        let pll = ClockConfig::new(8_000_000)
+            .sysclk(48_000_000)
+            .pll()
+            .unwrap();
+        assert_eq!(8_000_000 / pll.m * pll.n / pll.p, 48_000_000);
+        assert_eq!(8_000_000 / pll.m * pll.n / pll.q, 48_000_000);
+
+        // 100 MHz can not also give 48 MHz
+        assert_eq!(ClockConfig::new(8_000_000).sysclk(100_000_000).pll(), None);
+        assert_eq!(ClockConfig::new(8_000_000).sysclk(180_000_000).pll(), None);
+    }
+
+    #[test]
+    fn test_clocks() {
+        let clocks = ClockConfig::new(24_000_000).clocks();
+        assert_eq!(clocks.pclk1, 42_000_000);
+        assert_eq!(clocks.pclk2, 84_000_000);
+        assert_eq!(clocks.timclk1(), 84_000_000);
+        assert_eq!(clocks.timclk2(), 168_000_000);
+
+        let clocks = ClockConfig::new(24_000_000).sysclk(72_000_000).clocks();
+        assert_eq!((clocks.pclk1, clocks.pclk2), (36_000_000, 72_000_000));
+        assert_eq!(clocks.timclk2(), 72_000_000);
+
+        assert_eq!(flash_latency(168_000_000), 5);
+        assert_eq!(flash_latency(30_000_000), 0);
     }
 }
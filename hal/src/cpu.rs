@@ -10,34 +10,141 @@
 //! - `FlashReg`: Represents the Flash memory interface registers.
 //! - `RccReg`: Represents the Reset and Clock Control registers.
 //! - `NVICReg`: Represents the Nested Vectored Interrupt Controller registers.
+//! - `ScbReg`: Represents the System Control Block registers.
+//! - `SysTickReg`: Represents the SysTick timer registers.
+//! - `DwtReg`: Represents the Data Watchpoint and Trace unit registers (STM32F405).
+//! - `DcbReg`: Represents the Debug Control Block registers (STM32F405).
 //! - `GpioReg`: Represents the General-Purpose Input/Output registers.
 //! - `UsartReg`: Represents the Universal Synchronous/Asynchronous Receiver/Transmitter registers.
 //! - `TimAdvReg`: Represents the Advanced Control Timer registers.
 //! - `TimGenReg`: Represents the General Purpose Timer registers.
+//! - `Interrupt`: The interrupts of the chip, in vector table order.
+//! - `Vectors`: The vector table, built with a handler for each entry.
 //!
 //! ## Constants
 //!
 //! - `FLASH`: Base address for the Flash registers.
 //! - `RCC`: Base address for the RCC registers.
 //! - `NVIC`: Base address for the NVIC registers.
+//! - `SCB`: Base address for the System Control Block registers.
+//! - `SYST`: Base address for the SysTick timer registers.
+//! - `DWT`, `DCB`: Base addresses for the DWT and debug control registers.
 //! - `GPIOA`, `GPIOB`, `GPIOC`: Base addresses for the GPIO registers.
 //! - `USART1`: Base address for the USART1 registers.
 //! - `TIM1`, `TIM2`: Base addresses for the TIM1 and TIM2 registers.
+//! - `PRIORITY_BITS`: Number of interrupt priority bits in the NVIC.
+//! - `IRQ_COUNT`: Number of interrupts in the vector table.
 //!
 //! ## Macros
 //!
 //! - `write!`: Macro for writing to a register.
 //! - `read!`: Macro for reading from a register.
+//! - `clear!`: Macro for clearing a flag set by the hardware.
+//!
+//! ## Register Fields
+//!
+//! The fields of a register are types declared with `field!` in the SVD
+//! modules, which carry the offset, width and access of the field, so
+//! `write!(RCC.pllcfgr.PLLM, 8)` needs no width. Writing a read-only field,
+//! reading a write-only field or clearing a field that is not a flag fails
+//! to compile. A field of a register reached through a pointer variable is
+//! named with its module, as in `write!(tim.cr1.cr1::CEN, 1)`.
+//!
+//! ```rust
+//! # #[cfg(feature = "stm32f405")] {
+//! use core::ptr;
+//! use hal::cpu;
+//! use hal::cpu::*;
+//!
+//! cpu::init();
+//!
+//! hal::write!(RCC.pllcfgr.PLLM, 8);
+//! assert_eq!(hal::read!(RCC.pllcfgr.PLLM), 8);
+//! assert_eq!(hal::read!(RCC.pllcfgr), 8);
+//! # }
+//! ```
+//!
+//! ```rust,compile_fail
+//! use core::ptr;
+//! use hal::cpu;
+//! use hal::cpu::*;
+//!
+//! // the PLL ready flag is set by the hardware
+//! hal::write!(RCC.cr.PLLRDY, 1);
+//! ```
+//!
+//! Fields not known until run time, such as the pins of a GPIO register,
+//! still take an offset and width, as in `write!(GPIOA.moder[pin*2;2], 1)`.
+//!
+//! ## Interrupts
+//!
+//! Interrupts are enabled, pended and given a priority in the NVIC by their
+//! `Interrupt`, rather than by number. Priority 0 is the most urgent.
+//!
+//! State shared with an interrupt handler is accessed inside
+//! `critical_section`, which masks all interrupts with PRIMASK, or `free`,
+//! which masks only the interrupts at a given priority or less urgent with
+//! BASEPRI. Both can nest. The STM32F072 has no BASEPRI, so there `free`
+//! masks all interrupts.
+//!
+//! ```rust
+//! use hal::cpu::{self, Interrupt};
+//!
+//! cpu::init();
+//! cpu::set_priority(Interrupt::Usart1, 2);
+//! cpu::enable_irq(Interrupt::Usart1);
+//!
+//! let count = cpu::critical_section(|| {
+//!     // no interrupt runs here
+//!     assert!(cpu::primask());
+//!     1
+//! });
+//! assert_eq!(count, 1);
+//! ```
+//!
+//! ## Vector Table
+//!
+//! The startup code of an application builds its vector table with
+//! `Vectors`, giving the handler of each exception and `Interrupt` by name.
+//! A driver can install its own handler at run time with `set_handler`,
+//! which first copies the table to RAM and points the core at the copy,
+//! with VTOR on the STM32F405 and by mapping the start of the RAM at
+//! address 0 on the STM32F072. The linker script of the STM32F072 must put
+//! the `.vector_table_ram` section at the start of the RAM.
+//!
+//! ## Driver State
+//!
+//! Drivers keep their state in a `Global` rather than a `static mut`. On
+//! the chip it is a plain static, and in the simulation each thread has its
+//! own copy, so tests that each hold a `sim::Guard` can run in parallel.
+//!
+//! ## Cycle Counter
+//!
+//! The `cycles` module counts core clock cycles with the DWT cycle counter,
+//! for timing code too short for the microsecond timers. The Cortex-M0 of
+//! the STM32F072 has no cycle counter, so it is only on the STM32F405.
 //!
 //! ## Functions
 //!
-//! - `init`: Initializes the simulator memory (for `board-sim` feature).
+//! - `init`: Resets the simulated registers and models (for `std` feature).
+//! - `enable_irq`, `disable_irq`, `irq_enabled`: Enable an interrupt in the NVIC.
+//! - `pend_irq`, `unpend_irq`, `irq_pending`: Set an interrupt pending.
+//! - `set_priority`, `priority`: The priority of an interrupt.
+//! - `critical_section`: Runs a closure with all interrupts masked.
+//! - `free`: Runs a closure with the interrupts up to a priority masked.
+//! - `primask`, `set_primask`, `basepri`, `set_basepri`: The interrupt mask
+//!   registers of the core.
+//! - `ipsr`: The exception the core is handling, 0 in thread mode.
+//! - `relocate_vectors`, `vectors_in_ram`: Move the vector table to RAM.
+//! - `set_handler`, `handler`: The handler of an interrupt in the table in RAM.
 //!
 //! The following functions should not be use and instead use the macros.
 //!
 //! - `update_reg`: Updates a register with a masked value.
 //! - `write_reg`: Writes a value to a register.
 //! - `read_reg`: Reads a value from a register.
+//! - `read_bits`: Reads some bits of a register.
+//! - `write_field`, `read_field`, `clear_field`: Access a register field.
 //!
 //! ## Usage
 //!
@@ -46,6 +153,15 @@
 //! behavior if used incorrectly.
 //!
 
+#[cfg(not(feature = "std"))]
+use core::arch::asm;
+use core::cell::UnsafeCell;
+#[cfg(any(feature = "stm32f072", feature = "stm32f405"))]
+use core::ptr;
+
+#[cfg(any(feature = "stm32f072", feature = "stm32f405"))]
+use super::cpu;
+
 #[cfg(feature = "stm32f405")]
 use super::svd_stm32f405 as svd;
 
@@ -55,49 +171,237 @@ use super::svd_stm32f0x2 as svd;
 #[cfg(any(feature = "stm32f072", feature = "stm32f405"))]
 pub use svd::*;
 
-#[cfg(feature = "std")]
-extern crate std;
-
-#[cfg(feature = "std")]
-use std::collections::HashMap;
-#[cfg(feature = "std")]
-use std::sync::Mutex;
-
 #[cfg(feature = "stm32f405")]
+pub mod cycles;
+
+#[cfg(any(feature = "stm32f072", feature = "stm32f405"))]
 #[repr(C)]
 pub struct NVICReg {
     pub iser: [u32; 8], // Interrupt Set-Enable Registers
+    _reserved0: [u32; 24],
     pub icer: [u32; 8], // Interrupt Clear-Enable Registers
+    _reserved1: [u32; 24],
     pub ispr: [u32; 8], // Interrupt Set-Pending Registers
+    _reserved2: [u32; 24],
     pub icpr: [u32; 8], // Interrupt Clear-Pending Registers
+    _reserved3: [u32; 24],
     pub iabr: [u32; 8], // Interrupt Active Bit Registers
+    _reserved4: [u32; 56],
     pub ipr: [u32; 60], // Interrupt Priority Registers
-    pub stir: u32,      // Software Trigger Interrupt Register
+    _reserved5: [u32; 644],
+    pub stir: u32, // Software Trigger Interrupt Register
 }
 
-#[cfg(feature = "stm32f405")]
+#[cfg(any(feature = "stm32f072", feature = "stm32f405"))]
 pub const NVIC: *mut NVICReg = 0xE000_E100 as *mut NVICReg;
 
+#[cfg(any(feature = "stm32f072", feature = "stm32f405"))]
+#[repr(C)]
+pub struct ScbReg {
+    pub cpuid: u32,     // CPUID Base Register
+    pub icsr: u32,      // Interrupt Control and State Register
+    pub vtor: u32,      // Vector Table Offset Register
+    pub aircr: u32,     // Application Interrupt and Reset Control Register
+    pub scr: u32,       // System Control Register
+    pub ccr: u32,       // Configuration and Control Register
+    pub shpr: [u32; 3], // System Handler Priority Registers
+    pub shcsr: u32,     // System Handler Control and State Register
+}
+
+#[cfg(any(feature = "stm32f072", feature = "stm32f405"))]
+pub const SCB: *mut ScbReg = 0xE000_ED00 as *mut ScbReg;
+
+#[cfg(any(feature = "stm32f072", feature = "stm32f405"))]
+#[allow(non_snake_case)]
+pub mod SCB {
+    pub mod scr {
+        pub const SEVONPEND: u8 = 4;
+        pub const SLEEPDEEP: u8 = 2;
+        pub const SLEEPONEXIT: u8 = 1;
+        crate::cpu::field!(SEVONPEND, 1, ReadWrite);
+        crate::cpu::field!(SLEEPDEEP, 1, ReadWrite);
+        crate::cpu::field!(SLEEPONEXIT, 1, ReadWrite);
+    }
+}
+
+#[cfg(any(feature = "stm32f072", feature = "stm32f405"))]
+#[repr(C)]
+pub struct SysTickReg {
+    pub csr: u32,   // Control and Status Register
+    pub rvr: u32,   // Reload Value Register
+    pub cvr: u32,   // Current Value Register
+    pub calib: u32, // Calibration Value Register
+}
+
+#[cfg(any(feature = "stm32f072", feature = "stm32f405"))]
+pub const SYST: *mut SysTickReg = 0xE000_E010 as *mut SysTickReg;
+
+#[cfg(any(feature = "stm32f072", feature = "stm32f405"))]
+#[allow(non_snake_case)]
+pub mod SYST {
+    pub mod csr {
+        pub const COUNTFLAG: u8 = 16;
+        pub const CLKSOURCE: u8 = 2;
+        pub const TICKINT: u8 = 1;
+        pub const ENABLE: u8 = 0;
+        crate::cpu::field!(COUNTFLAG, 1, ReadOnly);
+        crate::cpu::field!(CLKSOURCE, 1, ReadWrite);
+        crate::cpu::field!(TICKINT, 1, ReadWrite);
+        crate::cpu::field!(ENABLE, 1, ReadWrite);
+    }
+    pub mod rvr {
+        pub const RELOAD: u8 = 0;
+        crate::cpu::field!(RELOAD, 24, ReadWrite);
+    }
+}
+
+#[cfg(feature = "stm32f405")]
+#[repr(C)]
+pub struct DwtReg {
+    pub ctrl: u32,     // Control Register
+    pub cyccnt: u32,   // Cycle Count Register
+    pub cpicnt: u32,   // CPI Count Register
+    pub exccnt: u32,   // Exception Overhead Count Register
+    pub sleepcnt: u32, // Sleep Count Register
+    pub lsucnt: u32,   // LSU Count Register
+    pub foldcnt: u32,  // Folded-instruction Count Register
+    pub pcsr: u32,     // Program Counter Sample Register
+}
+
+#[cfg(feature = "stm32f405")]
+pub const DWT: *mut DwtReg = 0xE000_1000 as *mut DwtReg;
+
+#[cfg(feature = "stm32f405")]
+#[allow(non_snake_case)]
+pub mod DWT {
+    pub mod ctrl {
+        pub const NOCYCCNT: u8 = 25;
+        pub const CYCCNTENA: u8 = 0;
+        crate::cpu::field!(NOCYCCNT, 1, ReadOnly);
+        crate::cpu::field!(CYCCNTENA, 1, ReadWrite);
+    }
+}
+
+#[cfg(feature = "stm32f405")]
+#[repr(C)]
+pub struct DcbReg {
+    pub dhcsr: u32, // Debug Halting Control and Status Register
+    pub dcrsr: u32, // Debug Core Register Selector Register
+    pub dcrdr: u32, // Debug Core Register Data Register
+    pub demcr: u32, // Debug Exception and Monitor Control Register
+}
+
+#[cfg(feature = "stm32f405")]
+pub const DCB: *mut DcbReg = 0xE000_EDF0 as *mut DcbReg;
+
+#[cfg(feature = "stm32f405")]
+#[allow(non_snake_case)]
+pub mod DCB {
+    pub mod demcr {
+        pub const TRCENA: u8 = 24;
+        crate::cpu::field!(TRCENA, 1, ReadWrite);
+    }
+}
+
+/// A field of a register, declared with `field!` next to the bit offset
+/// constants of the register.
+pub trait Field {
+    /// Bit offset of the field in the register.
+    const OFFSET: u8;
+    /// Width of the field in bits.
+    const WIDTH: u8;
+    /// How software can access the field: `ReadWrite`, `ReadOnly`,
+    /// `WriteOnly`, `WriteOneToClear`, or `WriteZeroToClear`.
+    type Access;
+
+    /// Bits of the field in the register. Fails to compile if the field
+    /// does not fit in the register.
+    const MASK: u32 = {
+        assert!(Self::WIDTH > 0 && Self::OFFSET as u32 + Self::WIDTH as u32 <= 32);
+        (u32::MAX >> (32 - Self::WIDTH as u32)) << Self::OFFSET
+    };
+}
+
+/// Field that can be read and written.
+pub struct ReadWrite;
+/// Field that is only changed by the hardware, such as a ready flag.
+pub struct ReadOnly;
+/// Field that triggers an action when written and reads as zero.
+pub struct WriteOnly;
+/// Flag set by the hardware and cleared by writing 1, where writing 0 has
+/// no effect. The other fields of the register must not be read-write.
+pub struct WriteOneToClear;
+/// Flag set by the hardware and cleared by writing 0, where writing 1 has
+/// no effect. The other fields of the register must not be read-write.
+pub struct WriteZeroToClear;
+
+/// Access of a field that can be read with `read!`.
+pub trait Readable {}
+impl Readable for ReadWrite {}
+impl Readable for ReadOnly {}
+impl Readable for WriteOneToClear {}
+impl Readable for WriteZeroToClear {}
+
+/// Access of a field that can be written with `write!`.
+pub trait Writable {}
+impl Writable for ReadWrite {}
+impl Writable for WriteOnly {}
+
+/// Access of a flag that can be cleared with `clear!`.
+pub trait Clearable {
+    /// True if the flag is cleared by writing 1.
+    const WITH_ONE: bool;
+}
+impl Clearable for WriteOneToClear {
+    const WITH_ONE: bool = true;
+}
+impl Clearable for WriteZeroToClear {
+    const WITH_ONE: bool = false;
+}
+
+/// Declares a register field with its offset, width in bits and access. The
+/// offset is the constant of the same name, or of the lowest bit for a field
+/// the SVD splits into one constant per bit.
+macro_rules! field {
+    ( $name:ident, $width:expr, $access:ident ) => {
+        $crate::cpu::field!($name = $name, $width, $access);
+    };
+    ( $name:ident = $offset:expr, $width:expr, $access:ident ) => {
+        pub struct $name {}
+
+        impl $crate::cpu::Field for $name {
+            const OFFSET: u8 = $offset;
+            const WIDTH: u8 = $width;
+            type Access = $crate::cpu::$access;
+        }
+    };
+}
+
+pub(crate) use field;
+
+// the register functions are only given register addresses by the macros
+#[cfg(not(feature = "std"))]
 #[inline(always)]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
 //#[inline(never)]
 pub fn update_reg(addr: *mut u32, mask: u32, val: u32) {
-    if cfg!(feature = "std") {
-        let mut v: u32 = read_reg(addr);
+    unsafe {
+        let mut v: u32 = core::ptr::read_volatile(addr);
         v &= !mask;
         v |= val;
-        write_reg(addr, v);
-    } else {
-        unsafe {
-            let mut v: u32 = core::ptr::read_volatile(addr);
-            v &= !mask;
-            v |= val;
-            core::ptr::write_volatile(addr, v);
-        }
+        core::ptr::write_volatile(addr, v);
     }
 }
 
+#[cfg(feature = "std")]
+#[inline(always)]
+pub fn update_reg(addr: *mut u32, mask: u32, val: u32) {
+    super::sim::update(addr, mask, val);
+}
+
 #[cfg(not(feature = "std"))]
 #[inline(always)]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
 pub fn write_reg(addr: *mut u32, val: u32) {
     unsafe {
         core::ptr::write_volatile(addr, val);
@@ -106,62 +410,99 @@ pub fn write_reg(addr: *mut u32, val: u32) {
 
 #[cfg(feature = "std")]
 #[inline(always)]
-//#[inline(never)]
 pub fn write_reg(addr: *mut u32, val: u32) {
-    unsafe {
-        if let Some(ref map_mutex) = SIM {
-            let mut map = map_mutex.lock().unwrap();
-            map.insert(addr, val);
-        }
-    }
+    super::sim::write(addr, val);
 }
 
 #[cfg(not(feature = "std"))]
 #[inline(always)]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
 pub fn read_reg(addr: *mut u32) -> u32 {
     unsafe { core::ptr::read_volatile(addr) }
 }
 
 #[cfg(feature = "std")]
 #[inline(always)]
-//#[inline(never)]
 pub fn read_reg(addr: *mut u32) -> u32 {
-    unsafe {
-        if let Some(ref map_mutex) = SIM {
-            let map = map_mutex.lock().unwrap();
-            if let Some(value) = map.get(&addr) {
-                let my_value: u32 = *value;
-                my_value
-            } else {
-                0
-            }
-        } else {
-            0
-        }
+    super::sim::read(addr)
+}
+
+/// Reads the bits in `mask` of the register at `addr`, leaving the other
+/// bits zero.
+#[cfg(not(feature = "std"))]
+#[inline(always)]
+pub fn read_bits(addr: *mut u32, mask: u32) -> u32 {
+    read_reg(addr) & mask
+}
+
+/// Reads the bits in `mask` of the register at `addr`, leaving the other
+/// bits zero.
+#[cfg(feature = "std")]
+#[inline(always)]
+pub fn read_bits(addr: *mut u32, mask: u32) -> u32 {
+    super::sim::read_bits(addr, mask)
+}
+
+/// Writes `value` to field `F` of the register at `addr`, leaving the other
+/// fields unchanged.
+#[inline(always)]
+pub fn write_field<F: Field>(addr: *mut u32, value: u32)
+where
+    F::Access: Writable,
+{
+    assert!(
+        value <= F::MASK >> F::OFFSET,
+        "value too wide for the field"
+    );
+    update_reg(addr, F::MASK, (value << F::OFFSET) & F::MASK);
+}
+
+/// Reads field `F` of the register at `addr`.
+#[inline(always)]
+pub fn read_field<F: Field>(addr: *mut u32) -> u32
+where
+    F::Access: Readable,
+{
+    read_bits(addr, F::MASK) >> F::OFFSET
+}
+
+/// Clears flag `F` of the register at `addr`. The flag is cleared by
+/// writing only its bits, 1 for a write-1-to-clear flag and 0 for a
+/// write-0-to-clear flag, so no other flag is cleared by the write. A
+/// read-modify-write would clear a flag set by the hardware between the
+/// read and the write.
+#[inline(always)]
+pub fn clear_field<F: Field>(addr: *mut u32)
+where
+    F::Access: Clearable,
+{
+    if <F::Access as Clearable>::WITH_ONE {
+        write_reg(addr, F::MASK);
+    } else {
+        write_reg(addr, !F::MASK);
     }
 }
 
 #[macro_export]
 macro_rules! write {
-    ( $x:ident.$y:ident[$z:ident;$w:expr],  $data:expr  ) => {
-        let offset: u8 = $x::$y::$z;
-        //let offset = concat_idents!($x, _, $y, _, $z);
-        let mut mask: u32 = (1u32 << $w) - 1;
-        let mut val: u32 = $data & mask;
-        mask = mask << offset;
-        val = val << offset;
-        unsafe {
-            let addr = ptr::addr_of_mut!((*$x).$y);
-            cpu::update_reg(addr, mask, val);
-        }
-    };
+    ( $x:ident.$y:ident.$m:ident::$z:ident,  $data:expr  ) => {{
+        let val: u32 = $data;
+        let addr = unsafe { ptr::addr_of_mut!((*$x).$y) };
+        cpu::write_field::<$m::$z>(addr, val);
+    }};
+
+    ( $x:ident.$y:ident.$z:ident,  $data:expr  ) => {{
+        let val: u32 = $data;
+        let addr = unsafe { ptr::addr_of_mut!((*$x).$y) };
+        cpu::write_field::<$x::$y::$z>(addr, val);
+    }};
 
     ( $x:ident.$y:ident[$z:expr;$w:expr],  $data:expr  ) => {{
         let offset: u8 = $z;
         let mut mask: u32 = (1u32 << $w) - 1;
         let mut val: u32 = $data & mask;
-        mask = mask << offset;
-        val = val << offset;
+        mask <<= offset;
+        val <<= offset;
         unsafe {
             let addr = ptr::addr_of_mut!((*$x).$y);
             cpu::update_reg(addr, mask, val);
@@ -170,10 +511,9 @@ macro_rules! write {
 
     ( $x:ident.$y:ident[$z:expr],  $data:expr  ) => {{
         let val: u32 = $data;
-        unsafe {
-            let addr = ptr::addr_of_mut!((*$x).$y[$z]);
-            cpu::write_reg(addr, val);
-        }
+        let index = $z;
+        let addr = unsafe { ptr::addr_of_mut!((*$x).$y[index]) };
+        cpu::write_reg(addr, val);
     }};
 
     ( $x:ident.$y:ident ,  $data:expr  ) => {
@@ -189,31 +529,29 @@ pub(crate) use write;
 
 #[macro_export]
 macro_rules! read {
-    ( $x:ident.$y:ident[$z:ident;$w:expr] ) => {{
-        let offset: u8 = $x::$y::$z;
-        let mask: u32 = (1u32 << $w) - 1;
-        let mut val: u32;
-
-        unsafe {
-            let addr = ptr::addr_of_mut!((*$x).$y);
-            val = cpu::read_reg(addr);
-        }
-        val = val >> offset;
-        val = val & mask;
-        val
+    ( $x:ident.$y:ident.$m:ident::$z:ident ) => {{
+        let addr = unsafe { ptr::addr_of_mut!((*$x).$y) };
+        cpu::read_field::<$m::$z>(addr)
+    }};
+    ( $x:ident.$y:ident.$z:ident ) => {{
+        let addr = unsafe { ptr::addr_of_mut!((*$x).$y) };
+        cpu::read_field::<$x::$y::$z>(addr)
     }};
     ( $x:ident.$y:ident[$z:expr;$w:expr] ) => {{
         let offset: u8 = $z;
         let mask: u32 = (1u32 << $w) - 1;
-        let mut val: u32;
+        let val: u32;
 
         unsafe {
             let addr = ptr::addr_of_mut!((*$x).$y);
-            val = cpu::read_reg(addr);
+            val = cpu::read_bits(addr, mask << offset);
         }
-        val = val >> offset;
-        val = val & mask;
-        val
+        val >> offset
+    }};
+    ( $x:ident.$y:ident[$z:expr] ) => {{
+        let index = $z;
+        let addr = unsafe { ptr::addr_of_mut!((*$x).$y[index]) };
+        cpu::read_reg(addr)
     }};
     ( $x:ident.$y:ident ) => {{
         let val: u32;
@@ -227,32 +565,824 @@ macro_rules! read {
 
 pub(crate) use read;
 
-#[cfg(feature = "std")]
-static mut SIM: Option<Mutex<&'static mut HashMap<*mut u32, u32>>> = None;
+#[macro_export]
+macro_rules! clear {
+    ( $x:ident.$y:ident.$m:ident::$z:ident ) => {{
+        let addr = unsafe { ptr::addr_of_mut!((*$x).$y) };
+        cpu::clear_field::<$m::$z>(addr);
+    }};
+    ( $x:ident.$y:ident.$z:ident ) => {{
+        let addr = unsafe { ptr::addr_of_mut!((*$x).$y) };
+        cpu::clear_field::<$x::$y::$z>(addr);
+    }};
+}
+
+pub(crate) use clear;
 
 #[cfg(not(feature = "std"))]
 fn init_sim() {}
 
 #[cfg(feature = "std")]
 fn init_sim() {
-    // initialize the simulator memory
-    static mut MEM: Option<HashMap<*mut u32, u32>> = None;
-    #[allow(static_mut_refs)]
-    unsafe {
-        MEM = Some(HashMap::new());
-        SIM = Some(Mutex::new(MEM.as_mut().unwrap()));
-    }
+    super::sim::reset();
 }
 
 pub fn init() {
     init_sim();
 }
 
+/// The interrupts of the STM32F405, numbered as in the vector table of the
+/// reference manual.
+#[cfg(feature = "stm32f405")]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[repr(u8)]
+pub enum Interrupt {
+    Wwdg = 0,
+    Pvd = 1,
+    TampStamp = 2,
+    RtcWkup = 3,
+    Flash = 4,
+    Rcc = 5,
+    Exti0 = 6,
+    Exti1 = 7,
+    Exti2 = 8,
+    Exti3 = 9,
+    Exti4 = 10,
+    Dma1Stream0 = 11,
+    Dma1Stream1 = 12,
+    Dma1Stream2 = 13,
+    Dma1Stream3 = 14,
+    Dma1Stream4 = 15,
+    Dma1Stream5 = 16,
+    Dma1Stream6 = 17,
+    Adc = 18,
+    Can1Tx = 19,
+    Can1Rx0 = 20,
+    Can1Rx1 = 21,
+    Can1Sce = 22,
+    Exti9_5 = 23,
+    Tim1BrkTim9 = 24,
+    Tim1UpTim10 = 25,
+    Tim1TrgComTim11 = 26,
+    Tim1Cc = 27,
+    Tim2 = 28,
+    Tim3 = 29,
+    Tim4 = 30,
+    I2c1Ev = 31,
+    I2c1Er = 32,
+    I2c2Ev = 33,
+    I2c2Er = 34,
+    Spi1 = 35,
+    Spi2 = 36,
+    Usart1 = 37,
+    Usart2 = 38,
+    Usart3 = 39,
+    Exti15_10 = 40,
+    RtcAlarm = 41,
+    OtgFsWkup = 42,
+    Tim8BrkTim12 = 43,
+    Tim8UpTim13 = 44,
+    Tim8TrgComTim14 = 45,
+    Tim8Cc = 46,
+    Dma1Stream7 = 47,
+    Fsmc = 48,
+    Sdio = 49,
+    Tim5 = 50,
+    Spi3 = 51,
+    Uart4 = 52,
+    Uart5 = 53,
+    Tim6Dac = 54,
+    Tim7 = 55,
+    Dma2Stream0 = 56,
+    Dma2Stream1 = 57,
+    Dma2Stream2 = 58,
+    Dma2Stream3 = 59,
+    Dma2Stream4 = 60,
+    Eth = 61,
+    EthWkup = 62,
+    Can2Tx = 63,
+    Can2Rx0 = 64,
+    Can2Rx1 = 65,
+    Can2Sce = 66,
+    OtgFs = 67,
+    Dma2Stream5 = 68,
+    Dma2Stream6 = 69,
+    Dma2Stream7 = 70,
+    Usart6 = 71,
+    I2c3Ev = 72,
+    I2c3Er = 73,
+    OtgHsEp1Out = 74,
+    OtgHsEp1In = 75,
+    OtgHsWkup = 76,
+    OtgHs = 77,
+    Dcmi = 78,
+    Cryp = 79,
+    HashRng = 80,
+    Fpu = 81,
+}
+
+/// The interrupts of the STM32F072, numbered as in the vector table of the
+/// reference manual.
+#[cfg(feature = "stm32f072")]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[repr(u8)]
+pub enum Interrupt {
+    Wwdg = 0,
+    PvdVddio2 = 1,
+    Rtc = 2,
+    Flash = 3,
+    RccCrs = 4,
+    Exti0_1 = 5,
+    Exti2_3 = 6,
+    Exti4_15 = 7,
+    Tsc = 8,
+    DmaCh1 = 9,
+    DmaCh2_3 = 10,
+    DmaCh4_7 = 11,
+    AdcComp = 12,
+    Tim1BrkUpTrgCom = 13,
+    Tim1Cc = 14,
+    Tim2 = 15,
+    Tim3 = 16,
+    Tim6Dac = 17,
+    Tim7 = 18,
+    Tim14 = 19,
+    Tim15 = 20,
+    Tim16 = 21,
+    Tim17 = 22,
+    I2c1 = 23,
+    I2c2 = 24,
+    Spi1 = 25,
+    Spi2 = 26,
+    Usart1 = 27,
+    Usart2 = 28,
+    Usart3_4 = 29,
+    CecCan = 30,
+    Usb = 31,
+}
+
+#[cfg(any(feature = "stm32f072", feature = "stm32f405"))]
+impl Interrupt {
+    /// Returns the position of the interrupt in the vector table.
+    pub const fn number(self) -> usize {
+        self as usize
+    }
+}
+
+/// Number of priority bits in the NVIC. Priorities go from 0, the most
+/// urgent, to `(1 << PRIORITY_BITS) - 1`.
+#[cfg(feature = "stm32f405")]
+pub const PRIORITY_BITS: u8 = 4;
+
+/// Number of priority bits in the NVIC. Priorities go from 0, the most
+/// urgent, to `(1 << PRIORITY_BITS) - 1`.
+#[cfg(feature = "stm32f072")]
+pub const PRIORITY_BITS: u8 = 2;
+
+/// Enables `irq` in the NVIC.
+#[cfg(any(feature = "stm32f072", feature = "stm32f405"))]
+pub fn enable_irq(irq: Interrupt) {
+    let n = irq.number();
+    cpu::write!(NVIC.iser[n / 32], 1 << (n % 32));
+}
+
+/// Disables `irq` in the NVIC. It can still become pending.
+#[cfg(any(feature = "stm32f072", feature = "stm32f405"))]
+pub fn disable_irq(irq: Interrupt) {
+    let n = irq.number();
+    cpu::write!(NVIC.icer[n / 32], 1 << (n % 32));
+}
+
+/// Returns true if `irq` is enabled in the NVIC.
+#[cfg(any(feature = "stm32f072", feature = "stm32f405"))]
+pub fn irq_enabled(irq: Interrupt) -> bool {
+    let n = irq.number();
+    cpu::read!(NVIC.iser[n / 32]) & (1 << (n % 32)) != 0
+}
+
+/// Sets `irq` pending, so its handler runs once it is enabled and no more
+/// urgent interrupt is running.
+#[cfg(any(feature = "stm32f072", feature = "stm32f405"))]
+pub fn pend_irq(irq: Interrupt) {
+    let n = irq.number();
+    cpu::write!(NVIC.ispr[n / 32], 1 << (n % 32));
+}
+
+/// Clears the pending state of `irq`.
+#[cfg(any(feature = "stm32f072", feature = "stm32f405"))]
+pub fn unpend_irq(irq: Interrupt) {
+    let n = irq.number();
+    cpu::write!(NVIC.icpr[n / 32], 1 << (n % 32));
+}
+
+/// Returns true if `irq` is pending.
+#[cfg(any(feature = "stm32f072", feature = "stm32f405"))]
+pub fn irq_pending(irq: Interrupt) -> bool {
+    let n = irq.number();
+    cpu::read!(NVIC.ispr[n / 32]) & (1 << (n % 32)) != 0
+}
+
+/// Sets the priority of `irq`, from 0 (the most urgent) to
+/// `(1 << PRIORITY_BITS) - 1`. All interrupts start at priority 0.
+#[cfg(any(feature = "stm32f072", feature = "stm32f405"))]
+pub fn set_priority(irq: Interrupt, priority: u8) {
+    assert!(priority < (1 << PRIORITY_BITS), "priority out of range");
+    let n = irq.number();
+    // the priority is in the top bits of the byte of the interrupt, and the
+    // STM32F072 only allows word access to IPR
+    let shift = (n % 4) * 8;
+    let value = (priority << (8 - PRIORITY_BITS)) as u32;
+    let addr = unsafe { ptr::addr_of_mut!((*NVIC).ipr[n / 4]) };
+    update_reg(addr, 0xFF << shift, value << shift);
+}
+
+/// Returns the priority of `irq`.
+#[cfg(any(feature = "stm32f072", feature = "stm32f405"))]
+pub fn priority(irq: Interrupt) -> u8 {
+    let n = irq.number();
+    let byte = (cpu::read!(NVIC.ipr[n / 4]) >> ((n % 4) * 8)) as u8;
+    byte >> (8 - PRIORITY_BITS)
+}
+
+/// Returns IPSR, the number of the exception the core is handling, or 0 in
+/// thread mode. Interrupt `n` is exception `n + 16`.
+#[cfg(not(feature = "std"))]
+#[inline(always)]
+pub fn ipsr() -> u16 {
+    let value: u32;
+    unsafe { asm!("mrs {}, IPSR", out(reg) value, options(nomem, nostack, preserves_flags)) };
+    (value & 0x1ff) as u16
+}
+
+#[cfg(feature = "std")]
+pub fn ipsr() -> u16 {
+    super::sim::ipsr()
+}
+
+/// Returns true if PRIMASK is set, so no interrupt can run.
+#[cfg(not(feature = "std"))]
+#[inline(always)]
+pub fn primask() -> bool {
+    let value: u32;
+    unsafe { asm!("mrs {}, PRIMASK", out(reg) value, options(nomem, nostack, preserves_flags)) };
+    value & 1 != 0
+}
+
+#[cfg(feature = "std")]
+pub fn primask() -> bool {
+    super::sim::primask()
+}
+
+/// Sets PRIMASK to mask all interrupts, or clears it to let them run.
+#[cfg(not(feature = "std"))]
+#[inline(always)]
+pub fn set_primask(masked: bool) {
+    // no nomem, so memory accesses are not moved out of a critical section
+    if masked {
+        unsafe { asm!("cpsid i", options(nostack, preserves_flags)) };
+    } else {
+        unsafe { asm!("cpsie i", options(nostack, preserves_flags)) };
+    }
+}
+
+#[cfg(feature = "std")]
+pub fn set_primask(masked: bool) {
+    super::sim::set_primask(masked);
+}
+
+/// Returns BASEPRI, the priority byte at which interrupts are masked, or 0
+/// when none are masked by it.
+#[cfg(all(feature = "stm32f405", not(feature = "std")))]
+#[inline(always)]
+pub fn basepri() -> u8 {
+    let value: u32;
+    unsafe { asm!("mrs {}, BASEPRI", out(reg) value, options(nomem, nostack, preserves_flags)) };
+    value as u8
+}
+
+#[cfg(all(feature = "stm32f405", feature = "std"))]
+pub fn basepri() -> u8 {
+    super::sim::basepri()
+}
+
+/// Sets BASEPRI to mask the interrupts with a priority byte of `value` or
+/// more, or to mask none when `value` is 0.
+#[cfg(all(feature = "stm32f405", not(feature = "std")))]
+#[inline(always)]
+pub fn set_basepri(value: u8) {
+    unsafe { asm!("msr BASEPRI, {}", in(reg) value as u32, options(nostack, preserves_flags)) };
+}
+
+#[cfg(all(feature = "stm32f405", feature = "std"))]
+pub fn set_basepri(value: u8) {
+    super::sim::set_basepri(value);
+}
+
+/// Runs `f` with all interrupts masked by PRIMASK, and returns its result.
+/// Critical sections can nest, and interrupts are only unmasked again when
+/// the outermost one ends.
+#[inline(always)]
+pub fn critical_section<R>(f: impl FnOnce() -> R) -> R {
+    let was_masked = primask();
+    set_primask(true);
+    let result = f();
+    if !was_masked {
+        set_primask(false);
+    }
+    result
+}
+
+/// Runs `f` with the interrupts of priority `priority` or less urgent masked
+/// by BASEPRI, and returns its result. More urgent interrupts still run, so
+/// `f` can share state with handlers at `priority` without holding up the
+/// rest. A priority of 0 masks all interrupts, the same as
+/// `critical_section`.
+#[cfg(feature = "stm32f405")]
+#[inline(always)]
+pub fn free<R>(priority: u8, f: impl FnOnce() -> R) -> R {
+    assert!(priority < (1 << PRIORITY_BITS), "priority out of range");
+    if priority == 0 {
+        return critical_section(f);
+    }
+    let old = basepri();
+    let new = priority << (8 - PRIORITY_BITS);
+    // only ever mask more, as an enclosing section may mask more already
+    if old == 0 || new < old {
+        set_basepri(new);
+    }
+    let result = f();
+    set_basepri(old);
+    result
+}
+
+/// Runs `f` with all interrupts masked, as the Cortex-M0 of the STM32F072
+/// has no BASEPRI, and returns its result.
+#[cfg(feature = "stm32f072")]
+#[inline(always)]
+pub fn free<R>(priority: u8, f: impl FnOnce() -> R) -> R {
+    assert!(priority < (1 << PRIORITY_BITS), "priority out of range");
+    critical_section(f)
+}
+
+/// State of a driver kept in a `static`, such as its configuration or a
+/// buffer shared with its interrupt handler. It is used in place of a
+/// `static mut`, and as with one, the driver must not race its own
+/// interrupts when accessing it.
+///
+/// With the `std` feature each thread has its own copy, made from the
+/// initial value when first used and cleared with the simulation, so tests
+/// running in parallel do not share the state of the drivers.
+#[repr(transparent)]
+pub struct Global<T> {
+    value: UnsafeCell<T>,
+}
+
+// SAFETY: accesses are through raw pointers, guarded by the drivers
+unsafe impl<T> Sync for Global<T> {}
+
+impl<T: Clone + 'static> Global<T> {
+    pub const fn new(value: T) -> Self {
+        Global {
+            value: UnsafeCell::new(value),
+        }
+    }
+
+    /// Returns a pointer to the value.
+    #[cfg(not(feature = "std"))]
+    #[inline(always)]
+    pub fn as_ptr(&self) -> *mut T {
+        self.value.get()
+    }
+
+    /// Returns a pointer to the copy of the value of this thread.
+    #[cfg(feature = "std")]
+    pub fn as_ptr(&self) -> *mut T {
+        let initial = self.value.get();
+        // SAFETY: the initial value is never written in the simulation
+        super::sim::global(initial as usize, || unsafe { (*initial).clone() })
+    }
+
+    /// Reads the value.
+    ///
+    /// # Safety
+    ///
+    /// An interrupt must not write the value during the read.
+    #[inline(always)]
+    pub unsafe fn get(&self) -> T
+    where
+        T: Copy,
+    {
+        core::ptr::read_volatile(self.as_ptr())
+    }
+
+    /// Writes the value.
+    ///
+    /// # Safety
+    ///
+    /// An interrupt must not access the value during the write.
+    #[inline(always)]
+    pub unsafe fn set(&self, value: T) {
+        core::ptr::write_volatile(self.as_ptr(), value);
+    }
+}
+
+/// Number of interrupts in the vector table of the STM32F405.
+#[cfg(feature = "stm32f405")]
+pub const IRQ_COUNT: usize = 82;
+
+/// Number of interrupts in the vector table of the STM32F072.
+#[cfg(feature = "stm32f072")]
+pub const IRQ_COUNT: usize = 32;
+
+/// The handler of an exception or interrupt.
+pub type Handler = extern "C" fn();
+
+/// An entry of the vector table, holding a handler or zero if it is
+/// reserved.
+#[derive(Copy, Clone)]
+#[repr(C)]
+pub union Vector {
+    handler: Handler,
+    reserved: usize,
+}
+
+#[cfg(any(feature = "stm32f072", feature = "stm32f405"))]
+const RESERVED: Vector = Vector { reserved: 0 };
+
+/// The vector table of the chip after the initial stack pointer and the
+/// reset vector, with an entry for each exception and interrupt. It is
+/// built with `new` and the functions that set one handler, so it can be a
+/// `static`:
+///
+/// ```rust
+/// use hal::cpu::{Interrupt, Vectors};
+///
+/// extern "C" fn default_handler() {}
+/// extern "C" fn tim2_handler() {}
+///
+/// static VECTORS: Vectors = Vectors::new(default_handler).irq(Interrupt::Tim2, tim2_handler);
+/// ```
+#[cfg(any(feature = "stm32f072", feature = "stm32f405"))]
+#[derive(Copy, Clone)]
+#[repr(C)]
+pub struct Vectors {
+    nmi: Vector,
+    hard_fault: Vector,
+    #[cfg(feature = "stm32f405")]
+    mem_manage: Vector,
+    #[cfg(feature = "stm32f405")]
+    bus_fault: Vector,
+    #[cfg(feature = "stm32f405")]
+    usage_fault: Vector,
+    #[cfg(feature = "stm32f405")]
+    reserved0: [Vector; 4],
+    #[cfg(feature = "stm32f072")]
+    reserved0: [Vector; 7],
+    sv_call: Vector,
+    #[cfg(feature = "stm32f405")]
+    debug_monitor: Vector,
+    #[cfg(feature = "stm32f405")]
+    reserved1: [Vector; 1],
+    #[cfg(feature = "stm32f072")]
+    reserved1: [Vector; 2],
+    pend_sv: Vector,
+    sys_tick: Vector,
+    irq: [Vector; IRQ_COUNT],
+}
+
+#[cfg(any(feature = "stm32f072", feature = "stm32f405"))]
+impl Vectors {
+    /// Returns a table with `default` as the handler of every exception and
+    /// interrupt.
+    pub const fn new(default: Handler) -> Self {
+        Self::filled(Vector { handler: default })
+    }
+
+    const fn filled(vector: Vector) -> Self {
+        Vectors {
+            nmi: vector,
+            hard_fault: vector,
+            #[cfg(feature = "stm32f405")]
+            mem_manage: vector,
+            #[cfg(feature = "stm32f405")]
+            bus_fault: vector,
+            #[cfg(feature = "stm32f405")]
+            usage_fault: vector,
+            reserved0: [RESERVED; _],
+            sv_call: vector,
+            #[cfg(feature = "stm32f405")]
+            debug_monitor: vector,
+            reserved1: [RESERVED; _],
+            pend_sv: vector,
+            sys_tick: vector,
+            irq: [vector; IRQ_COUNT],
+        }
+    }
+
+    /// Sets the handler of the non-maskable interrupt.
+    pub const fn nmi(mut self, handler: Handler) -> Self {
+        self.nmi = Vector { handler };
+        self
+    }
+
+    /// Sets the handler of the hard fault.
+    pub const fn hard_fault(mut self, handler: Handler) -> Self {
+        self.hard_fault = Vector { handler };
+        self
+    }
+
+    /// Sets the handler of the memory management fault.
+    #[cfg(feature = "stm32f405")]
+    pub const fn mem_manage(mut self, handler: Handler) -> Self {
+        self.mem_manage = Vector { handler };
+        self
+    }
+
+    /// Sets the handler of the bus fault.
+    #[cfg(feature = "stm32f405")]
+    pub const fn bus_fault(mut self, handler: Handler) -> Self {
+        self.bus_fault = Vector { handler };
+        self
+    }
+
+    /// Sets the handler of the usage fault.
+    #[cfg(feature = "stm32f405")]
+    pub const fn usage_fault(mut self, handler: Handler) -> Self {
+        self.usage_fault = Vector { handler };
+        self
+    }
+
+    /// Sets the handler of the supervisor call.
+    pub const fn sv_call(mut self, handler: Handler) -> Self {
+        self.sv_call = Vector { handler };
+        self
+    }
+
+    /// Sets the handler of the debug monitor.
+    #[cfg(feature = "stm32f405")]
+    pub const fn debug_monitor(mut self, handler: Handler) -> Self {
+        self.debug_monitor = Vector { handler };
+        self
+    }
+
+    /// Sets the handler of the pendable service call.
+    pub const fn pend_sv(mut self, handler: Handler) -> Self {
+        self.pend_sv = Vector { handler };
+        self
+    }
+
+    /// Sets the handler of the SysTick timer.
+    pub const fn sys_tick(mut self, handler: Handler) -> Self {
+        self.sys_tick = Vector { handler };
+        self
+    }
+
+    /// Sets the handler of `irq`.
+    pub const fn irq(mut self, irq: Interrupt, handler: Handler) -> Self {
+        self.irq[irq.number()] = Vector { handler };
+        self
+    }
+}
+
+/// The whole vector table, as VTOR points to it.
+#[cfg(any(feature = "stm32f072", feature = "stm32f405"))]
+#[cfg_attr(feature = "stm32f405", repr(C, align(512)))] // VTOR needs the size rounded up to a power of 2
+#[cfg_attr(feature = "stm32f072", repr(C))]
+#[derive(Copy, Clone)]
+struct VectorTable {
+    stack: usize,
+    reset: Vector,
+    vectors: Vectors,
+}
+
+#[cfg(any(feature = "stm32f072", feature = "stm32f405"))]
+const _: () = assert!(
+    core::mem::offset_of!(VectorTable, vectors) + core::mem::offset_of!(Vectors, irq)
+        == 16 * core::mem::size_of::<usize>()
+);
+
+// the STM32F072 has no VTOR, so its table is used by mapping the start of
+// the RAM at address 0, and must be put there by the linker script
+#[cfg(any(feature = "stm32f072", feature = "stm32f405"))]
+#[cfg_attr(
+    all(feature = "stm32f072", not(feature = "std")),
+    link_section = ".vector_table_ram"
+)]
+static RAM_VECTORS: Global<VectorTable> = Global::new(VectorTable {
+    stack: 0,
+    reset: RESERVED,
+    vectors: Vectors::filled(RESERVED),
+});
+
+/// Returns true if the core takes its vectors from the table in RAM.
+#[cfg(feature = "stm32f405")]
+pub fn vectors_in_ram() -> bool {
+    let table = RAM_VECTORS.as_ptr();
+    cpu::read!(SCB.vtor) == table as u32
+}
+
+/// Returns true if the core takes its vectors from the table in RAM.
+#[cfg(feature = "stm32f072")]
+pub fn vectors_in_ram() -> bool {
+    cpu::read!(SYSCFG.cfgr1.MEM_MODE) == 0b11
+}
+
+/// Copies the vector table from the flash to RAM and points the core at
+/// the copy, so the handlers can be changed with `set_handler`. It does
+/// nothing if the table is already in RAM.
+#[cfg(any(feature = "stm32f072", feature = "stm32f405"))]
+#[inline(never)]
+pub fn relocate_vectors() {
+    if vectors_in_ram() {
+        return;
+    }
+    let table = RAM_VECTORS.as_ptr();
+
+    // the simulation has no table in the flash, so starts from an empty one
+    #[cfg(not(feature = "std"))]
+    unsafe {
+        let flash = super::flash::FLASH_BASE as *const VectorTable;
+        ptr::copy_nonoverlapping(flash, table, 1);
+    }
+
+    #[cfg(feature = "stm32f405")]
+    cpu::write!(SCB.vtor, table as u32);
+
+    #[cfg(feature = "stm32f072")]
+    {
+        let at_start = table as u32 == 0x2000_0000;
+        assert!(
+            cfg!(feature = "std") || at_start,
+            "RAM vector table must be at the start of RAM"
+        );
+        cpu::write!(RCC.apb2enr.SYSCFGEN, 1);
+        cpu::write!(SYSCFG.cfgr1.MEM_MODE, 0b11);
+    }
+}
+
+/// Makes `handler` the handler of `irq`, moving the vector table to RAM
+/// first if needed. Drivers use this to install their own handlers rather
+/// than rely on the startup code of the application.
+#[cfg(any(feature = "stm32f072", feature = "stm32f405"))]
+pub fn set_handler(irq: Interrupt, handler: Handler) {
+    relocate_vectors();
+    unsafe {
+        let entry = ptr::addr_of_mut!((*RAM_VECTORS.as_ptr()).vectors.irq[irq.number()]);
+        ptr::write_volatile(entry, Vector { handler });
+    }
+}
+
+/// Returns the handler of `irq` in the table in RAM, or `None` if the table
+/// is not in RAM or has no handler for `irq`.
+#[cfg(any(feature = "stm32f072", feature = "stm32f405"))]
+pub fn handler(irq: Interrupt) -> Option<Handler> {
+    if !vectors_in_ram() {
+        return None;
+    }
+    let entry = unsafe {
+        ptr::read_volatile(ptr::addr_of!(
+            (*RAM_VECTORS.as_ptr()).vectors.irq[irq.number()]
+        ))
+    };
+    if unsafe { entry.reserved } == 0 {
+        None
+    } else {
+        Some(unsafe { entry.handler })
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    //use core::ptr;
+    use super::*;
+    use crate::sim;
+
+    #[test]
+    #[cfg(feature = "stm32f405")]
+    fn test_nvic() {
+        let _sim = sim::Guard::new();
+
+        enable_irq(Interrupt::Usart1);
+        enable_irq(Interrupt::Dma2Stream7);
+        assert!(irq_enabled(Interrupt::Usart1));
+        assert_eq!(read!(NVIC.iser[1]), 1 << (37 - 32));
+        assert_eq!(read!(NVIC.iser[2]), 1 << (70 - 64));
+
+        disable_irq(Interrupt::Usart1);
+        assert!(!irq_enabled(Interrupt::Usart1));
+        assert!(irq_enabled(Interrupt::Dma2Stream7));
+
+        pend_irq(Interrupt::Tim2);
+        assert!(irq_pending(Interrupt::Tim2));
+        unpend_irq(Interrupt::Tim2);
+        assert!(!irq_pending(Interrupt::Tim2));
+    }
+
+    #[test]
+    #[cfg(feature = "stm32f405")]
+    fn test_priority() {
+        let _sim = sim::Guard::new();
+
+        set_priority(Interrupt::Usart1, 3);
+        set_priority(Interrupt::Usart2, 15);
+        assert_eq!(priority(Interrupt::Usart1), 3);
+        assert_eq!(priority(Interrupt::Usart2), 15);
+        assert_eq!(priority(Interrupt::Usart3), 0);
+
+        // USART1 is byte 1 of IPR9, with the priority in the top 4 bits
+        assert_eq!(read!(NVIC.ipr[9]), (3 << 12) | (15 << 20));
+    }
+
+    #[test]
+    #[should_panic(expected = "priority out of range")]
+    fn test_priority_range() {
+        let _sim = sim::Guard::new();
+        set_priority(Interrupt::Usart1, 16);
+    }
+
+    #[test]
+    fn test_clear_field() {
+        let _sim = sim::Guard::new();
+        let sr = unsafe { ptr::addr_of_mut!((*WWDG).sr) };
+
+        // a write-0-to-clear flag is cleared by writing 0 to it and 1 to the
+        // rest, without reading the register
+        sim::start_trace();
+        clear!(WWDG.sr.EWIF);
+        let trace = sim::stop_trace();
+        assert_eq!(trace.0, [sim::Event::write(sr, !(1 << WWDG::sr::EWIF))]);
+    }
+
+    #[test]
+    #[should_panic(expected = "value too wide for the field")]
+    fn test_write_field_width() {
+        let _sim = sim::Guard::new();
+        write!(WWDG.cfr.WDGTB, 0b100);
+    }
 
-    //use crate::board;
-    //use crate::cpu;
-    // crate::cpu::*;
+    #[test]
+    fn test_critical_section() {
+        let _sim = sim::Guard::new();
+
+        let value = critical_section(|| {
+            assert!(primask());
+            // a nested section leaves the interrupts masked when it ends
+            critical_section(|| assert!(primask()));
+            assert!(primask());
+            42
+        });
+        assert_eq!(value, 42);
+        assert!(!primask());
+    }
+
+    #[test]
+    #[cfg(feature = "stm32f405")]
+    fn test_free() {
+        let _sim = sim::Guard::new();
+
+        free(4, || {
+            assert_eq!(basepri(), 4 << 4);
+            assert!(!primask());
+            // a nested section never masks less than the outer one
+            free(8, || assert_eq!(basepri(), 4 << 4));
+            free(2, || assert_eq!(basepri(), 2 << 4));
+            assert_eq!(basepri(), 4 << 4);
+        });
+        assert_eq!(basepri(), 0);
+
+        free(0, || assert!(primask()));
+        assert!(!primask());
+    }
+
+    extern "C" fn default_handler() {}
+
+    static TIM2_CALLED: core::sync::atomic::AtomicBool = core::sync::atomic::AtomicBool::new(false);
+
+    extern "C" fn tim2_handler() {
+        TIM2_CALLED.store(true, core::sync::atomic::Ordering::Relaxed);
+    }
+
+    #[test]
+    fn test_vectors() {
+        let vectors = Vectors::new(default_handler).irq(Interrupt::Tim2, tim2_handler);
+        let words = unsafe { &*(ptr::addr_of!(vectors) as *const [usize; 14 + IRQ_COUNT]) };
+
+        assert_eq!(words[0], default_handler as *const () as usize); // NMI
+        assert_eq!(words[5], 0); // reserved after the usage fault
+        let tim2 = Interrupt::Tim2.number();
+        assert_eq!(words[14 + tim2], tim2_handler as *const () as usize);
+        assert_eq!(words[14 + tim2 - 1], default_handler as *const () as usize);
+    }
+
+    #[test]
+    fn test_set_handler() {
+        let _sim = sim::Guard::new();
+
+        assert!(!vectors_in_ram());
+        assert!(handler(Interrupt::Tim2).is_none());
+
+        set_handler(Interrupt::Tim2, tim2_handler);
+        assert!(vectors_in_ram());
+        handler(Interrupt::Tim2).unwrap()();
+        assert!(TIM2_CALLED.load(core::sync::atomic::Ordering::Relaxed));
+    }
 }
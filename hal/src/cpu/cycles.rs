@@ -0,0 +1,159 @@
+//! # Cycles Module
+//!
+//! This module counts core clock cycles with the cycle counter of the Data
+//! Watchpoint and Trace (DWT) unit, to time code too short for the 1 µs
+//! timers, such as rendering a glyph.
+//!
+//! The counter is 32 bits and wraps after about 25 s at 168 MHz, so a
+//! measurement must be shorter than that. Cycles are converted to time with
+//! the core clock, HCLK, which is SYSCLK unless the AHB prescaler divides it.
+//!
+//! In the simulation the counter advances on each read while it is enabled.
+//!
+//! ## Structures
+//!
+//! - `Span`: A measurement started by `start`.
+//!
+//! ## Functions
+//!
+//! - `enable`: Starts the cycle counter from zero.
+//! - `enabled`: Returns whether the cycle counter is running.
+//! - `now`: Returns the cycle counter.
+//! - `start`: Starts a measurement.
+//! - `measure`: Runs a closure and returns the cycles it took.
+//! - `to_ns`: Converts cycles to nanoseconds at the core clock.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use hal::cpu::cycles;
+//!
+//! hal::init(16_000_000);
+//! cycles::enable();
+//!
+//! let span = cycles::start();
+//! let (sum, taken) = cycles::measure(|| (1..=10).sum::<u32>());
+//! assert_eq!(sum, 55);
+//! assert!(span.elapsed() >= taken);
+//! ```
+
+use core::ptr;
+
+use super::super::clock;
+use super::super::cpu;
+use super::super::cpu::*;
+
+/// Starts the cycle counter from zero. The trace unit is enabled first, as
+/// the DWT registers do nothing until it is.
+pub fn enable() {
+    cpu::write!(DCB.demcr.TRCENA, 1);
+    assert_eq!(cpu::read!(DWT.ctrl.NOCYCCNT), 0, "no cycle counter");
+    cpu::write!(DWT.cyccnt, 0);
+    cpu::write!(DWT.ctrl.CYCCNTENA, 1);
+}
+
+/// Returns whether the cycle counter is running.
+pub fn enabled() -> bool {
+    cpu::read!(DCB.demcr.TRCENA) != 0 && cpu::read!(DWT.ctrl.CYCCNTENA) != 0
+}
+
+/// Returns the cycle counter, which wraps at 32 bits.
+#[inline(always)]
+pub fn now() -> u32 {
+    cpu::read!(DWT.cyccnt)
+}
+
+/// A measurement of the cycles since it was started.
+#[derive(Copy, Clone, Debug)]
+pub struct Span {
+    start: u32,
+}
+
+impl Span {
+    /// Returns the cycles since the span was started.
+    #[inline(always)]
+    pub fn elapsed(&self) -> u32 {
+        now().wrapping_sub(self.start)
+    }
+
+    /// Returns the nanoseconds since the span was started.
+    pub fn elapsed_ns(&self) -> u64 {
+        to_ns(self.elapsed())
+    }
+}
+
+/// Starts a measurement. The cycle counter must be enabled.
+#[inline(always)]
+pub fn start() -> Span {
+    Span { start: now() }
+}
+
+/// Runs `f` and returns its result with the cycles it took.
+#[inline(always)]
+pub fn measure<R>(f: impl FnOnce() -> R) -> (R, u32) {
+    let span = start();
+    let result = f();
+    (result, span.elapsed())
+}
+
+/// Converts `cycles` of the core clock to nanoseconds.
+pub fn to_ns(cycles: u32) -> u64 {
+    cycles as u64 * 1_000_000_000 / clock::clocks().hclk as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sim;
+
+    #[test]
+    fn test_enable() {
+        let _sim = sim::Guard::new();
+        crate::init(16_000_000);
+
+        assert!(!enabled());
+        enable();
+        assert!(enabled());
+        assert_eq!(cpu::read!(DCB.demcr.TRCENA), 1);
+        assert_eq!(cpu::read!(DWT.ctrl.CYCCNTENA), 1);
+    }
+
+    #[test]
+    fn test_measure() {
+        let _sim = sim::Guard::new();
+        crate::init(16_000_000);
+        enable();
+
+        let span = start();
+        let (value, taken) = measure(|| {
+            now();
+            now();
+            7
+        });
+        assert_eq!(value, 7);
+        // the simulated counter steps once per read
+        assert_eq!(taken, 3);
+        assert_eq!(span.elapsed(), 5);
+    }
+
+    #[test]
+    fn test_wrap() {
+        let _sim = sim::Guard::new();
+        crate::init(16_000_000);
+        enable();
+
+        cpu::write!(DWT.cyccnt, u32::MAX);
+        let span = start();
+        assert_eq!(span.elapsed(), 1);
+    }
+
+    #[test]
+    fn test_to_ns() {
+        let _sim = sim::Guard::new();
+        crate::init(16_000_000);
+
+        let hclk = clock::clocks().hclk;
+        assert_eq!(to_ns(hclk), 1_000_000_000);
+        assert_eq!(to_ns(hclk / 1_000_000), 1_000);
+    }
+}
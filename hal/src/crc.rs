@@ -0,0 +1,253 @@
+//! # CRC Module
+//!
+//! This module computes 32 bit CRCs with the CRC calculation unit (section 4
+//! of RM0090 and section 6 of RM0091), for checking link frames, settings
+//! saved to flash, and firmware images.
+//!
+//! The unit computes CRC-32/MPEG-2: the 0x04C11DB7 polynomial, an initial
+//! value of 0xFFFFFFFF, no bit reversal, and no final XOR. The usual CRC-32
+//! of Ethernet, zip, and `crc32` on the host uses the same polynomial with the
+//! bits of each byte and of the result reversed, and a final XOR, which is
+//! done in software around the unit.
+//!
+//! The unit only takes whole 32 bit words, so a trailing 1 to 3 bytes of a
+//! buffer are added in software. The simulation does all of it in software,
+//! which gives the same results bit for bit.
+//!
+//! The unit has a single state, so a `Crc` loads its state into the unit at
+//! the start of each `update`. An update must not be interrupted by another
+//! update from an interrupt handler.
+//!
+//! ## Structures
+//!
+//! - `Crc`: A CRC computed over one or more buffers.
+//!
+//! ## Enums
+//!
+//! - `Algorithm`: Selects CRC-32/MPEG-2 or the standard CRC-32.
+//!
+//! ## Functions
+//!
+//! - `checksum`: Computes the CRC of a single buffer.
+//! - `crc32`: Computes the standard CRC-32 of a single buffer.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use hal::cpu;
+//! use hal::crc::{self, Algorithm, Crc};
+//!
+//! cpu::init();
+//!
+//! assert_eq!(crc::crc32(b"123456789"), 0xCBF4_3926);
+//!
+//! // the same CRC over a header and a payload
+//! let mut crc = Crc::new(Algorithm::Crc32);
+//! crc.update(b"1234");
+//! crc.update(b"56789");
+//! assert_eq!(crc.value(), 0xCBF4_3926);
+//! ```
+
+#[cfg(not(feature = "std"))]
+use core::ptr;
+
+#[cfg(not(feature = "std"))]
+use super::cpu;
+#[cfg(not(feature = "std"))]
+use super::cpu::*;
+
+#[cfg(all(not(feature = "std"), feature = "stm32f072"))]
+use super::cpu::CRC::cr;
+
+/// Polynomial of the CRC unit.
+pub const POLY: u32 = 0x04C1_1DB7;
+
+/// Initial value of the CRC unit after a reset.
+const INIT: u32 = 0xFFFF_FFFF;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Algorithm {
+    /// CRC-32/MPEG-2 as computed by the CRC unit.
+    Mpeg2,
+    /// The standard CRC-32 (ISO-HDLC), as used by Ethernet and zip.
+    Crc32,
+}
+
+pub struct Crc {
+    algorithm: Algorithm,
+    /// The register of the CRC unit, which for `Crc32` is over the bit
+    /// reversed bytes.
+    state: u32,
+}
+
+impl Crc {
+    pub fn new(algorithm: Algorithm) -> Self {
+        Crc {
+            algorithm,
+            state: INIT,
+        }
+    }
+
+    /// Adds `data` to the CRC.
+    #[inline(never)]
+    pub fn update(&mut self, data: &[u8]) {
+        let reverse = self.algorithm == Algorithm::Crc32;
+        let mut words = data.chunks_exact(4);
+
+        let words_iter = (&mut words).map(|word| {
+            let bytes = [word[0], word[1], word[2], word[3]];
+            if reverse {
+                // reverses the bits of each byte, and puts the first byte in
+                // the top bits as the unit expects
+                u32::from_le_bytes(bytes).reverse_bits()
+            } else {
+                u32::from_be_bytes(bytes)
+            }
+        });
+        self.state = update_words(self.state, words_iter);
+
+        for byte in words.remainder() {
+            let byte = if reverse { byte.reverse_bits() } else { *byte };
+            self.state = software_byte(self.state, byte);
+        }
+    }
+
+    /// Returns the CRC of all the data added so far. More data can still be
+    /// added after this.
+    pub fn value(&self) -> u32 {
+        match self.algorithm {
+            Algorithm::Mpeg2 => self.state,
+            Algorithm::Crc32 => !self.state.reverse_bits(),
+        }
+    }
+}
+
+/// Computes the CRC of `data`.
+pub fn checksum(algorithm: Algorithm, data: &[u8]) -> u32 {
+    let mut crc = Crc::new(algorithm);
+    crc.update(data);
+    crc.value()
+}
+
+/// Computes the standard CRC-32 of `data`, the same as `crc32` on the host.
+pub fn crc32(data: &[u8]) -> u32 {
+    checksum(Algorithm::Crc32, data)
+}
+
+/// Adds the top 8 bits of `byte` shifted in from the most significant bit.
+fn software_byte(state: u32, byte: u8) -> u32 {
+    shift(state ^ ((byte as u32) << 24), 8)
+}
+
+/// Shifts `bits` bits out of the top of `state`, adding the polynomial for
+/// each 1 shifted out.
+fn shift(mut state: u32, bits: u32) -> u32 {
+    for _ in 0..bits {
+        state = if state & 0x8000_0000 != 0 {
+            (state << 1) ^ POLY
+        } else {
+            state << 1
+        };
+    }
+    state
+}
+
+/// Undoes `shift` of 32 bits. The polynomial has its lowest bit set, so the
+/// lowest bit of a state shows if the polynomial was added to it.
+#[cfg(any(test, all(not(feature = "std"), feature = "stm32f405")))]
+fn unshift(mut state: u32) -> u32 {
+    for _ in 0..32 {
+        state = if state & 1 != 0 {
+            ((state ^ POLY) >> 1) | 0x8000_0000
+        } else {
+            state >> 1
+        };
+    }
+    state
+}
+
+// the simulation has no CRC unit
+#[cfg(feature = "std")]
+fn update_words(state: u32, words: impl Iterator<Item = u32>) -> u32 {
+    words.fold(state, |state, word| shift(state ^ word, 32))
+}
+
+#[cfg(not(feature = "std"))]
+fn update_words(state: u32, mut words: impl Iterator<Item = u32>) -> u32 {
+    let Some(first) = words.next() else {
+        return state;
+    };
+
+    load(state);
+    cpu::write!(CRC.dr, first);
+    for word in words {
+        cpu::write!(CRC.dr, word);
+    }
+    cpu::read!(CRC.dr)
+}
+
+/// Resets the CRC unit to `state`. The STM32F405 can only reset to
+/// 0xFFFFFFFF, so the word that takes it from there to `state` is written.
+#[cfg(all(not(feature = "std"), feature = "stm32f405"))]
+fn load(state: u32) {
+    cpu::write!(RCC.ahb1enr.CRCEN, 1);
+    cpu::write!(CRC.cr.RESET, 1);
+    if state != INIT {
+        cpu::write!(CRC.dr, unshift(state) ^ INIT);
+    }
+}
+
+/// Resets the CRC unit to `state`, set up for CRC-32/MPEG-2 as the
+/// polynomial, size, and bit reversal can be changed on the STM32F072.
+#[cfg(all(not(feature = "std"), feature = "stm32f072"))]
+fn load(state: u32) {
+    cpu::write!(RCC.ahbenr.CRCEN, 1);
+    cpu::write!(CRC.pol, POLY);
+    cpu::write!(CRC.init, state);
+    cpu::write!(CRC.cr, 1 << cr::RESET); // 32 bits, no bit reversal
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_values() {
+        // the check values from the catalogue of parametrised CRC algorithms
+        assert_eq!(checksum(Algorithm::Mpeg2, b"123456789"), 0x0376_E6E7);
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+        assert_eq!(crc32(b""), 0);
+        assert_eq!(checksum(Algorithm::Mpeg2, b""), 0xFFFF_FFFF);
+    }
+
+    #[test]
+    fn test_incremental() {
+        let data: [u8; 23] = core::array::from_fn(|i| (i * 37) as u8);
+        for algorithm in [Algorithm::Mpeg2, Algorithm::Crc32] {
+            let expected = checksum(algorithm, &data);
+            for split in 0..data.len() {
+                let mut crc = Crc::new(algorithm);
+                crc.update(&data[..split]);
+                crc.update(&data[split..]);
+                assert_eq!(crc.value(), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_word_matches_bytes() {
+        let state = 0x1234_5678;
+        let bytes = [0xDE, 0xAD, 0xBE, 0xEF];
+        let by_bytes = bytes.iter().fold(state, |s, b| software_byte(s, *b));
+        assert_eq!(shift(state ^ 0xDEAD_BEEF, 32), by_bytes);
+    }
+
+    #[test]
+    fn test_unshift() {
+        for state in [0, 1, INIT, 0x8000_0000, 0x0376_E6E7] {
+            assert_eq!(shift(unshift(state), 32), state);
+            // the word written to the unit after a reset to load a state
+            assert_eq!(shift(INIT ^ (unshift(state) ^ INIT), 32), state);
+        }
+    }
+}
@@ -0,0 +1,204 @@
+//! # Delay Module
+//!
+//! This module provides blocking delays, and the SysTick timer as a periodic
+//! tick that can be used as a clock instead of TIM2.
+//!
+//! The delays busy wait on the SysTick counter, which counts down at the
+//! core clock, so they work as soon as the clocks are set up, before TIM2 or
+//! any interrupt is running. If SysTick is not running as a tick, the first
+//! delay starts it free running without its interrupt.
+//!
+//! `start_tick` makes SysTick raise its exception every period, and the
+//! SysTick handler of the vector table must call `handle_systick_irq` to
+//! count the ticks. `tick_time` then gives the time in whole ticks.
+//!
+//! With the `std` feature the delays do not wait but move the simulated time
+//! ahead, so `timer::now` sees the delay and tests run at full speed.
+//!
+//! ## Functions
+//!
+//! - `delay_us`: Waits for a number of microseconds.
+//! - `delay_ms`: Waits for a number of milliseconds.
+//! - `start_tick`: Starts SysTick interrupting every period.
+//! - `stop_tick`: Stops the SysTick interrupt.
+//! - `handle_systick_irq`: Counts a tick, called from the SysTick handler.
+//! - `ticks`: Returns the number of ticks since `start_tick`.
+//! - `tick_time`: Returns the time since `start_tick` in whole ticks.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use hal::{delay, timer};
+//!
+//! hal::init(16_000_000);
+//!
+//! let start = timer::now();
+//! delay::delay_ms(120);
+//! assert!(timer::now() - start >= timer::Duration::from_millis(120));
+//! ```
+
+use core::ptr;
+
+use super::clock;
+use super::cpu;
+use super::cpu::*;
+use super::timer::Instant;
+
+/// Largest reload of the 24 bit SysTick counter.
+const MAX_RELOAD: u32 = 0x00FF_FFFF;
+
+static TICKS: Global<u64> = Global::new(0);
+static TICK_US: Global<u32> = Global::new(0);
+
+/// Returns the number of SysTick counts in a microsecond.
+#[cfg(not(feature = "std"))]
+fn counts_per_us() -> u32 {
+    let hclk = clock::clocks().hclk;
+    if cpu::read!(SYST.csr.CLKSOURCE) != 0 {
+        hclk / 1_000_000
+    } else {
+        hclk / 8 / 1_000_000
+    }
+}
+
+/// Waits for `us` microseconds.
+#[cfg(not(feature = "std"))]
+#[inline(never)]
+pub fn delay_us(us: u32) {
+    if cpu::read!(SYST.csr.ENABLE) == 0 {
+        cpu::write!(SYST.rvr, MAX_RELOAD);
+        cpu::write!(SYST.cvr, 0);
+        cpu::write!(SYST.csr.CLKSOURCE, 1);
+        cpu::write!(SYST.csr.ENABLE, 1);
+    }
+    let reload = cpu::read!(SYST.rvr.RELOAD) + 1;
+
+    let mut remaining = us as u64 * counts_per_us() as u64;
+    let mut last = cpu::read!(SYST.cvr);
+    while remaining > 0 {
+        let count = cpu::read!(SYST.cvr);
+        // the counter counts down and starts again from the reload at zero
+        let elapsed = if count <= last {
+            last - count
+        } else {
+            last + reload - count
+        };
+        remaining = remaining.saturating_sub(elapsed as u64);
+        last = count;
+    }
+}
+
+/// Moves the simulated time `us` microseconds ahead instead of waiting.
+#[cfg(feature = "std")]
+pub fn delay_us(us: u32) {
+    super::sim::advance_time(us as u64);
+}
+
+/// Waits for `ms` milliseconds.
+pub fn delay_ms(ms: u32) {
+    for _ in 0..ms {
+        delay_us(1_000);
+    }
+}
+
+/// Starts SysTick raising its exception every `period_us` microseconds, and
+/// clears the tick count. The period must fit in the 24 bit counter, which
+/// is about 99 ms with a 168 MHz core clock.
+#[inline(never)]
+pub fn start_tick(period_us: u32) {
+    let reload = period_us as u64 * (clock::clocks().hclk / 1_000_000) as u64;
+    assert!(
+        (2..=MAX_RELOAD as u64 + 1).contains(&reload),
+        "tick period out of range for SysTick"
+    );
+
+    cpu::write!(SYST.csr, 0);
+    unsafe {
+        TICKS.set(0);
+        TICK_US.set(period_us);
+    }
+    cpu::write!(SYST.rvr, reload as u32 - 1);
+    cpu::write!(SYST.cvr, 0);
+    cpu::write!(
+        SYST.csr,
+        (1 << SYST::csr::CLKSOURCE) | (1 << SYST::csr::TICKINT) | (1 << SYST::csr::ENABLE)
+    );
+}
+
+/// Stops the SysTick exception. The counter keeps running for the delays.
+pub fn stop_tick() {
+    cpu::write!(SYST.csr.TICKINT, 0);
+}
+
+/// Counts a tick. The SysTick handler of the vector table must call this.
+#[inline(never)]
+pub fn handle_systick_irq() {
+    unsafe {
+        let ticks = TICKS.get();
+        TICKS.set(ticks.wrapping_add(1));
+    }
+}
+
+/// Returns the number of ticks since `start_tick`. The count is 64 bits so
+/// it does not wrap, and is read with interrupts masked as the core reads it
+/// in two halves.
+pub fn ticks() -> u64 {
+    cpu::critical_section(|| unsafe { TICKS.get() })
+}
+
+/// Returns the time since `start_tick`, in whole ticks.
+pub fn tick_time() -> Instant {
+    let period = unsafe { TICK_US.get() };
+    Instant::from_micros(ticks() * period as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sim;
+    use crate::timer::{self, Duration};
+
+    #[test]
+    fn test_delay_advances_time() {
+        let start = timer::now();
+        delay_ms(2_000);
+        delay_us(500);
+        assert!(timer::now() - start >= Duration::from_micros(2_000_500));
+    }
+
+    #[test]
+    fn test_tick() {
+        let _sim = sim::Guard::new();
+        crate::init(16_000_000);
+
+        start_tick(1_000);
+        let rvr = unsafe { ptr::addr_of_mut!((*SYST).rvr) };
+        assert_eq!(cpu::read_reg(rvr), clock::clocks().hclk / 1_000 - 1);
+        assert_eq!(cpu::read!(SYST.csr.TICKINT), 1);
+
+        handle_systick_irq();
+        handle_systick_irq();
+        assert_eq!(ticks(), 2);
+        assert_eq!(tick_time(), Instant::from_micros(2_000));
+
+        // the count carries on past 32 bits, which is 49 days of 1 ms ticks
+        unsafe { TICKS.set(u32::MAX as u64) };
+        handle_systick_irq();
+        assert_eq!(ticks(), 1 << 32);
+        assert_eq!(tick_time(), Instant::from_micros((1 << 32) * 1_000));
+
+        stop_tick();
+        assert_eq!(cpu::read!(SYST.csr.TICKINT), 0);
+        assert_eq!(cpu::read!(SYST.csr.ENABLE), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "tick period out of range for SysTick")]
+    fn test_tick_range() {
+        let _sim = sim::Guard::new();
+        crate::init(16_000_000);
+
+        // a reload of 0 would stop SysTick
+        start_tick(0);
+    }
+}
@@ -0,0 +1,268 @@
+//! # Device Module
+//!
+//! This module reads what identifies the chip, its 96 bit unique ID and the
+//! size of its flash, which ST writes to the system memory at the factory,
+//! and the cause of the last reset from the reset flags of the RCC.
+//!
+//! The reset flags stay set across resets until cleared, so the boot code
+//! should call `take_reset_cause` once, which clears them for the next
+//! reset. A reset sets more than one flag, as every internal reset also
+//! drives the reset pin, so `ResetCause` is the most specific of them.
+//!
+//! With the `std` feature the unique ID and flash size are fixed values in
+//! the simulated memory.
+//!
+//! ## Enums
+//!
+//! - `ResetCause`: The cause of the last reset.
+//!
+//! ## Functions
+//!
+//! - `uid`: Returns the 96 bit unique ID as three words.
+//! - `flash_size_kb`: Returns the size of the flash in KB.
+//! - `reset_cause`: Returns the cause of the last reset.
+//! - `take_reset_cause`: Returns the cause of the last reset and clears the
+//!   reset flags.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use hal::device::{self, ResetCause};
+//!
+//! hal::init(16_000_000);
+//!
+//! assert_eq!(device::flash_size_kb(), hal::flash::FLASH_SIZE / 1024);
+//! assert_ne!(device::uid(), [0; 3]);
+//!
+//! let cause = device::take_reset_cause();
+//! assert_eq!(cause, ResetCause::Unknown);
+//! ```
+
+use core::ptr;
+
+use super::cpu;
+use super::cpu::*;
+
+/// Address of the 96 bit unique ID.
+#[cfg(feature = "stm32f405")]
+pub const UID_BASE: u32 = 0x1FFF_7A10;
+
+/// Address of the 16 bit flash size, in KB.
+#[cfg(feature = "stm32f405")]
+pub const FLASH_SIZE_BASE: u32 = 0x1FFF_7A22;
+
+/// Address of the 96 bit unique ID.
+#[cfg(feature = "stm32f072")]
+pub const UID_BASE: u32 = 0x1FFF_F7AC;
+
+/// Address of the 16 bit flash size, in KB.
+#[cfg(feature = "stm32f072")]
+pub const FLASH_SIZE_BASE: u32 = 0x1FFF_F7CC;
+
+/// The cause of the last reset, decoded from the reset flags in `RCC.csr`.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ResetCause {
+    /// Entering standby or stop mode when the option bytes make it reset.
+    LowPower,
+    /// The window watchdog (WWDG) expired.
+    WindowWatchdog,
+    /// The independent watchdog (IWDG) expired.
+    IndependentWatchdog,
+    /// Software asked for a reset through `SYSRESETREQ`.
+    Software,
+    /// The option bytes were reloaded (STM32F072).
+    OptionBytes,
+    /// The supply came up, or dropped below the brown out level.
+    PowerOn,
+    /// The reset pin was pulled low from outside.
+    Pin,
+    /// No reset flag is set, as after they were cleared.
+    Unknown,
+}
+
+impl ResetCause {
+    /// Decodes the most specific cause from the reset flags of `csr`.
+    pub fn from_csr(csr: u32) -> Self {
+        let set = |bit: u8| csr & (1 << bit) != 0;
+
+        #[cfg(feature = "stm32f405")]
+        let (iwdg, power_on, option_bytes, pin) = (
+            set(RCC::csr::WDGRSTF),
+            set(RCC::csr::PORRSTF) || set(RCC::csr::BORRSTF),
+            false,
+            set(RCC::csr::PADRSTF),
+        );
+        #[cfg(feature = "stm32f072")]
+        let (iwdg, power_on, option_bytes, pin) = (
+            set(RCC::csr::IWDGRSTF),
+            set(RCC::csr::PORRSTF),
+            set(RCC::csr::OBLRSTF),
+            set(RCC::csr::PINRSTF),
+        );
+
+        if set(RCC::csr::LPWRRSTF) {
+            ResetCause::LowPower
+        } else if set(RCC::csr::WWDGRSTF) {
+            ResetCause::WindowWatchdog
+        } else if iwdg {
+            ResetCause::IndependentWatchdog
+        } else if set(RCC::csr::SFTRSTF) {
+            ResetCause::Software
+        } else if option_bytes {
+            ResetCause::OptionBytes
+        } else if power_on {
+            ResetCause::PowerOn
+        } else if pin {
+            ResetCause::Pin
+        } else {
+            ResetCause::Unknown
+        }
+    }
+
+    /// Returns a short name of the cause, to print on the console.
+    pub fn name(self) -> &'static [u8] {
+        match self {
+            ResetCause::LowPower => b"low power",
+            ResetCause::WindowWatchdog => b"window watchdog",
+            ResetCause::IndependentWatchdog => b"independent watchdog",
+            ResetCause::Software => b"software",
+            ResetCause::OptionBytes => b"option bytes",
+            ResetCause::PowerOn => b"power on",
+            ResetCause::Pin => b"reset pin",
+            ResetCause::Unknown => b"unknown",
+        }
+    }
+}
+
+#[cfg(not(feature = "std"))]
+fn read_u32(addr: u32) -> u32 {
+    unsafe { ptr::read_volatile(addr as *const u32) }
+}
+
+#[cfg(not(feature = "std"))]
+fn read_u16(addr: u32) -> u16 {
+    unsafe { ptr::read_volatile(addr as *const u16) }
+}
+
+// The system memory is not there on the host, so the simulation keeps the
+// unique ID and flash size as memories, filled in by `sim::reset`.
+
+#[cfg(feature = "std")]
+fn read_u32(addr: u32) -> u32 {
+    let offset = (addr - UID_BASE) as usize;
+    super::sim::with_memory(UID_BASE, 12, 0, |mem| {
+        u32::from_le_bytes(mem[offset..offset + 4].try_into().unwrap())
+    })
+}
+
+#[cfg(feature = "std")]
+fn read_u16(addr: u32) -> u16 {
+    assert_eq!(addr, FLASH_SIZE_BASE);
+    super::sim::with_memory(FLASH_SIZE_BASE, 2, 0, |mem| {
+        u16::from_le_bytes([mem[0], mem[1]])
+    })
+}
+
+/// Returns the 96 bit unique ID, lowest word first. It holds the wafer
+/// position, wafer number and lot number of the die, and no two chips share
+/// it.
+pub fn uid() -> [u32; 3] {
+    [
+        read_u32(UID_BASE),
+        read_u32(UID_BASE + 4),
+        read_u32(UID_BASE + 8),
+    ]
+}
+
+/// Returns the size of the flash in KB.
+pub fn flash_size_kb() -> u32 {
+    read_u16(FLASH_SIZE_BASE) as u32
+}
+
+/// Returns the cause of the last reset, leaving the reset flags set.
+pub fn reset_cause() -> ResetCause {
+    ResetCause::from_csr(cpu::read!(RCC.csr))
+}
+
+/// Returns the cause of the last reset and clears the reset flags, so the
+/// next reset reports only its own cause.
+pub fn take_reset_cause() -> ResetCause {
+    let cause = reset_cause();
+    cpu::write!(RCC.csr.RMVF, 1);
+    cause
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sim;
+
+    #[test]
+    fn test_uid() {
+        let _sim = sim::Guard::new();
+        crate::init(16_000_000);
+
+        let id = uid();
+        assert_ne!(id, [0; 3]);
+
+        sim::with_memory(UID_BASE, 12, 0, |mem| mem[4] = 0x5A);
+        assert_eq!(uid()[1] & 0xFF, 0x5A);
+        assert_eq!(uid()[0], id[0]);
+
+        assert_eq!(flash_size_kb(), crate::flash::FLASH_SIZE / 1024);
+    }
+
+    #[test]
+    fn test_from_csr() {
+        let bit = |b: u8| 1u32 << b;
+
+        assert_eq!(ResetCause::from_csr(0), ResetCause::Unknown);
+        assert_eq!(
+            ResetCause::from_csr(bit(RCC::csr::PORRSTF) | bit(RCC::csr::SFTRSTF)),
+            ResetCause::Software
+        );
+        assert_eq!(
+            ResetCause::from_csr(bit(RCC::csr::WWDGRSTF) | bit(RCC::csr::LPWRRSTF)),
+            ResetCause::LowPower
+        );
+
+        #[cfg(feature = "stm32f405")]
+        {
+            let pin = bit(RCC::csr::PADRSTF);
+            assert_eq!(ResetCause::from_csr(pin), ResetCause::Pin);
+            // a power on reset sets all three
+            let por = pin | bit(RCC::csr::PORRSTF) | bit(RCC::csr::BORRSTF);
+            assert_eq!(ResetCause::from_csr(por), ResetCause::PowerOn);
+            assert_eq!(
+                ResetCause::from_csr(pin | bit(RCC::csr::BORRSTF)),
+                ResetCause::PowerOn
+            );
+            assert_eq!(
+                ResetCause::from_csr(pin | bit(RCC::csr::WDGRSTF)),
+                ResetCause::IndependentWatchdog
+            );
+            assert_eq!(
+                ResetCause::from_csr(pin | bit(RCC::csr::WWDGRSTF)),
+                ResetCause::WindowWatchdog
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "stm32f405")]
+    fn test_take_reset_cause() {
+        let _sim = sim::Guard::new();
+        crate::init(16_000_000);
+
+        cpu::write!(
+            RCC.csr,
+            (1 << RCC::csr::WDGRSTF) | (1 << RCC::csr::PADRSTF) | (1 << RCC::csr::LSION)
+        );
+        assert_eq!(reset_cause(), ResetCause::IndependentWatchdog);
+        assert_eq!(take_reset_cause(), ResetCause::IndependentWatchdog);
+
+        // the flags are cleared and the LSI is left on
+        assert_eq!(reset_cause(), ResetCause::Unknown);
+        assert_eq!(cpu::read!(RCC.csr.LSION), 1);
+    }
+}
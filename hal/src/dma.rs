@@ -0,0 +1,222 @@
+//! # DMA Module
+//!
+//! This module provides a minimal driver for the streams of the DMA1 and DMA2
+//! controllers on the STM32F405. It is used by other drivers, such as SPI, to move
+//! a block of memory to a peripheral without using the CPU.
+//!
+//! The request mapping of which stream and channel serve which peripheral is in
+//! tables 42 and 43 of the RM0090 Reference Manual.
+//!
+//! ## Structures
+//!
+//! - `Stream`: Represents a single stream of DMA1 or DMA2.
+//!
+//! ## Methods for `Stream`
+//!
+//! - `start_mem_to_periph`: Starts a memory to peripheral transfer.
+//! - `complete`: Checks if the transfer complete flag is set.
+//! - `error`: Checks if the transfer error or direct mode error flag is set.
+//! - `clear_flags`: Clears all the event flags of the stream.
+//! - `stop`: Disables the stream.
+//! - `enable_irq`: Enables the stream interrupt in the NVIC.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use hal::cpu;
+//! use hal::dma;
+//!
+//! cpu::init();
+//!
+//! let data = [0x55u8; 16];
+//! let stream = dma::Stream(cpu::DMA2, 3);
+//! let spi1_dr = 0x4001_300C;
+//! stream.start_mem_to_periph(3, spi1_dr, data.as_ptr() as u32, data.len() as u16, dma::Size::Byte);
+//! ```
+
+use core::ptr;
+
+use super::cpu;
+use super::cpu::*;
+
+use super::cpu::DMA::s0cr as scr;
+
+/// Registers for one DMA stream. The streams are laid out one after the other
+/// starting at offset 0x10 of the DMA controller.
+#[repr(C)]
+pub struct StreamReg {
+    pub cr: u32,
+    pub ndtr: u32,
+    pub par: u32,
+    pub m0ar: u32,
+    pub m1ar: u32,
+    pub fcr: u32,
+}
+
+/// Size of each data item moved by the DMA.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Size {
+    Byte = 0b00,
+    HalfWord = 0b01,
+    Word = 0b10,
+}
+
+/// A stream of a DMA controller, given by the controller and stream number (0 to 7).
+#[derive(Copy, Clone)]
+pub struct Stream(pub *mut cpu::DmaReg, pub u8);
+
+impl Stream {
+    fn regs(&self) -> *mut StreamReg {
+        debug_assert!(self.1 < 8);
+        (self.0 as usize + 0x10 + 0x18 * self.1 as usize) as *mut StreamReg
+    }
+
+    /// Bit offset of this stream's flags in the LISR/HISR and LIFCR/HIFCR registers.
+    fn flag_shift(&self) -> u8 {
+        [0, 6, 16, 22][(self.1 % 4) as usize]
+    }
+
+    fn flags(&self) -> u32 {
+        let dma = self.0;
+        let v = if self.1 < 4 {
+            cpu::read!(dma.lisr)
+        } else {
+            cpu::read!(dma.hisr)
+        };
+        (v >> self.flag_shift()) & 0x3D
+    }
+
+    /// Returns the interrupt of the stream.
+    pub fn irq(&self) -> Interrupt {
+        use Interrupt::*;
+        let dma1 = [
+            Dma1Stream0,
+            Dma1Stream1,
+            Dma1Stream2,
+            Dma1Stream3,
+            Dma1Stream4,
+            Dma1Stream5,
+            Dma1Stream6,
+            Dma1Stream7,
+        ];
+        let dma2 = [
+            Dma2Stream0,
+            Dma2Stream1,
+            Dma2Stream2,
+            Dma2Stream3,
+            Dma2Stream4,
+            Dma2Stream5,
+            Dma2Stream6,
+            Dma2Stream7,
+        ];
+        if self.0 == DMA1 {
+            dma1[self.1 as usize]
+        } else {
+            dma2[self.1 as usize]
+        }
+    }
+
+    /// Enables the interrupt for this stream in the NVIC.
+    pub fn enable_irq(&self) {
+        cpu::enable_irq(self.irq());
+    }
+
+    /// Returns true if the transfer complete flag is set.
+    pub fn complete(&self) -> bool {
+        (self.flags() & (1 << 5)) != 0
+    }
+
+    /// Returns true if the transfer error or direct mode error flag is set,
+    /// which stop the stream. The FIFO error flag is left out, as it can be
+    /// set in direct mode while the transfer carries on.
+    pub fn error(&self) -> bool {
+        (self.flags() & 0b1100) != 0
+    }
+
+    /// Clears the transfer complete, half transfer, and all error flags.
+    pub fn clear_flags(&self) {
+        let dma = self.0;
+        let v: u32 = 0x3D << self.flag_shift();
+        if self.1 < 4 {
+            cpu::write!(dma.lifcr, v);
+        } else {
+            cpu::write!(dma.hifcr, v);
+        }
+    }
+
+    /// Disables the stream and waits until the hardware has stopped it.
+    #[inline(never)]
+    pub fn stop(&self) {
+        let s = self.regs();
+        cpu::write!(s.cr.scr::EN, 0);
+
+        #[cfg(not(feature = "std"))]
+        while cpu::read!(s.cr.scr::EN) != 0 {}
+    }
+
+    /// Starts a transfer of `len` items of `size` from memory at `mem` to the
+    /// peripheral data register at `periph` using request `channel` of this stream.
+    ///
+    /// The memory must stay valid and unchanged until `complete` returns true.
+    /// The transfer complete and error interrupts of the stream are enabled, but
+    /// the NVIC is not changed; use `enable_irq` for that.
+    #[inline(never)]
+    pub fn start_mem_to_periph(&self, channel: u8, periph: u32, mem: u32, len: u16, size: Size) {
+        debug_assert!(channel < 8);
+
+        if self.0 == DMA1 {
+            cpu::write!(RCC.ahb1enr.DMA1EN, 1);
+        } else {
+            cpu::write!(RCC.ahb1enr.DMA2EN, 1);
+        }
+
+        self.stop();
+        self.clear_flags();
+
+        let s = self.regs();
+        cpu::write!(s.par, periph);
+        cpu::write!(s.m0ar, mem);
+        cpu::write!(s.ndtr, len as u32);
+
+        cpu::write!(s.cr.scr::CHSEL, channel as u32);
+        cpu::write!(s.cr.scr::DBM, 0b0); // no double buffer
+        cpu::write!(s.cr.scr::PL, 0b10); // high priority
+        cpu::write!(s.cr.scr::MSIZE, size as u32);
+        cpu::write!(s.cr.scr::PSIZE, size as u32);
+        cpu::write!(s.cr.scr::MINC, 0b1); // increment memory
+        cpu::write!(s.cr.scr::PINC, 0b0); // fixed peripheral
+        cpu::write!(s.cr.scr::CIRC, 0b0); // not circular
+        cpu::write!(s.cr.scr::DIR, 0b01); // memory to peripheral
+        cpu::write!(s.cr.scr::PFCTRL, 0b0); // DMA is flow controller
+
+        // direct mode, no FIFO
+        cpu::write!(s.fcr, 0);
+
+        cpu::write!(s.cr.scr::TCIE, 0b1); // transfer complete interrupt
+        cpu::write!(s.cr.scr::TEIE, 0b1); // transfer error interrupt
+        cpu::write!(s.cr.scr::DMEIE, 0b1); // direct mode error interrupt
+
+        cpu::write!(s.cr.scr::EN, 0b1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stream_irq() {
+        assert_eq!(Stream(DMA1, 0).irq().number(), 11);
+        assert_eq!(Stream(DMA1, 4).irq().number(), 15);
+        assert_eq!(Stream(DMA1, 7).irq().number(), 47);
+        assert_eq!(Stream(DMA2, 3).irq().number(), 59);
+        assert_eq!(Stream(DMA2, 7).irq().number(), 70);
+    }
+
+    #[test]
+    fn test_stream_regs() {
+        // stream 7 control register is at offset 0xB8 of the controller
+        let s = Stream(DMA2, 7);
+        assert_eq!(s.regs() as usize, DMA2 as usize + 0xB8);
+    }
+}
@@ -0,0 +1,499 @@
+//! # Flash Module
+//!
+//! This module erases and programs the internal flash of the STM32F405 and
+//! STM32F072. It is used to store persistent settings, message history, and
+//! firmware updates.
+//!
+//! The STM32F405 flash is divided into sectors of 16K, 64K, and 128K bytes and
+//! is programmed 8, 16, 32 bits at a time depending on the supply voltage
+//! (section 3.6 of RM0090). The STM32F072 flash is divided into 2K byte pages
+//! and is always programmed 16 bits at a time (section 3.2 of RM0091).
+//!
+//! After each program operation the data is read back and compared.
+//!
+//! ## Enums
+//!
+//! - `Error`: Reasons an erase or program operation failed.
+//! - `VoltageRange`: Supply voltage range which sets the program parallelism (STM32F405).
+//!
+//! ## Functions
+//!
+//! - `unlock`: Unlocks the flash control register so it can be erased and programmed.
+//! - `lock`: Locks the flash control register again.
+//! - `erase`: Erases the sector or page that contains an address.
+//! - `erase_sector`: Erases a sector by number (STM32F405).
+//! - `erase_page`: Erases the page at an address (STM32F072).
+//! - `program_half_word`: Programs 16 bits.
+//! - `program_word`: Programs 32 bits.
+//! - `write`: Programs a slice of bytes.
+//! - `read`: Reads flash into a slice of bytes.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use hal::cpu;
+//! use hal::flash;
+//!
+//! cpu::init();
+//!
+//! # #[cfg(feature = "stm32f072")]
+//! # let addr = 0x0801_F800;
+//! # #[cfg(feature = "stm32f405")]
+//! let addr = 0x080E_0000; // start of the last sector of the STM32F405
+//! flash::unlock();
+//! flash::erase(addr).unwrap();
+//! flash::write(addr, b"settings").unwrap();
+//! flash::lock();
+//!
+//! let mut data = [0u8; 8];
+//! flash::read(addr, &mut data);
+//! assert_eq!(&data, b"settings");
+//! ```
+
+use core::ptr;
+
+#[cfg(feature = "std")]
+extern crate std;
+
+use super::cpu;
+use super::cpu::*;
+
+use super::cpu::FLASH::sr;
+
+/// Address of the first byte of flash.
+pub const FLASH_BASE: u32 = 0x0800_0000;
+
+/// Size of the flash in bytes.
+#[cfg(feature = "stm32f405")]
+pub const FLASH_SIZE: u32 = 1024 * 1024;
+
+/// Size of the flash in bytes.
+#[cfg(feature = "stm32f072")]
+pub const FLASH_SIZE: u32 = 128 * 1024;
+
+/// Size of an erase page in bytes.
+#[cfg(feature = "stm32f072")]
+pub const PAGE_SIZE: u32 = 2 * 1024;
+
+const KEY1: u32 = 0x4567_0123;
+const KEY2: u32 = 0xCDEF_89AB;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Error {
+    /// The control register is locked; call `unlock` first.
+    Locked,
+    /// The address is outside the flash, or not aligned for the operation.
+    InvalidAddress,
+    /// The sector or page is write protected (WRPERR / WRPRT).
+    WriteProtected,
+    /// The program sequence was wrong (PGSERR), or on the STM32F072 the
+    /// location was not erased before programming (PGERR).
+    Sequence,
+    /// The program access was not aligned (PGAERR).
+    Alignment,
+    /// The program size did not match the parallelism (PGPERR).
+    Parallelism,
+    /// The operation failed with read protection active (OPERR).
+    Operation,
+    /// The data read back after programming did not match, usually because
+    /// the location was not erased. Has the address of the first mismatch.
+    Verify(u32),
+}
+
+/// Supply voltage range of the STM32F405, which limits how many bits can be
+/// programmed at once.
+#[cfg(feature = "stm32f405")]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum VoltageRange {
+    /// 1.8V to 2.1V, program 8 bits at a time.
+    V1_8,
+    /// 2.1V to 2.7V, program 16 bits at a time.
+    V2_1,
+    /// 2.7V to 3.6V, program 32 bits at a time.
+    V2_7,
+}
+
+#[cfg(feature = "stm32f405")]
+static VOLTAGE_RANGE: Global<VoltageRange> = Global::new(VoltageRange::V2_7);
+
+/// Sets the supply voltage range used to choose the program parallelism.
+/// The default is 2.7V to 3.6V.
+#[cfg(feature = "stm32f405")]
+pub fn set_voltage_range(range: VoltageRange) {
+    unsafe { VOLTAGE_RANGE.set(range) }
+}
+
+#[cfg(feature = "stm32f405")]
+fn voltage_range() -> VoltageRange {
+    unsafe { VOLTAGE_RANGE.get() }
+}
+
+/// Unlocks the flash control register so it can be erased and programmed.
+#[inline(never)]
+pub fn unlock() {
+    if !is_locked() {
+        return;
+    }
+    cpu::write!(FLASH.keyr, KEY1);
+    cpu::write!(FLASH.keyr, KEY2);
+
+    // the hardware clears LOCK after the key sequence
+    #[cfg(feature = "std")]
+    cpu::write!(FLASH.cr.LOCK, 0);
+}
+
+/// Locks the flash control register so it can not be erased or programmed
+/// until `unlock` is called.
+#[inline(never)]
+pub fn lock() {
+    cpu::write!(FLASH.cr.LOCK, 1);
+}
+
+pub fn is_locked() -> bool {
+    cpu::read!(FLASH.cr.LOCK) != 0
+}
+
+#[inline(always)]
+fn wait_not_busy() {
+    #[cfg(not(feature = "std"))]
+    while cpu::read!(FLASH.sr.BSY) != 0 {}
+}
+
+/// Checks the error flags after an operation and clears them.
+#[cfg(feature = "stm32f405")]
+fn check_errors() -> Result<(), Error> {
+    let flags = cpu::read!(FLASH.sr);
+
+    // write 1 to clear the end of operation and all the error flags
+    cpu::write!(FLASH.sr, flags & 0xF3);
+
+    if flags & (1 << sr::WRPERR) != 0 {
+        Err(Error::WriteProtected)
+    } else if flags & (1 << sr::PGSERR) != 0 {
+        Err(Error::Sequence)
+    } else if flags & (1 << sr::PGAERR) != 0 {
+        Err(Error::Alignment)
+    } else if flags & (1 << sr::PGPERR) != 0 {
+        Err(Error::Parallelism)
+    } else if flags & (1 << sr::OPERR) != 0 {
+        Err(Error::Operation)
+    } else {
+        Ok(())
+    }
+}
+
+/// Checks the error flags after an operation and clears them.
+#[cfg(feature = "stm32f072")]
+fn check_errors() -> Result<(), Error> {
+    let flags = cpu::read!(FLASH.sr);
+
+    // write 1 to clear the end of operation and the error flags
+    cpu::write!(FLASH.sr, flags & 0x34);
+
+    if flags & (1 << sr::WRPRT) != 0 {
+        Err(Error::WriteProtected)
+    } else if flags & (1 << sr::PGERR) != 0 {
+        Err(Error::Sequence)
+    } else {
+        Ok(())
+    }
+}
+
+fn check_range(addr: u32, len: u32) -> Result<(), Error> {
+    if addr < FLASH_BASE || (addr - FLASH_BASE) as u64 + len as u64 > FLASH_SIZE as u64 {
+        return Err(Error::InvalidAddress);
+    }
+    Ok(())
+}
+
+/// Returns the sector that contains `addr`, or None if it is not in flash.
+#[cfg(feature = "stm32f405")]
+pub fn sector(addr: u32) -> Option<u8> {
+    if check_range(addr, 1).is_err() {
+        return None;
+    }
+    let offset = addr - FLASH_BASE;
+    let n = match offset {
+        0..=0xFFFF => offset / 0x4000,
+        0x1_0000..=0x1_FFFF => 4,
+        _ => 4 + offset / 0x2_0000,
+    };
+    Some(n as u8)
+}
+
+/// Returns the start address and size in bytes of sector `n`.
+#[cfg(feature = "stm32f405")]
+pub fn sector_range(n: u8) -> (u32, u32) {
+    assert!(n < 12);
+    let n = n as u32;
+    match n {
+        0..=3 => (FLASH_BASE + n * 0x4000, 0x4000),
+        4 => (FLASH_BASE + 0x1_0000, 0x1_0000),
+        _ => (FLASH_BASE + (n - 4) * 0x2_0000, 0x2_0000),
+    }
+}
+
+/// Erases sector `n`, setting every byte in it to 0xFF.
+#[cfg(feature = "stm32f405")]
+#[inline(never)]
+pub fn erase_sector(n: u8) -> Result<(), Error> {
+    if n >= 12 {
+        return Err(Error::InvalidAddress);
+    }
+    if is_locked() {
+        return Err(Error::Locked);
+    }
+
+    wait_not_busy();
+    // clear flags left over from an earlier operation, which already reported them
+    let _ = check_errors();
+
+    // erase parallelism follows the voltage range as well
+    cpu::write!(FLASH.cr.PSIZE, psize(voltage_range()));
+    cpu::write!(FLASH.cr.SNB, n as u32);
+    cpu::write!(FLASH.cr.SER, 1);
+    cpu::write!(FLASH.cr.STRT, 1);
+
+    wait_not_busy();
+    cpu::write!(FLASH.cr.SER, 0);
+
+    let (start, size) = sector_range(n);
+    sim_erase(start, size);
+
+    check_errors()
+}
+
+/// Erases the page that starts at `addr`, setting every byte in it to 0xFF.
+#[cfg(feature = "stm32f072")]
+#[inline(never)]
+pub fn erase_page(addr: u32) -> Result<(), Error> {
+    check_range(addr, PAGE_SIZE)?;
+    if !(addr - FLASH_BASE).is_multiple_of(PAGE_SIZE) {
+        return Err(Error::InvalidAddress);
+    }
+    if is_locked() {
+        return Err(Error::Locked);
+    }
+
+    wait_not_busy();
+    // clear flags left over from an earlier operation, which already reported them
+    let _ = check_errors();
+
+    cpu::write!(FLASH.cr.PER, 1);
+    cpu::write!(FLASH.ar, addr);
+    cpu::write!(FLASH.cr.STRT, 1);
+
+    wait_not_busy();
+    cpu::write!(FLASH.cr.PER, 0);
+
+    sim_erase(addr, PAGE_SIZE);
+
+    check_errors()
+}
+
+/// Erases the sector or page that contains `addr`.
+pub fn erase(addr: u32) -> Result<(), Error> {
+    #[cfg(feature = "stm32f405")]
+    {
+        let n = sector(addr).ok_or(Error::InvalidAddress)?;
+        erase_sector(n)
+    }
+
+    #[cfg(feature = "stm32f072")]
+    {
+        check_range(addr, 1)?;
+        let page = addr - (addr - FLASH_BASE) % PAGE_SIZE;
+        erase_page(page)
+    }
+}
+
+#[cfg(feature = "stm32f405")]
+fn psize(range: VoltageRange) -> u32 {
+    match range {
+        VoltageRange::V1_8 => 0b00,
+        VoltageRange::V2_1 => 0b01,
+        VoltageRange::V2_7 => 0b10,
+    }
+}
+
+/// Programs one unit of `bits` (8, 16, or 32) at `addr` and checks the result.
+#[inline(never)]
+fn program(addr: u32, value: u32, bits: u32) -> Result<(), Error> {
+    if is_locked() {
+        return Err(Error::Locked);
+    }
+
+    wait_not_busy();
+    // clear flags left over from an earlier operation, which already reported them
+    let _ = check_errors();
+
+    #[cfg(feature = "stm32f405")]
+    cpu::write!(FLASH.cr.PSIZE, bits / 16);
+    cpu::write!(FLASH.cr.PG, 1);
+
+    match bits {
+        8 => mem_write(addr, value, 1),
+        16 => mem_write(addr, value, 2),
+        _ => mem_write(addr, value, 4),
+    }
+
+    wait_not_busy();
+    cpu::write!(FLASH.cr.PG, 0);
+
+    check_errors()?;
+
+    // verify
+    for i in 0..bits / 8 {
+        if mem_read(addr + i) != (value >> (8 * i)) as u8 {
+            return Err(Error::Verify(addr + i));
+        }
+    }
+    Ok(())
+}
+
+/// Programs the 16 bit `value` at the half-word aligned `addr`, which must be erased.
+pub fn program_half_word(addr: u32, value: u16) -> Result<(), Error> {
+    check_range(addr, 2)?;
+    if !addr.is_multiple_of(2) {
+        return Err(Error::InvalidAddress);
+    }
+
+    #[cfg(feature = "stm32f405")]
+    if voltage_range() == VoltageRange::V1_8 {
+        program(addr, value as u32 & 0xFF, 8)?;
+        return program(addr + 1, (value >> 8) as u32, 8);
+    }
+
+    program(addr, value as u32, 16)
+}
+
+/// Programs the 32 bit `value` at the word aligned `addr`, which must be erased.
+pub fn program_word(addr: u32, value: u32) -> Result<(), Error> {
+    check_range(addr, 4)?;
+    if !addr.is_multiple_of(4) {
+        return Err(Error::InvalidAddress);
+    }
+
+    #[cfg(feature = "stm32f405")]
+    if voltage_range() == VoltageRange::V2_7 {
+        return program(addr, value, 32);
+    }
+
+    program_half_word(addr, value as u16)?;
+    program_half_word(addr + 2, (value >> 16) as u16)
+}
+
+/// Programs `data` starting at `addr`, which must be erased. The widest
+/// program size allowed by the alignment and voltage range is used for each
+/// part. On the STM32F072 `addr` and the length of `data` must be even.
+#[inline(never)]
+pub fn write(addr: u32, data: &[u8]) -> Result<(), Error> {
+    check_range(addr, data.len() as u32)?;
+
+    let mut i = 0;
+    while i < data.len() {
+        let a = addr + i as u32;
+        let left = data.len() - i;
+
+        if a.is_multiple_of(4) && left >= 4 {
+            let v = u32::from_le_bytes([data[i], data[i + 1], data[i + 2], data[i + 3]]);
+            program_word(a, v)?;
+            i += 4;
+        } else if a.is_multiple_of(2) && left >= 2 {
+            program_half_word(a, u16::from_le_bytes([data[i], data[i + 1]]))?;
+            i += 2;
+        } else {
+            // the STM32F072 can only program half-words
+            #[cfg(feature = "stm32f072")]
+            return Err(Error::InvalidAddress);
+
+            #[cfg(feature = "stm32f405")]
+            {
+                program(a, data[i] as u32, 8)?;
+                i += 1;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Reads `data.len()` bytes of flash starting at `addr`.
+pub fn read(addr: u32, data: &mut [u8]) {
+    assert!(check_range(addr, data.len() as u32).is_ok());
+    for (i, d) in data.iter_mut().enumerate() {
+        *d = mem_read(addr + i as u32);
+    }
+}
+
+// The flash is memory mapped on the hardware. In the simulation it is a
+// buffer where erase sets bytes to 0xFF and programming can only clear bits,
+// the same as real flash.
+
+#[cfg(feature = "std")]
+fn sim_flash<R>(f: impl FnOnce(&mut [u8]) -> R) -> R {
+    super::sim::with_memory(FLASH_BASE, FLASH_SIZE as usize, 0xFF, f)
+}
+
+#[cfg(feature = "std")]
+fn sim_erase(start: u32, size: u32) {
+    let start = (start - FLASH_BASE) as usize;
+    sim_flash(|mem| mem[start..start + size as usize].fill(0xFF));
+}
+
+#[cfg(not(feature = "std"))]
+#[inline(always)]
+fn sim_erase(_start: u32, _size: u32) {}
+
+#[cfg(feature = "std")]
+fn mem_read(addr: u32) -> u8 {
+    sim_flash(|mem| mem[(addr - FLASH_BASE) as usize])
+}
+
+#[cfg(not(feature = "std"))]
+fn mem_read(addr: u32) -> u8 {
+    unsafe { ptr::read_volatile(addr as *const u8) }
+}
+
+#[cfg(feature = "std")]
+fn mem_write(addr: u32, value: u32, bytes: u32) {
+    sim_flash(|mem| {
+        for i in 0..bytes {
+            mem[(addr - FLASH_BASE + i) as usize] &= (value >> (8 * i)) as u8;
+        }
+    });
+}
+
+#[cfg(not(feature = "std"))]
+fn mem_write(addr: u32, value: u32, bytes: u32) {
+    unsafe {
+        match bytes {
+            1 => ptr::write_volatile(addr as *mut u8, value as u8),
+            2 => ptr::write_volatile(addr as *mut u16, value as u16),
+            _ => ptr::write_volatile(addr as *mut u32, value),
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "stm32f405")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sector() {
+        assert_eq!(sector(0x0800_0000), Some(0));
+        assert_eq!(sector(0x0800_7FFF), Some(1));
+        assert_eq!(sector(0x0801_0000), Some(4));
+        assert_eq!(sector(0x0802_0000), Some(5));
+        assert_eq!(sector(0x080F_FFFF), Some(11));
+        assert_eq!(sector(0x0810_0000), None);
+        assert_eq!(sector(0x0000_0000), None);
+    }
+
+    #[test]
+    fn test_sector_range() {
+        assert_eq!(sector_range(3), (0x0800_C000, 0x4000));
+        assert_eq!(sector_range(4), (0x0801_0000, 0x1_0000));
+        assert_eq!(sector_range(11), (0x080E_0000, 0x2_0000));
+    }
+}
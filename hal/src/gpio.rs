@@ -8,10 +8,15 @@
 //! ## Structures
 //!
 //! - `Pin`: Represents a GPIO pin and provides methods to configure and control it.
+//! - `Gpio`: A pin with its port, number and mode in the type.
+//! - `Edge`: Selects which edges of a pin trigger an external interrupt.
 //!
 //! ## Functions
 //!
 //! - `init`: Initializes the GPIO peripheral by enabling the necessary clocks.
+//! - `take`: Hands out all the typed pins, once.
+//! - `handle_exti_irq`: Calls the handlers of all pending EXTI lines. Call this from
+//!   every EXTI interrupt vector.
 //!
 //! ## Methods for `Pin`
 //!
@@ -23,6 +28,38 @@
 //! - `low`: Sets the pin state to low.
 //! - `high`: Sets the pin state to high.
 //! - `read`: Reads the current state of the pin.
+//! - `enable_interrupt`: Routes the pin to its EXTI line and registers an edge handler.
+//! - `disable_interrupt`: Masks the EXTI line of the pin and removes the handler.
+//! - `interrupt_pending`: Checks if the EXTI line of the pin has a pending edge.
+//! - `clear_interrupt`: Clears the pending edge of the EXTI line of the pin.
+//!
+//! Each EXTI line is shared by the pins with the same number on all ports, so
+//! only one of PA0, PB0, PC0, ... can have an interrupt at a time.
+//!
+//! ## Typed Pins
+//!
+//! `Gpio` is a pin with its port, number and mode in the type, named by the
+//! aliases `PA0` to `PC15`, and on the STM32F072 `PF0` to `PF15`. It starts
+//! `Unconfigured`, and `into_output`, `into_input`, `into_alternate` and the
+//! other conversions return it in the new mode, so an input can not be
+//! driven and an output can not be read. `pin` gives the untyped `Pin` for
+//! the functions that take one.
+//!
+//! The typed pins are only made by `take`, which returns them all as the
+//! fields of `Pins` the first time it is called, so two drivers can not be
+//! given the same pin.
+//!
+//! The drivers take their pins through `AltFn`, which is only implemented for
+//! the pins in the alternate function tables of `af`, so a board can not
+//! wire a pin to a signal it does not have:
+//!
+//! ```rust,compile_fail
+//! use hal::{gpio, uart};
+//!
+//! // PA10 is the RX of USART1, not the TX
+//! let pins = gpio::take().unwrap();
+//! uart::init1(115_200, pins.pa10, pins.pa9);
+//! ```
 //!
 //! ## Usage
 //!
@@ -56,6 +93,7 @@
 //! }
 //! ```
 
+use core::marker::PhantomData;
 use core::ptr;
 
 use super::cpu;
@@ -64,15 +102,80 @@ pub use super::cpu::*;
 
 #[cfg(feature = "stm32f405")]
 pub fn init() {
-    cpu::write!(RCC.ahb1enr[GPIOAEN;1], 1 );
-    cpu::write!(RCC.ahb1enr[GPIOBEN;1], 1 );
-    cpu::write!(RCC.ahb1enr[GPIOCEN;1], 1 );
+    cpu::write!(RCC.ahb1enr.GPIOAEN, 1);
+    cpu::write!(RCC.ahb1enr.GPIOBEN, 1);
+    cpu::write!(RCC.ahb1enr.GPIOCEN, 1);
 }
 
 #[cfg(feature = "stm32f072")]
 pub fn init() {
-    cpu::write!(RCC.ahbenr[IOPAEN;1], 1);
-    cpu::write!(RCC.ahbenr[IOPBEN;1], 1);
+    cpu::write!(RCC.ahbenr.IOPAEN, 1);
+    cpu::write!(RCC.ahbenr.IOPBEN, 1);
+    cpu::write!(RCC.ahbenr.IOPCEN, 1);
+    cpu::write!(RCC.ahbenr.IOPFEN, 1);
+}
+
+/// Edges of the input that trigger an external interrupt.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Edge {
+    Rising,
+    Falling,
+    Both,
+}
+
+/// The handler of each EXTI line, if it has one.
+type ExtiHandlers = [Option<fn()>; 16];
+
+static EXTI_HANDLERS: Global<ExtiHandlers> = Global::new([None; 16]);
+
+/// Returns the interrupt that serves EXTI `line`.
+#[cfg(feature = "stm32f405")]
+pub fn exti_irq(line: u8) -> Interrupt {
+    debug_assert!(line < 16);
+    match line {
+        0 => Interrupt::Exti0,
+        1 => Interrupt::Exti1,
+        2 => Interrupt::Exti2,
+        3 => Interrupt::Exti3,
+        4 => Interrupt::Exti4,
+        5..=9 => Interrupt::Exti9_5,
+        _ => Interrupt::Exti15_10,
+    }
+}
+
+/// Returns the interrupt that serves EXTI `line`.
+#[cfg(feature = "stm32f072")]
+pub fn exti_irq(line: u8) -> Interrupt {
+    debug_assert!(line < 16);
+    match line {
+        0..=1 => Interrupt::Exti0_1,
+        2..=3 => Interrupt::Exti2_3,
+        _ => Interrupt::Exti4_15,
+    }
+}
+
+/// Calls the registered handler of every EXTI line from 0 to 15 that is
+/// pending and unmasked, clearing the pending bit before each call.
+///
+/// The vectors for the shared lines (EXTI9_5, EXTI15_10, EXTI4_15, ...) can all
+/// call this same function.
+#[inline(never)]
+pub fn handle_exti_irq() {
+    let mut pending = cpu::read!(EXTI.pr) & cpu::read!(EXTI.imr) & 0xFFFF;
+
+    // lowest line first
+    while pending != 0 {
+        let line = pending.trailing_zeros() as usize;
+        pending &= pending - 1;
+
+        // write 1 to clear
+        cpu::write!(EXTI.pr, 1 << line);
+
+        let handler = unsafe { ptr::read_volatile(ptr::addr_of!((*EXTI_HANDLERS.as_ptr())[line])) };
+        if let Some(handler) = handler {
+            handler();
+        }
+    }
 }
 
 #[derive(Copy, Clone)]
@@ -82,7 +185,7 @@ impl Pin {
     #[allow(dead_code)]
     pub fn new(gpio: *mut cpu::GpioReg, p: u8) -> Pin {
         assert!(p < 16);
-        return Pin(gpio, p);
+        Pin(gpio, p)
     }
 
     #[inline(never)]
@@ -96,10 +199,10 @@ impl Pin {
         cpu::write!( gpio.moder[pin_num*2;2], 0b01);
 
         // set output as low
-        cpu::write!( gpio.odr[pin_num*1;1], 0b0);
+        cpu::write!( gpio.odr[pin_num;1], 0b0);
 
         // set as push-pull
-        cpu::write!( gpio.otyper[pin_num*1;1], 0b0);
+        cpu::write!( gpio.otyper[pin_num;1], 0b0);
 
         // set no pull up , no pull down
         cpu::write!( gpio.pupdr[pin_num*2;2], 0b00);
@@ -120,10 +223,10 @@ impl Pin {
         cpu::write!( gpio.moder[pin_num*2;2], 0b10);
 
         // set output as low
-        cpu::write!( gpio.odr[pin_num*1;1], 0b0);
+        cpu::write!( gpio.odr[pin_num;1], 0b0);
 
         // set as push-pull
-        cpu::write!( gpio.otyper[pin_num*1;1], 0b0);
+        cpu::write!( gpio.otyper[pin_num;1], 0b0);
 
         // set no pull up , no pull down
         cpu::write!( gpio.pupdr[pin_num*2;2], 0b00);
@@ -152,10 +255,10 @@ impl Pin {
         cpu::write!( gpio.moder[pin_num*2;2], 0b01);
 
         // Set output type to open-drain
-        cpu::write!(gpio.otyper[pin_num * 1; 1], 0b1);
+        cpu::write!(gpio.otyper[pin_num; 1], 0b1);
 
         // set output as high
-        cpu::write!( gpio.odr[pin_num*1;1], 0b0);
+        cpu::write!( gpio.odr[pin_num;1], 0b0);
 
         // set  pull up
         cpu::write!( gpio.pupdr[pin_num*2;2], 0b01);
@@ -176,6 +279,20 @@ impl Pin {
         cpu::write!( gpio.moder[pin_num*2;2], 0b00);
     }
 
+    /// Sets the pin to analog mode, which disconnects the input buffer and
+    /// draws the least current. Used to park pins before low power modes.
+    #[inline(never)]
+    pub fn analog(&self) {
+        let gpio = self.0;
+        let pin_num = self.1;
+
+        // set no pull up , no pull down
+        cpu::write!( gpio.pupdr[pin_num*2;2], 0b00);
+
+        // set mode to analog
+        cpu::write!( gpio.moder[pin_num*2;2], 0b11);
+    }
+
     #[inline(never)]
     #[allow(dead_code)]
     pub fn pulldown(&self) {
@@ -209,7 +326,7 @@ impl Pin {
         let gpio = self.0;
         let pin_num = self.1 as u32;
 
-        cpu::write!(gpio.bsrr, 0b1 << (pin_num + 0));
+        cpu::write!(gpio.bsrr, 0b1 << pin_num);
     }
 
     #[inline(always)]
@@ -217,9 +334,457 @@ impl Pin {
         let gpio = self.0;
         let pin_num = self.1;
 
-        let val = cpu::read!( gpio.idr[pin_num*1;1] );
+        let val = cpu::read!( gpio.idr[pin_num;1] );
         val != 0
     }
+    /// Returns the port index of the pin as used by SYSCFG (0 for GPIOA, 1 for GPIOB, ...).
+    pub fn port_index(&self) -> u32 {
+        ((self.0 as usize - GPIOA as usize) / 0x400) as u32
+    }
+
+    /// Enables the clock of the port of the pin, which must be on before the
+    /// pin can be configured.
+    #[cfg(feature = "stm32f405")]
+    pub fn enable_clock(&self) {
+        let port = self.port_index() as u8;
+        cpu::write!(RCC.ahb1enr[RCC::ahb1enr::GPIOAEN + port; 1], 1);
+    }
+
+    /// Enables the clock of the port of the pin, which must be on before the
+    /// pin can be configured.
+    #[cfg(feature = "stm32f072")]
+    pub fn enable_clock(&self) {
+        let port = self.port_index() as u8;
+        cpu::write!(RCC.ahbenr[RCC::ahbenr::IOPAEN + port; 1], 1);
+    }
+
+    /// Routes the pin to its EXTI line, selects the trigger `edge`, registers
+    /// `handler` and enables the interrupt in the EXTI and the NVIC.
+    ///
+    /// The pin should already be configured as an input. The `handler` runs in
+    /// interrupt context from `handle_exti_irq` with the pending bit cleared.
+    #[inline(never)]
+    pub fn enable_interrupt(&self, edge: Edge, handler: fn()) {
+        let line = self.1;
+        debug_assert!(line < 16);
+
+        // SYSCFG holds the EXTI port selection
+        cpu::write!(RCC.apb2enr.SYSCFGEN, 1);
+
+        // mask the line while changing it
+        cpu::write!(EXTI.imr[line;1], 0);
+
+        unsafe {
+            ptr::write_volatile(
+                ptr::addr_of_mut!((*EXTI_HANDLERS.as_ptr())[line as usize]),
+                Some(handler),
+            );
+        }
+
+        let port = self.port_index();
+        match line / 4 {
+            0 => cpu::write!(SYSCFG.exticr1[(line % 4)*4;4], port),
+            1 => cpu::write!(SYSCFG.exticr2[(line % 4)*4;4], port),
+            2 => cpu::write!(SYSCFG.exticr3[(line % 4)*4;4], port),
+            _ => cpu::write!(SYSCFG.exticr4[(line % 4)*4;4], port),
+        }
+
+        let rising = (edge == Edge::Rising || edge == Edge::Both) as u32;
+        let falling = (edge == Edge::Falling || edge == Edge::Both) as u32;
+        cpu::write!(EXTI.rtsr[line;1], rising);
+        cpu::write!(EXTI.ftsr[line;1], falling);
+
+        // drop any edge seen before the handler was set
+        self.clear_interrupt();
+
+        cpu::write!(EXTI.imr[line;1], 1);
+
+        cpu::enable_irq(exti_irq(line));
+    }
+
+    /// Masks the EXTI line of the pin and removes its handler. The NVIC is not
+    /// changed as the vector may be shared with other lines.
+    #[inline(never)]
+    pub fn disable_interrupt(&self) {
+        let line = self.1;
+        debug_assert!(line < 16);
+
+        cpu::write!(EXTI.imr[line;1], 0);
+        cpu::write!(EXTI.rtsr[line;1], 0);
+        cpu::write!(EXTI.ftsr[line;1], 0);
+        self.clear_interrupt();
+
+        unsafe {
+            ptr::write_volatile(
+                ptr::addr_of_mut!((*EXTI_HANDLERS.as_ptr())[line as usize]),
+                None,
+            );
+        }
+    }
+
+    /// Returns true if an edge has been detected on the EXTI line of the pin and
+    /// not yet cleared.
+    #[inline(always)]
+    pub fn interrupt_pending(&self) -> bool {
+        let line = self.1;
+        cpu::read!(EXTI.pr[line;1]) != 0
+    }
+
+    /// Clears the pending edge of the EXTI line of the pin.
+    #[inline(always)]
+    pub fn clear_interrupt(&self) {
+        let line = self.1 as u32;
+        // write 1 to clear, a read-modify-write would clear the other lines
+        cpu::write!(EXTI.pr, 1 << line);
+    }
+}
+
+/// Mode of a typed pin that has not been set up since reset.
+pub struct Unconfigured;
+
+/// Mode of a typed pin set up as an input with a pull-down resistor.
+pub struct Input;
+
+/// Mode of a typed pin set up as a push-pull output.
+pub struct Output;
+
+/// Mode of a typed pin set up as an open-drain output with a pull-up resistor.
+pub struct OpenDrain;
+
+/// Mode of a typed pin set up as analog.
+pub struct Analog;
+
+/// Mode of a typed pin routed to a peripheral with alternate function `AF`.
+pub struct Alternate<const AF: u8>;
+
+/// A pin with its port, number and mode in the type, such as `PA9<Output>`.
+///
+/// The port is the letter of its GPIO, so `Gpio<'A', 9>` is PA9. The
+/// conversions set up the pin and return it in its new mode, so only the
+/// methods of that mode can be called. Use the aliases such as `PA9` rather
+/// than this type, as they only exist for the ports of the chip.
+pub struct Gpio<const P: char, const N: u8, M = Unconfigured> {
+    mode: PhantomData<M>,
+}
+
+/// Returns the registers of the GPIO port with the letter `port`.
+const fn port_regs(port: char) -> *mut cpu::GpioReg {
+    match port {
+        'A' => GPIOA,
+        'B' => GPIOB,
+        'C' => GPIOC,
+        #[cfg(feature = "stm32f072")]
+        'F' => GPIOF,
+        _ => panic!("No such GPIO port"),
+    }
+}
+
+impl<const P: char, const N: u8> Gpio<P, N> {
+    /// Returns the pin in the state it has after reset. Outside of the
+    /// crate the pins come from `take`, so each has a single owner.
+    pub(crate) const fn new() -> Self {
+        Gpio { mode: PhantomData }
+    }
+}
+
+impl<const P: char, const N: u8, M> Gpio<P, N, M> {
+    /// Returns the pin without its type, for the functions that take a `Pin`.
+    pub const fn pin(&self) -> Pin {
+        Pin(port_regs(P), N)
+    }
+
+    fn into_mode<T>(self) -> Gpio<P, N, T> {
+        Gpio { mode: PhantomData }
+    }
+
+    /// Sets the pin up as a push-pull output, driven low.
+    pub fn into_output(self) -> Gpio<P, N, Output> {
+        self.pin().output();
+        self.into_mode()
+    }
+
+    /// Sets the pin up as an open-drain output with a pull-up resistor.
+    pub fn into_open_drain(self) -> Gpio<P, N, OpenDrain> {
+        self.pin().open_drain();
+        self.into_mode()
+    }
+
+    /// Sets the pin up as an input with a pull-down resistor.
+    pub fn into_input(self) -> Gpio<P, N, Input> {
+        self.pin().input();
+        self.into_mode()
+    }
+
+    /// Sets the pin up as analog, see `Pin::analog`.
+    pub fn into_analog(self) -> Gpio<P, N, Analog> {
+        self.pin().analog();
+        self.into_mode()
+    }
+
+    /// Routes the pin to a peripheral with alternate function `AF`. The
+    /// drivers do this themselves from the tables in `af`, so this is for
+    /// peripherals that do not have a driver yet.
+    pub fn into_alternate<const AF: u8>(self) -> Gpio<P, N, Alternate<AF>> {
+        self.pin().alt_fun(AF, false);
+        self.into_mode()
+    }
+}
+
+impl<const P: char, const N: u8> Gpio<P, N, Output> {
+    pub fn low(&self) {
+        self.pin().low();
+    }
+
+    pub fn high(&self) {
+        self.pin().high();
+    }
+}
+
+impl<const P: char, const N: u8> Gpio<P, N, OpenDrain> {
+    /// Pulls the pin low.
+    pub fn low(&self) {
+        self.pin().low();
+    }
+
+    /// Lets the pin float up to high.
+    pub fn high(&self) {
+        self.pin().high();
+    }
+}
+
+impl<const P: char, const N: u8> Gpio<P, N, Input> {
+    pub fn read(&self) -> bool {
+        self.pin().read()
+    }
+
+    pub fn pulldown(&self) {
+        self.pin().pulldown();
+    }
+
+    pub fn pullup(&self) {
+        self.pin().pullup();
+    }
+}
+
+macro_rules! pins {
+    ($(#[$cfg:meta] $port:literal: $($name:ident $field:ident = $n:literal),+;)+) => {
+        $($(
+            #[doc = concat!("Pin ", stringify!($name), ".")]
+            #[$cfg]
+            pub type $name<M = Unconfigured> = Gpio<$port, $n, M>;
+        )+)+
+
+        /// All the pins of the chip, each in the state it has after reset.
+        /// They are handed out once by `take`.
+        pub struct Pins {
+            $($(
+                #[$cfg]
+                pub $field: $name,
+            )+)+
+        }
+
+        impl Pins {
+            const fn new() -> Self {
+                Pins {
+                    $($(
+                        #[$cfg]
+                        $field: Gpio::new(),
+                    )+)+
+                }
+            }
+        }
+    };
+}
+
+pins! {
+    #[cfg(any(feature = "stm32f072", feature = "stm32f405"))]
+    'A': PA0 pa0 = 0, PA1 pa1 = 1, PA2 pa2 = 2, PA3 pa3 = 3, PA4 pa4 = 4, PA5 pa5 = 5,
+        PA6 pa6 = 6, PA7 pa7 = 7, PA8 pa8 = 8, PA9 pa9 = 9, PA10 pa10 = 10, PA11 pa11 = 11,
+        PA12 pa12 = 12, PA13 pa13 = 13, PA14 pa14 = 14, PA15 pa15 = 15;
+    #[cfg(any(feature = "stm32f072", feature = "stm32f405"))]
+    'B': PB0 pb0 = 0, PB1 pb1 = 1, PB2 pb2 = 2, PB3 pb3 = 3, PB4 pb4 = 4, PB5 pb5 = 5,
+        PB6 pb6 = 6, PB7 pb7 = 7, PB8 pb8 = 8, PB9 pb9 = 9, PB10 pb10 = 10, PB11 pb11 = 11,
+        PB12 pb12 = 12, PB13 pb13 = 13, PB14 pb14 = 14, PB15 pb15 = 15;
+    #[cfg(any(feature = "stm32f072", feature = "stm32f405"))]
+    'C': PC0 pc0 = 0, PC1 pc1 = 1, PC2 pc2 = 2, PC3 pc3 = 3, PC4 pc4 = 4, PC5 pc5 = 5,
+        PC6 pc6 = 6, PC7 pc7 = 7, PC8 pc8 = 8, PC9 pc9 = 9, PC10 pc10 = 10, PC11 pc11 = 11,
+        PC12 pc12 = 12, PC13 pc13 = 13, PC14 pc14 = 14, PC15 pc15 = 15;
+    #[cfg(feature = "stm32f072")]
+    'F': PF0 pf0 = 0, PF1 pf1 = 1, PF2 pf2 = 2, PF3 pf3 = 3, PF4 pf4 = 4, PF5 pf5 = 5,
+        PF6 pf6 = 6, PF7 pf7 = 7, PF8 pf8 = 8, PF9 pf9 = 9, PF10 pf10 = 10, PF11 pf11 = 11,
+        PF12 pf12 = 12, PF13 pf13 = 13, PF14 pf14 = 14, PF15 pf15 = 15;
+}
+
+static TAKEN: Global<bool> = Global::new(false);
+
+/// Returns all the pins of the chip the first time it is called, and None
+/// after that, so each pin has a single owner.
+pub fn take() -> Option<Pins> {
+    cpu::critical_section(|| unsafe {
+        if TAKEN.get() {
+            return None;
+        }
+        TAKEN.set(true);
+        Some(Pins::new())
+    })
+}
+
+/// Pins that can serve the peripheral signal `S`, such as `af::Usart1Tx`,
+/// with the alternate function that routes it to the pin.
+///
+/// This is only implemented for the pins in the tables of `af`, so a driver
+/// that takes its pins through it can not be given a pin that does not work.
+pub trait AltFn<S> {
+    const AF: u8;
+
+    /// Returns the pin without its type.
+    fn pin(&self) -> Pin;
+}
+
+/// Returns the pin and alternate function that routes signal `S` to it, for
+/// the drivers that take the pin as `impl AltFn<S>`.
+pub(crate) fn alt_fn<S, T: AltFn<S>>(pin: &T) -> (Pin, u8) {
+    (pin.pin(), T::AF)
+}
+
+/// A pin that is not connected, for a signal a driver can do without, such
+/// as the MISO of an SPI bus that only transmits.
+pub struct NoPin;
+
+/// A pin that can serve the peripheral signal `S`, or `NoPin`.
+pub trait MaybeAltFn<S> {
+    /// Returns the pin without its type and its alternate function, or
+    /// None for `NoPin`.
+    fn alt_fn(&self) -> Option<(Pin, u8)>;
+}
+
+impl<S> MaybeAltFn<S> for NoPin {
+    fn alt_fn(&self) -> Option<(Pin, u8)> {
+        None
+    }
+}
+
+macro_rules! af_table {
+    ($($signal:ident: $($pin:ident = $af:literal),+;)+) => {
+        $(
+            $(
+                impl<M> AltFn<$signal> for $pin<M> {
+                    const AF: u8 = $af;
+
+                    fn pin(&self) -> Pin {
+                        Gpio::pin(self)
+                    }
+                }
+
+                impl<M> MaybeAltFn<$signal> for $pin<M> {
+                    fn alt_fn(&self) -> Option<(Pin, u8)> {
+                        Some((Gpio::pin(self), $af))
+                    }
+                }
+            )+
+        )+
+    };
+}
+
+/// The peripheral signals that pins can be routed to, and the table of the
+/// pins that can serve each on the chip.
+///
+/// The STM32F405 table is from table 9 "Alternate function mapping" of the
+/// datasheet stm32f405zg.pdf, and the STM32F072 table from tables 14 to 16
+/// "Alternate functions" of the datasheet stm32f072rb.pdf. Only the
+/// signals of the drivers in this crate are listed.
+pub mod af {
+    use super::*;
+
+    #[cfg(feature = "stm32f072")]
+    pub struct Mco;
+
+    pub struct Usart1Tx;
+    pub struct Usart1Rx;
+    #[cfg(feature = "stm32f072")]
+    pub struct Usart2Tx;
+    #[cfg(feature = "stm32f072")]
+    pub struct Usart2Rx;
+
+    pub struct Spi1Sck;
+    pub struct Spi1Miso;
+    pub struct Spi1Mosi;
+    pub struct Spi2Sck;
+    pub struct Spi2Miso;
+    pub struct Spi2Mosi;
+    #[cfg(feature = "stm32f405")]
+    pub struct Spi3Sck;
+    #[cfg(feature = "stm32f405")]
+    pub struct Spi3Miso;
+    #[cfg(feature = "stm32f405")]
+    pub struct Spi3Mosi;
+
+    pub struct Tim1Ch1;
+    pub struct Tim1Ch2;
+    pub struct Tim1Ch3;
+    pub struct Tim1Ch4;
+    pub struct Tim3Ch1;
+    pub struct Tim3Ch2;
+    pub struct Tim3Ch3;
+    pub struct Tim3Ch4;
+    #[cfg(feature = "stm32f405")]
+    pub struct Tim4Ch1;
+    #[cfg(feature = "stm32f405")]
+    pub struct Tim4Ch2;
+    #[cfg(feature = "stm32f405")]
+    pub struct Tim4Ch3;
+    #[cfg(feature = "stm32f405")]
+    pub struct Tim4Ch4;
+
+    #[cfg(feature = "stm32f405")]
+    af_table! {
+        Usart1Tx: PA9 = 7, PB6 = 7;
+        Usart1Rx: PA10 = 7, PB7 = 7;
+        Spi1Sck: PA5 = 5, PB3 = 5;
+        Spi1Miso: PA6 = 5, PB4 = 5;
+        Spi1Mosi: PA7 = 5, PB5 = 5;
+        Spi2Sck: PB10 = 5, PB13 = 5;
+        Spi2Miso: PB14 = 5, PC2 = 5;
+        Spi2Mosi: PB15 = 5, PC3 = 5;
+        Spi3Sck: PB3 = 6, PC10 = 6;
+        Spi3Miso: PB4 = 6, PC11 = 6;
+        Spi3Mosi: PB5 = 6, PC12 = 6;
+        Tim1Ch1: PA8 = 1;
+        Tim1Ch2: PA9 = 1;
+        Tim1Ch3: PA10 = 1;
+        Tim1Ch4: PA11 = 1;
+        Tim3Ch1: PA6 = 2, PB4 = 2, PC6 = 2;
+        Tim3Ch2: PA7 = 2, PB5 = 2, PC7 = 2;
+        Tim3Ch3: PB0 = 2, PC8 = 2;
+        Tim3Ch4: PB1 = 2, PC9 = 2;
+        Tim4Ch1: PB6 = 2;
+        Tim4Ch2: PB7 = 2;
+        Tim4Ch3: PB8 = 2;
+        Tim4Ch4: PB9 = 2;
+    }
+
+    #[cfg(feature = "stm32f072")]
+    af_table! {
+        Mco: PA8 = 0;
+        Usart1Tx: PA9 = 1, PB6 = 0;
+        Usart1Rx: PA10 = 1, PB7 = 0;
+        Usart2Tx: PA2 = 1, PA14 = 1;
+        Usart2Rx: PA3 = 1, PA15 = 1;
+        Spi1Sck: PA5 = 0, PB3 = 0;
+        Spi1Miso: PA6 = 0, PB4 = 0;
+        Spi1Mosi: PA7 = 0, PB5 = 0;
+        Spi2Sck: PB10 = 5, PB13 = 0;
+        Spi2Miso: PB14 = 0, PC2 = 1;
+        Spi2Mosi: PB15 = 0, PC3 = 1;
+        Tim1Ch1: PA8 = 2;
+        Tim1Ch2: PA9 = 2;
+        Tim1Ch3: PA10 = 2;
+        Tim1Ch4: PA11 = 2;
+        Tim3Ch1: PA6 = 1, PB4 = 1, PC6 = 0;
+        Tim3Ch2: PA7 = 1, PB5 = 1, PC7 = 0;
+        Tim3Ch3: PB0 = 1, PC8 = 0;
+        Tim3Ch4: PB1 = 1, PC9 = 0;
+    }
 }
 
 #[cfg(test)]
@@ -230,13 +795,18 @@ mod tests {
     fn test_pin_output() {
         let gpio = 0x40020000 as *mut cpu::GpioReg; // Example GPIO base address
         let pin = Pin::new(gpio, 5);
+        crate::sim::start_trace();
         pin.output();
+        let trace = crate::sim::stop_trace();
 
         // Verify that the pin is set to output mode
-        assert_eq!(cpu::read!(gpio.moder[5 * 2; 2]), 0b00);
+        assert_eq!(cpu::read!(gpio.moder[5 * 2; 2]), 0b01);
+        let moder = unsafe { ptr::addr_of_mut!((*gpio).moder) };
+        let mode = crate::sim::Event::write_bits(moder, 0b11 << 10, 0b01 << 10);
+        trace.assert_in_order(&[mode]);
 
         // Verify that the pin is set to low
-        assert_eq!(cpu::read!(gpio.odr[5 * 1; 1]), 0b0);
+        assert_eq!(cpu::read!(gpio.odr[5; 1]), 0b0);
     }
 
     #[test]
@@ -250,15 +820,103 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "stm32f405")]
     fn test_pin_read() {
         let gpio = 0x40020000 as *mut cpu::GpioReg; // Example GPIO base address
         let pin = Pin::new(gpio, 5);
 
         // Simulate pin state
-        cpu::write!(gpio.idr[5 * 1; 1], 1);
-        assert_eq!(pin.read(), false);
+        cpu::write!(gpio.idr[5; 1], 1);
+        assert!(!pin.read());
+
+        cpu::write!(gpio.idr[5; 1], 0);
+        assert!(!pin.read());
+    }
+
+    #[test]
+    fn test_pin_read_input() {
+        let _sim = crate::sim::Guard::new();
+        let pin = Pin::new(GPIOA, 7);
+        pin.input();
+
+        crate::sim::with_model(GPIOA, |gpio: &mut crate::sim::Gpio| gpio.input(7, true));
+        assert!(pin.read());
+        assert!(!Pin::new(GPIOA, 6).read());
+
+        crate::sim::with_model(GPIOA, |gpio: &mut crate::sim::Gpio| gpio.input(7, false));
+        assert!(!pin.read());
+    }
 
-        cpu::write!(gpio.idr[5 * 1; 1], 0);
-        assert_eq!(!pin.read(), true);
+    #[test]
+    fn test_take_once() {
+        let _sim = crate::sim::Guard::new();
+        let pins = take().unwrap();
+        assert_eq!(pins.pc13.pin().port_index(), 2);
+        assert_eq!(pins.pc13.pin().1, 13);
+        assert!(take().is_none());
+    }
+
+    #[test]
+    fn test_typed_pin() {
+        let _sim = crate::sim::Guard::new();
+        let pin = PB4::new();
+        assert_eq!(pin.pin().port_index(), 1);
+        assert_eq!(pin.pin().1, 4);
+
+        let pin = pin.into_output();
+        pin.high();
+        assert_eq!(cpu::read!(GPIOB.moder[4 * 2; 2]), 0b01);
+        assert_eq!(cpu::read!(GPIOB.bsrr), 1 << 4);
+
+        let pin = pin.into_input();
+        crate::sim::with_model(GPIOB, |gpio: &mut crate::sim::Gpio| gpio.input(4, true));
+        assert!(pin.read());
+        assert_eq!(cpu::read!(GPIOB.pupdr[4 * 2; 2]), 0b10);
+
+        let _pin = pin.into_alternate::<5>();
+        assert_eq!(cpu::read!(GPIOB.moder[4 * 2; 2]), 0b10);
+        assert_eq!(cpu::read!(GPIOB.afrl[4 * 4; 4]), 5);
+    }
+
+    #[test]
+    #[cfg(feature = "stm32f405")]
+    fn test_af_table() {
+        assert_eq!(<PA9 as AltFn<af::Usart1Tx>>::AF, 7);
+        assert_eq!(<PB3 as AltFn<af::Spi1Sck>>::AF, 5);
+        assert_eq!(<PB3 as AltFn<af::Spi3Sck>>::AF, 6);
+        assert_eq!(<PA7 as AltFn<af::Tim3Ch2>>::AF, 2);
+        assert_eq!(<PA8 as AltFn<af::Tim1Ch1>>::AF, 1);
+        assert!(MaybeAltFn::<af::Spi1Miso>::alt_fn(&NoPin).is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "stm32f405")]
+    fn test_port_index() {
+        assert_eq!(Pin::new(cpu::GPIOA, 0).port_index(), 0);
+        assert_eq!(Pin::new(cpu::GPIOB, 3).port_index(), 1);
+        assert_eq!(Pin::new(cpu::GPIOC, 13).port_index(), 2);
+    }
+
+    #[test]
+    fn test_enable_clock() {
+        let _sim = crate::sim::Guard::new();
+
+        Pin::new(cpu::GPIOB, 6).enable_clock();
+        #[cfg(feature = "stm32f405")]
+        assert_eq!(cpu::read!(RCC.ahb1enr), 1 << RCC::ahb1enr::GPIOBEN);
+        #[cfg(feature = "stm32f072")]
+        {
+            let ports = cpu::read!(RCC.ahbenr) & (0x7F << RCC::ahbenr::IOPAEN);
+            assert_eq!(ports, 1 << RCC::ahbenr::IOPBEN);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "stm32f405")]
+    fn test_exti_irq() {
+        assert_eq!(exti_irq(0).number(), 6);
+        assert_eq!(exti_irq(4).number(), 10);
+        assert_eq!(exti_irq(7).number(), 23);
+        assert_eq!(exti_irq(15).number(), 40);
     }
 }
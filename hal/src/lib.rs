@@ -28,11 +28,22 @@
 //! - `board`: Board-specific configurations and initializations.
 //! - `clock`: Clock configuration and management.
 //! - `cpu`: Function to access registers on the CPU
+//! - `crc`: CRC-32 checksums with the CRC unit.
+//! - `delay`: Blocking delays and the SysTick tick.
+//! - `device`: Unique ID, flash size, and the cause of the last reset.
+//! - `dma`: Direct Memory Access (DMA) stream management.
+//! - `flash`: Erasing and programming the internal flash.
 //! - `gpio`: General Purpose Input/Output (GPIO) management.
+//! - `power`: Sleep, stop, and standby low power modes.
+//! - `rtc`: Real time clock, backup registers, and backup SRAM.
 //! - `semihost`: Semihosting support.
+//! - `sim`: Simulated registers and peripheral models (`std` feature).
+//! - `spi`: Serial Peripheral Interface (SPI) bus management.
 //! - `svd`: Constants from the System View Description (SVD)
+//! - `tim`: PWM outputs and input capture on the timers.
 //! - `timer`: Timer configuration and management.
 //! - `uart`: Serial port Receiver/Transmitter (UART) management.
+//! - `usb`: USB CDC-ACM serial port device (STM32F405).
 //!
 //! ## Usage
 //!
@@ -53,10 +64,24 @@
 //pub mod board;
 pub mod clock;
 pub mod cpu;
+pub mod crc;
+pub mod delay;
+pub mod device;
+#[cfg(feature = "stm32f405")]
+pub mod dma;
+pub mod flash;
 pub mod gpio;
+pub mod power;
+mod ring;
+pub mod rtc;
+#[cfg(feature = "std")]
+pub mod sim;
 
+pub mod tim;
 pub mod timer;
 pub mod uart;
+#[cfg(feature = "stm32f405")]
+pub mod usb;
 
 pub mod spi;
 pub mod svd_stm32f0x2;
@@ -78,6 +103,5 @@ pub fn init(hse_clk_freq: u32) {
 #[inline(never)]
 /// Validates the hardware has been correctly initialized.
 pub fn validate() {
-    #[cfg(not(feature = "std"))]
     clock::validate();
 }
@@ -0,0 +1,161 @@
+//! # Power Module
+//!
+//! This module puts the CPU into its low power modes (section 5.3 of RM0090
+//! and section 6.3 of RM0091).
+//!
+//! - Sleep: The CPU clock stops and any interrupt wakes it.
+//! - Stop: All clocks stop and the regulator is in low power mode. The RAM
+//!   and registers are kept. Any EXTI line wakes it, including GPIO interrupts
+//!   from `Pin::enable_interrupt` and the RTC alarm and wakeup timer from the
+//!   `rtc` module. The CPU wakes on the HSI, so interrupts are masked while
+//!   in stop and the clocks are restored with `clock::restore` before the
+//!   interrupt that woke it is handled.
+//! - Standby: Everything but the backup domain is powered off and the chip
+//!   resets when woken. Only the WKUP pin (PA0), the RTC alarm, and the RTC
+//!   wakeup timer can wake it.
+//!
+//! The `timer` clock does not count while in stop or standby.
+//!
+//! The IWDG keeps counting in stop and standby. Once it is started, stop
+//! needs the RTC wakeup timer set to a period shorter than the IWDG timeout,
+//! so the chip wakes in time for `enter` to feed the watchdog, and an IWDG
+//! reset ends standby like any other wakeup.
+//!
+//! ## Enums
+//!
+//! - `Mode`: Selects the sleep, stop, or standby mode.
+//!
+//! ## Functions
+//!
+//! - `enter`: Enters a low power mode and waits to be woken.
+//! - `enable_wakeup_pin`: Enables the WKUP pin to wake from standby.
+//! - `woke_from_standby`: Checks if the last reset was a wake from standby.
+//! - `clear_flags`: Clears the standby and wakeup flags.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use hal::power::{self, Mode};
+//! use hal::rtc::{self, ClockSource};
+//!
+//! fn tick() {}
+//!
+//! hal::init(16_000_000);
+//! rtc::init(ClockSource::Lse);
+//!
+//! // wake up every 10 seconds
+//! rtc::set_wakeup(10, tick);
+//! power::enter(Mode::Stop);
+//! ```
+
+use core::ptr;
+
+use super::clock;
+use super::cpu;
+use super::cpu::*;
+use super::rtc;
+use super::watch_dog;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Mode {
+    Sleep,
+    Stop,
+    Standby,
+}
+
+/// Waits for an interrupt. This does nothing in the simulation.
+#[inline(always)]
+fn wfi() {
+    #[cfg(target_arch = "arm")]
+    unsafe {
+        core::arch::asm!("dsb", "wfi", "isb");
+    }
+}
+
+/// Enters `mode` and waits for a wake up event.
+///
+/// This returns after the interrupt that woke the CPU from sleep or stop has
+/// been handled, which after stop is once the clocks are restored. On hardware it does not return from standby as the chip
+/// resets when woken.
+///
+/// Panics if the IWDG is running and stop would outlast its timeout, as the
+/// RTC wakeup timer is off or too slow.
+#[inline(never)]
+pub fn enter(mode: Mode) {
+    cpu::write!(RCC.apb1enr.PWREN, 1);
+
+    match mode {
+        Mode::Sleep => {
+            cpu::write!(SCB.scr.SLEEPDEEP, 0);
+            wfi();
+        }
+        Mode::Stop => {
+            let iwdg = watch_dog::iwdg_timeout_ms();
+            if let Some(timeout_ms) = iwdg {
+                let period_ms = rtc::wakeup_period().map(|s| s as u64 * 1000);
+                assert!(
+                    period_ms.is_some_and(|p| p < timeout_ms as u64),
+                    "stop would outlast the IWDG"
+                );
+                watch_dog::iwdg_alive();
+            }
+
+            cpu::write!(PWR.cr.PDDS, 0);
+            cpu::write!(PWR.cr.LPDS, 1); // regulator in low power mode
+            cpu::write!(SCB.scr.SLEEPDEEP, 1);
+
+            // a masked interrupt still ends WFI, but its handler only runs
+            // once the clocks are back from the HSI
+            cpu::critical_section(|| {
+                wfi();
+
+                if iwdg.is_some() {
+                    watch_dog::iwdg_alive();
+                }
+                cpu::write!(SCB.scr.SLEEPDEEP, 0);
+                clock::restore();
+            });
+        }
+        Mode::Standby => {
+            cpu::write!(PWR.cr.PDDS, 1);
+            // a wakeup flag left set stops the chip entering standby
+            rtc::clear_flags();
+            cpu::write!(PWR.cr.CWUF, 1);
+            cpu::write!(SCB.scr.SLEEPDEEP, 1);
+
+            wfi();
+        }
+    }
+}
+
+/// Enables or disables the WKUP pin (PA0) as a rising edge wake up from
+/// standby. When enabled the pin is forced to an input with a pull down.
+#[cfg(feature = "stm32f405")]
+#[inline(never)]
+pub fn enable_wakeup_pin(enable: bool) {
+    cpu::write!(RCC.apb1enr.PWREN, 1);
+    cpu::write!(PWR.csr.EWUP, enable as u32);
+}
+
+/// Enables or disables the WKUP1 pin (PA0) as a rising edge wake up from
+/// standby. When enabled the pin is forced to an input with a pull down.
+#[cfg(feature = "stm32f072")]
+#[inline(never)]
+pub fn enable_wakeup_pin(enable: bool) {
+    cpu::write!(RCC.apb1enr.PWREN, 1);
+    cpu::write!(PWR.csr.EWUP1, enable as u32);
+}
+
+/// Returns true if the chip was reset by waking from standby.
+pub fn woke_from_standby() -> bool {
+    cpu::write!(RCC.apb1enr.PWREN, 1);
+    cpu::read!(PWR.csr.SBF) != 0
+}
+
+/// Clears the standby and wakeup flags, so `woke_from_standby` is false until
+/// the next time the chip wakes from standby.
+pub fn clear_flags() {
+    cpu::write!(RCC.apb1enr.PWREN, 1);
+    cpu::write!(PWR.cr.CSBF, 1);
+    cpu::write!(PWR.cr.CWUF, 1);
+}
@@ -0,0 +1,71 @@
+//! # Ring Module
+//!
+//! This module provides the byte FIFO that the drivers use to pass data
+//! between an interrupt handler and the functions that read and write it.
+//! It does no locking, so the driver masks its interrupt while it uses it.
+
+/// Fixed size byte FIFO.
+#[derive(Clone)]
+pub(crate) struct Ring<const N: usize> {
+    buf: [u8; N],
+    head: usize,
+    len: usize,
+}
+
+impl<const N: usize> Ring<N> {
+    pub const fn new() -> Self {
+        Ring {
+            buf: [0; N],
+            head: 0,
+            len: 0,
+        }
+    }
+
+    pub fn push(&mut self, byte: u8) -> bool {
+        if self.len == N {
+            return false;
+        }
+        self.buf[(self.head + self.len) % N] = byte;
+        self.len += 1;
+        true
+    }
+
+    pub fn pop(&mut self) -> Option<u8> {
+        if self.len == 0 {
+            return None;
+        }
+        let byte = self.buf[self.head];
+        self.head = (self.head + 1) % N;
+        self.len -= 1;
+        Some(byte)
+    }
+
+    pub fn free(&self) -> usize {
+        N - self.len
+    }
+
+    pub fn clear(&mut self) {
+        self.head = 0;
+        self.len = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ring() {
+        let mut ring: Ring<4> = Ring::new();
+        assert!(ring.push(1) && ring.push(2) && ring.push(3) && ring.push(4));
+        assert!(!ring.push(5));
+        assert_eq!(ring.pop(), Some(1));
+        assert!(ring.push(5));
+        assert_eq!(ring.free(), 0);
+        assert_eq!(
+            [ring.pop(), ring.pop(), ring.pop(), ring.pop()],
+            [Some(2), Some(3), Some(4), Some(5)]
+        );
+        assert_eq!(ring.pop(), None);
+    }
+}
@@ -0,0 +1,592 @@
+//! # RTC Module
+//!
+//! This module provides the real time clock (RTC) and the other parts of the
+//! backup domain, which keep running from VBAT when the rest of the chip is
+//! reset or in standby.
+//!
+//! The RTC keeps the date and time in BCD in its own registers (section 26 of
+//! RM0090 and section 26 of RM0091). It is clocked by the 32.768 kHz LSE
+//! crystal or the less accurate internal LSI oscillator.
+//!
+//! ## Structures
+//!
+//! - `DateTime`: A calendar date and time of day.
+//!
+//! ## Enums
+//!
+//! - `ClockSource`: Selects the LSE or LSI oscillator for the RTC.
+//!
+//! ## Functions
+//!
+//! - `init`: Enables the backup domain and starts the RTC from a clock source.
+//! - `is_set`: Checks if the calendar has been set since the backup domain was reset.
+//! - `set`: Sets the calendar date and time.
+//! - `now`: Reads the calendar date and time, including subseconds.
+//! - `set_alarm`: Calls a handler every day at a given time.
+//! - `cancel_alarm`: Stops the alarm.
+//! - `set_wakeup`: Calls a handler periodically from the wakeup timer.
+//! - `cancel_wakeup`: Stops the wakeup timer.
+//! - `wakeup_period`: Returns the period of the wakeup timer, if it is on.
+//! - `clear_flags`: Clears the alarm and wakeup flags.
+//! - `handle_irq`: Handles the RTC alarm and wakeup interrupts.
+//! - `backup_read`, `backup_write`: Access the RTC backup registers.
+//! - `backup_sram_enable`, `backup_sram_read`, `backup_sram_write`: Access the
+//!   4K byte backup SRAM (STM32F405).
+//! - `weekday`: Computes the day of the week of a date.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use hal::cpu;
+//! use hal::rtc::{self, ClockSource, DateTime};
+//!
+//! cpu::init();
+//! rtc::init(ClockSource::Lse);
+//!
+//! if !rtc::is_set() {
+//!     rtc::set(&DateTime::new(2025, 1, 31, 12, 30, 0));
+//! }
+//!
+//! let now = rtc::now();
+//!
+//! // keep a boot counter that survives resets
+//! let boots = rtc::backup_read(0);
+//! rtc::backup_write(0, boots + 1);
+//! ```
+
+use core::ptr;
+
+#[cfg(feature = "std")]
+extern crate std;
+
+use super::cpu;
+use super::cpu::*;
+
+use super::cpu::RTC::isr;
+
+/// Number of 32 bit RTC backup registers.
+#[cfg(feature = "stm32f405")]
+pub const BACKUP_REGS: usize = 20;
+
+/// Number of 32 bit RTC backup registers.
+#[cfg(feature = "stm32f072")]
+pub const BACKUP_REGS: usize = 5;
+
+/// Size of the backup SRAM in bytes.
+#[cfg(feature = "stm32f405")]
+pub const BACKUP_SRAM_SIZE: usize = 4 * 1024;
+
+#[cfg(feature = "stm32f405")]
+const BACKUP_SRAM_BASE: u32 = 0x4002_4000;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ClockSource {
+    /// External 32.768 kHz crystal.
+    Lse,
+    /// Internal low speed RC oscillator, about 32 kHz on the STM32F405 and
+    /// 40 kHz on the STM32F072.
+    Lsi,
+}
+
+/// A calendar date and time of day in 24 hour format. The year is from 2000
+/// to 2099, `weekday` is 1 for Monday to 7 for Sunday, and `millis` is the
+/// fraction of the second.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub struct DateTime {
+    pub year: u16,
+    pub month: u8,
+    pub day: u8,
+    pub weekday: u8,
+    pub hour: u8,
+    pub minute: u8,
+    pub second: u8,
+    pub millis: u16,
+}
+
+impl DateTime {
+    /// Creates a date and time, working out the day of the week.
+    pub fn new(year: u16, month: u8, day: u8, hour: u8, minute: u8, second: u8) -> Self {
+        DateTime {
+            year,
+            month,
+            day,
+            weekday: weekday(year, month, day),
+            hour,
+            minute,
+            second,
+            millis: 0,
+        }
+    }
+}
+
+/// Returns the day of the week of a date, 1 for Monday to 7 for Sunday.
+pub fn weekday(year: u16, month: u8, day: u8) -> u8 {
+    // Sakamoto's method, which gives 0 for Sunday
+    const T: [u16; 12] = [0, 3, 2, 5, 0, 3, 5, 1, 4, 6, 2, 4];
+    let y = if month < 3 { year - 1 } else { year };
+    let d = (y + y / 4 - y / 100 + y / 400 + T[month as usize - 1] + day as u16) % 7;
+    if d == 0 {
+        7
+    } else {
+        d as u8
+    }
+}
+
+pub fn to_bcd(v: u8) -> u32 {
+    debug_assert!(v < 100);
+    (((v / 10) << 4) | (v % 10)) as u32
+}
+
+pub fn from_bcd(v: u32) -> u8 {
+    (((v >> 4) & 0xF) * 10 + (v & 0xF)) as u8
+}
+
+/// Synchronous prescaler for a 1 Hz calendar from the clock source, with the
+/// asynchronous prescaler fixed at 128.
+fn prediv_s(source: ClockSource) -> u32 {
+    match source {
+        ClockSource::Lse => 32_768 / 128 - 1,
+        #[cfg(feature = "stm32f405")]
+        ClockSource::Lsi => 32_000 / 128 - 1,
+        #[cfg(feature = "stm32f072")]
+        ClockSource::Lsi => 40_000 / 128 - 1,
+    }
+}
+
+static PREDIV_S: Global<u32> = Global::new(255);
+static ALARM_HANDLER: Global<Option<fn()>> = Global::new(None);
+static WAKEUP_HANDLER: Global<Option<fn()>> = Global::new(None);
+static WAKEUP_SECONDS: Global<u32> = Global::new(0);
+
+/// Allows writes to the backup domain, which includes RCC BDCR and the RTC.
+fn enable_backup_access() {
+    cpu::write!(RCC.apb1enr.PWREN, 1);
+    cpu::write!(PWR.cr.DBP, 1);
+}
+
+fn unlock() {
+    cpu::write!(RTC.wpr, 0xCA);
+    cpu::write!(RTC.wpr, 0x53);
+}
+
+fn lock() {
+    cpu::write!(RTC.wpr, 0xFF);
+}
+
+/// Clears the `ISR` flag at bit `flag`. The flags are cleared by writing 0
+/// and a 1 leaves them unchanged, so only `flag` is written 0, and `INIT` as
+/// well since it is the one read-write bit of `ISR`.
+fn clear_flag(flag: u8) {
+    cpu::write!(RTC.isr, !((1 << flag) | (1 << isr::INIT)));
+}
+
+fn enter_init() {
+    cpu::write!(RTC.isr.INIT, 1);
+
+    #[cfg(not(feature = "std"))]
+    while cpu::read!(RTC.isr.INITF) == 0 {}
+}
+
+fn exit_init() {
+    cpu::write!(RTC.isr.INIT, 0);
+
+    // wait for the shadow registers to be updated
+    clear_flag(isr::RSF);
+    #[cfg(not(feature = "std"))]
+    while cpu::read!(RTC.isr.RSF) == 0 {}
+}
+
+#[cfg(feature = "stm32f405")]
+fn select_clock(source: ClockSource) {
+    let sel = match source {
+        ClockSource::Lse => 0b01,
+        ClockSource::Lsi => 0b10,
+    };
+    cpu::write!(RCC.bdcr.RTCSEL, sel);
+}
+
+#[cfg(feature = "stm32f072")]
+fn select_clock(source: ClockSource) {
+    let sel = match source {
+        ClockSource::Lse => 0b01,
+        ClockSource::Lsi => 0b10,
+    };
+    cpu::write!(RCC.bdcr.RTCSEL, sel);
+}
+
+#[cfg(feature = "stm32f405")]
+fn clock_selected() -> u32 {
+    cpu::read!(RCC.bdcr.RTCSEL)
+}
+
+#[cfg(feature = "stm32f072")]
+fn clock_selected() -> u32 {
+    cpu::read!(RCC.bdcr.RTCSEL)
+}
+
+/// Enables the backup domain and starts the RTC from `source`.
+///
+/// If the RTC is already running from `source`, which is the case after a
+/// reset when VBAT was kept, the calendar is left running. Changing the clock
+/// source resets the backup domain, which clears the calendar and the backup
+/// registers.
+#[inline(never)]
+pub fn init(source: ClockSource) {
+    enable_backup_access();
+
+    let wanted = match source {
+        ClockSource::Lse => 0b01,
+        ClockSource::Lsi => 0b10,
+    };
+    let current = clock_selected();
+    if current != 0 && current != wanted {
+        // the clock source can only be changed by a backup domain reset
+        cpu::write!(RCC.bdcr.BDRST, 1);
+        cpu::write!(RCC.bdcr.BDRST, 0);
+    }
+
+    match source {
+        ClockSource::Lse => {
+            cpu::write!(RCC.bdcr.LSEON, 1);
+            #[cfg(not(feature = "std"))]
+            while cpu::read!(RCC.bdcr.LSERDY) == 0 {}
+        }
+        ClockSource::Lsi => {
+            cpu::write!(RCC.csr.LSION, 1);
+            #[cfg(not(feature = "std"))]
+            while cpu::read!(RCC.csr.LSIRDY) == 0 {}
+        }
+    }
+
+    let ps = prediv_s(source);
+    unsafe { PREDIV_S.set(ps) };
+
+    select_clock(source);
+    cpu::write!(RCC.bdcr.RTCEN, 1);
+
+    if cpu::read!(RTC.prer.PREDIV_S) == ps {
+        return;
+    }
+
+    unlock();
+    enter_init();
+
+    // the two prescalers must be written separately, synchronous first
+    cpu::write!(RTC.prer.PREDIV_S, ps);
+    cpu::write!(RTC.prer.PREDIV_A, 128 - 1);
+
+    cpu::write!(RTC.cr.FMT, 0); // 24 hour format
+
+    exit_init();
+    lock();
+}
+
+/// Returns true if the calendar has been set since the backup domain was reset.
+pub fn is_set() -> bool {
+    cpu::read!(RTC.isr.INITS) != 0
+}
+
+/// Sets the calendar date and time. `millis` and `weekday` are ignored, the
+/// day of the week is worked out from the date.
+#[inline(never)]
+pub fn set(dt: &DateTime) {
+    assert!(dt.year >= 2000 && dt.year < 2100);
+    assert!(dt.month >= 1 && dt.month <= 12 && dt.day >= 1 && dt.day <= 31);
+    assert!(dt.hour < 24 && dt.minute < 60 && dt.second < 60);
+
+    let tr = (to_bcd(dt.hour) << 16) | (to_bcd(dt.minute) << 8) | to_bcd(dt.second);
+    let dr = (to_bcd((dt.year - 2000) as u8) << 16)
+        | ((weekday(dt.year, dt.month, dt.day) as u32) << 13)
+        | (to_bcd(dt.month) << 8)
+        | to_bcd(dt.day);
+
+    enable_backup_access();
+    unlock();
+    enter_init();
+
+    cpu::write!(RTC.tr, tr);
+    cpu::write!(RTC.dr, dr);
+
+    exit_init();
+    lock();
+}
+
+/// Decodes the TR, DR, and SSR register values into a `DateTime`.
+pub fn decode(tr: u32, dr: u32, ssr: u32, prediv_s: u32) -> DateTime {
+    let ss = ssr & 0xFFFF;
+    // the subsecond counter counts down from PREDIV_S
+    let millis = (prediv_s.saturating_sub(ss) * 1000 / (prediv_s + 1)) as u16;
+
+    DateTime {
+        year: 2000 + from_bcd(dr >> 16) as u16,
+        month: from_bcd((dr >> 8) & 0x1F),
+        day: from_bcd(dr & 0x3F),
+        weekday: ((dr >> 13) & 0x7) as u8,
+        hour: from_bcd((tr >> 16) & 0x3F),
+        minute: from_bcd((tr >> 8) & 0x7F),
+        second: from_bcd(tr & 0x7F),
+        millis,
+    }
+}
+
+/// Reads the calendar date and time.
+#[inline(never)]
+pub fn now() -> DateTime {
+    // reading SSR then TR locks the shadow registers until DR is read
+    let ssr = cpu::read!(RTC.ssr);
+    let tr = cpu::read!(RTC.tr);
+    let dr = cpu::read!(RTC.dr);
+
+    let ps = unsafe { PREDIV_S.get() };
+    decode(tr, dr, ssr, ps)
+}
+
+/// Enables the rising edge of EXTI `line`, which is connected to an RTC event.
+fn enable_exti(line: u8) {
+    cpu::write!(EXTI.imr[line;1], 1);
+    cpu::write!(EXTI.rtsr[line;1], 1);
+}
+
+#[cfg(feature = "stm32f405")]
+const ALARM_EXTI: u8 = 17;
+#[cfg(feature = "stm32f405")]
+const WAKEUP_EXTI: u8 = 22;
+#[cfg(feature = "stm32f405")]
+const ALARM_IRQ: Interrupt = Interrupt::RtcAlarm;
+#[cfg(feature = "stm32f405")]
+const WAKEUP_IRQ: Interrupt = Interrupt::RtcWkup;
+
+#[cfg(feature = "stm32f072")]
+const ALARM_EXTI: u8 = 17;
+#[cfg(feature = "stm32f072")]
+const WAKEUP_EXTI: u8 = 20;
+#[cfg(feature = "stm32f072")]
+const ALARM_IRQ: Interrupt = Interrupt::Rtc;
+#[cfg(feature = "stm32f072")]
+const WAKEUP_IRQ: Interrupt = Interrupt::Rtc;
+
+/// Calls `handler` from the RTC interrupt every day at `hour`:`minute`:`second`.
+#[inline(never)]
+pub fn set_alarm(hour: u8, minute: u8, second: u8, handler: fn()) {
+    assert!(hour < 24 && minute < 60 && second < 60);
+
+    unsafe { ALARM_HANDLER.set(Some(handler)) };
+
+    enable_backup_access();
+    unlock();
+
+    cpu::write!(RTC.cr.ALRAE, 0);
+    #[cfg(not(feature = "std"))]
+    while cpu::read!(RTC.isr.ALRAWF) == 0 {}
+
+    // MSK4 set so the date is ignored and the alarm is every day
+    let alarm = (1 << 31) | (to_bcd(hour) << 16) | (to_bcd(minute) << 8) | to_bcd(second);
+    cpu::write!(RTC.alrmar, alarm);
+    cpu::write!(RTC.alrmassr, 0); // ignore the subseconds
+
+    clear_flag(isr::ALRAF);
+    cpu::write!(RTC.cr.ALRAIE, 1);
+    cpu::write!(RTC.cr.ALRAE, 1);
+
+    lock();
+
+    enable_exti(ALARM_EXTI);
+    cpu::enable_irq(ALARM_IRQ);
+}
+
+/// Stops the alarm.
+#[inline(never)]
+pub fn cancel_alarm() {
+    enable_backup_access();
+    unlock();
+    cpu::write!(RTC.cr.ALRAIE, 0);
+    cpu::write!(RTC.cr.ALRAE, 0);
+    lock();
+
+    unsafe { ALARM_HANDLER.set(None) };
+}
+
+/// Calls `handler` from the RTC interrupt every `seconds`, from 1 to 65536.
+/// The wakeup timer also wakes the chip from the stop and standby modes.
+#[inline(never)]
+pub fn set_wakeup(seconds: u32, handler: fn()) {
+    assert!((1..=0x1_0000).contains(&seconds));
+
+    unsafe { WAKEUP_HANDLER.set(Some(handler)) };
+    unsafe { WAKEUP_SECONDS.set(seconds) };
+
+    enable_backup_access();
+    unlock();
+
+    cpu::write!(RTC.cr.WUTE, 0);
+    #[cfg(not(feature = "std"))]
+    while cpu::read!(RTC.isr.WUTWF) == 0 {}
+
+    cpu::write!(RTC.wutr, seconds - 1);
+    cpu::write!(RTC.cr.WUCKSEL, 0b100); // 1 Hz calendar clock
+
+    clear_flag(isr::WUTF);
+    cpu::write!(RTC.cr.WUTIE, 1);
+    cpu::write!(RTC.cr.WUTE, 1);
+
+    lock();
+
+    enable_exti(WAKEUP_EXTI);
+    cpu::enable_irq(WAKEUP_IRQ);
+}
+
+/// Stops the wakeup timer.
+#[inline(never)]
+pub fn cancel_wakeup() {
+    enable_backup_access();
+    unlock();
+    cpu::write!(RTC.cr.WUTIE, 0);
+    cpu::write!(RTC.cr.WUTE, 0);
+    lock();
+
+    unsafe { WAKEUP_HANDLER.set(None) };
+    unsafe { WAKEUP_SECONDS.set(0) };
+}
+
+/// Returns the period in seconds of the wakeup timer, or `None` if it is off.
+pub fn wakeup_period() -> Option<u32> {
+    match unsafe { WAKEUP_SECONDS.get() } {
+        0 => None,
+        seconds => Some(seconds),
+    }
+}
+
+/// Clears the alarm and wakeup flags and their EXTI lines without calling
+/// the handlers. A flag left set stops the chip entering standby, or wakes
+/// it at once.
+#[inline(never)]
+pub fn clear_flags() {
+    clear_flag(isr::ALRAF);
+    clear_flag(isr::WUTF);
+    cpu::write!(EXTI.pr, (1 << ALARM_EXTI) | (1 << WAKEUP_EXTI));
+}
+
+/// Handles the RTC alarm and wakeup interrupts. Call this from the RTC_Alarm
+/// and RTC_WKUP vectors on the STM32F405, or the RTC vector on the STM32F072.
+#[inline(never)]
+pub fn handle_irq() {
+    let flags = cpu::read!(RTC.isr);
+
+    if flags & (1 << isr::ALRAF) != 0 {
+        clear_flag(isr::ALRAF);
+        cpu::write!(EXTI.pr, 1 << ALARM_EXTI);
+
+        if let Some(handler) = unsafe { ALARM_HANDLER.get() } {
+            handler();
+        }
+    }
+
+    if flags & (1 << isr::WUTF) != 0 {
+        clear_flag(isr::WUTF);
+        cpu::write!(EXTI.pr, 1 << WAKEUP_EXTI);
+
+        if let Some(handler) = unsafe { WAKEUP_HANDLER.get() } {
+            handler();
+        }
+    }
+}
+
+/// Reads backup register `n`. The backup registers keep their value through
+/// resets and standby as long as VDD or VBAT is present.
+pub fn backup_read(n: usize) -> u32 {
+    assert!(n < BACKUP_REGS);
+    cpu::read!(RTC.bkpr[n])
+}
+
+/// Writes backup register `n`.
+pub fn backup_write(n: usize, value: u32) {
+    assert!(n < BACKUP_REGS);
+    enable_backup_access();
+    cpu::write!(RTC.bkpr[n], value);
+}
+
+/// Turns on the backup SRAM and its regulator, so it keeps its contents in
+/// standby and when running from VBAT.
+#[cfg(feature = "stm32f405")]
+#[inline(never)]
+pub fn backup_sram_enable() {
+    enable_backup_access();
+    cpu::write!(RCC.ahb1enr.BKPSRAMEN, 1);
+    cpu::write!(PWR.csr.BRE, 1);
+
+    #[cfg(not(feature = "std"))]
+    while cpu::read!(PWR.csr.BRR) == 0 {}
+}
+
+/// Reads `data.len()` bytes of backup SRAM starting at `offset`.
+#[cfg(feature = "stm32f405")]
+pub fn backup_sram_read(offset: usize, data: &mut [u8]) {
+    assert!(offset + data.len() <= BACKUP_SRAM_SIZE);
+    for (i, d) in data.iter_mut().enumerate() {
+        *d = sram_read(offset + i);
+    }
+}
+
+/// Writes `data` to the backup SRAM starting at `offset`.
+#[cfg(feature = "stm32f405")]
+pub fn backup_sram_write(offset: usize, data: &[u8]) {
+    assert!(offset + data.len() <= BACKUP_SRAM_SIZE);
+    for (i, d) in data.iter().enumerate() {
+        sram_write(offset + i, *d);
+    }
+}
+
+#[cfg(all(feature = "stm32f405", not(feature = "std")))]
+fn sram_read(offset: usize) -> u8 {
+    unsafe { ptr::read_volatile((BACKUP_SRAM_BASE as usize + offset) as *const u8) }
+}
+
+#[cfg(all(feature = "stm32f405", not(feature = "std")))]
+fn sram_write(offset: usize, value: u8) {
+    unsafe { ptr::write_volatile((BACKUP_SRAM_BASE as usize + offset) as *mut u8, value) }
+}
+
+// the simulation keeps the backup SRAM in a simulated memory
+#[cfg(all(feature = "stm32f405", feature = "std"))]
+fn sram_read(offset: usize) -> u8 {
+    super::sim::with_memory(BACKUP_SRAM_BASE, BACKUP_SRAM_SIZE, 0, |mem| mem[offset])
+}
+
+#[cfg(all(feature = "stm32f405", feature = "std"))]
+fn sram_write(offset: usize, value: u8) {
+    super::sim::with_memory(BACKUP_SRAM_BASE, BACKUP_SRAM_SIZE, 0, |mem| {
+        mem[offset] = value
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_weekday() {
+        assert_eq!(weekday(2000, 1, 1), 6); // Saturday
+        assert_eq!(weekday(2024, 2, 29), 4); // Thursday
+        assert_eq!(weekday(2025, 1, 5), 7); // Sunday
+        assert_eq!(weekday(2025, 3, 3), 1); // Monday
+    }
+
+    #[test]
+    fn test_bcd() {
+        assert_eq!(to_bcd(59), 0x59);
+        assert_eq!(from_bcd(0x59), 59);
+        assert_eq!(from_bcd(to_bcd(7)), 7);
+    }
+
+    #[test]
+    fn test_decode() {
+        // 2025-03-03 (Monday) 13:45:09 and a quarter second
+        let tr = 0x13_45_09;
+        let dr = (0x25 << 16) | (1 << 13) | (0x03 << 8) | 0x03;
+        let dt = decode(tr, dr, 191, 255);
+
+        let mut expected = DateTime::new(2025, 3, 3, 13, 45, 9);
+        expected.millis = 250;
+        assert_eq!(dt, expected);
+    }
+}
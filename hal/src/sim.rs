@@ -0,0 +1,895 @@
+//! # Sim Module
+//!
+//! This module holds the simulated registers used with the `std` feature, and
+//! the models that give peripherals their behaviour in the simulation.
+//!
+//! Each thread has its own simulation, made when it first accesses a
+//! register, so tests running in parallel do not see each other's registers.
+//! It also holds the thread's copies of the driver state kept in
+//! `cpu::Global` statics.
+//! A test can hold a `Guard` to start from a new simulation and drop it at
+//! the end.
+//!
+//! A register without a model keeps the last value written to it. A model
+//! covers the registers of one peripheral and is called for each read and
+//! write of them, so status flags can follow the state of the peripheral and
+//! the drivers run the same code paths as on the hardware.
+//!
+//! The models added by `cpu::init` are:
+//!
+//! - `Rcc`: The ready flags follow their enable bits, and `SWS` follows `SW`.
+//!   Writing `RMVF` clears the reset flags.
+//! - `Nvic`: Writing 1 to a set or clear register sets or clears the enable
+//!   or pending bit, and writing 0 leaves it unchanged.
+//! - `Usart`: Bytes written to the data register are kept in a buffer, and
+//!   optionally printed to stdout. `RXNE` is set while there is input.
+//! - `Counter`: `CNT` of TIM2 advances on each read while the counter is
+//!   enabled, and sets `UIF` when it wraps at `ARR`. Writing 0 to a flag of
+//!   `SR` clears it, and writing 1 leaves it unchanged.
+//! - `Spi`: Frames sent in full duplex are received back, as if MISO were
+//!   wired to MOSI. `RXNE` is set while a frame is unread, and a frame sent
+//!   while one is unread sets `OVR`, which reading `DR` and then `SR`
+//!   clears.
+//! - `Gpio`: `IDR` reads the levels of the input pins, which are set with
+//!   `input`, and writes to it are ignored as it is read-only.
+//! - `Rtc`: Writing 0 to a flag of `ISR` clears it, and writing 1 leaves it
+//!   unchanged, as do writes to the read-only bits.
+//! - `CycleCounter`: `CYCCNT` of the DWT advances on each read while the
+//!   counter is enabled (STM32F405).
+//!
+//! The system memory read by `device` is simulated with a fixed unique ID
+//! and the flash size of the chip.
+//!
+//! A trace records each register access in order, with the bits accessed,
+//! so tests can check the order of writes and not only the final values.
+//! Recording is off until `start_trace` is called.
+//!
+//! ## Traits
+//!
+//! - `Model`: The behaviour of a peripheral.
+//!
+//! ## Structures
+//!
+//! - `Regs`: The registers of the peripheral being accessed by a model.
+//! - `Event`: A register access recorded in a trace.
+//! - `Trace`: The recorded accesses, with functions to check them.
+//! - `Guard`: A new simulation for the current thread, cleared when dropped.
+//!
+//! ## Functions
+//!
+//! - `reset`: Clears all registers and adds the default models.
+//! - `add_model`: Adds a model for a peripheral, replacing any it had.
+//! - `with_model`: Calls a function with the model of a peripheral.
+//! - `with_memory`: Calls a function with a simulated memory, such as flash.
+//! - `start_trace`, `take_trace`, `stop_trace`: Record the register accesses.
+//! - `primask`, `set_primask`, `basepri`, `set_basepri`: The interrupt masks
+//!   of the core, used by `cpu`.
+//! - `ipsr`, `set_ipsr`: The exception the core is handling, used by `cpu`.
+//! - `advance_time`, `time_ahead`: Virtual time added to the host clock, so
+//!   delays do not wait.
+//! - `read`, `read_bits`, `write`, `update`: Access a register, used by the
+//!   register functions of `cpu`.
+//! - `raise`: Sets a status flag as the hardware does.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use hal::{cpu, sim, uart};
+//!
+//! cpu::init();
+//! sim::with_model(cpu::USART1, |usart: &mut sim::Usart| usart.input(b"A"));
+//!
+//! assert!(!uart::empty1());
+//! assert_eq!(uart::read1(), b'A');
+//! assert!(uart::empty1());
+//! ```
+//!
+//! ```rust
+//! use hal::{cpu, gpio, sim};
+//! use hal::sim::Event;
+//!
+//! let _sim = sim::Guard::new();
+//! sim::start_trace();
+//! gpio::Pin(cpu::GPIOB, 3).output();
+//! let trace = sim::stop_trace();
+//!
+//! // MODER of pin 3 set to output
+//! let moder = unsafe { core::ptr::addr_of_mut!((*cpu::GPIOB).moder) };
+//! trace.assert_in_order(&[Event::write_bits(moder, 0b11 << 6, 0b01 << 6)]);
+//! ```
+
+extern crate std;
+
+use core::any::Any;
+use core::cell::RefCell;
+use core::marker::PhantomData;
+use core::mem::{offset_of, size_of};
+use std::boxed::Box;
+use std::collections::{HashMap, VecDeque};
+use std::vec;
+use std::vec::Vec;
+
+use super::cpu::*;
+use super::device::{FLASH_SIZE_BASE, UID_BASE};
+use super::flash::FLASH_SIZE;
+
+// the status register is SR on the STM32F405 and ISR on the STM32F072
+#[cfg(feature = "stm32f072")]
+use super::cpu::USART::isr as status;
+#[cfg(feature = "stm32f405")]
+use super::cpu::USART::sr as status;
+
+/// The behaviour of a peripheral in the simulation.
+///
+/// Offsets are in bytes from the base address of the peripheral. Models are
+/// called with the simulation borrowed, so they must not use `cpu::read_reg`
+/// or `cpu::write_reg` and should only use `regs`.
+pub trait Model: Any {
+    /// Returns the value read from the register at `offset`.
+    fn read(&mut self, regs: &mut Regs, offset: usize) -> u32 {
+        regs.get(offset)
+    }
+
+    /// Handles `value` being written to the register at `offset`.
+    fn write(&mut self, regs: &mut Regs, offset: usize, value: u32) {
+        regs.set(offset, value);
+    }
+}
+
+/// The stored values of the registers of one peripheral.
+pub struct Regs<'a> {
+    mem: &'a mut HashMap<usize, u32>,
+    base: usize,
+}
+
+impl Regs<'_> {
+    /// Returns the value stored in the register at `offset`.
+    pub fn get(&self, offset: usize) -> u32 {
+        self.mem.get(&(self.base + offset)).copied().unwrap_or(0)
+    }
+
+    /// Stores `value` in the register at `offset`.
+    pub fn set(&mut self, offset: usize, value: u32) {
+        self.mem.insert(self.base + offset, value);
+    }
+}
+
+struct Hook {
+    base: usize,
+    size: usize,
+    model: Box<dyn Model>,
+}
+
+#[derive(Default)]
+struct Sim {
+    mem: HashMap<usize, u32>,
+    hooks: Vec<Hook>,
+    /// Memories that are not registers, by base address.
+    memories: HashMap<u32, Vec<u8>>,
+    /// The accesses since `start_trace`, if recording.
+    trace: Option<Vec<Event>>,
+    /// The interrupt masks of the core, which are not registers.
+    primask: bool,
+    basepri: u8,
+    /// The number of the exception being handled, 0 in thread mode.
+    ipsr: u16,
+    /// Microseconds the simulated time is ahead of the host clock.
+    time_ahead: u64,
+    /// The copies of the `cpu::Global` statics, by address of the static.
+    globals: HashMap<usize, Box<dyn Any>>,
+}
+
+/// Whether a register access read or wrote it.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Op {
+    Read,
+    Write,
+}
+
+/// A register access recorded in a trace. `mask` has the bits that were
+/// accessed and `value` their value, so a write of one field has the bits
+/// of the field in `mask`.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Event {
+    pub op: Op,
+    pub addr: usize,
+    pub mask: u32,
+    pub value: u32,
+}
+
+impl Event {
+    /// A read of the whole register at `addr` that returned `value`.
+    pub fn read(addr: *mut u32, value: u32) -> Self {
+        Event::read_bits(addr, u32::MAX, value)
+    }
+
+    /// A read of the bits in `mask` of the register at `addr`.
+    pub fn read_bits(addr: *mut u32, mask: u32, value: u32) -> Self {
+        Event {
+            op: Op::Read,
+            addr: addr as usize,
+            mask,
+            value: value & mask,
+        }
+    }
+
+    /// A write of `value` to the whole register at `addr`.
+    pub fn write(addr: *mut u32, value: u32) -> Self {
+        Event::write_bits(addr, u32::MAX, value)
+    }
+
+    /// A write of the bits in `mask` of the register at `addr`, leaving the
+    /// other bits unchanged.
+    pub fn write_bits(addr: *mut u32, mask: u32, value: u32) -> Self {
+        Event {
+            op: Op::Write,
+            addr: addr as usize,
+            mask,
+            value: value & mask,
+        }
+    }
+}
+
+/// An ordered list of register accesses.
+#[derive(Clone, Default, Debug)]
+pub struct Trace(pub Vec<Event>);
+
+impl Trace {
+    /// Returns the values written to the whole register at `addr`, in order.
+    /// Writes of some bits of the register are left out.
+    pub fn writes(&self, addr: *mut u32) -> Vec<u32> {
+        let addr = addr as usize;
+        let events = self.0.iter();
+        let writes = events.filter(|e| e.op == Op::Write && e.addr == addr && e.mask == u32::MAX);
+        writes.map(|e| e.value).collect()
+    }
+
+    /// Returns the accesses of the register at `addr`, in order.
+    pub fn of(&self, addr: *mut u32) -> Trace {
+        let addr = addr as usize;
+        Trace(self.0.iter().filter(|e| e.addr == addr).copied().collect())
+    }
+
+    /// Returns the position after the last of `expected` if they are all in
+    /// the trace in the same order, with any other accesses between them.
+    pub fn find(&self, expected: &[Event]) -> Option<usize> {
+        let mut next = 0;
+        for event in expected {
+            next += self.0[next..].iter().position(|e| e == event)? + 1;
+        }
+        Some(next)
+    }
+
+    /// Panics with the first missing access unless `expected` are all in
+    /// the trace in the same order.
+    #[track_caller]
+    pub fn assert_in_order(&self, expected: &[Event]) {
+        for n in 1..=expected.len() {
+            if self.find(&expected[..n]).is_none() {
+                panic!(
+                    "access {} of {} not found in order: {:?}",
+                    n,
+                    expected.len(),
+                    expected[n - 1]
+                );
+            }
+        }
+    }
+}
+
+std::thread_local! {
+    static SIM: RefCell<Option<Sim>> = const { RefCell::new(None) };
+}
+
+/// The unique ID of the simulated chip, as it is in the system memory.
+const SIM_UID: [u8; 12] = [
+    0x2A, 0x00, 0x3C, 0x00, 0x0D, 0x51, 0x33, 0x30, 0x38, 0x39, 0x35, 0x35,
+];
+
+fn with_sim<R>(f: impl FnOnce(&mut Sim) -> R) -> R {
+    SIM.with(|sim| f(sim.borrow_mut().get_or_insert_with(new_sim)))
+}
+
+fn new_sim() -> Sim {
+    let mut sim = Sim::default();
+    add(&mut sim, RCC, Rcc::default());
+    add(&mut sim, NVIC, Nvic);
+    add(&mut sim, USART1, Usart::default());
+    #[cfg(feature = "stm32f072")]
+    add(&mut sim, USART2, Usart::default());
+    add(&mut sim, TIM2, Counter::default());
+    add(&mut sim, SPI1, Spi::default());
+    add(&mut sim, SPI2, Spi::default());
+    #[cfg(feature = "stm32f405")]
+    add(&mut sim, SPI3, Spi::default());
+    add(&mut sim, GPIOA, Gpio::default());
+    add(&mut sim, GPIOB, Gpio::default());
+    add(&mut sim, GPIOC, Gpio::default());
+    add(&mut sim, RTC, Rtc);
+    #[cfg(feature = "stm32f405")]
+    add(&mut sim, DWT, CycleCounter::default());
+    sim.memories.insert(UID_BASE, SIM_UID.to_vec());
+    let flash_kb = (FLASH_SIZE / 1024) as u16;
+    sim.memories
+        .insert(FLASH_SIZE_BASE, flash_kb.to_le_bytes().to_vec());
+    sim
+}
+
+fn add<T>(sim: &mut Sim, peripheral: *mut T, model: impl Model) {
+    let base = peripheral as usize;
+    sim.hooks.retain(|hook| hook.base != base);
+    sim.hooks.push(Hook {
+        base,
+        size: size_of::<T>(),
+        model: Box::new(model),
+    });
+}
+
+/// Clears all registers of this thread and adds the default models. The
+/// memories are kept, as flash is on the hardware, as is the state of the
+/// drivers, and a trace that is being recorded carries on, so it can include
+/// `cpu::init`.
+pub fn reset() {
+    SIM.with(|sim| {
+        let mut sim = sim.borrow_mut();
+        let old = sim.take().unwrap_or_default();
+        let mut new = new_sim();
+        new.memories.extend(old.memories);
+        *sim = Some(Sim {
+            trace: old.trace,
+            globals: old.globals,
+            ..new
+        });
+    });
+}
+
+/// Gives the current thread a new simulation, with all registers, memories
+/// and driver state cleared, and clears it again when dropped. Each test
+/// that holds one sees only its own state, so tests can run in parallel.
+#[must_use]
+pub struct Guard {
+    // the simulation belongs to the thread that made the guard
+    _thread: PhantomData<*const ()>,
+}
+
+impl Guard {
+    pub fn new() -> Self {
+        SIM.with(|sim| *sim.borrow_mut() = Some(new_sim()));
+        Guard {
+            _thread: PhantomData,
+        }
+    }
+}
+
+impl Default for Guard {
+    fn default() -> Self {
+        Guard::new()
+    }
+}
+
+impl Drop for Guard {
+    fn drop(&mut self) {
+        // a panic in a model leaves the simulation borrowed
+        let _ = SIM.try_with(|sim| sim.try_borrow_mut().map(|mut sim| *sim = None));
+    }
+}
+
+/// Returns the copy for this thread of the `cpu::Global` at `addr`, made
+/// with `initial` when first used.
+pub(crate) fn global<T: 'static>(addr: usize, initial: impl FnOnce() -> T) -> *mut T {
+    with_sim(|sim| {
+        let value = sim
+            .globals
+            .entry(addr)
+            .or_insert_with(|| Box::new(initial()));
+        value.downcast_mut::<T>().expect("global of another type") as *mut T
+    })
+}
+
+/// Calls `f` with the `size` bytes of simulated memory at `base`, such as
+/// the flash, which start as `fill` bytes.
+pub fn with_memory<R>(base: u32, size: usize, fill: u8, f: impl FnOnce(&mut [u8]) -> R) -> R {
+    with_sim(|sim| {
+        let mem = sim.memories.entry(base).or_insert_with(|| vec![fill; size]);
+        f(mem)
+    })
+}
+
+/// Starts recording a trace of the register accesses, dropping any trace
+/// already recorded.
+pub fn start_trace() {
+    with_sim(|sim| sim.trace = Some(Vec::new()));
+}
+
+/// Returns the trace recorded since `start_trace`, and keeps recording.
+pub fn take_trace() -> Trace {
+    with_sim(|sim| Trace(sim.trace.as_mut().map(core::mem::take).unwrap_or_default()))
+}
+
+/// Stops recording and returns the trace recorded since `start_trace`.
+pub fn stop_trace() -> Trace {
+    with_sim(|sim| Trace(sim.trace.take().unwrap_or_default()))
+}
+
+/// Adds `model` for the registers of `peripheral`, replacing any model it had.
+pub fn add_model<T>(peripheral: *mut T, model: impl Model) {
+    with_sim(|sim| add(sim, peripheral, model));
+}
+
+/// Calls `f` with the model of `peripheral`. Returns None if it has no model
+/// of type `M`. The simulation is borrowed while `f` runs, so it must not
+/// access the registers.
+pub fn with_model<M: Model, T, R>(peripheral: *mut T, f: impl FnOnce(&mut M) -> R) -> Option<R> {
+    let base = peripheral as usize;
+    with_sim(|sim| {
+        let hook = sim.hooks.iter_mut().find(|hook| hook.base == base)?;
+        let model: &mut dyn Any = hook.model.as_mut();
+        model.downcast_mut::<M>().map(f)
+    })
+}
+
+/// Moves the simulated time of this thread `us` microseconds ahead of the
+/// host clock, used by the delays of `delay` so they do not wait.
+pub fn advance_time(us: u64) {
+    with_sim(|sim| sim.time_ahead += us);
+}
+
+/// Returns how far the simulated time of this thread is ahead of the host
+/// clock, in microseconds, used by `timer::now`.
+pub fn time_ahead() -> u64 {
+    with_sim(|sim| sim.time_ahead)
+}
+
+/// Returns the simulated PRIMASK, used by `cpu::primask`.
+pub fn primask() -> bool {
+    with_sim(|sim| sim.primask)
+}
+
+/// Sets the simulated PRIMASK, used by `cpu::set_primask`.
+pub fn set_primask(masked: bool) {
+    with_sim(|sim| sim.primask = masked);
+}
+
+/// Returns the simulated BASEPRI, used by `cpu::basepri`.
+pub fn basepri() -> u8 {
+    with_sim(|sim| sim.basepri)
+}
+
+/// Sets the simulated BASEPRI, used by `cpu::set_basepri`.
+pub fn set_basepri(value: u8) {
+    with_sim(|sim| sim.basepri = value);
+}
+
+/// Returns the simulated IPSR, used by `cpu::ipsr`.
+pub fn ipsr() -> u16 {
+    with_sim(|sim| sim.ipsr)
+}
+
+/// Sets the simulated IPSR, so code can be run as if from the handler of
+/// exception `number`, or from thread mode with 0.
+pub fn set_ipsr(number: u16) {
+    with_sim(|sim| sim.ipsr = number);
+}
+
+/// Returns the model covering `addr`, with the registers of its peripheral.
+fn hook_for(sim: &mut Sim, addr: usize) -> Option<(&mut Box<dyn Model>, Regs<'_>, usize)> {
+    let hook = sim
+        .hooks
+        .iter_mut()
+        .find(|hook| addr >= hook.base && addr < hook.base + hook.size)?;
+    let regs = Regs {
+        mem: &mut sim.mem,
+        base: hook.base,
+    };
+    Some((&mut hook.model, regs, addr - hook.base))
+}
+
+fn load(sim: &mut Sim, addr: usize) -> u32 {
+    match hook_for(sim, addr) {
+        Some((model, mut regs, offset)) => model.read(&mut regs, offset),
+        None => sim.mem.get(&addr).copied().unwrap_or(0),
+    }
+}
+
+fn store(sim: &mut Sim, addr: usize, value: u32) {
+    match hook_for(sim, addr) {
+        Some((model, mut regs, offset)) => model.write(&mut regs, offset, value),
+        None => {
+            sim.mem.insert(addr, value);
+        }
+    }
+}
+
+fn record(sim: &mut Sim, event: Event) {
+    if let Some(trace) = sim.trace.as_mut() {
+        trace.push(event);
+    }
+}
+
+/// Reads the register at `addr`.
+pub fn read(addr: *mut u32) -> u32 {
+    read_bits(addr, u32::MAX)
+}
+
+/// Reads the bits in `mask` of the register at `addr`.
+pub fn read_bits(addr: *mut u32, mask: u32) -> u32 {
+    with_sim(|sim| {
+        let value = load(sim, addr as usize) & mask;
+        record(sim, Event::read_bits(addr, mask, value));
+        value
+    })
+}
+
+/// Writes `value` to the register at `addr`.
+pub fn write(addr: *mut u32, value: u32) {
+    with_sim(|sim| {
+        store(sim, addr as usize, value);
+        record(sim, Event::write(addr, value));
+    })
+}
+
+/// Replaces the bits in `mask` of the register at `addr` with `value`. This
+/// is traced as one write of the bits in `mask`.
+pub fn update(addr: *mut u32, mask: u32, value: u32) {
+    with_sim(|sim| {
+        let old = load(sim, addr as usize);
+        store(sim, addr as usize, (old & !mask) | value);
+        record(sim, Event::write_bits(addr, mask, value));
+    })
+}
+
+/// Sets `bits` of the register at `addr` as the hardware does, such as a
+/// status flag, without calling the model of the register or tracing it.
+pub fn raise(addr: *mut u32, bits: u32) {
+    with_sim(|sim| {
+        let value = sim.mem.get(&(addr as usize)).copied().unwrap_or(0);
+        sim.mem.insert(addr as usize, value | bits);
+    })
+}
+
+/// Returns `value` with bit `to` set to bit `from`.
+fn follow(value: u32, from: u8, to: u8) -> u32 {
+    (value & !(1 << to)) | (((value >> from) & 1) << to)
+}
+
+/// The oscillators and PLLs start as soon as they are enabled, unless
+/// `hse_fails` is set, and the system clock switches as soon as it is
+/// selected.
+#[derive(Default)]
+pub struct Rcc {
+    /// Makes the HSE never become ready, to test the HSI fallback.
+    pub hse_fails: bool,
+}
+
+impl Model for Rcc {
+    fn read(&mut self, regs: &mut Regs, offset: usize) -> u32 {
+        let value = regs.get(offset);
+        match offset {
+            o if o == offset_of!(RccReg, cr) => {
+                let mut value = follow(value, RCC::cr::HSION, RCC::cr::HSIRDY);
+                value = follow(value, RCC::cr::HSEON, RCC::cr::HSERDY);
+                if self.hse_fails {
+                    value &= !(1 << RCC::cr::HSERDY);
+                }
+                #[cfg(feature = "stm32f405")]
+                {
+                    value = follow(value, RCC::cr::PLLI2SON, RCC::cr::PLLI2SRDY);
+                }
+                follow(value, RCC::cr::PLLON, RCC::cr::PLLRDY)
+            }
+            o if o == offset_of!(RccReg, cfgr) => {
+                let sw = (value >> RCC::cfgr::SW::OFFSET) & 0b11;
+                (value & !(0b11 << RCC::cfgr::SWS::OFFSET)) | (sw << RCC::cfgr::SWS::OFFSET)
+            }
+            o if o == offset_of!(RccReg, bdcr) => {
+                follow(value, RCC::bdcr::LSEON, RCC::bdcr::LSERDY)
+            }
+            o if o == offset_of!(RccReg, csr) => follow(value, RCC::csr::LSION, RCC::csr::LSIRDY),
+            _ => value,
+        }
+    }
+
+    fn write(&mut self, regs: &mut Regs, offset: usize, value: u32) {
+        // writing RMVF clears the reset flags, which are the top byte of csr
+        if offset == offset_of!(RccReg, csr) && value & (1 << RCC::csr::RMVF) != 0 {
+            regs.set(offset, value & 0x00FF_FFFF);
+        } else {
+            regs.set(offset, value);
+        }
+    }
+}
+
+/// The set and clear registers of the NVIC act on the same enable and
+/// pending bits, which read back from both.
+pub struct Nvic;
+
+impl Nvic {
+    /// Returns the offset of the set register holding the bits at `offset`,
+    /// and whether `offset` is the clear register.
+    fn bits(offset: usize) -> Option<(usize, bool)> {
+        let iser = offset_of!(NVICReg, iser);
+        let icer = offset_of!(NVICReg, icer);
+        let ispr = offset_of!(NVICReg, ispr);
+        let icpr = offset_of!(NVICReg, icpr);
+        match offset {
+            o if (iser..iser + 32).contains(&o) => Some((o, false)),
+            o if (icer..icer + 32).contains(&o) => Some((o - icer + iser, true)),
+            o if (ispr..ispr + 32).contains(&o) => Some((o, false)),
+            o if (icpr..icpr + 32).contains(&o) => Some((o - icpr + ispr, true)),
+            _ => None,
+        }
+    }
+}
+
+impl Model for Nvic {
+    fn read(&mut self, regs: &mut Regs, offset: usize) -> u32 {
+        match Nvic::bits(offset) {
+            Some((set, _)) => regs.get(set),
+            None => regs.get(offset),
+        }
+    }
+
+    fn write(&mut self, regs: &mut Regs, offset: usize, value: u32) {
+        match Nvic::bits(offset) {
+            Some((set, false)) => regs.set(set, regs.get(set) | value),
+            Some((set, true)) => regs.set(set, regs.get(set) & !value),
+            None => regs.set(offset, value),
+        }
+    }
+}
+
+/// A USART that transmits at once and receives the bytes given to `input`.
+#[derive(Default)]
+pub struct Usart {
+    /// Prints the transmitted bytes to stdout as well.
+    pub echo: bool,
+    output: Vec<u8>,
+    input: VecDeque<u8>,
+}
+
+impl Usart {
+    /// Queues `data` to be received.
+    pub fn input(&mut self, data: &[u8]) {
+        self.input.extend(data);
+    }
+
+    /// Returns the bytes transmitted since the last call.
+    pub fn take_output(&mut self) -> Vec<u8> {
+        core::mem::take(&mut self.output)
+    }
+
+    fn status(&self, value: u32) -> u32 {
+        let rxne = !self.input.is_empty() as u32;
+        let value = value | (1 << status::TXE) | (1 << status::TC);
+        (value & !(1 << status::RXNE)) | (rxne << status::RXNE)
+    }
+
+    fn transmit(&mut self, value: u32) {
+        let byte = value as u8;
+        self.output.push(byte);
+        if self.echo {
+            use std::io::Write;
+            let _ = std::io::stdout().write_all(&[byte]);
+        }
+    }
+}
+
+#[cfg(feature = "stm32f405")]
+impl Model for Usart {
+    fn read(&mut self, regs: &mut Regs, offset: usize) -> u32 {
+        match offset {
+            o if o == offset_of!(UsartReg, sr) => self.status(regs.get(offset)),
+            o if o == offset_of!(UsartReg, dr) => self.input.pop_front().unwrap_or(0) as u32,
+            _ => regs.get(offset),
+        }
+    }
+
+    fn write(&mut self, regs: &mut Regs, offset: usize, value: u32) {
+        match offset {
+            o if o == offset_of!(UsartReg, dr) => self.transmit(value),
+            // the flags of SR are cleared by writing 0
+            o if o == offset_of!(UsartReg, sr) => regs.set(o, regs.get(o) & value),
+            _ => regs.set(offset, value),
+        }
+    }
+}
+
+#[cfg(feature = "stm32f072")]
+impl Model for Usart {
+    fn read(&mut self, regs: &mut Regs, offset: usize) -> u32 {
+        match offset {
+            o if o == offset_of!(UsartReg, isr) => self.status(regs.get(offset)),
+            o if o == offset_of!(UsartReg, rdr) => self.input.pop_front().unwrap_or(0) as u32,
+            _ => regs.get(offset),
+        }
+    }
+
+    fn write(&mut self, regs: &mut Regs, offset: usize, value: u32) {
+        if offset == offset_of!(UsartReg, tdr) {
+            self.transmit(value);
+        } else {
+            regs.set(offset, value);
+        }
+    }
+}
+
+/// A general purpose timer counting up by `step` on each read of `CNT`, as
+/// long as the counter is enabled and `ARR` is not zero. When it passes
+/// `ARR` it wraps to zero and sets `UIF`.
+pub struct Counter {
+    pub step: u32,
+}
+
+impl Default for Counter {
+    fn default() -> Self {
+        Counter { step: 1 }
+    }
+}
+
+impl Model for Counter {
+    fn read(&mut self, regs: &mut Regs, offset: usize) -> u32 {
+        let cnt = regs.get(offset);
+        if offset != offset_of!(TimGenReg, cnt) {
+            return cnt;
+        }
+        let cr1 = regs.get(offset_of!(TimGenReg, cr1));
+        let arr = regs.get(offset_of!(TimGenReg, arr));
+        if cr1 & (1 << TIM_GEN::cr1::CEN) == 0 || arr == 0 {
+            return cnt;
+        }
+
+        let next = cnt as u64 + self.step as u64;
+        if next > arr as u64 {
+            let sr = offset_of!(TimGenReg, sr);
+            regs.set(sr, regs.get(sr) | (1 << TIM_GEN::sr::UIF));
+            regs.set(offset, ((next - arr as u64 - 1) % (arr as u64 + 1)) as u32);
+        } else {
+            regs.set(offset, next as u32);
+        }
+        cnt
+    }
+
+    fn write(&mut self, regs: &mut Regs, offset: usize, value: u32) {
+        if offset == offset_of!(TimGenReg, sr) {
+            regs.set(offset, regs.get(offset) & value);
+        } else {
+            regs.set(offset, value);
+        }
+    }
+}
+
+/// An SPI master that sends each frame at once and, in full duplex, receives
+/// it back.
+#[derive(Default)]
+pub struct Spi {
+    received: Option<u32>,
+    overrun: bool,
+    dr_read: bool,
+}
+
+impl Model for Spi {
+    fn read(&mut self, regs: &mut Regs, offset: usize) -> u32 {
+        if offset == offset_of!(Spi1Reg, dr) {
+            self.dr_read = true;
+            return self.received.take().unwrap_or(0);
+        }
+        if offset != offset_of!(Spi1Reg, sr) {
+            return regs.get(offset);
+        }
+
+        let bit = |b: u8, set: bool| (set as u32) << b;
+        let flags = bit(SPI1::sr::TXE, true)
+            | bit(SPI1::sr::RXNE, self.received.is_some())
+            | bit(SPI1::sr::OVR, self.overrun);
+        let mask = bit(SPI1::sr::TXE, true) | bit(SPI1::sr::RXNE, true) | bit(SPI1::sr::OVR, true);
+        // reading DR and then SR clears the overrun
+        if self.dr_read {
+            self.overrun = false;
+            self.dr_read = false;
+        }
+        (regs.get(offset) & !mask) | flags
+    }
+
+    fn write(&mut self, regs: &mut Regs, offset: usize, value: u32) {
+        if offset != offset_of!(Spi1Reg, dr) {
+            regs.set(offset, value);
+            return;
+        }
+
+        let cr1 = regs.get(offset_of!(Spi1Reg, cr1));
+        if cr1 & (1 << SPI1::cr1::BIDIMODE) != 0 {
+            return; // only sends
+        }
+        if self.received.is_some() {
+            self.overrun = true;
+        } else {
+            self.received = Some(value);
+        }
+    }
+}
+
+/// The levels driven on the input pins of a GPIO port, read from `IDR`.
+#[derive(Default)]
+pub struct Gpio {
+    idr: u32,
+}
+
+impl Gpio {
+    /// Drives pin `pin` of the port high or low.
+    pub fn input(&mut self, pin: u8, high: bool) {
+        if high {
+            self.idr |= 1 << pin;
+        } else {
+            self.idr &= !(1 << pin);
+        }
+    }
+}
+
+impl Model for Gpio {
+    fn read(&mut self, regs: &mut Regs, offset: usize) -> u32 {
+        if offset == offset_of!(GpioReg, idr) {
+            self.idr
+        } else {
+            regs.get(offset)
+        }
+    }
+
+    fn write(&mut self, regs: &mut Regs, offset: usize, value: u32) {
+        if offset != offset_of!(GpioReg, idr) {
+            regs.set(offset, value);
+        }
+    }
+}
+
+/// The RTC, with the write-0-to-clear flags of `ISR`. Only `INIT` of the
+/// other bits can be written.
+pub struct Rtc;
+
+impl Model for Rtc {
+    fn write(&mut self, regs: &mut Regs, offset: usize, value: u32) {
+        if offset != offset_of!(RtcReg, isr) {
+            regs.set(offset, value);
+            return;
+        }
+
+        let bit = |b: u8| 1u32 << b;
+        let flags = bit(RTC::isr::RSF)
+            | bit(RTC::isr::ALRAF)
+            | bit(RTC::isr::WUTF)
+            | bit(RTC::isr::TSF)
+            | bit(RTC::isr::TSOVF)
+            | bit(RTC::isr::TAMP1F)
+            | bit(RTC::isr::TAMP2F);
+        let init = bit(RTC::isr::INIT);
+        let isr = regs.get(offset);
+        let read_only = isr & !(flags | init);
+        let still_set = isr & value & flags;
+        regs.set(offset, read_only | still_set | (value & init));
+    }
+}
+
+/// The cycle counter of the DWT, counting up by `step` on each read of
+/// `CYCCNT` while `CYCCNTENA` is set, and wrapping at 32 bits.
+#[cfg(feature = "stm32f405")]
+pub struct CycleCounter {
+    pub step: u32,
+}
+
+#[cfg(feature = "stm32f405")]
+impl Default for CycleCounter {
+    fn default() -> Self {
+        CycleCounter { step: 1 }
+    }
+}
+
+#[cfg(feature = "stm32f405")]
+impl Model for CycleCounter {
+    fn read(&mut self, regs: &mut Regs, offset: usize) -> u32 {
+        let value = regs.get(offset);
+        let ctrl = regs.get(offset_of!(DwtReg, ctrl));
+        if offset == offset_of!(DwtReg, cyccnt) && ctrl & (1 << DWT::ctrl::CYCCNTENA) != 0 {
+            regs.set(offset, value.wrapping_add(self.step));
+        }
+        value
+    }
+}
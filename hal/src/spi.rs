@@ -1,3 +1,64 @@
+//! # SPI Module
+//!
+//! This module provides functionality for using the SPI1, SPI2 and SPI3 peripherals
+//! as a bus master. It supports all four SPI modes, full duplex transfers,
+//! 8 and 16 bit frames, and DMA transmission of large buffers.
+//!
+//! ## Enums
+//!
+//! - `Bus`: Selects which SPI peripheral to use.
+//! - `Mode`: The clock polarity and phase of the bus.
+//!
+//! ## Structures
+//!
+//! - `Spi1`, `Spi2`, `Spi3`: The buses as types, for `init`, which only takes
+//!   pins that the alternate function tables in `gpio::af` list for the bus.
+//!
+//! ## Functions
+//!
+//! - `init`: Initializes an SPI bus with the given frequency, mode and pins.
+//! - `write`: Sends 8 bit data and waits for it to be sent.
+//! - `write_wide`: Sends 16 bit data and waits for it to be sent.
+//! - `transfer`: Sends data while replacing it with the data received.
+//! - `read`: Receives data while sending 0xFF.
+//! - `write_dma`: Starts a DMA transfer of 8 bit data.
+//! - `write_wide_dma`: Starts a DMA transfer of 16 bit data.
+//! - `busy`: Checks if a DMA transfer is still in progress.
+//! - `take_dma_error`: Checks if a DMA transfer stopped with an error.
+//! - `flush`: Waits for any DMA transfer to finish.
+//! - `set_dma_callback`: Sets a function called when a DMA transfer finishes.
+//! - `handle_dma_irq`: Handles the DMA interrupt for a bus.
+//!
+//! ## DMA
+//!
+//! The DMA streams used are, from table 42 and 43 of the RM0090 Reference Manual:
+//!
+//! - SPI1_TX: DMA2 Stream 3 Channel 3
+//! - SPI2_TX: DMA1 Stream 4 Channel 0
+//! - SPI3_TX: DMA1 Stream 5 Channel 0
+//!
+//! The interrupt for that stream must call `handle_dma_irq` for the completion
+//! callback to run. Without it, `busy` still notices when the transfer is done.
+//! A transfer the DMA stops with an error ends the same way, with the rest of
+//! the data not sent, and is recorded for `take_dma_error`.
+//!
+//! The STM32F072 has SPI1 and SPI2 only, and its DMA is not used yet, so there
+//! `write_dma` and `write_wide_dma` send the data before they return and then
+//! call the callback.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use hal::{cpu, gpio, spi};
+//!
+//! cpu::init();
+//!
+//! let pins = gpio::take().unwrap();
+//! spi::init::<spi::Spi1>(10_000_000, spi::Mode::Mode0, pins.pa5, gpio::NoPin, pins.pa7);
+//!
+//! spi::write(spi::Bus::Spi1, &[0x2A, 0x00]);
+//! ```
+
 #[allow(unused_imports)]
 use core::ptr;
 
@@ -5,94 +66,733 @@ use core::ptr;
 use super::cpu;
 pub use super::cpu::*;
 use super::gpio;
+use super::gpio::{af, AltFn, MaybeAltFn};
+
+use super::clock;
+#[allow(unused_imports)]
+use super::cpu::SPI1::{cr1, cr2, sr};
+#[cfg(feature = "stm32f405")]
+use super::dma;
+
+/// The SPI peripheral to use.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Bus {
+    Spi1 = 0,
+    Spi2 = 1,
+    #[cfg(feature = "stm32f405")]
+    Spi3 = 2,
+}
+
+/// SPI mode giving the clock polarity (CPOL) and phase (CPHA).
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Mode {
+    /// Clock idles low, data sampled on rising edge.
+    Mode0,
+    /// Clock idles low, data sampled on falling edge.
+    Mode1,
+    /// Clock idles high, data sampled on falling edge.
+    Mode2,
+    /// Clock idles high, data sampled on rising edge.
+    Mode3,
+}
+
+impl Mode {
+    /// Returns the (CPOL, CPHA) bits for the mode.
+    pub fn cpol_cpha(&self) -> (u32, u32) {
+        match self {
+            Mode::Mode0 => (0, 0),
+            Mode::Mode1 => (0, 1),
+            Mode::Mode2 => (1, 0),
+            Mode::Mode3 => (1, 1),
+        }
+    }
+}
+
+/// Computes the BR field of CR1 for the fastest clock that does not exceed `spi_freq`.
+///
+/// The SPI clock is `pclk_freq / 2^(BR+1)`. If `spi_freq` is slower than the
+/// slowest possible clock, the slowest clock is used.
+pub fn baud_prescaler(pclk_freq: u32, spi_freq: u32) -> u32 {
+    assert!(spi_freq > 0);
+    let mut br: u32 = 0;
+    while br < 7 && (pclk_freq >> (br + 1)) > spi_freq {
+        br += 1;
+    }
+    br
+}
+
+struct BusInfo {
+    regs: *mut cpu::Spi1Reg,
+    pclk_freq: u32,
+    #[cfg(feature = "stm32f405")]
+    tx_stream: dma::Stream,
+    #[cfg(feature = "stm32f405")]
+    tx_channel: u8,
+}
+
+#[cfg(feature = "stm32f405")]
+fn bus_info(bus: Bus) -> BusInfo {
+    // SPI1 is on APB2, SPI2 and SPI3 are on APB1.
+    let clocks = clock::clocks();
+    match bus {
+        Bus::Spi1 => BusInfo {
+            regs: SPI1,
+            pclk_freq: clocks.pclk2,
+            tx_stream: dma::Stream(DMA2, 3),
+            tx_channel: 3,
+        },
+        Bus::Spi2 => BusInfo {
+            regs: SPI2,
+            pclk_freq: clocks.pclk1,
+            tx_stream: dma::Stream(DMA1, 4),
+            tx_channel: 0,
+        },
+        Bus::Spi3 => BusInfo {
+            regs: SPI3,
+            pclk_freq: clocks.pclk1,
+            tx_stream: dma::Stream(DMA1, 5),
+            tx_channel: 0,
+        },
+    }
+}
 
 #[cfg(feature = "stm32f072")]
-#[inline(never)]
-pub fn init1(spi_freq: u32, scl_pin: gpio::Pin, sda_pin: gpio::Pin) {
-    let _ = (spi_freq, scl_pin, sda_pin);
-    assert!(false);
+fn bus_info(bus: Bus) -> BusInfo {
+    // there is a single APB clock
+    let pclk_freq = clock::clocks().pclk1;
+    match bus {
+        Bus::Spi1 => BusInfo {
+            regs: SPI1,
+            pclk_freq,
+        },
+        Bus::Spi2 => BusInfo {
+            regs: SPI2,
+            pclk_freq,
+        },
+    }
+}
+
+#[cfg(feature = "stm32f405")]
+static DMA_ACTIVE: Global<[bool; 3]> = Global::new([false; 3]);
+
+/// The function to call when a DMA transfer ends, for each bus.
+type DmaCallbacks = [Option<fn()>; 3];
+
+static DMA_CALLBACK: Global<DmaCallbacks> = Global::new([None; 3]);
+
+/// True for each bus whose last DMA transfer stopped with an error.
+#[cfg(feature = "stm32f405")]
+static DMA_ERROR: Global<[bool; 3]> = Global::new([false; 3]);
+
+#[cfg(feature = "stm32f405")]
+fn dma_active(bus: Bus) -> bool {
+    unsafe { ptr::read_volatile(ptr::addr_of!((*DMA_ACTIVE.as_ptr())[bus as usize])) }
+}
+
+#[cfg(feature = "stm32f405")]
+fn set_dma_active(bus: Bus, active: bool) {
+    unsafe {
+        ptr::write_volatile(
+            ptr::addr_of_mut!((*DMA_ACTIVE.as_ptr())[bus as usize]),
+            active,
+        )
+    }
+}
+
+/// An SPI peripheral as a type, with the signals its pins must serve, so
+/// `init` only takes pins that work for the bus.
+pub trait Instance {
+    const BUS: Bus;
+    type Sck;
+    type Miso;
+    type Mosi;
+}
+
+pub struct Spi1;
+pub struct Spi2;
+#[cfg(feature = "stm32f405")]
+pub struct Spi3;
+
+impl Instance for Spi1 {
+    const BUS: Bus = Bus::Spi1;
+    type Sck = af::Spi1Sck;
+    type Miso = af::Spi1Miso;
+    type Mosi = af::Spi1Mosi;
+}
+
+impl Instance for Spi2 {
+    const BUS: Bus = Bus::Spi2;
+    type Sck = af::Spi2Sck;
+    type Miso = af::Spi2Miso;
+    type Mosi = af::Spi2Mosi;
 }
 
 #[cfg(feature = "stm32f405")]
+impl Instance for Spi3 {
+    const BUS: Bus = Bus::Spi3;
+    type Sck = af::Spi3Sck;
+    type Miso = af::Spi3Miso;
+    type Mosi = af::Spi3Mosi;
+}
+
+/// Initializes the SPI bus `S` as master.
+///
+/// If `miso_pin` is `gpio::NoPin`, the bus is set up as a single data line
+/// that only transmits, otherwise it is set up as full duplex.
+/// The clock is the fastest the peripheral can do that is not above `spi_freq`.
 #[inline(never)]
-pub fn init1(spi_freq: u32, scl_pin: gpio::Pin, sda_pin: gpio::Pin) {
-    scl_pin.output();
-    sda_pin.output();
+pub fn init<S: Instance>(
+    spi_freq: u32,
+    mode: Mode,
+    sck_pin: impl AltFn<S::Sck>,
+    miso_pin: impl MaybeAltFn<S::Miso>,
+    mosi_pin: impl AltFn<S::Mosi>,
+) {
+    let bus = S::BUS;
+    let info = bus_info(bus);
+    let spi = info.regs;
 
-    sda_pin.pullup();
-    scl_pin.pulldown();
+    flush(bus);
 
-    // enable clock for SPI1
-    cpu::write!(RCC.apb2enr[SPI1EN;1], 0b1);
+    // enable clock for the SPI
+    match bus {
+        Bus::Spi1 => {
+            cpu::write!(RCC.apb2enr.SPI1EN, 0b1);
+        }
+        Bus::Spi2 => {
+            cpu::write!(RCC.apb1enr.SPI2EN, 0b1);
+        }
+        #[cfg(feature = "stm32f405")]
+        Bus::Spi3 => {
+            cpu::write!(RCC.apb1enr.SPI3EN, 0b1);
+        }
+    }
+
+    // set up pins
+    let (sck_pin, sck_af) = gpio::alt_fn(&sck_pin);
+    sck_pin.alt_fun(sck_af, true);
+    let (mosi_pin, mosi_af) = gpio::alt_fn(&mosi_pin);
+    mosi_pin.alt_fun(mosi_af, true);
+    let miso_pin = miso_pin.alt_fn();
+    if let Some((miso_pin, miso_af)) = miso_pin {
+        miso_pin.alt_fun(miso_af, true);
+    }
 
-    // set up pins - AF from Figur 26 of RM0090 Reference Manual
-    // also see table 9 "Alternate function mapping" table of the datasheet stm32f405zg.pdf
-    scl_pin.alt_fun(5, true); // AF5 for PA5 is SPI1_SCK
-    sda_pin.alt_fun(5, true); // AF5 for PA7 is SPI1_MOSI
-    assert!(scl_pin.0 == gpio::GPIOA);
-    assert!(sda_pin.0 == gpio::GPIOA);
-    assert!(scl_pin.1 == 5);
-    assert!(sda_pin.1 == 7);
+    cpu::write!(spi.cr1.cr1::SPE, 0b0); // disable while configuring
+    cpu::write!(spi.cr2, 0); // no interrupts or DMA
 
     // set up the SPI
-    cpu::write!(SPI1.cr1[DFF;1], 0b0); // set to 8 bit frame
-    cpu::write!(SPI1.cr1[LSBFIRST;1], 0b0); // set MSB send first
-    cpu::write!(SPI1.cr1[RXONLY;1], 0b0); // set to full duplex
-    cpu::write!(SPI1.cr1[CRCEN;1], 0b0); // disable CRC
+    write_frame(spi, false); // set to 8 bit frame
+    cpu::write!(spi.cr1.cr1::LSBFIRST, 0b0); // set MSB send first
+    cpu::write!(spi.cr1.cr1::RXONLY, 0b0); // set to full duplex
+    cpu::write!(spi.cr1.cr1::CRCEN, 0b0); // disable CRC
 
-    // TODO - Brett has SPI at about 2.5 Mhz not 10 MHz
-    assert!(spi_freq >= 48_000_000 / 8);
-    //cpu::write!( SPI1.cr1[BR;3] , 0b100 ); // set baud rate to 2.5 Mhz (0b100 )
-    cpu::write!( SPI1.cr1[BR;3] , 0b010 ); // set baud rate to 10 Mhz (0b010 )
+    let br = baud_prescaler(info.pclk_freq, spi_freq);
+    cpu::write!(spi.cr1.cr1::BR, br);
 
-    cpu::write!( SPI1.cr1[MSTR;1] , 0b1 ); // set to master mode
-    cpu::write!(SPI1.cr1[BIDIMODE;1], 0b1); // set BIDIMODE to 1 line both directions
-    cpu::write!(SPI1.cr1[BIDIOE;1], 0b1); // set BIDIOE to output
+    cpu::write!(spi.cr1.cr1::MSTR, 0b1); // set to master mode
 
-    cpu::write!( SPI1.cr1[SSM;1] , 0b1 );
-    cpu::write!( SPI1.cr1[SSI;1] , 0b1 );
+    if miso_pin.is_some() {
+        cpu::write!(spi.cr1.cr1::BIDIMODE, 0b0); // 2 line full duplex
+        cpu::write!(spi.cr1.cr1::BIDIOE, 0b0);
+    } else {
+        cpu::write!(spi.cr1.cr1::BIDIMODE, 0b1); // set BIDIMODE to 1 line both directions
+        cpu::write!(spi.cr1.cr1::BIDIOE, 0b1); // set BIDIOE to output
+    }
+
+    // software slave management with internal NSS high
+    cpu::write!(spi.cr1.cr1::SSM, 0b1);
+    cpu::write!(spi.cr1.cr1::SSI, 0b1);
+
+    let (cpol, cpha) = mode.cpol_cpha();
+    cpu::write!(spi.cr1.cr1::CPOL, cpol);
+    cpu::write!(spi.cr1.cr1::CPHA, cpha);
+
+    cpu::write!(spi.cr1.cr1::SPE, 0b1); // enable SPI
+}
+
+/// Waits for the SPI to finish sending the last frame.
+fn wait_idle(spi: *mut cpu::Spi1Reg) {
+    #[cfg(feature = "std")]
+    let _ = spi;
 
-    cpu::write!( SPI1.cr1[CPOL;1] , 0b0 ); // low when idle
-    cpu::write!( SPI1.cr1[CPHA;1] , 0b0 ); // sample on rising edge
+    #[cfg(not(feature = "std"))]
+    {
+        while cpu::read!(spi.sr.sr::TXE) == 0 {}
+        while cpu::read!(spi.sr.sr::BSY) != 0 {}
+    }
+}
+
+/// Returns true if the frame size is 16 bits.
+#[cfg(feature = "stm32f405")]
+fn frame_wide(spi: *mut cpu::Spi1Reg) -> bool {
+    cpu::read!(spi.cr1.cr1::DFF) == 1
+}
 
-    cpu::write!( SPI1.cr1[SPE;1] , 0b0 ); // do ot enable SPI yet
-    cpu::write!( SPI1.cr1[SPE;1] , 0b1 ); // enable SPI
+/// Returns true if the frame size is 16 bits.
+#[cfg(feature = "stm32f072")]
+fn frame_wide(spi: *mut cpu::Spi1Reg) -> bool {
+    cpu::read!(spi.cr2.cr2::DS) == 0b1111
 }
 
+/// Writes the frame size, 16 bits if `wide`, otherwise 8 bits, with the DFF bit.
 #[cfg(feature = "stm32f405")]
+fn write_frame(spi: *mut cpu::Spi1Reg, wide: bool) {
+    cpu::write!(spi.cr1.cr1::DFF, if wide { 1 } else { 0 });
+}
+
+/// Writes the frame size, 16 bits if `wide`, otherwise 8 bits, with the DS
+/// bits. FRXTH sets RXNE once a whole frame is received.
+#[cfg(feature = "stm32f072")]
+fn write_frame(spi: *mut cpu::Spi1Reg, wide: bool) {
+    if wide {
+        cpu::write!(spi.cr2.cr2::DS, 0b1111);
+        cpu::write!(spi.cr2.cr2::FRXTH, 0b0);
+    } else {
+        cpu::write!(spi.cr2.cr2::DS, 0b0111);
+        cpu::write!(spi.cr2.cr2::FRXTH, 0b1);
+    }
+}
+
+/// Sets the frame size to 16 bits if `wide`, otherwise 8 bits.
+/// The frame size can only be changed while the SPI is disabled.
+fn set_frame(spi: *mut cpu::Spi1Reg, wide: bool) {
+    if frame_wide(spi) == wide {
+        return;
+    }
+    wait_idle(spi);
+    cpu::write!(spi.cr1.cr1::SPE, 0b0);
+    write_frame(spi, wide);
+    cpu::write!(spi.cr1.cr1::SPE, 0b1);
+}
+
+/// Writes an 8 bit frame to DR. The STM32F072 packs a wider write to DR into
+/// two frames, so there it is written as a byte.
+#[cfg(all(feature = "stm32f072", not(feature = "std")))]
+fn write_dr8(spi: *mut cpu::Spi1Reg, d: u8) {
+    unsafe { ptr::write_volatile(ptr::addr_of_mut!((*spi).dr) as *mut u8, d) };
+}
+
+/// Writes an 8 bit frame to DR.
+#[cfg(any(feature = "stm32f405", feature = "std"))]
+fn write_dr8(spi: *mut cpu::Spi1Reg, d: u8) {
+    cpu::write!(spi.dr, d as u32);
+}
+
+/// Reads an 8 bit frame from DR, as a byte on the STM32F072 like `write_dr8`.
+#[cfg(all(feature = "stm32f072", not(feature = "std")))]
+fn read_dr8(spi: *mut cpu::Spi1Reg) -> u8 {
+    unsafe { ptr::read_volatile(ptr::addr_of!((*spi).dr) as *const u8) }
+}
+
+/// Reads an 8 bit frame from DR.
+#[cfg(any(feature = "stm32f405", feature = "std"))]
+fn read_dr8(spi: *mut cpu::Spi1Reg) -> u8 {
+    cpu::read!(spi.dr) as u8
+}
+
+/// Discards the frames received while only sending, and clears the overrun
+/// they cause once two are unread, so `transfer` later reads its own frames.
+fn drain_rx(spi: *mut cpu::Spi1Reg) {
+    while cpu::read!(spi.sr.sr::RXNE) == 1 {
+        let _ = cpu::read!(spi.dr);
+    }
+    // reading DR and then SR clears OVR
+    let _ = cpu::read!(spi.sr);
+}
+
+/// Sends one frame and returns the frame received at the same time.
+fn exchange(spi: *mut cpu::Spi1Reg, d: u8) -> u8 {
+    #[cfg(not(feature = "std"))]
+    while cpu::read!(spi.sr.sr::TXE) == 0 {}
+    write_dr8(spi, d);
+
+    #[cfg(not(feature = "std"))]
+    while cpu::read!(spi.sr.sr::RXNE) == 0 {}
+    read_dr8(spi)
+}
+
+/// Sends 8 bit frames and waits until they have been sent. On a full duplex
+/// bus the frames received at the same time are discarded.
 #[inline(never)]
-pub fn write1(data: &[u8]) {
-    // wait for SPI to not be busy
-    while cpu::read!(SPI1.sr[BSY;1]) != 0 {}
+pub fn write(bus: Bus, data: &[u8]) {
+    let spi = bus_info(bus).regs;
+
+    flush(bus);
+    set_frame(spi, false);
 
     for &d in data {
-        cpu::write!(SPI1.dr, d as u32); // send 8 buts of data
+        // wait for transmit buffer to be empty
+        #[cfg(not(feature = "std"))]
+        while cpu::read!(spi.sr.sr::TXE) == 0 {}
+
+        write_dr8(spi, d); // send 8 bits of data
+        drain_rx(spi);
+    }
 
+    wait_idle(spi);
+    drain_rx(spi);
+}
+
+/// Sends 16 bit frames and waits until they have been sent, discarding the
+/// frames received like `write`.
+#[inline(never)]
+pub fn write_wide(bus: Bus, data: &[u16]) {
+    let spi = bus_info(bus).regs;
+
+    flush(bus);
+    set_frame(spi, true);
+
+    for &d in data {
         // wait for transmit buffer to be empty
-        while cpu::read!(SPI1.sr[TXE;1]) == 0 {}
+        #[cfg(not(feature = "std"))]
+        while cpu::read!(spi.sr.sr::TXE) == 0 {}
+
+        cpu::write!(spi.dr, d as u32); // send 16 bits of data
+        drain_rx(spi);
     }
 
-    // wait for SPI to not be busy
-    while cpu::read!(SPI1.sr[BSY;1]) != 0 {}
+    wait_idle(spi);
+    drain_rx(spi);
+    set_frame(spi, false);
 }
 
+/// Sends the bytes in `data` and replaces each with the byte received.
+///
+/// The bus must have been set up with a MISO pin.
+#[inline(never)]
+pub fn transfer(bus: Bus, data: &mut [u8]) {
+    let spi = bus_info(bus).regs;
+
+    flush(bus);
+    assert!(cpu::read!(spi.cr1.cr1::BIDIMODE) == 0);
+    set_frame(spi, false);
+
+    // discard any stale received data
+    drain_rx(spi);
+
+    for d in data.iter_mut() {
+        *d = exchange(spi, *d);
+    }
+
+    wait_idle(spi);
+}
+
+/// Reads bytes into `data` while sending 0xFF.
+///
+/// The bus must have been set up with a MISO pin.
+pub fn read(bus: Bus, data: &mut [u8]) {
+    for d in data.iter_mut() {
+        *d = 0xFF;
+    }
+    transfer(bus, data);
+}
+
+#[cfg(feature = "stm32f405")]
+fn start_dma(bus: Bus, mem: u32, len: usize, size: dma::Size) {
+    let info = bus_info(bus);
+    let spi = info.regs;
+
+    assert!(len <= 0xFFFF);
+
+    flush(bus);
+    set_frame(spi, size == dma::Size::HalfWord);
+
+    if len == 0 {
+        set_frame(spi, false);
+        return;
+    }
+
+    set_dma_active(bus, true);
+
+    #[cfg(feature = "std")]
+    {
+        // no DMA in the simulator so the transfer finishes right away
+        let _ = (mem, info.tx_channel);
+        finish_dma(bus);
+    }
+
+    #[cfg(not(feature = "std"))]
+    {
+        let dr: u32 = unsafe { ptr::addr_of!((*spi).dr) as u32 };
+        info.tx_stream
+            .start_mem_to_periph(info.tx_channel, dr, mem, len as u16, size);
+        info.tx_stream.enable_irq();
+
+        cpu::write!(spi.cr2.cr2::TXDMAEN, 0b1);
+    }
+}
+
+/// Stops the DMA for the bus once the last frame is out and calls the callback.
+///
+/// It is called from both `busy` and `handle_dma_irq`, so only the first to
+/// see the transfer active finishes it.
+#[cfg(feature = "stm32f405")]
+fn finish_dma(bus: Bus) {
+    let claimed = cpu::critical_section(|| {
+        let active = dma_active(bus);
+        set_dma_active(bus, false);
+        active
+    });
+    if !claimed {
+        return;
+    }
+    let info = bus_info(bus);
+    let spi = info.regs;
+
+    info.tx_stream.clear_flags();
+    wait_idle(spi);
+    cpu::write!(spi.cr2.cr2::TXDMAEN, 0b0);
+    drain_rx(spi);
+    set_frame(spi, false);
+
+    dma_callback(bus);
+}
+
+/// Records that the DMA stopped the transfer on the bus with an error, and
+/// ends it as `finish_dma` does.
+#[cfg(feature = "stm32f405")]
+fn fail_dma(bus: Bus) {
+    bus_info(bus).tx_stream.stop();
+    unsafe { ptr::write_volatile(ptr::addr_of_mut!((*DMA_ERROR.as_ptr())[bus as usize]), true) }
+    finish_dma(bus);
+}
+
+fn dma_callback(bus: Bus) {
+    if let Some(callback) = unsafe { DMA_CALLBACK.get()[bus as usize] } {
+        callback();
+    }
+}
+
+/// Starts sending 8 bit frames using DMA and returns right away.
+///
+/// # Safety
+///
+/// The `data` must not be changed or freed until `busy` returns false.
 #[cfg(feature = "stm32f405")]
 #[inline(never)]
-pub fn write1_wide(data: &[u16]) {
-    // wait for SPI to not be busy
-    while cpu::read!(SPI1.sr[BSY;1]) != 0 {}
+pub unsafe fn write_dma(bus: Bus, data: &[u8]) {
+    start_dma(bus, data.as_ptr() as u32, data.len(), dma::Size::Byte);
+}
 
-    cpu::write!(SPI1.cr1[DFF;1], 0b1); // set to 16 bit frame
+/// Starts sending 16 bit frames using DMA and returns right away.
+///
+/// # Safety
+///
+/// The `data` must not be changed or freed until `busy` returns false.
+#[cfg(feature = "stm32f405")]
+#[inline(never)]
+pub unsafe fn write_wide_dma(bus: Bus, data: &[u16]) {
+    start_dma(bus, data.as_ptr() as u32, data.len(), dma::Size::HalfWord);
+}
 
-    for &d in data {
-        cpu::write!(SPI1.dr, d as u32); // send 16 bits of data
+/// Sends 8 bit frames, and calls the DMA callback once they have been sent.
+///
+/// There is no DMA on the STM32F072 yet, so this waits for the data to be
+/// sent the same as `write`.
+///
+/// # Safety
+///
+/// There is nothing to uphold here. It is `unsafe` so that callers are the
+/// same on both chips, as on the STM32F405 `data` is read by the DMA later.
+#[cfg(feature = "stm32f072")]
+#[inline(never)]
+pub unsafe fn write_dma(bus: Bus, data: &[u8]) {
+    write(bus, data);
+    dma_callback(bus);
+}
 
-        // wait for transmit buffer to be empty
-        while cpu::read!(SPI1.sr[TXE;1]) == 0 {}
+/// Sends 16 bit frames, and calls the DMA callback once they have been sent.
+///
+/// # Safety
+///
+/// There is nothing to uphold here, see `write_dma`.
+#[cfg(feature = "stm32f072")]
+#[inline(never)]
+pub unsafe fn write_wide_dma(bus: Bus, data: &[u16]) {
+    write_wide(bus, data);
+    dma_callback(bus);
+}
+
+/// Returns true while a DMA transfer on the bus is still in progress.
+#[cfg(feature = "stm32f405")]
+#[inline(never)]
+pub fn busy(bus: Bus) -> bool {
+    if !dma_active(bus) {
+        return false;
+    }
+    let stream = bus_info(bus).tx_stream;
+    if stream.error() {
+        fail_dma(bus);
+        return false;
+    }
+    if stream.complete() {
+        finish_dma(bus);
+        return false;
+    }
+    true
+}
+
+/// Returns true while a DMA transfer on the bus is still in progress, which
+/// is never on the STM32F072 as `write_dma` waits for the data to be sent.
+#[cfg(feature = "stm32f072")]
+pub fn busy(bus: Bus) -> bool {
+    let _ = bus;
+    false
+}
+
+/// Waits until any DMA transfer on the bus has finished.
+pub fn flush(bus: Bus) {
+    while busy(bus) {}
+}
+
+/// Returns true if a DMA transfer on the bus stopped with an error since the
+/// last call, which clears it. The data after the error was not sent.
+#[cfg(feature = "stm32f405")]
+pub fn take_dma_error(bus: Bus) -> bool {
+    cpu::critical_section(|| unsafe {
+        let errors = DMA_ERROR.as_ptr();
+        let error = (*errors)[bus as usize];
+        (*errors)[bus as usize] = false;
+        error
+    })
+}
+
+/// Returns false, as the STM32F072 does not use DMA for SPI yet.
+#[cfg(feature = "stm32f072")]
+pub fn take_dma_error(bus: Bus) -> bool {
+    let _ = bus;
+    false
+}
+
+/// Sets a function to call when a DMA transfer on the bus finishes, with or
+/// without an error. It is called by whichever of `handle_dma_irq`, `busy`
+/// or `flush` first sees the transfer end, so it can run in the DMA
+/// interrupt or in the caller of `busy`. On the STM32F072 it is called by
+/// `write_dma` and `write_wide_dma` before they return.
+pub fn set_dma_callback(bus: Bus, callback: Option<fn()>) {
+    unsafe {
+        (*DMA_CALLBACK.as_ptr())[bus as usize] = callback;
     }
+}
 
-    // wait for SPI to not be busy
-    while cpu::read!(SPI1.sr[BSY;1]) != 0 {}
+/// Handles the interrupt for the TX DMA stream of the bus.
+#[cfg(feature = "stm32f405")]
+#[inline(never)]
+pub fn handle_dma_irq(bus: Bus) {
+    let stream = bus_info(bus).tx_stream;
+    if stream.error() {
+        fail_dma(bus);
+    } else if stream.complete() {
+        finish_dma(bus);
+    }
+}
 
-    cpu::write!(SPI1.cr1[DFF;1], 0b0); // set to 8 bit frame
+/// Does nothing, as the STM32F072 does not use DMA for SPI yet.
+#[cfg(feature = "stm32f072")]
+pub fn handle_dma_irq(bus: Bus) {
+    let _ = bus;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sim;
+
+    #[test]
+    fn test_baud_prescaler() {
+        // 84 MHz / 8 = 10.5 MHz is too fast for 10 MHz
+        assert_eq!(baud_prescaler(84_000_000, 10_000_000), 0b011);
+        assert_eq!(baud_prescaler(84_000_000, 10_500_000), 0b010);
+        assert_eq!(baud_prescaler(84_000_000, 42_000_000), 0b000);
+        assert_eq!(baud_prescaler(84_000_000, 100_000_000), 0b000);
+        assert_eq!(baud_prescaler(42_000_000, 1_000_000), 0b101);
+        assert_eq!(baud_prescaler(84_000_000, 1), 0b111);
+    }
+
+    #[test]
+    fn test_mode() {
+        assert_eq!(Mode::Mode0.cpol_cpha(), (0, 0));
+        assert_eq!(Mode::Mode1.cpol_cpha(), (0, 1));
+        assert_eq!(Mode::Mode2.cpol_cpha(), (1, 0));
+        assert_eq!(Mode::Mode3.cpol_cpha(), (1, 1));
+    }
+
+    #[test]
+    fn test_write_full_duplex() {
+        let _sim = sim::Guard::new();
+        crate::init(16_000_000);
+
+        let (sck, miso, mosi) = (gpio::PA5::new(), gpio::PA6::new(), gpio::PA7::new());
+        init::<Spi1>(1_000_000, Mode::Mode0, sck, miso, mosi);
+
+        // the frames received while only sending are read, so none overrun
+        write(Bus::Spi1, &[0x01, 0x02, 0x03]);
+        write_wide(Bus::Spi1, &[0x1234, 0x5678]);
+        assert_eq!(cpu::read!(SPI1.sr.sr::OVR), 0);
+        assert_eq!(cpu::read!(SPI1.sr.sr::RXNE), 0);
+
+        // MISO is wired to MOSI in the simulation
+        let mut data = [0xA5, 0x5A];
+        transfer(Bus::Spi1, &mut data);
+        assert_eq!(data, [0xA5, 0x5A]);
+    }
+
+    #[cfg(feature = "stm32f405")]
+    #[test]
+    fn test_finish_dma_once() {
+        static mut CALLS: u32 = 0;
+        fn done() {
+            unsafe { CALLS += 1 };
+        }
+
+        let _sim = sim::Guard::new();
+        set_dma_callback(Bus::Spi2, Some(done));
+        set_dma_active(Bus::Spi2, true);
+
+        // both busy and the DMA interrupt can see the transfer complete
+        finish_dma(Bus::Spi2);
+        finish_dma(Bus::Spi2);
+        assert_eq!(unsafe { CALLS }, 1);
+        assert!(!busy(Bus::Spi2));
+        set_dma_callback(Bus::Spi2, None);
+    }
+
+    #[cfg(feature = "stm32f405")]
+    #[test]
+    fn test_dma_error() {
+        let _sim = sim::Guard::new();
+        let hisr = unsafe { ptr::addr_of_mut!((*DMA1).hisr) };
+
+        // SPI2 sends on DMA1 stream 4, whose flags are the low bits of HISR
+        set_dma_active(Bus::Spi2, true);
+        sim::raise(hisr, 1 << 0); // FEIF, which does not stop the transfer
+        assert!(busy(Bus::Spi2));
+        handle_dma_irq(Bus::Spi2);
+        assert!(busy(Bus::Spi2));
+        assert!(!take_dma_error(Bus::Spi2));
+
+        sim::raise(hisr, 1 << 3); // TEIF
+        handle_dma_irq(Bus::Spi2);
+        assert!(!busy(Bus::Spi2));
+        assert!(take_dma_error(Bus::Spi2));
+        assert!(!take_dma_error(Bus::Spi2));
+    }
+
+    #[cfg(feature = "stm32f405")]
+    #[test]
+    fn test_write_dma_callback() {
+        static mut DONE: bool = false;
+        fn done() {
+            unsafe { DONE = true };
+        }
+
+        set_dma_callback(Bus::Spi3, Some(done));
+        let data = [0x1234u16; 8];
+        unsafe { write_wide_dma(Bus::Spi3, &data) };
+        flush(Bus::Spi3);
+        assert!(!busy(Bus::Spi3));
+        assert!(unsafe { DONE });
+        set_dma_callback(Bus::Spi3, None);
+    }
 }
@@ -1,9 +1,11 @@
-// DO NOT EDIT. This was generated by svd2rusty
+// Register definitions in the format svd2rusty emits. The vendor SVD files
+// are not in the repository, so these are kept up to date by hand.
 //! This module provides definitions for various hardware registers and their fields.
 
 #![allow(unused)]
 #![allow(non_snake_case)]
 #![allow(non_upper_case_globals)]
+#![allow(non_camel_case_types)]
 
 pub mod GPIO {
     pub mod moder {
@@ -23,6 +25,22 @@ pub mod GPIO {
         pub const MODER2: u8 = 4;
         pub const MODER1: u8 = 2;
         pub const MODER0: u8 = 0;
+        crate::cpu::field!(MODER15, 2, ReadWrite);
+        crate::cpu::field!(MODER14, 2, ReadWrite);
+        crate::cpu::field!(MODER13, 2, ReadWrite);
+        crate::cpu::field!(MODER12, 2, ReadWrite);
+        crate::cpu::field!(MODER11, 2, ReadWrite);
+        crate::cpu::field!(MODER10, 2, ReadWrite);
+        crate::cpu::field!(MODER9, 2, ReadWrite);
+        crate::cpu::field!(MODER8, 2, ReadWrite);
+        crate::cpu::field!(MODER7, 2, ReadWrite);
+        crate::cpu::field!(MODER6, 2, ReadWrite);
+        crate::cpu::field!(MODER5, 2, ReadWrite);
+        crate::cpu::field!(MODER4, 2, ReadWrite);
+        crate::cpu::field!(MODER3, 2, ReadWrite);
+        crate::cpu::field!(MODER2, 2, ReadWrite);
+        crate::cpu::field!(MODER1, 2, ReadWrite);
+        crate::cpu::field!(MODER0, 2, ReadWrite);
     }
     pub mod otyper {
         pub const OT15: u8 = 15;
@@ -41,6 +59,22 @@ pub mod GPIO {
         pub const OT2: u8 = 2;
         pub const OT1: u8 = 1;
         pub const OT0: u8 = 0;
+        crate::cpu::field!(OT15, 1, ReadWrite);
+        crate::cpu::field!(OT14, 1, ReadWrite);
+        crate::cpu::field!(OT13, 1, ReadWrite);
+        crate::cpu::field!(OT12, 1, ReadWrite);
+        crate::cpu::field!(OT11, 1, ReadWrite);
+        crate::cpu::field!(OT10, 1, ReadWrite);
+        crate::cpu::field!(OT9, 1, ReadWrite);
+        crate::cpu::field!(OT8, 1, ReadWrite);
+        crate::cpu::field!(OT7, 1, ReadWrite);
+        crate::cpu::field!(OT6, 1, ReadWrite);
+        crate::cpu::field!(OT5, 1, ReadWrite);
+        crate::cpu::field!(OT4, 1, ReadWrite);
+        crate::cpu::field!(OT3, 1, ReadWrite);
+        crate::cpu::field!(OT2, 1, ReadWrite);
+        crate::cpu::field!(OT1, 1, ReadWrite);
+        crate::cpu::field!(OT0, 1, ReadWrite);
     }
     pub mod ospeedr {
         pub const OSPEEDR15: u8 = 30;
@@ -59,6 +93,22 @@ pub mod GPIO {
         pub const OSPEEDR2: u8 = 4;
         pub const OSPEEDR1: u8 = 2;
         pub const OSPEEDR0: u8 = 0;
+        crate::cpu::field!(OSPEEDR15, 2, ReadWrite);
+        crate::cpu::field!(OSPEEDR14, 2, ReadWrite);
+        crate::cpu::field!(OSPEEDR13, 2, ReadWrite);
+        crate::cpu::field!(OSPEEDR12, 2, ReadWrite);
+        crate::cpu::field!(OSPEEDR11, 2, ReadWrite);
+        crate::cpu::field!(OSPEEDR10, 2, ReadWrite);
+        crate::cpu::field!(OSPEEDR9, 2, ReadWrite);
+        crate::cpu::field!(OSPEEDR8, 2, ReadWrite);
+        crate::cpu::field!(OSPEEDR7, 2, ReadWrite);
+        crate::cpu::field!(OSPEEDR6, 2, ReadWrite);
+        crate::cpu::field!(OSPEEDR5, 2, ReadWrite);
+        crate::cpu::field!(OSPEEDR4, 2, ReadWrite);
+        crate::cpu::field!(OSPEEDR3, 2, ReadWrite);
+        crate::cpu::field!(OSPEEDR2, 2, ReadWrite);
+        crate::cpu::field!(OSPEEDR1, 2, ReadWrite);
+        crate::cpu::field!(OSPEEDR0, 2, ReadWrite);
     }
     pub mod pupdr {
         pub const PUPDR15: u8 = 30;
@@ -77,6 +127,22 @@ pub mod GPIO {
         pub const PUPDR2: u8 = 4;
         pub const PUPDR1: u8 = 2;
         pub const PUPDR0: u8 = 0;
+        crate::cpu::field!(PUPDR15, 2, ReadWrite);
+        crate::cpu::field!(PUPDR14, 2, ReadWrite);
+        crate::cpu::field!(PUPDR13, 2, ReadWrite);
+        crate::cpu::field!(PUPDR12, 2, ReadWrite);
+        crate::cpu::field!(PUPDR11, 2, ReadWrite);
+        crate::cpu::field!(PUPDR10, 2, ReadWrite);
+        crate::cpu::field!(PUPDR9, 2, ReadWrite);
+        crate::cpu::field!(PUPDR8, 2, ReadWrite);
+        crate::cpu::field!(PUPDR7, 2, ReadWrite);
+        crate::cpu::field!(PUPDR6, 2, ReadWrite);
+        crate::cpu::field!(PUPDR5, 2, ReadWrite);
+        crate::cpu::field!(PUPDR4, 2, ReadWrite);
+        crate::cpu::field!(PUPDR3, 2, ReadWrite);
+        crate::cpu::field!(PUPDR2, 2, ReadWrite);
+        crate::cpu::field!(PUPDR1, 2, ReadWrite);
+        crate::cpu::field!(PUPDR0, 2, ReadWrite);
     }
     pub mod idr {
         pub const IDR15: u8 = 15;
@@ -95,6 +161,22 @@ pub mod GPIO {
         pub const IDR2: u8 = 2;
         pub const IDR1: u8 = 1;
         pub const IDR0: u8 = 0;
+        crate::cpu::field!(IDR15, 1, ReadOnly);
+        crate::cpu::field!(IDR14, 1, ReadOnly);
+        crate::cpu::field!(IDR13, 1, ReadOnly);
+        crate::cpu::field!(IDR12, 1, ReadOnly);
+        crate::cpu::field!(IDR11, 1, ReadOnly);
+        crate::cpu::field!(IDR10, 1, ReadOnly);
+        crate::cpu::field!(IDR9, 1, ReadOnly);
+        crate::cpu::field!(IDR8, 1, ReadOnly);
+        crate::cpu::field!(IDR7, 1, ReadOnly);
+        crate::cpu::field!(IDR6, 1, ReadOnly);
+        crate::cpu::field!(IDR5, 1, ReadOnly);
+        crate::cpu::field!(IDR4, 1, ReadOnly);
+        crate::cpu::field!(IDR3, 1, ReadOnly);
+        crate::cpu::field!(IDR2, 1, ReadOnly);
+        crate::cpu::field!(IDR1, 1, ReadOnly);
+        crate::cpu::field!(IDR0, 1, ReadOnly);
     }
     pub mod odr {
         pub const ODR15: u8 = 15;
@@ -113,6 +195,22 @@ pub mod GPIO {
         pub const ODR2: u8 = 2;
         pub const ODR1: u8 = 1;
         pub const ODR0: u8 = 0;
+        crate::cpu::field!(ODR15, 1, ReadWrite);
+        crate::cpu::field!(ODR14, 1, ReadWrite);
+        crate::cpu::field!(ODR13, 1, ReadWrite);
+        crate::cpu::field!(ODR12, 1, ReadWrite);
+        crate::cpu::field!(ODR11, 1, ReadWrite);
+        crate::cpu::field!(ODR10, 1, ReadWrite);
+        crate::cpu::field!(ODR9, 1, ReadWrite);
+        crate::cpu::field!(ODR8, 1, ReadWrite);
+        crate::cpu::field!(ODR7, 1, ReadWrite);
+        crate::cpu::field!(ODR6, 1, ReadWrite);
+        crate::cpu::field!(ODR5, 1, ReadWrite);
+        crate::cpu::field!(ODR4, 1, ReadWrite);
+        crate::cpu::field!(ODR3, 1, ReadWrite);
+        crate::cpu::field!(ODR2, 1, ReadWrite);
+        crate::cpu::field!(ODR1, 1, ReadWrite);
+        crate::cpu::field!(ODR0, 1, ReadWrite);
     }
     pub mod bsrr {
         pub const BR15: u8 = 31;
@@ -147,6 +245,38 @@ pub mod GPIO {
         pub const BS2: u8 = 2;
         pub const BS1: u8 = 1;
         pub const BS0: u8 = 0;
+        crate::cpu::field!(BR15, 1, WriteOnly);
+        crate::cpu::field!(BR14, 1, WriteOnly);
+        crate::cpu::field!(BR13, 1, WriteOnly);
+        crate::cpu::field!(BR12, 1, WriteOnly);
+        crate::cpu::field!(BR11, 1, WriteOnly);
+        crate::cpu::field!(BR10, 1, WriteOnly);
+        crate::cpu::field!(BR9, 1, WriteOnly);
+        crate::cpu::field!(BR8, 1, WriteOnly);
+        crate::cpu::field!(BR7, 1, WriteOnly);
+        crate::cpu::field!(BR6, 1, WriteOnly);
+        crate::cpu::field!(BR5, 1, WriteOnly);
+        crate::cpu::field!(BR4, 1, WriteOnly);
+        crate::cpu::field!(BR3, 1, WriteOnly);
+        crate::cpu::field!(BR2, 1, WriteOnly);
+        crate::cpu::field!(BR1, 1, WriteOnly);
+        crate::cpu::field!(BR0, 1, WriteOnly);
+        crate::cpu::field!(BS15, 1, WriteOnly);
+        crate::cpu::field!(BS14, 1, WriteOnly);
+        crate::cpu::field!(BS13, 1, WriteOnly);
+        crate::cpu::field!(BS12, 1, WriteOnly);
+        crate::cpu::field!(BS11, 1, WriteOnly);
+        crate::cpu::field!(BS10, 1, WriteOnly);
+        crate::cpu::field!(BS9, 1, WriteOnly);
+        crate::cpu::field!(BS8, 1, WriteOnly);
+        crate::cpu::field!(BS7, 1, WriteOnly);
+        crate::cpu::field!(BS6, 1, WriteOnly);
+        crate::cpu::field!(BS5, 1, WriteOnly);
+        crate::cpu::field!(BS4, 1, WriteOnly);
+        crate::cpu::field!(BS3, 1, WriteOnly);
+        crate::cpu::field!(BS2, 1, WriteOnly);
+        crate::cpu::field!(BS1, 1, WriteOnly);
+        crate::cpu::field!(BS0, 1, WriteOnly);
     }
     pub mod lckr {
         pub const LCKK: u8 = 16;
@@ -166,6 +296,23 @@ pub mod GPIO {
         pub const LCK2: u8 = 2;
         pub const LCK1: u8 = 1;
         pub const LCK0: u8 = 0;
+        crate::cpu::field!(LCKK, 1, ReadWrite);
+        crate::cpu::field!(LCK15, 1, ReadWrite);
+        crate::cpu::field!(LCK14, 1, ReadWrite);
+        crate::cpu::field!(LCK13, 1, ReadWrite);
+        crate::cpu::field!(LCK12, 1, ReadWrite);
+        crate::cpu::field!(LCK11, 1, ReadWrite);
+        crate::cpu::field!(LCK10, 1, ReadWrite);
+        crate::cpu::field!(LCK9, 1, ReadWrite);
+        crate::cpu::field!(LCK8, 1, ReadWrite);
+        crate::cpu::field!(LCK7, 1, ReadWrite);
+        crate::cpu::field!(LCK6, 1, ReadWrite);
+        crate::cpu::field!(LCK5, 1, ReadWrite);
+        crate::cpu::field!(LCK4, 1, ReadWrite);
+        crate::cpu::field!(LCK3, 1, ReadWrite);
+        crate::cpu::field!(LCK2, 1, ReadWrite);
+        crate::cpu::field!(LCK1, 1, ReadWrite);
+        crate::cpu::field!(LCK0, 1, ReadWrite);
     }
     pub mod afrl {
         pub const AFRL7: u8 = 28;
@@ -176,6 +323,14 @@ pub mod GPIO {
         pub const AFRL2: u8 = 8;
         pub const AFRL1: u8 = 4;
         pub const AFRL0: u8 = 0;
+        crate::cpu::field!(AFRL7, 4, ReadWrite);
+        crate::cpu::field!(AFRL6, 4, ReadWrite);
+        crate::cpu::field!(AFRL5, 4, ReadWrite);
+        crate::cpu::field!(AFRL4, 4, ReadWrite);
+        crate::cpu::field!(AFRL3, 4, ReadWrite);
+        crate::cpu::field!(AFRL2, 4, ReadWrite);
+        crate::cpu::field!(AFRL1, 4, ReadWrite);
+        crate::cpu::field!(AFRL0, 4, ReadWrite);
     }
     pub mod afrh {
         pub const AFRH15: u8 = 28;
@@ -186,6 +341,14 @@ pub mod GPIO {
         pub const AFRH10: u8 = 8;
         pub const AFRH9: u8 = 4;
         pub const AFRH8: u8 = 0;
+        crate::cpu::field!(AFRH15, 4, ReadWrite);
+        crate::cpu::field!(AFRH14, 4, ReadWrite);
+        crate::cpu::field!(AFRH13, 4, ReadWrite);
+        crate::cpu::field!(AFRH12, 4, ReadWrite);
+        crate::cpu::field!(AFRH11, 4, ReadWrite);
+        crate::cpu::field!(AFRH10, 4, ReadWrite);
+        crate::cpu::field!(AFRH9, 4, ReadWrite);
+        crate::cpu::field!(AFRH8, 4, ReadWrite);
     }
     pub mod brr {
         pub const BR0: u8 = 0;
@@ -204,6 +367,22 @@ pub mod GPIO {
         pub const BR13: u8 = 13;
         pub const BR14: u8 = 14;
         pub const BR15: u8 = 15;
+        crate::cpu::field!(BR0, 1, WriteOnly);
+        crate::cpu::field!(BR1, 1, WriteOnly);
+        crate::cpu::field!(BR2, 1, WriteOnly);
+        crate::cpu::field!(BR3, 1, WriteOnly);
+        crate::cpu::field!(BR4, 1, WriteOnly);
+        crate::cpu::field!(BR5, 1, WriteOnly);
+        crate::cpu::field!(BR6, 1, WriteOnly);
+        crate::cpu::field!(BR7, 1, WriteOnly);
+        crate::cpu::field!(BR8, 1, WriteOnly);
+        crate::cpu::field!(BR9, 1, WriteOnly);
+        crate::cpu::field!(BR10, 1, WriteOnly);
+        crate::cpu::field!(BR11, 1, WriteOnly);
+        crate::cpu::field!(BR12, 1, WriteOnly);
+        crate::cpu::field!(BR13, 1, WriteOnly);
+        crate::cpu::field!(BR14, 1, WriteOnly);
+        crate::cpu::field!(BR15, 1, WriteOnly);
     }
 }
 
@@ -211,14 +390,20 @@ pub mod WWDG {
     pub mod cr {
         pub const WDGA: u8 = 7;
         pub const T: u8 = 0;
+        crate::cpu::field!(WDGA, 1, ReadWrite);
+        crate::cpu::field!(T, 7, ReadWrite);
     }
     pub mod cfr {
         pub const EWI: u8 = 9;
         pub const WDGTB: u8 = 7;
         pub const W: u8 = 0;
+        crate::cpu::field!(EWI, 1, ReadWrite);
+        crate::cpu::field!(WDGTB, 2, ReadWrite);
+        crate::cpu::field!(W, 7, ReadWrite);
     }
     pub mod sr {
         pub const EWIF: u8 = 0;
+        crate::cpu::field!(EWIF, 1, WriteZeroToClear);
     }
 }
 
@@ -234,6 +419,16 @@ pub mod RCC {
         pub const CSSON: u8 = 19;
         pub const PLLON: u8 = 24;
         pub const PLLRDY: u8 = 25;
+        crate::cpu::field!(HSION, 1, ReadWrite);
+        crate::cpu::field!(HSIRDY, 1, ReadOnly);
+        crate::cpu::field!(HSITRIM, 5, ReadWrite);
+        crate::cpu::field!(HSICAL, 8, ReadOnly);
+        crate::cpu::field!(HSEON, 1, ReadWrite);
+        crate::cpu::field!(HSERDY, 1, ReadOnly);
+        crate::cpu::field!(HSEBYP, 1, ReadWrite);
+        crate::cpu::field!(CSSON, 1, ReadWrite);
+        crate::cpu::field!(PLLON, 1, ReadWrite);
+        crate::cpu::field!(PLLRDY, 1, ReadOnly);
     }
     pub mod cfgr {
         pub const SW: u8 = 0;
@@ -247,6 +442,17 @@ pub mod RCC {
         pub const MCO: u8 = 24;
         pub const MCOPRE: u8 = 28;
         pub const PLLNODIV: u8 = 31;
+        crate::cpu::field!(SW, 2, ReadWrite);
+        crate::cpu::field!(SWS, 2, ReadOnly);
+        crate::cpu::field!(HPRE, 4, ReadWrite);
+        crate::cpu::field!(PPRE, 3, ReadWrite);
+        crate::cpu::field!(ADCPRE, 1, ReadWrite);
+        crate::cpu::field!(PLLSRC, 2, ReadWrite);
+        crate::cpu::field!(PLLXTPRE, 1, ReadWrite);
+        crate::cpu::field!(PLLMUL, 4, ReadWrite);
+        crate::cpu::field!(MCO, 4, ReadWrite);
+        crate::cpu::field!(MCOPRE, 3, ReadWrite);
+        crate::cpu::field!(PLLNODIV, 1, ReadWrite);
     }
     pub mod cir {
         pub const LSIRDYF: u8 = 0;
@@ -272,6 +478,29 @@ pub mod RCC {
         pub const HSI14RDYC: u8 = 21;
         pub const HSI48RDYC: u8 = 22;
         pub const CSSC: u8 = 23;
+        crate::cpu::field!(LSIRDYF, 1, ReadOnly);
+        crate::cpu::field!(LSERDYF, 1, ReadOnly);
+        crate::cpu::field!(HSIRDYF, 1, ReadOnly);
+        crate::cpu::field!(HSERDYF, 1, ReadOnly);
+        crate::cpu::field!(PLLRDYF, 1, ReadOnly);
+        crate::cpu::field!(HSI14RDYF, 1, ReadOnly);
+        crate::cpu::field!(HSI48RDYF, 1, ReadOnly);
+        crate::cpu::field!(CSSF, 1, ReadOnly);
+        crate::cpu::field!(LSIRDYIE, 1, ReadWrite);
+        crate::cpu::field!(LSERDYIE, 1, ReadWrite);
+        crate::cpu::field!(HSIRDYIE, 1, ReadWrite);
+        crate::cpu::field!(HSERDYIE, 1, ReadWrite);
+        crate::cpu::field!(PLLRDYIE, 1, ReadWrite);
+        crate::cpu::field!(HSI14RDYE, 1, ReadWrite);
+        crate::cpu::field!(HSI48RDYIE, 1, ReadWrite);
+        crate::cpu::field!(LSIRDYC, 1, WriteOnly);
+        crate::cpu::field!(LSERDYC, 1, WriteOnly);
+        crate::cpu::field!(HSIRDYC, 1, WriteOnly);
+        crate::cpu::field!(HSERDYC, 1, WriteOnly);
+        crate::cpu::field!(PLLRDYC, 1, WriteOnly);
+        crate::cpu::field!(HSI14RDYC, 1, WriteOnly);
+        crate::cpu::field!(HSI48RDYC, 1, WriteOnly);
+        crate::cpu::field!(CSSC, 1, WriteOnly);
     }
     pub mod apb2rstr {
         pub const SYSCFGRST: u8 = 0;
@@ -283,6 +512,15 @@ pub mod RCC {
         pub const TIM16RST: u8 = 17;
         pub const TIM17RST: u8 = 18;
         pub const DBGMCURST: u8 = 22;
+        crate::cpu::field!(SYSCFGRST, 1, ReadWrite);
+        crate::cpu::field!(ADCRST, 1, ReadWrite);
+        crate::cpu::field!(TIM1RST, 1, ReadWrite);
+        crate::cpu::field!(SPI1RST, 1, ReadWrite);
+        crate::cpu::field!(USART1RST, 1, ReadWrite);
+        crate::cpu::field!(TIM15RST, 1, ReadWrite);
+        crate::cpu::field!(TIM16RST, 1, ReadWrite);
+        crate::cpu::field!(TIM17RST, 1, ReadWrite);
+        crate::cpu::field!(DBGMCURST, 1, ReadWrite);
     }
     pub mod apb1rstr {
         pub const TIM2RST: u8 = 0;
@@ -303,6 +541,24 @@ pub mod RCC {
         pub const PWRRST: u8 = 28;
         pub const DACRST: u8 = 29;
         pub const CECRST: u8 = 30;
+        crate::cpu::field!(TIM2RST, 1, ReadWrite);
+        crate::cpu::field!(TIM3RST, 1, ReadWrite);
+        crate::cpu::field!(TIM6RST, 1, ReadWrite);
+        crate::cpu::field!(TIM7RST, 1, ReadWrite);
+        crate::cpu::field!(TIM14RST, 1, ReadWrite);
+        crate::cpu::field!(WWDGRST, 1, ReadWrite);
+        crate::cpu::field!(SPI2RST, 1, ReadWrite);
+        crate::cpu::field!(USART2RST, 1, ReadWrite);
+        crate::cpu::field!(USART3RST, 1, ReadWrite);
+        crate::cpu::field!(USART4RST, 1, ReadWrite);
+        crate::cpu::field!(I2C1RST, 1, ReadWrite);
+        crate::cpu::field!(I2C2RST, 1, ReadWrite);
+        crate::cpu::field!(USBRST, 1, ReadWrite);
+        crate::cpu::field!(CANRST, 1, ReadWrite);
+        crate::cpu::field!(CRSRST, 1, ReadWrite);
+        crate::cpu::field!(PWRRST, 1, ReadWrite);
+        crate::cpu::field!(DACRST, 1, ReadWrite);
+        crate::cpu::field!(CECRST, 1, ReadWrite);
     }
     pub mod ahbenr {
         pub const DMA1EN: u8 = 0;
@@ -315,6 +571,16 @@ pub mod RCC {
         pub const IOPDEN: u8 = 20;
         pub const IOPFEN: u8 = 22;
         pub const TSCEN: u8 = 24;
+        crate::cpu::field!(DMA1EN, 1, ReadWrite);
+        crate::cpu::field!(SRAMEN, 1, ReadWrite);
+        crate::cpu::field!(FLITFEN, 1, ReadWrite);
+        crate::cpu::field!(CRCEN, 1, ReadWrite);
+        crate::cpu::field!(IOPAEN, 1, ReadWrite);
+        crate::cpu::field!(IOPBEN, 1, ReadWrite);
+        crate::cpu::field!(IOPCEN, 1, ReadWrite);
+        crate::cpu::field!(IOPDEN, 1, ReadWrite);
+        crate::cpu::field!(IOPFEN, 1, ReadWrite);
+        crate::cpu::field!(TSCEN, 1, ReadWrite);
     }
     pub mod apb2enr {
         pub const SYSCFGEN: u8 = 0;
@@ -326,6 +592,15 @@ pub mod RCC {
         pub const TIM16EN: u8 = 17;
         pub const TIM17EN: u8 = 18;
         pub const DBGMCUEN: u8 = 22;
+        crate::cpu::field!(SYSCFGEN, 1, ReadWrite);
+        crate::cpu::field!(ADCEN, 1, ReadWrite);
+        crate::cpu::field!(TIM1EN, 1, ReadWrite);
+        crate::cpu::field!(SPI1EN, 1, ReadWrite);
+        crate::cpu::field!(USART1EN, 1, ReadWrite);
+        crate::cpu::field!(TIM15EN, 1, ReadWrite);
+        crate::cpu::field!(TIM16EN, 1, ReadWrite);
+        crate::cpu::field!(TIM17EN, 1, ReadWrite);
+        crate::cpu::field!(DBGMCUEN, 1, ReadWrite);
     }
     pub mod apb1enr {
         pub const TIM2EN: u8 = 0;
@@ -346,6 +621,24 @@ pub mod RCC {
         pub const PWREN: u8 = 28;
         pub const DACEN: u8 = 29;
         pub const CECEN: u8 = 30;
+        crate::cpu::field!(TIM2EN, 1, ReadWrite);
+        crate::cpu::field!(TIM3EN, 1, ReadWrite);
+        crate::cpu::field!(TIM6EN, 1, ReadWrite);
+        crate::cpu::field!(TIM7EN, 1, ReadWrite);
+        crate::cpu::field!(TIM14EN, 1, ReadWrite);
+        crate::cpu::field!(WWDGEN, 1, ReadWrite);
+        crate::cpu::field!(SPI2EN, 1, ReadWrite);
+        crate::cpu::field!(USART2EN, 1, ReadWrite);
+        crate::cpu::field!(USART3EN, 1, ReadWrite);
+        crate::cpu::field!(USART4EN, 1, ReadWrite);
+        crate::cpu::field!(I2C1EN, 1, ReadWrite);
+        crate::cpu::field!(I2C2EN, 1, ReadWrite);
+        crate::cpu::field!(USBRST, 1, ReadWrite);
+        crate::cpu::field!(CANEN, 1, ReadWrite);
+        crate::cpu::field!(CRSEN, 1, ReadWrite);
+        crate::cpu::field!(PWREN, 1, ReadWrite);
+        crate::cpu::field!(DACEN, 1, ReadWrite);
+        crate::cpu::field!(CECEN, 1, ReadWrite);
     }
     pub mod bdcr {
         pub const LSEON: u8 = 0;
@@ -355,6 +648,13 @@ pub mod RCC {
         pub const RTCSEL: u8 = 8;
         pub const RTCEN: u8 = 15;
         pub const BDRST: u8 = 16;
+        crate::cpu::field!(LSEON, 1, ReadWrite);
+        crate::cpu::field!(LSERDY, 1, ReadOnly);
+        crate::cpu::field!(LSEBYP, 1, ReadWrite);
+        crate::cpu::field!(LSEDRV, 2, ReadWrite);
+        crate::cpu::field!(RTCSEL, 2, ReadWrite);
+        crate::cpu::field!(RTCEN, 1, ReadWrite);
+        crate::cpu::field!(BDRST, 1, ReadWrite);
     }
     pub mod csr {
         pub const LSION: u8 = 0;
@@ -367,6 +667,16 @@ pub mod RCC {
         pub const IWDGRSTF: u8 = 29;
         pub const WWDGRSTF: u8 = 30;
         pub const LPWRRSTF: u8 = 31;
+        crate::cpu::field!(LSION, 1, ReadWrite);
+        crate::cpu::field!(LSIRDY, 1, ReadOnly);
+        crate::cpu::field!(RMVF, 1, ReadWrite);
+        crate::cpu::field!(OBLRSTF, 1, ReadOnly);
+        crate::cpu::field!(PINRSTF, 1, ReadOnly);
+        crate::cpu::field!(PORRSTF, 1, ReadOnly);
+        crate::cpu::field!(SFTRSTF, 1, ReadOnly);
+        crate::cpu::field!(IWDGRSTF, 1, ReadOnly);
+        crate::cpu::field!(WWDGRSTF, 1, ReadOnly);
+        crate::cpu::field!(LPWRRSTF, 1, ReadOnly);
     }
     pub mod ahbrstr {
         pub const IOPARST: u8 = 17;
@@ -375,9 +685,16 @@ pub mod RCC {
         pub const IOPDRST: u8 = 20;
         pub const IOPFRST: u8 = 22;
         pub const TSCRST: u8 = 24;
+        crate::cpu::field!(IOPARST, 1, ReadWrite);
+        crate::cpu::field!(IOPBRST, 1, ReadWrite);
+        crate::cpu::field!(IOPCRST, 1, ReadWrite);
+        crate::cpu::field!(IOPDRST, 1, ReadWrite);
+        crate::cpu::field!(IOPFRST, 1, ReadWrite);
+        crate::cpu::field!(TSCRST, 1, ReadWrite);
     }
     pub mod cfgr2 {
         pub const PREDIV: u8 = 0;
+        crate::cpu::field!(PREDIV, 4, ReadWrite);
     }
     pub mod cfgr3 {
         pub const USART1SW: u8 = 0;
@@ -386,6 +703,12 @@ pub mod RCC {
         pub const USBSW: u8 = 7;
         pub const ADCSW: u8 = 8;
         pub const USART2SW: u8 = 16;
+        crate::cpu::field!(USART1SW, 2, ReadWrite);
+        crate::cpu::field!(I2C1SW, 1, ReadWrite);
+        crate::cpu::field!(CECSW, 1, ReadWrite);
+        crate::cpu::field!(USBSW, 1, ReadWrite);
+        crate::cpu::field!(ADCSW, 1, ReadWrite);
+        crate::cpu::field!(USART2SW, 2, ReadWrite);
     }
     pub mod cr2 {
         pub const HSI14ON: u8 = 0;
@@ -396,6 +719,14 @@ pub mod RCC {
         pub const HSI48ON: u8 = 16;
         pub const HSI48RDY: u8 = 17;
         pub const HSI48CAL: u8 = 24;
+        crate::cpu::field!(HSI14ON, 1, ReadWrite);
+        crate::cpu::field!(HSI14RDY, 1, ReadOnly);
+        crate::cpu::field!(HSI14DIS, 1, ReadWrite);
+        crate::cpu::field!(HSI14TRIM, 5, ReadWrite);
+        crate::cpu::field!(HSI14CAL, 8, ReadOnly);
+        crate::cpu::field!(HSI48ON, 1, ReadWrite);
+        crate::cpu::field!(HSI48RDY, 1, ReadOnly);
+        crate::cpu::field!(HSI48CAL, 8, ReadOnly);
     }
 }
 
@@ -422,6 +753,27 @@ pub mod USART {
         pub const RTOIE: u8 = 26;
         pub const EOBIE: u8 = 27;
         pub const M1: u8 = 28;
+        crate::cpu::field!(UE, 1, ReadWrite);
+        crate::cpu::field!(UESM, 1, ReadWrite);
+        crate::cpu::field!(RE, 1, ReadWrite);
+        crate::cpu::field!(TE, 1, ReadWrite);
+        crate::cpu::field!(IDLEIE, 1, ReadWrite);
+        crate::cpu::field!(RXNEIE, 1, ReadWrite);
+        crate::cpu::field!(TCIE, 1, ReadWrite);
+        crate::cpu::field!(TXEIE, 1, ReadWrite);
+        crate::cpu::field!(PEIE, 1, ReadWrite);
+        crate::cpu::field!(PS, 1, ReadWrite);
+        crate::cpu::field!(PCE, 1, ReadWrite);
+        crate::cpu::field!(WAKE, 1, ReadWrite);
+        crate::cpu::field!(M, 1, ReadWrite);
+        crate::cpu::field!(MME, 1, ReadWrite);
+        crate::cpu::field!(CMIE, 1, ReadWrite);
+        crate::cpu::field!(OVER8, 1, ReadWrite);
+        crate::cpu::field!(DEDT, 5, ReadWrite);
+        crate::cpu::field!(DEAT, 5, ReadWrite);
+        crate::cpu::field!(RTOIE, 1, ReadWrite);
+        crate::cpu::field!(EOBIE, 1, ReadWrite);
+        crate::cpu::field!(M1, 1, ReadWrite);
     }
     pub mod cr2 {
         pub const ADD4: u8 = 28;
@@ -443,6 +795,25 @@ pub mod USART {
         pub const LBDIE: u8 = 6;
         pub const LBDL: u8 = 5;
         pub const ADDM7: u8 = 4;
+        crate::cpu::field!(ADD4, 4, ReadWrite);
+        crate::cpu::field!(ADD0, 4, ReadWrite);
+        crate::cpu::field!(RTOEN, 1, ReadWrite);
+        crate::cpu::field!(ABRMOD, 2, ReadWrite);
+        crate::cpu::field!(ABREN, 1, ReadWrite);
+        crate::cpu::field!(MSBFIRST, 1, ReadWrite);
+        crate::cpu::field!(DATAINV, 1, ReadWrite);
+        crate::cpu::field!(TXINV, 1, ReadWrite);
+        crate::cpu::field!(RXINV, 1, ReadWrite);
+        crate::cpu::field!(SWAP, 1, ReadWrite);
+        crate::cpu::field!(LINEN, 1, ReadWrite);
+        crate::cpu::field!(STOP, 2, ReadWrite);
+        crate::cpu::field!(CLKEN, 1, ReadWrite);
+        crate::cpu::field!(CPOL, 1, ReadWrite);
+        crate::cpu::field!(CPHA, 1, ReadWrite);
+        crate::cpu::field!(LBCL, 1, ReadWrite);
+        crate::cpu::field!(LBDIE, 1, ReadWrite);
+        crate::cpu::field!(LBDL, 1, ReadWrite);
+        crate::cpu::field!(ADDM7, 1, ReadWrite);
     }
     pub mod cr3 {
         pub const WUFIE: u8 = 22;
@@ -464,18 +835,43 @@ pub mod USART {
         pub const IRLP: u8 = 2;
         pub const IREN: u8 = 1;
         pub const EIE: u8 = 0;
+        crate::cpu::field!(WUFIE, 1, ReadWrite);
+        crate::cpu::field!(WUS, 2, ReadWrite);
+        crate::cpu::field!(SCARCNT, 3, ReadWrite);
+        crate::cpu::field!(DEP, 1, ReadWrite);
+        crate::cpu::field!(DEM, 1, ReadWrite);
+        crate::cpu::field!(DDRE, 1, ReadWrite);
+        crate::cpu::field!(OVRDIS, 1, ReadWrite);
+        crate::cpu::field!(ONEBIT, 1, ReadWrite);
+        crate::cpu::field!(CTSIE, 1, ReadWrite);
+        crate::cpu::field!(CTSE, 1, ReadWrite);
+        crate::cpu::field!(RTSE, 1, ReadWrite);
+        crate::cpu::field!(DMAT, 1, ReadWrite);
+        crate::cpu::field!(DMAR, 1, ReadWrite);
+        crate::cpu::field!(SCEN, 1, ReadWrite);
+        crate::cpu::field!(NACK, 1, ReadWrite);
+        crate::cpu::field!(HDSEL, 1, ReadWrite);
+        crate::cpu::field!(IRLP, 1, ReadWrite);
+        crate::cpu::field!(IREN, 1, ReadWrite);
+        crate::cpu::field!(EIE, 1, ReadWrite);
     }
     pub mod brr {
         pub const DIV_Mantissa: u8 = 4;
         pub const DIV_Fraction: u8 = 0;
+        crate::cpu::field!(DIV_Mantissa, 12, ReadWrite);
+        crate::cpu::field!(DIV_Fraction, 4, ReadWrite);
     }
     pub mod gtpr {
         pub const GT: u8 = 8;
         pub const PSC: u8 = 0;
+        crate::cpu::field!(GT, 8, ReadWrite);
+        crate::cpu::field!(PSC, 8, ReadWrite);
     }
     pub mod rtor {
         pub const BLEN: u8 = 24;
         pub const RTO: u8 = 0;
+        crate::cpu::field!(BLEN, 8, ReadWrite);
+        crate::cpu::field!(RTO, 24, ReadWrite);
     }
     pub mod rqr {
         pub const TXFRQ: u8 = 4;
@@ -483,6 +879,11 @@ pub mod USART {
         pub const MMRQ: u8 = 2;
         pub const SBKRQ: u8 = 1;
         pub const ABRRQ: u8 = 0;
+        crate::cpu::field!(TXFRQ, 1, WriteOnly);
+        crate::cpu::field!(RXFRQ, 1, WriteOnly);
+        crate::cpu::field!(MMRQ, 1, WriteOnly);
+        crate::cpu::field!(SBKRQ, 1, WriteOnly);
+        crate::cpu::field!(ABRRQ, 1, WriteOnly);
     }
     pub mod isr {
         pub const REACK: u8 = 22;
@@ -507,6 +908,28 @@ pub mod USART {
         pub const NF: u8 = 2;
         pub const FE: u8 = 1;
         pub const PE: u8 = 0;
+        crate::cpu::field!(REACK, 1, ReadOnly);
+        crate::cpu::field!(TEACK, 1, ReadOnly);
+        crate::cpu::field!(WUF, 1, ReadOnly);
+        crate::cpu::field!(RWU, 1, ReadOnly);
+        crate::cpu::field!(SBKF, 1, ReadOnly);
+        crate::cpu::field!(CMF, 1, ReadOnly);
+        crate::cpu::field!(BUSY, 1, ReadOnly);
+        crate::cpu::field!(ABRF, 1, ReadOnly);
+        crate::cpu::field!(ABRE, 1, ReadOnly);
+        crate::cpu::field!(EOBF, 1, ReadOnly);
+        crate::cpu::field!(RTOF, 1, ReadOnly);
+        crate::cpu::field!(CTS, 1, ReadOnly);
+        crate::cpu::field!(CTSIF, 1, ReadOnly);
+        crate::cpu::field!(LBDF, 1, ReadOnly);
+        crate::cpu::field!(TXE, 1, ReadOnly);
+        crate::cpu::field!(TC, 1, ReadOnly);
+        crate::cpu::field!(RXNE, 1, ReadOnly);
+        crate::cpu::field!(IDLE, 1, ReadOnly);
+        crate::cpu::field!(ORE, 1, ReadOnly);
+        crate::cpu::field!(NF, 1, ReadOnly);
+        crate::cpu::field!(FE, 1, ReadOnly);
+        crate::cpu::field!(PE, 1, ReadOnly);
     }
     pub mod icr {
         pub const WUCF: u8 = 20;
@@ -521,12 +944,151 @@ pub mod USART {
         pub const NCF: u8 = 2;
         pub const FECF: u8 = 1;
         pub const PECF: u8 = 0;
+        crate::cpu::field!(WUCF, 1, WriteOnly);
+        crate::cpu::field!(CMCF, 1, WriteOnly);
+        crate::cpu::field!(EOBCF, 1, WriteOnly);
+        crate::cpu::field!(RTOCF, 1, WriteOnly);
+        crate::cpu::field!(CTSCF, 1, WriteOnly);
+        crate::cpu::field!(LBDCF, 1, WriteOnly);
+        crate::cpu::field!(TCCF, 1, WriteOnly);
+        crate::cpu::field!(IDLECF, 1, WriteOnly);
+        crate::cpu::field!(ORECF, 1, WriteOnly);
+        crate::cpu::field!(NCF, 1, WriteOnly);
+        crate::cpu::field!(FECF, 1, WriteOnly);
+        crate::cpu::field!(PECF, 1, WriteOnly);
     }
     pub mod rdr {
         pub const RDR: u8 = 0;
+        crate::cpu::field!(RDR, 9, ReadOnly);
     }
     pub mod tdr {
         pub const TDR: u8 = 0;
+        crate::cpu::field!(TDR, 9, ReadWrite);
+    }
+}
+
+pub mod SPI1 {
+    pub mod cr1 {
+        pub const BIDIMODE: u8 = 15;
+        pub const BIDIOE: u8 = 14;
+        pub const CRCEN: u8 = 13;
+        pub const CRCNEXT: u8 = 12;
+        pub const CRCL: u8 = 11;
+        pub const RXONLY: u8 = 10;
+        pub const SSM: u8 = 9;
+        pub const SSI: u8 = 8;
+        pub const LSBFIRST: u8 = 7;
+        pub const SPE: u8 = 6;
+        pub const BR: u8 = 3;
+        pub const MSTR: u8 = 2;
+        pub const CPOL: u8 = 1;
+        pub const CPHA: u8 = 0;
+        crate::cpu::field!(BIDIMODE, 1, ReadWrite);
+        crate::cpu::field!(BIDIOE, 1, ReadWrite);
+        crate::cpu::field!(CRCEN, 1, ReadWrite);
+        crate::cpu::field!(CRCNEXT, 1, ReadWrite);
+        crate::cpu::field!(CRCL, 1, ReadWrite);
+        crate::cpu::field!(RXONLY, 1, ReadWrite);
+        crate::cpu::field!(SSM, 1, ReadWrite);
+        crate::cpu::field!(SSI, 1, ReadWrite);
+        crate::cpu::field!(LSBFIRST, 1, ReadWrite);
+        crate::cpu::field!(SPE, 1, ReadWrite);
+        crate::cpu::field!(BR, 3, ReadWrite);
+        crate::cpu::field!(MSTR, 1, ReadWrite);
+        crate::cpu::field!(CPOL, 1, ReadWrite);
+        crate::cpu::field!(CPHA, 1, ReadWrite);
+    }
+    pub mod cr2 {
+        pub const LDMA_TX: u8 = 14;
+        pub const LDMA_RX: u8 = 13;
+        pub const FRXTH: u8 = 12;
+        pub const DS: u8 = 8;
+        pub const TXEIE: u8 = 7;
+        pub const RXNEIE: u8 = 6;
+        pub const ERRIE: u8 = 5;
+        pub const FRF: u8 = 4;
+        pub const NSSP: u8 = 3;
+        pub const SSOE: u8 = 2;
+        pub const TXDMAEN: u8 = 1;
+        pub const RXDMAEN: u8 = 0;
+        crate::cpu::field!(LDMA_TX, 1, ReadWrite);
+        crate::cpu::field!(LDMA_RX, 1, ReadWrite);
+        crate::cpu::field!(FRXTH, 1, ReadWrite);
+        crate::cpu::field!(DS, 4, ReadWrite);
+        crate::cpu::field!(TXEIE, 1, ReadWrite);
+        crate::cpu::field!(RXNEIE, 1, ReadWrite);
+        crate::cpu::field!(ERRIE, 1, ReadWrite);
+        crate::cpu::field!(FRF, 1, ReadWrite);
+        crate::cpu::field!(NSSP, 1, ReadWrite);
+        crate::cpu::field!(SSOE, 1, ReadWrite);
+        crate::cpu::field!(TXDMAEN, 1, ReadWrite);
+        crate::cpu::field!(RXDMAEN, 1, ReadWrite);
+    }
+    pub mod sr {
+        pub const FTLVL: u8 = 11;
+        pub const FRLVL: u8 = 9;
+        pub const FRE: u8 = 8;
+        pub const BSY: u8 = 7;
+        pub const OVR: u8 = 6;
+        pub const MODF: u8 = 5;
+        pub const CRCERR: u8 = 4;
+        pub const UDR: u8 = 3;
+        pub const CHSIDE: u8 = 2;
+        pub const TXE: u8 = 1;
+        pub const RXNE: u8 = 0;
+        crate::cpu::field!(FTLVL, 2, ReadOnly);
+        crate::cpu::field!(FRLVL, 2, ReadOnly);
+        crate::cpu::field!(FRE, 1, ReadOnly);
+        crate::cpu::field!(BSY, 1, ReadOnly);
+        crate::cpu::field!(OVR, 1, ReadOnly);
+        crate::cpu::field!(MODF, 1, ReadOnly);
+        crate::cpu::field!(CRCERR, 1, WriteZeroToClear);
+        crate::cpu::field!(UDR, 1, ReadOnly);
+        crate::cpu::field!(CHSIDE, 1, ReadOnly);
+        crate::cpu::field!(TXE, 1, ReadOnly);
+        crate::cpu::field!(RXNE, 1, ReadOnly);
+    }
+    pub mod dr {
+        pub const DR: u8 = 0;
+        crate::cpu::field!(DR, 16, ReadWrite);
+    }
+    pub mod crcpr {
+        pub const CRCPOLY: u8 = 0;
+        crate::cpu::field!(CRCPOLY, 16, ReadWrite);
+    }
+    pub mod rxcrcr {
+        pub const RxCRC: u8 = 0;
+        crate::cpu::field!(RxCRC, 16, ReadOnly);
+    }
+    pub mod txcrcr {
+        pub const TxCRC: u8 = 0;
+        crate::cpu::field!(TxCRC, 16, ReadOnly);
+    }
+    pub mod i2scfgr {
+        pub const I2SMOD: u8 = 11;
+        pub const I2SE: u8 = 10;
+        pub const I2SCFG: u8 = 8;
+        pub const PCMSYNC: u8 = 7;
+        pub const I2SSTD: u8 = 4;
+        pub const CKPOL: u8 = 3;
+        pub const DATLEN: u8 = 1;
+        pub const CHLEN: u8 = 0;
+        crate::cpu::field!(I2SMOD, 1, ReadWrite);
+        crate::cpu::field!(I2SE, 1, ReadWrite);
+        crate::cpu::field!(I2SCFG, 2, ReadWrite);
+        crate::cpu::field!(PCMSYNC, 1, ReadWrite);
+        crate::cpu::field!(I2SSTD, 2, ReadWrite);
+        crate::cpu::field!(CKPOL, 1, ReadWrite);
+        crate::cpu::field!(DATLEN, 2, ReadWrite);
+        crate::cpu::field!(CHLEN, 1, ReadWrite);
+    }
+    pub mod i2spr {
+        pub const MCKOE: u8 = 9;
+        pub const ODD: u8 = 8;
+        pub const I2SDIV: u8 = 0;
+        crate::cpu::field!(MCKOE, 1, ReadWrite);
+        crate::cpu::field!(ODD, 1, ReadWrite);
+        crate::cpu::field!(I2SDIV, 8, ReadWrite);
     }
 }
 
@@ -535,18 +1097,27 @@ pub mod FLASH {
         pub const LATENCY: u8 = 0;
         pub const PRFTBE: u8 = 4;
         pub const PRFTBS: u8 = 5;
+        crate::cpu::field!(LATENCY, 3, ReadWrite);
+        crate::cpu::field!(PRFTBE, 1, ReadWrite);
+        crate::cpu::field!(PRFTBS, 1, ReadOnly);
     }
     pub mod keyr {
         pub const FKEYR: u8 = 0;
+        crate::cpu::field!(FKEYR, 32, WriteOnly);
     }
     pub mod optkeyr {
         pub const OPTKEYR: u8 = 0;
+        crate::cpu::field!(OPTKEYR, 32, WriteOnly);
     }
     pub mod sr {
         pub const EOP: u8 = 5;
         pub const WRPRT: u8 = 4;
         pub const PGERR: u8 = 2;
         pub const BSY: u8 = 0;
+        crate::cpu::field!(EOP, 1, WriteOneToClear);
+        crate::cpu::field!(WRPRT, 1, WriteOneToClear);
+        crate::cpu::field!(PGERR, 1, WriteOneToClear);
+        crate::cpu::field!(BSY, 1, ReadOnly);
     }
     pub mod cr {
         pub const FORCE_OPTLOAD: u8 = 13;
@@ -560,9 +1131,21 @@ pub mod FLASH {
         pub const MER: u8 = 2;
         pub const PER: u8 = 1;
         pub const PG: u8 = 0;
+        crate::cpu::field!(FORCE_OPTLOAD, 1, ReadWrite);
+        crate::cpu::field!(EOPIE, 1, ReadWrite);
+        crate::cpu::field!(ERRIE, 1, ReadWrite);
+        crate::cpu::field!(OPTWRE, 1, ReadWrite);
+        crate::cpu::field!(LOCK, 1, ReadWrite);
+        crate::cpu::field!(STRT, 1, ReadWrite);
+        crate::cpu::field!(OPTER, 1, ReadWrite);
+        crate::cpu::field!(OPTPG, 1, ReadWrite);
+        crate::cpu::field!(MER, 1, ReadWrite);
+        crate::cpu::field!(PER, 1, ReadWrite);
+        crate::cpu::field!(PG, 1, ReadWrite);
     }
     pub mod ar {
         pub const FAR: u8 = 0;
+        crate::cpu::field!(FAR, 32, WriteOnly);
     }
     pub mod obr {
         pub const OPTERR: u8 = 0;
@@ -577,9 +1160,22 @@ pub mod FLASH {
         pub const BOOT_SEL: u8 = 15;
         pub const Data0: u8 = 16;
         pub const Data1: u8 = 24;
+        crate::cpu::field!(OPTERR, 1, ReadOnly);
+        crate::cpu::field!(RDPRT, 2, ReadOnly);
+        crate::cpu::field!(WDG_SW, 1, ReadOnly);
+        crate::cpu::field!(nRST_STOP, 1, ReadOnly);
+        crate::cpu::field!(nRST_STDBY, 1, ReadOnly);
+        crate::cpu::field!(nBOOT0, 1, ReadOnly);
+        crate::cpu::field!(nBOOT1, 1, ReadOnly);
+        crate::cpu::field!(VDDA_MONITOR, 1, ReadOnly);
+        crate::cpu::field!(RAM_PARITY_CHECK, 1, ReadOnly);
+        crate::cpu::field!(BOOT_SEL, 1, ReadOnly);
+        crate::cpu::field!(Data0, 8, ReadOnly);
+        crate::cpu::field!(Data1, 8, ReadOnly);
     }
     pub mod wrpr {
         pub const WRP: u8 = 0;
+        crate::cpu::field!(WRP, 32, ReadOnly);
     }
 }
 
@@ -588,10 +1184,15 @@ pub mod DBGMCU {
         pub const DEV_ID: u8 = 0;
         pub const DIV_ID: u8 = 12;
         pub const REV_ID: u8 = 16;
+        crate::cpu::field!(DEV_ID, 12, ReadOnly);
+        crate::cpu::field!(DIV_ID, 4, ReadOnly);
+        crate::cpu::field!(REV_ID, 16, ReadOnly);
     }
     pub mod cr {
         pub const DBG_STOP: u8 = 1;
         pub const DBG_STANDBY: u8 = 2;
+        crate::cpu::field!(DBG_STOP, 1, ReadWrite);
+        crate::cpu::field!(DBG_STANDBY, 1, ReadWrite);
     }
     pub mod apb1_fz {
         pub const DBG_TIM2_STOP: u8 = 0;
@@ -604,12 +1205,1322 @@ pub mod DBGMCU {
         pub const DBG_IWDG_STOP: u8 = 12;
         pub const DBG_I2C1_SMBUS_TIMEOUT: u8 = 21;
         pub const DBG_CAN_STOP: u8 = 25;
+        crate::cpu::field!(DBG_TIM2_STOP, 1, ReadWrite);
+        crate::cpu::field!(DBG_TIM3_STOP, 1, ReadWrite);
+        crate::cpu::field!(DBG_TIM6_STOP, 1, ReadWrite);
+        crate::cpu::field!(DBG_TIM7_STOP, 1, ReadWrite);
+        crate::cpu::field!(DBG_TIM14_STOP, 1, ReadWrite);
+        crate::cpu::field!(DBG_RTC_STOP, 1, ReadWrite);
+        crate::cpu::field!(DBG_WWDG_STOP, 1, ReadWrite);
+        crate::cpu::field!(DBG_IWDG_STOP, 1, ReadWrite);
+        crate::cpu::field!(DBG_I2C1_SMBUS_TIMEOUT, 1, ReadWrite);
+        crate::cpu::field!(DBG_CAN_STOP, 1, ReadWrite);
     }
     pub mod apb2_fz {
         pub const DBG_TIM1_STOP: u8 = 11;
         pub const DBG_TIM15_STOP: u8 = 16;
         pub const DBG_TIM16_STOP: u8 = 17;
         pub const DBG_TIM17_STOP: u8 = 18;
+        crate::cpu::field!(DBG_TIM1_STOP, 1, ReadWrite);
+        crate::cpu::field!(DBG_TIM15_STOP, 1, ReadWrite);
+        crate::cpu::field!(DBG_TIM16_STOP, 1, ReadWrite);
+        crate::cpu::field!(DBG_TIM17_STOP, 1, ReadWrite);
+    }
+}
+
+pub mod TIM_ADV {
+    pub mod cr1 {
+        pub const CKD: u8 = 8;
+        pub const ARPE: u8 = 7;
+        pub const CMS: u8 = 5;
+        pub const DIR: u8 = 4;
+        pub const OPM: u8 = 3;
+        pub const URS: u8 = 2;
+        pub const UDIS: u8 = 1;
+        pub const CEN: u8 = 0;
+        crate::cpu::field!(CKD, 2, ReadWrite);
+        crate::cpu::field!(ARPE, 1, ReadWrite);
+        crate::cpu::field!(CMS, 2, ReadWrite);
+        crate::cpu::field!(DIR, 1, ReadWrite);
+        crate::cpu::field!(OPM, 1, ReadWrite);
+        crate::cpu::field!(URS, 1, ReadWrite);
+        crate::cpu::field!(UDIS, 1, ReadWrite);
+        crate::cpu::field!(CEN, 1, ReadWrite);
+    }
+    pub mod cr2 {
+        pub const OIS4: u8 = 14;
+        pub const OIS3N: u8 = 13;
+        pub const OIS3: u8 = 12;
+        pub const OIS2N: u8 = 11;
+        pub const OIS2: u8 = 10;
+        pub const OIS1N: u8 = 9;
+        pub const OIS1: u8 = 8;
+        pub const TI1S: u8 = 7;
+        pub const MMS: u8 = 4;
+        pub const CCDS: u8 = 3;
+        pub const CCUS: u8 = 2;
+        pub const CCPC: u8 = 0;
+        crate::cpu::field!(OIS4, 1, ReadWrite);
+        crate::cpu::field!(OIS3N, 1, ReadWrite);
+        crate::cpu::field!(OIS3, 1, ReadWrite);
+        crate::cpu::field!(OIS2N, 1, ReadWrite);
+        crate::cpu::field!(OIS2, 1, ReadWrite);
+        crate::cpu::field!(OIS1N, 1, ReadWrite);
+        crate::cpu::field!(OIS1, 1, ReadWrite);
+        crate::cpu::field!(TI1S, 1, ReadWrite);
+        crate::cpu::field!(MMS, 3, ReadWrite);
+        crate::cpu::field!(CCDS, 1, ReadWrite);
+        crate::cpu::field!(CCUS, 1, ReadWrite);
+        crate::cpu::field!(CCPC, 1, ReadWrite);
+    }
+    pub mod smcr {
+        pub const ETP: u8 = 15;
+        pub const ECE: u8 = 14;
+        pub const ETPS: u8 = 12;
+        pub const ETF: u8 = 8;
+        pub const MSM: u8 = 7;
+        pub const TS: u8 = 4;
+        pub const SMS: u8 = 0;
+        crate::cpu::field!(ETP, 1, ReadWrite);
+        crate::cpu::field!(ECE, 1, ReadWrite);
+        crate::cpu::field!(ETPS, 2, ReadWrite);
+        crate::cpu::field!(ETF, 4, ReadWrite);
+        crate::cpu::field!(MSM, 1, ReadWrite);
+        crate::cpu::field!(TS, 3, ReadWrite);
+        crate::cpu::field!(SMS, 3, ReadWrite);
+    }
+    pub mod dier {
+        pub const TDE: u8 = 14;
+        pub const COMDE: u8 = 13;
+        pub const CC4DE: u8 = 12;
+        pub const CC3DE: u8 = 11;
+        pub const CC2DE: u8 = 10;
+        pub const CC1DE: u8 = 9;
+        pub const UDE: u8 = 8;
+        pub const TIE: u8 = 6;
+        pub const CC4IE: u8 = 4;
+        pub const CC3IE: u8 = 3;
+        pub const CC2IE: u8 = 2;
+        pub const CC1IE: u8 = 1;
+        pub const UIE: u8 = 0;
+        pub const BIE: u8 = 7;
+        pub const COMIE: u8 = 5;
+        crate::cpu::field!(TDE, 1, ReadWrite);
+        crate::cpu::field!(COMDE, 1, ReadWrite);
+        crate::cpu::field!(CC4DE, 1, ReadWrite);
+        crate::cpu::field!(CC3DE, 1, ReadWrite);
+        crate::cpu::field!(CC2DE, 1, ReadWrite);
+        crate::cpu::field!(CC1DE, 1, ReadWrite);
+        crate::cpu::field!(UDE, 1, ReadWrite);
+        crate::cpu::field!(TIE, 1, ReadWrite);
+        crate::cpu::field!(CC4IE, 1, ReadWrite);
+        crate::cpu::field!(CC3IE, 1, ReadWrite);
+        crate::cpu::field!(CC2IE, 1, ReadWrite);
+        crate::cpu::field!(CC1IE, 1, ReadWrite);
+        crate::cpu::field!(UIE, 1, ReadWrite);
+        crate::cpu::field!(BIE, 1, ReadWrite);
+        crate::cpu::field!(COMIE, 1, ReadWrite);
+    }
+    pub mod sr {
+        pub const CC4OF: u8 = 12;
+        pub const CC3OF: u8 = 11;
+        pub const CC2OF: u8 = 10;
+        pub const CC1OF: u8 = 9;
+        pub const BIF: u8 = 7;
+        pub const TIF: u8 = 6;
+        pub const COMIF: u8 = 5;
+        pub const CC4IF: u8 = 4;
+        pub const CC3IF: u8 = 3;
+        pub const CC2IF: u8 = 2;
+        pub const CC1IF: u8 = 1;
+        pub const UIF: u8 = 0;
+        crate::cpu::field!(CC4OF, 1, WriteZeroToClear);
+        crate::cpu::field!(CC3OF, 1, WriteZeroToClear);
+        crate::cpu::field!(CC2OF, 1, WriteZeroToClear);
+        crate::cpu::field!(CC1OF, 1, WriteZeroToClear);
+        crate::cpu::field!(BIF, 1, WriteZeroToClear);
+        crate::cpu::field!(TIF, 1, WriteZeroToClear);
+        crate::cpu::field!(COMIF, 1, WriteZeroToClear);
+        crate::cpu::field!(CC4IF, 1, WriteZeroToClear);
+        crate::cpu::field!(CC3IF, 1, WriteZeroToClear);
+        crate::cpu::field!(CC2IF, 1, WriteZeroToClear);
+        crate::cpu::field!(CC1IF, 1, WriteZeroToClear);
+        crate::cpu::field!(UIF, 1, WriteZeroToClear);
+    }
+    pub mod egr {
+        pub const BG: u8 = 7;
+        pub const TG: u8 = 6;
+        pub const COMG: u8 = 5;
+        pub const CC4G: u8 = 4;
+        pub const CC3G: u8 = 3;
+        pub const CC2G: u8 = 2;
+        pub const CC1G: u8 = 1;
+        pub const UG: u8 = 0;
+        crate::cpu::field!(BG, 1, WriteOnly);
+        crate::cpu::field!(TG, 1, WriteOnly);
+        crate::cpu::field!(COMG, 1, WriteOnly);
+        crate::cpu::field!(CC4G, 1, WriteOnly);
+        crate::cpu::field!(CC3G, 1, WriteOnly);
+        crate::cpu::field!(CC2G, 1, WriteOnly);
+        crate::cpu::field!(CC1G, 1, WriteOnly);
+        crate::cpu::field!(UG, 1, WriteOnly);
+    }
+    pub mod ccmr1_output {
+        pub const OC2CE: u8 = 15;
+        pub const OC2M: u8 = 12;
+        pub const OC2PE: u8 = 11;
+        pub const OC2FE: u8 = 10;
+        pub const CC2S: u8 = 8;
+        pub const OC1CE: u8 = 7;
+        pub const OC1M: u8 = 4;
+        pub const OC1PE: u8 = 3;
+        pub const OC1FE: u8 = 2;
+        pub const CC1S: u8 = 0;
+        crate::cpu::field!(OC2CE, 1, ReadWrite);
+        crate::cpu::field!(OC2M, 3, ReadWrite);
+        crate::cpu::field!(OC2PE, 1, ReadWrite);
+        crate::cpu::field!(OC2FE, 1, ReadWrite);
+        crate::cpu::field!(CC2S, 2, ReadWrite);
+        crate::cpu::field!(OC1CE, 1, ReadWrite);
+        crate::cpu::field!(OC1M, 3, ReadWrite);
+        crate::cpu::field!(OC1PE, 1, ReadWrite);
+        crate::cpu::field!(OC1FE, 1, ReadWrite);
+        crate::cpu::field!(CC1S, 2, ReadWrite);
+    }
+    pub mod ccmr1_input {
+        pub const IC2F: u8 = 12;
+        pub const IC2PCS: u8 = 10;
+        pub const CC2S: u8 = 8;
+        pub const IC1F: u8 = 4;
+        pub const ICPCS: u8 = 2;
+        pub const CC1S: u8 = 0;
+        crate::cpu::field!(IC2F, 4, ReadWrite);
+        crate::cpu::field!(IC2PCS, 2, ReadWrite);
+        crate::cpu::field!(CC2S, 2, ReadWrite);
+        crate::cpu::field!(IC1F, 4, ReadWrite);
+        crate::cpu::field!(ICPCS, 2, ReadWrite);
+        crate::cpu::field!(CC1S, 2, ReadWrite);
+    }
+    pub mod ccmr2_output {
+        pub const OC4CE: u8 = 15;
+        pub const OC4M: u8 = 12;
+        pub const OC4PE: u8 = 11;
+        pub const OC4FE: u8 = 10;
+        pub const CC4S: u8 = 8;
+        pub const OC3CE: u8 = 7;
+        pub const OC3M: u8 = 4;
+        pub const OC3PE: u8 = 3;
+        pub const OC3FE: u8 = 2;
+        pub const CC3S: u8 = 0;
+        crate::cpu::field!(OC4CE, 1, ReadWrite);
+        crate::cpu::field!(OC4M, 3, ReadWrite);
+        crate::cpu::field!(OC4PE, 1, ReadWrite);
+        crate::cpu::field!(OC4FE, 1, ReadWrite);
+        crate::cpu::field!(CC4S, 2, ReadWrite);
+        crate::cpu::field!(OC3CE, 1, ReadWrite);
+        crate::cpu::field!(OC3M, 3, ReadWrite);
+        crate::cpu::field!(OC3PE, 1, ReadWrite);
+        crate::cpu::field!(OC3FE, 1, ReadWrite);
+        crate::cpu::field!(CC3S, 2, ReadWrite);
+    }
+    pub mod ccmr2_input {
+        pub const IC4F: u8 = 12;
+        pub const IC4PSC: u8 = 10;
+        pub const CC4S: u8 = 8;
+        pub const IC3F: u8 = 4;
+        pub const IC3PSC: u8 = 2;
+        pub const CC3S: u8 = 0;
+        crate::cpu::field!(IC4F, 4, ReadWrite);
+        crate::cpu::field!(IC4PSC, 2, ReadWrite);
+        crate::cpu::field!(CC4S, 2, ReadWrite);
+        crate::cpu::field!(IC3F, 4, ReadWrite);
+        crate::cpu::field!(IC3PSC, 2, ReadWrite);
+        crate::cpu::field!(CC3S, 2, ReadWrite);
+    }
+    pub mod ccer {
+        pub const CC4P: u8 = 13;
+        pub const CC4E: u8 = 12;
+        pub const CC3NP: u8 = 11;
+        pub const CC3NE: u8 = 10;
+        pub const CC3P: u8 = 9;
+        pub const CC3E: u8 = 8;
+        pub const CC2NP: u8 = 7;
+        pub const CC2NE: u8 = 6;
+        pub const CC2P: u8 = 5;
+        pub const CC2E: u8 = 4;
+        pub const CC1NP: u8 = 3;
+        pub const CC1NE: u8 = 2;
+        pub const CC1P: u8 = 1;
+        pub const CC1E: u8 = 0;
+        crate::cpu::field!(CC4P, 1, ReadWrite);
+        crate::cpu::field!(CC4E, 1, ReadWrite);
+        crate::cpu::field!(CC3NP, 1, ReadWrite);
+        crate::cpu::field!(CC3NE, 1, ReadWrite);
+        crate::cpu::field!(CC3P, 1, ReadWrite);
+        crate::cpu::field!(CC3E, 1, ReadWrite);
+        crate::cpu::field!(CC2NP, 1, ReadWrite);
+        crate::cpu::field!(CC2NE, 1, ReadWrite);
+        crate::cpu::field!(CC2P, 1, ReadWrite);
+        crate::cpu::field!(CC2E, 1, ReadWrite);
+        crate::cpu::field!(CC1NP, 1, ReadWrite);
+        crate::cpu::field!(CC1NE, 1, ReadWrite);
+        crate::cpu::field!(CC1P, 1, ReadWrite);
+        crate::cpu::field!(CC1E, 1, ReadWrite);
+    }
+    pub mod cnt {
+        pub const CNT: u8 = 0;
+        crate::cpu::field!(CNT, 16, ReadWrite);
+    }
+    pub mod psc {
+        pub const PSC: u8 = 0;
+        crate::cpu::field!(PSC, 16, ReadWrite);
+    }
+    pub mod arr {
+        pub const ARR: u8 = 0;
+        crate::cpu::field!(ARR, 16, ReadWrite);
+    }
+    pub mod rcr {
+        pub const REP: u8 = 0;
+        crate::cpu::field!(REP, 8, ReadWrite);
+    }
+    pub mod ccr1 {
+        pub const CCR1: u8 = 0;
+        crate::cpu::field!(CCR1, 16, ReadWrite);
+    }
+    pub mod ccr2 {
+        pub const CCR2: u8 = 0;
+        crate::cpu::field!(CCR2, 16, ReadWrite);
+    }
+    pub mod ccr3 {
+        pub const CCR3: u8 = 0;
+        crate::cpu::field!(CCR3, 16, ReadWrite);
+    }
+    pub mod ccr4 {
+        pub const CCR4: u8 = 0;
+        crate::cpu::field!(CCR4, 16, ReadWrite);
+    }
+    pub mod bdtr {
+        pub const MOE: u8 = 15;
+        pub const AOE: u8 = 14;
+        pub const BKP: u8 = 13;
+        pub const BKE: u8 = 12;
+        pub const OSSR: u8 = 11;
+        pub const OSSI: u8 = 10;
+        pub const LOCK: u8 = 8;
+        pub const DTG: u8 = 0;
+        crate::cpu::field!(MOE, 1, ReadWrite);
+        crate::cpu::field!(AOE, 1, ReadWrite);
+        crate::cpu::field!(BKP, 1, ReadWrite);
+        crate::cpu::field!(BKE, 1, ReadWrite);
+        crate::cpu::field!(OSSR, 1, ReadWrite);
+        crate::cpu::field!(OSSI, 1, ReadWrite);
+        crate::cpu::field!(LOCK, 2, ReadWrite);
+        crate::cpu::field!(DTG, 8, ReadWrite);
+    }
+    pub mod dcr {
+        pub const DBL: u8 = 8;
+        pub const DBA: u8 = 0;
+        crate::cpu::field!(DBL, 5, ReadWrite);
+        crate::cpu::field!(DBA, 5, ReadWrite);
+    }
+    pub mod dmar {
+        pub const DMAB: u8 = 0;
+        crate::cpu::field!(DMAB, 16, ReadWrite);
+    }
+}
+
+pub mod TIM_GEN {
+    pub mod cr1 {
+        pub const CKD: u8 = 8;
+        pub const ARPE: u8 = 7;
+        pub const CMS: u8 = 5;
+        pub const DIR: u8 = 4;
+        pub const OPM: u8 = 3;
+        pub const URS: u8 = 2;
+        pub const UDIS: u8 = 1;
+        pub const CEN: u8 = 0;
+        crate::cpu::field!(CKD, 2, ReadWrite);
+        crate::cpu::field!(ARPE, 1, ReadWrite);
+        crate::cpu::field!(CMS, 2, ReadWrite);
+        crate::cpu::field!(DIR, 1, ReadWrite);
+        crate::cpu::field!(OPM, 1, ReadWrite);
+        crate::cpu::field!(URS, 1, ReadWrite);
+        crate::cpu::field!(UDIS, 1, ReadWrite);
+        crate::cpu::field!(CEN, 1, ReadWrite);
+    }
+    pub mod cr2 {
+        pub const TI1S: u8 = 7;
+        pub const MMS: u8 = 4;
+        pub const CCDS: u8 = 3;
+        crate::cpu::field!(TI1S, 1, ReadWrite);
+        crate::cpu::field!(MMS, 3, ReadWrite);
+        crate::cpu::field!(CCDS, 1, ReadWrite);
+    }
+    pub mod smcr {
+        pub const ETP: u8 = 15;
+        pub const ECE: u8 = 14;
+        pub const ETPS: u8 = 12;
+        pub const ETF: u8 = 8;
+        pub const MSM: u8 = 7;
+        pub const TS: u8 = 4;
+        pub const SMS: u8 = 0;
+        crate::cpu::field!(ETP, 1, ReadWrite);
+        crate::cpu::field!(ECE, 1, ReadWrite);
+        crate::cpu::field!(ETPS, 2, ReadWrite);
+        crate::cpu::field!(ETF, 4, ReadWrite);
+        crate::cpu::field!(MSM, 1, ReadWrite);
+        crate::cpu::field!(TS, 3, ReadWrite);
+        crate::cpu::field!(SMS, 3, ReadWrite);
+    }
+    pub mod dier {
+        pub const TDE: u8 = 14;
+        pub const CC4DE: u8 = 12;
+        pub const CC3DE: u8 = 11;
+        pub const CC2DE: u8 = 10;
+        pub const CC1DE: u8 = 9;
+        pub const UDE: u8 = 8;
+        pub const TIE: u8 = 6;
+        pub const CC4IE: u8 = 4;
+        pub const CC3IE: u8 = 3;
+        pub const CC2IE: u8 = 2;
+        pub const CC1IE: u8 = 1;
+        pub const UIE: u8 = 0;
+        crate::cpu::field!(TDE, 1, ReadWrite);
+        crate::cpu::field!(CC4DE, 1, ReadWrite);
+        crate::cpu::field!(CC3DE, 1, ReadWrite);
+        crate::cpu::field!(CC2DE, 1, ReadWrite);
+        crate::cpu::field!(CC1DE, 1, ReadWrite);
+        crate::cpu::field!(UDE, 1, ReadWrite);
+        crate::cpu::field!(TIE, 1, ReadWrite);
+        crate::cpu::field!(CC4IE, 1, ReadWrite);
+        crate::cpu::field!(CC3IE, 1, ReadWrite);
+        crate::cpu::field!(CC2IE, 1, ReadWrite);
+        crate::cpu::field!(CC1IE, 1, ReadWrite);
+        crate::cpu::field!(UIE, 1, ReadWrite);
+    }
+    pub mod sr {
+        pub const CC4OF: u8 = 12;
+        pub const CC3OF: u8 = 11;
+        pub const CC2OF: u8 = 10;
+        pub const CC1OF: u8 = 9;
+        pub const TIF: u8 = 6;
+        pub const CC4IF: u8 = 4;
+        pub const CC3IF: u8 = 3;
+        pub const CC2IF: u8 = 2;
+        pub const CC1IF: u8 = 1;
+        pub const UIF: u8 = 0;
+        crate::cpu::field!(CC4OF, 1, WriteZeroToClear);
+        crate::cpu::field!(CC3OF, 1, WriteZeroToClear);
+        crate::cpu::field!(CC2OF, 1, WriteZeroToClear);
+        crate::cpu::field!(CC1OF, 1, WriteZeroToClear);
+        crate::cpu::field!(TIF, 1, WriteZeroToClear);
+        crate::cpu::field!(CC4IF, 1, WriteZeroToClear);
+        crate::cpu::field!(CC3IF, 1, WriteZeroToClear);
+        crate::cpu::field!(CC2IF, 1, WriteZeroToClear);
+        crate::cpu::field!(CC1IF, 1, WriteZeroToClear);
+        crate::cpu::field!(UIF, 1, WriteZeroToClear);
+    }
+    pub mod egr {
+        pub const TG: u8 = 6;
+        pub const CC4G: u8 = 4;
+        pub const CC3G: u8 = 3;
+        pub const CC2G: u8 = 2;
+        pub const CC1G: u8 = 1;
+        pub const UG: u8 = 0;
+        crate::cpu::field!(TG, 1, WriteOnly);
+        crate::cpu::field!(CC4G, 1, WriteOnly);
+        crate::cpu::field!(CC3G, 1, WriteOnly);
+        crate::cpu::field!(CC2G, 1, WriteOnly);
+        crate::cpu::field!(CC1G, 1, WriteOnly);
+        crate::cpu::field!(UG, 1, WriteOnly);
+    }
+    pub mod ccmr1_output {
+        pub const OC2CE: u8 = 15;
+        pub const OC2M: u8 = 12;
+        pub const OC2PE: u8 = 11;
+        pub const OC2FE: u8 = 10;
+        pub const CC2S: u8 = 8;
+        pub const OC1CE: u8 = 7;
+        pub const OC1M: u8 = 4;
+        pub const OC1PE: u8 = 3;
+        pub const OC1FE: u8 = 2;
+        pub const CC1S: u8 = 0;
+        crate::cpu::field!(OC2CE, 1, ReadWrite);
+        crate::cpu::field!(OC2M, 3, ReadWrite);
+        crate::cpu::field!(OC2PE, 1, ReadWrite);
+        crate::cpu::field!(OC2FE, 1, ReadWrite);
+        crate::cpu::field!(CC2S, 2, ReadWrite);
+        crate::cpu::field!(OC1CE, 1, ReadWrite);
+        crate::cpu::field!(OC1M, 3, ReadWrite);
+        crate::cpu::field!(OC1PE, 1, ReadWrite);
+        crate::cpu::field!(OC1FE, 1, ReadWrite);
+        crate::cpu::field!(CC1S, 2, ReadWrite);
+    }
+    pub mod ccmr1_input {
+        pub const IC2F: u8 = 12;
+        pub const IC2PCS: u8 = 10;
+        pub const CC2S: u8 = 8;
+        pub const IC1F: u8 = 4;
+        pub const ICPCS: u8 = 2;
+        pub const CC1S: u8 = 0;
+        crate::cpu::field!(IC2F, 4, ReadWrite);
+        crate::cpu::field!(IC2PCS, 2, ReadWrite);
+        crate::cpu::field!(CC2S, 2, ReadWrite);
+        crate::cpu::field!(IC1F, 4, ReadWrite);
+        crate::cpu::field!(ICPCS, 2, ReadWrite);
+        crate::cpu::field!(CC1S, 2, ReadWrite);
+    }
+    pub mod ccmr2_output {
+        pub const OC4CE: u8 = 15;
+        pub const OC4M: u8 = 12;
+        pub const OC4PE: u8 = 11;
+        pub const OC4FE: u8 = 10;
+        pub const CC4S: u8 = 8;
+        pub const OC3CE: u8 = 7;
+        pub const OC3M: u8 = 4;
+        pub const OC3PE: u8 = 3;
+        pub const OC3FE: u8 = 2;
+        pub const CC3S: u8 = 0;
+        crate::cpu::field!(OC4CE, 1, ReadWrite);
+        crate::cpu::field!(OC4M, 3, ReadWrite);
+        crate::cpu::field!(OC4PE, 1, ReadWrite);
+        crate::cpu::field!(OC4FE, 1, ReadWrite);
+        crate::cpu::field!(CC4S, 2, ReadWrite);
+        crate::cpu::field!(OC3CE, 1, ReadWrite);
+        crate::cpu::field!(OC3M, 3, ReadWrite);
+        crate::cpu::field!(OC3PE, 1, ReadWrite);
+        crate::cpu::field!(OC3FE, 1, ReadWrite);
+        crate::cpu::field!(CC3S, 2, ReadWrite);
+    }
+    pub mod ccmr2_input {
+        pub const IC4F: u8 = 12;
+        pub const IC4PSC: u8 = 10;
+        pub const CC4S: u8 = 8;
+        pub const IC3F: u8 = 4;
+        pub const IC3PSC: u8 = 2;
+        pub const CC3S: u8 = 0;
+        crate::cpu::field!(IC4F, 4, ReadWrite);
+        crate::cpu::field!(IC4PSC, 2, ReadWrite);
+        crate::cpu::field!(CC4S, 2, ReadWrite);
+        crate::cpu::field!(IC3F, 4, ReadWrite);
+        crate::cpu::field!(IC3PSC, 2, ReadWrite);
+        crate::cpu::field!(CC3S, 2, ReadWrite);
+    }
+    pub mod ccer {
+        pub const CC4NP: u8 = 15;
+        pub const CC4P: u8 = 13;
+        pub const CC4E: u8 = 12;
+        pub const CC3NP: u8 = 11;
+        pub const CC3P: u8 = 9;
+        pub const CC3E: u8 = 8;
+        pub const CC2NP: u8 = 7;
+        pub const CC2P: u8 = 5;
+        pub const CC2E: u8 = 4;
+        pub const CC1NP: u8 = 3;
+        pub const CC1P: u8 = 1;
+        pub const CC1E: u8 = 0;
+        crate::cpu::field!(CC4NP, 1, ReadWrite);
+        crate::cpu::field!(CC4P, 1, ReadWrite);
+        crate::cpu::field!(CC4E, 1, ReadWrite);
+        crate::cpu::field!(CC3NP, 1, ReadWrite);
+        crate::cpu::field!(CC3P, 1, ReadWrite);
+        crate::cpu::field!(CC3E, 1, ReadWrite);
+        crate::cpu::field!(CC2NP, 1, ReadWrite);
+        crate::cpu::field!(CC2P, 1, ReadWrite);
+        crate::cpu::field!(CC2E, 1, ReadWrite);
+        crate::cpu::field!(CC1NP, 1, ReadWrite);
+        crate::cpu::field!(CC1P, 1, ReadWrite);
+        crate::cpu::field!(CC1E, 1, ReadWrite);
+    }
+    pub mod cnt {
+        pub const CNT_H: u8 = 16;
+        pub const CNT_L: u8 = 0;
+        crate::cpu::field!(CNT_H, 16, ReadWrite);
+        crate::cpu::field!(CNT_L, 16, ReadWrite);
+    }
+    pub mod psc {
+        pub const PSC: u8 = 0;
+        crate::cpu::field!(PSC, 16, ReadWrite);
+    }
+    pub mod arr {
+        pub const ARR_H: u8 = 16;
+        pub const ARR_L: u8 = 0;
+        crate::cpu::field!(ARR_H, 16, ReadWrite);
+        crate::cpu::field!(ARR_L, 16, ReadWrite);
+    }
+    pub mod ccr1 {
+        pub const CCR1_H: u8 = 16;
+        pub const CCR1_L: u8 = 0;
+        crate::cpu::field!(CCR1_H, 16, ReadWrite);
+        crate::cpu::field!(CCR1_L, 16, ReadWrite);
+    }
+    pub mod ccr2 {
+        pub const CCR2_H: u8 = 16;
+        pub const CCR2_L: u8 = 0;
+        crate::cpu::field!(CCR2_H, 16, ReadWrite);
+        crate::cpu::field!(CCR2_L, 16, ReadWrite);
+    }
+    pub mod ccr3 {
+        pub const CCR3_H: u8 = 16;
+        pub const CCR3_L: u8 = 0;
+        crate::cpu::field!(CCR3_H, 16, ReadWrite);
+        crate::cpu::field!(CCR3_L, 16, ReadWrite);
+    }
+    pub mod ccr4 {
+        pub const CCR4_H: u8 = 16;
+        pub const CCR4_L: u8 = 0;
+        crate::cpu::field!(CCR4_H, 16, ReadWrite);
+        crate::cpu::field!(CCR4_L, 16, ReadWrite);
+    }
+    pub mod dcr {
+        pub const DBL: u8 = 8;
+        pub const DBA: u8 = 0;
+        crate::cpu::field!(DBL, 5, ReadWrite);
+        crate::cpu::field!(DBA, 5, ReadWrite);
+    }
+    pub mod dmar {
+        pub const DMAB: u8 = 0;
+        crate::cpu::field!(DMAB, 16, ReadWrite);
+    }
+}
+
+pub mod SYSCFG {
+    pub mod cfgr1 {
+        pub const MEM_MODE: u8 = 0;
+        pub const ADC_DMA_RMP: u8 = 8;
+        pub const USART1_TX_DMA_RMP: u8 = 9;
+        pub const USART1_RX_DMA_RMP: u8 = 10;
+        pub const TIM16_DMA_RMP: u8 = 11;
+        pub const TIM17_DMA_RMP: u8 = 12;
+        crate::cpu::field!(MEM_MODE, 2, ReadWrite);
+        crate::cpu::field!(ADC_DMA_RMP, 1, ReadWrite);
+        crate::cpu::field!(USART1_TX_DMA_RMP, 1, ReadWrite);
+        crate::cpu::field!(USART1_RX_DMA_RMP, 1, ReadWrite);
+        crate::cpu::field!(TIM16_DMA_RMP, 1, ReadWrite);
+        crate::cpu::field!(TIM17_DMA_RMP, 1, ReadWrite);
+    }
+    pub mod exticr1 {
+        pub const EXTI3: u8 = 12;
+        pub const EXTI2: u8 = 8;
+        pub const EXTI1: u8 = 4;
+        pub const EXTI0: u8 = 0;
+        crate::cpu::field!(EXTI3, 4, ReadWrite);
+        crate::cpu::field!(EXTI2, 4, ReadWrite);
+        crate::cpu::field!(EXTI1, 4, ReadWrite);
+        crate::cpu::field!(EXTI0, 4, ReadWrite);
+    }
+    pub mod exticr2 {
+        pub const EXTI7: u8 = 12;
+        pub const EXTI6: u8 = 8;
+        pub const EXTI5: u8 = 4;
+        pub const EXTI4: u8 = 0;
+        crate::cpu::field!(EXTI7, 4, ReadWrite);
+        crate::cpu::field!(EXTI6, 4, ReadWrite);
+        crate::cpu::field!(EXTI5, 4, ReadWrite);
+        crate::cpu::field!(EXTI4, 4, ReadWrite);
+    }
+    pub mod exticr3 {
+        pub const EXTI11: u8 = 12;
+        pub const EXTI10: u8 = 8;
+        pub const EXTI9: u8 = 4;
+        pub const EXTI8: u8 = 0;
+        crate::cpu::field!(EXTI11, 4, ReadWrite);
+        crate::cpu::field!(EXTI10, 4, ReadWrite);
+        crate::cpu::field!(EXTI9, 4, ReadWrite);
+        crate::cpu::field!(EXTI8, 4, ReadWrite);
+    }
+    pub mod exticr4 {
+        pub const EXTI15: u8 = 12;
+        pub const EXTI14: u8 = 8;
+        pub const EXTI13: u8 = 4;
+        pub const EXTI12: u8 = 0;
+        crate::cpu::field!(EXTI15, 4, ReadWrite);
+        crate::cpu::field!(EXTI14, 4, ReadWrite);
+        crate::cpu::field!(EXTI13, 4, ReadWrite);
+        crate::cpu::field!(EXTI12, 4, ReadWrite);
+    }
+    pub mod cfgr2 {
+        pub const LOCUP_LOCK: u8 = 0;
+        pub const SRAM_PARITY_LOCK: u8 = 1;
+        pub const PVD_LOCK: u8 = 2;
+        pub const SRAM_PEF: u8 = 8;
+        crate::cpu::field!(LOCUP_LOCK, 1, ReadWrite);
+        crate::cpu::field!(SRAM_PARITY_LOCK, 1, ReadWrite);
+        crate::cpu::field!(PVD_LOCK, 1, ReadWrite);
+        crate::cpu::field!(SRAM_PEF, 1, WriteOneToClear);
+    }
+}
+
+pub mod EXTI {
+    pub mod imr {
+        pub const MR0: u8 = 0;
+        pub const MR1: u8 = 1;
+        pub const MR2: u8 = 2;
+        pub const MR3: u8 = 3;
+        pub const MR4: u8 = 4;
+        pub const MR5: u8 = 5;
+        pub const MR6: u8 = 6;
+        pub const MR7: u8 = 7;
+        pub const MR8: u8 = 8;
+        pub const MR9: u8 = 9;
+        pub const MR10: u8 = 10;
+        pub const MR11: u8 = 11;
+        pub const MR12: u8 = 12;
+        pub const MR13: u8 = 13;
+        pub const MR14: u8 = 14;
+        pub const MR15: u8 = 15;
+        pub const MR16: u8 = 16;
+        pub const MR17: u8 = 17;
+        pub const MR18: u8 = 18;
+        pub const MR19: u8 = 19;
+        pub const MR20: u8 = 20;
+        pub const MR21: u8 = 21;
+        pub const MR22: u8 = 22;
+        pub const MR23: u8 = 23;
+        pub const MR24: u8 = 24;
+        pub const MR25: u8 = 25;
+        pub const MR26: u8 = 26;
+        pub const MR27: u8 = 27;
+        pub const MR28: u8 = 28;
+        pub const MR29: u8 = 29;
+        pub const MR30: u8 = 30;
+        pub const MR31: u8 = 31;
+        crate::cpu::field!(MR0, 1, ReadWrite);
+        crate::cpu::field!(MR1, 1, ReadWrite);
+        crate::cpu::field!(MR2, 1, ReadWrite);
+        crate::cpu::field!(MR3, 1, ReadWrite);
+        crate::cpu::field!(MR4, 1, ReadWrite);
+        crate::cpu::field!(MR5, 1, ReadWrite);
+        crate::cpu::field!(MR6, 1, ReadWrite);
+        crate::cpu::field!(MR7, 1, ReadWrite);
+        crate::cpu::field!(MR8, 1, ReadWrite);
+        crate::cpu::field!(MR9, 1, ReadWrite);
+        crate::cpu::field!(MR10, 1, ReadWrite);
+        crate::cpu::field!(MR11, 1, ReadWrite);
+        crate::cpu::field!(MR12, 1, ReadWrite);
+        crate::cpu::field!(MR13, 1, ReadWrite);
+        crate::cpu::field!(MR14, 1, ReadWrite);
+        crate::cpu::field!(MR15, 1, ReadWrite);
+        crate::cpu::field!(MR16, 1, ReadWrite);
+        crate::cpu::field!(MR17, 1, ReadWrite);
+        crate::cpu::field!(MR18, 1, ReadWrite);
+        crate::cpu::field!(MR19, 1, ReadWrite);
+        crate::cpu::field!(MR20, 1, ReadWrite);
+        crate::cpu::field!(MR21, 1, ReadWrite);
+        crate::cpu::field!(MR22, 1, ReadWrite);
+        crate::cpu::field!(MR23, 1, ReadWrite);
+        crate::cpu::field!(MR24, 1, ReadWrite);
+        crate::cpu::field!(MR25, 1, ReadWrite);
+        crate::cpu::field!(MR26, 1, ReadWrite);
+        crate::cpu::field!(MR27, 1, ReadWrite);
+        crate::cpu::field!(MR28, 1, ReadWrite);
+        crate::cpu::field!(MR29, 1, ReadWrite);
+        crate::cpu::field!(MR30, 1, ReadWrite);
+        crate::cpu::field!(MR31, 1, ReadWrite);
+    }
+    pub mod emr {
+        pub const MR0: u8 = 0;
+        pub const MR1: u8 = 1;
+        pub const MR2: u8 = 2;
+        pub const MR3: u8 = 3;
+        pub const MR4: u8 = 4;
+        pub const MR5: u8 = 5;
+        pub const MR6: u8 = 6;
+        pub const MR7: u8 = 7;
+        pub const MR8: u8 = 8;
+        pub const MR9: u8 = 9;
+        pub const MR10: u8 = 10;
+        pub const MR11: u8 = 11;
+        pub const MR12: u8 = 12;
+        pub const MR13: u8 = 13;
+        pub const MR14: u8 = 14;
+        pub const MR15: u8 = 15;
+        pub const MR16: u8 = 16;
+        pub const MR17: u8 = 17;
+        pub const MR18: u8 = 18;
+        pub const MR19: u8 = 19;
+        pub const MR20: u8 = 20;
+        pub const MR21: u8 = 21;
+        pub const MR22: u8 = 22;
+        pub const MR23: u8 = 23;
+        pub const MR24: u8 = 24;
+        pub const MR25: u8 = 25;
+        pub const MR26: u8 = 26;
+        pub const MR27: u8 = 27;
+        pub const MR28: u8 = 28;
+        pub const MR29: u8 = 29;
+        pub const MR30: u8 = 30;
+        pub const MR31: u8 = 31;
+        crate::cpu::field!(MR0, 1, ReadWrite);
+        crate::cpu::field!(MR1, 1, ReadWrite);
+        crate::cpu::field!(MR2, 1, ReadWrite);
+        crate::cpu::field!(MR3, 1, ReadWrite);
+        crate::cpu::field!(MR4, 1, ReadWrite);
+        crate::cpu::field!(MR5, 1, ReadWrite);
+        crate::cpu::field!(MR6, 1, ReadWrite);
+        crate::cpu::field!(MR7, 1, ReadWrite);
+        crate::cpu::field!(MR8, 1, ReadWrite);
+        crate::cpu::field!(MR9, 1, ReadWrite);
+        crate::cpu::field!(MR10, 1, ReadWrite);
+        crate::cpu::field!(MR11, 1, ReadWrite);
+        crate::cpu::field!(MR12, 1, ReadWrite);
+        crate::cpu::field!(MR13, 1, ReadWrite);
+        crate::cpu::field!(MR14, 1, ReadWrite);
+        crate::cpu::field!(MR15, 1, ReadWrite);
+        crate::cpu::field!(MR16, 1, ReadWrite);
+        crate::cpu::field!(MR17, 1, ReadWrite);
+        crate::cpu::field!(MR18, 1, ReadWrite);
+        crate::cpu::field!(MR19, 1, ReadWrite);
+        crate::cpu::field!(MR20, 1, ReadWrite);
+        crate::cpu::field!(MR21, 1, ReadWrite);
+        crate::cpu::field!(MR22, 1, ReadWrite);
+        crate::cpu::field!(MR23, 1, ReadWrite);
+        crate::cpu::field!(MR24, 1, ReadWrite);
+        crate::cpu::field!(MR25, 1, ReadWrite);
+        crate::cpu::field!(MR26, 1, ReadWrite);
+        crate::cpu::field!(MR27, 1, ReadWrite);
+        crate::cpu::field!(MR28, 1, ReadWrite);
+        crate::cpu::field!(MR29, 1, ReadWrite);
+        crate::cpu::field!(MR30, 1, ReadWrite);
+        crate::cpu::field!(MR31, 1, ReadWrite);
+    }
+    pub mod rtsr {
+        pub const TR0: u8 = 0;
+        pub const TR1: u8 = 1;
+        pub const TR2: u8 = 2;
+        pub const TR3: u8 = 3;
+        pub const TR4: u8 = 4;
+        pub const TR5: u8 = 5;
+        pub const TR6: u8 = 6;
+        pub const TR7: u8 = 7;
+        pub const TR8: u8 = 8;
+        pub const TR9: u8 = 9;
+        pub const TR10: u8 = 10;
+        pub const TR11: u8 = 11;
+        pub const TR12: u8 = 12;
+        pub const TR13: u8 = 13;
+        pub const TR14: u8 = 14;
+        pub const TR15: u8 = 15;
+        pub const TR16: u8 = 16;
+        pub const TR17: u8 = 17;
+        pub const TR18: u8 = 18;
+        pub const TR19: u8 = 19;
+        pub const TR20: u8 = 20;
+        pub const TR21: u8 = 21;
+        pub const TR22: u8 = 22;
+        pub const TR23: u8 = 23;
+        pub const TR24: u8 = 24;
+        pub const TR25: u8 = 25;
+        pub const TR26: u8 = 26;
+        pub const TR27: u8 = 27;
+        pub const TR28: u8 = 28;
+        pub const TR29: u8 = 29;
+        pub const TR30: u8 = 30;
+        pub const TR31: u8 = 31;
+        crate::cpu::field!(TR0, 1, ReadWrite);
+        crate::cpu::field!(TR1, 1, ReadWrite);
+        crate::cpu::field!(TR2, 1, ReadWrite);
+        crate::cpu::field!(TR3, 1, ReadWrite);
+        crate::cpu::field!(TR4, 1, ReadWrite);
+        crate::cpu::field!(TR5, 1, ReadWrite);
+        crate::cpu::field!(TR6, 1, ReadWrite);
+        crate::cpu::field!(TR7, 1, ReadWrite);
+        crate::cpu::field!(TR8, 1, ReadWrite);
+        crate::cpu::field!(TR9, 1, ReadWrite);
+        crate::cpu::field!(TR10, 1, ReadWrite);
+        crate::cpu::field!(TR11, 1, ReadWrite);
+        crate::cpu::field!(TR12, 1, ReadWrite);
+        crate::cpu::field!(TR13, 1, ReadWrite);
+        crate::cpu::field!(TR14, 1, ReadWrite);
+        crate::cpu::field!(TR15, 1, ReadWrite);
+        crate::cpu::field!(TR16, 1, ReadWrite);
+        crate::cpu::field!(TR17, 1, ReadWrite);
+        crate::cpu::field!(TR18, 1, ReadWrite);
+        crate::cpu::field!(TR19, 1, ReadWrite);
+        crate::cpu::field!(TR20, 1, ReadWrite);
+        crate::cpu::field!(TR21, 1, ReadWrite);
+        crate::cpu::field!(TR22, 1, ReadWrite);
+        crate::cpu::field!(TR23, 1, ReadWrite);
+        crate::cpu::field!(TR24, 1, ReadWrite);
+        crate::cpu::field!(TR25, 1, ReadWrite);
+        crate::cpu::field!(TR26, 1, ReadWrite);
+        crate::cpu::field!(TR27, 1, ReadWrite);
+        crate::cpu::field!(TR28, 1, ReadWrite);
+        crate::cpu::field!(TR29, 1, ReadWrite);
+        crate::cpu::field!(TR30, 1, ReadWrite);
+        crate::cpu::field!(TR31, 1, ReadWrite);
+    }
+    pub mod ftsr {
+        pub const TR0: u8 = 0;
+        pub const TR1: u8 = 1;
+        pub const TR2: u8 = 2;
+        pub const TR3: u8 = 3;
+        pub const TR4: u8 = 4;
+        pub const TR5: u8 = 5;
+        pub const TR6: u8 = 6;
+        pub const TR7: u8 = 7;
+        pub const TR8: u8 = 8;
+        pub const TR9: u8 = 9;
+        pub const TR10: u8 = 10;
+        pub const TR11: u8 = 11;
+        pub const TR12: u8 = 12;
+        pub const TR13: u8 = 13;
+        pub const TR14: u8 = 14;
+        pub const TR15: u8 = 15;
+        pub const TR16: u8 = 16;
+        pub const TR17: u8 = 17;
+        pub const TR18: u8 = 18;
+        pub const TR19: u8 = 19;
+        pub const TR20: u8 = 20;
+        pub const TR21: u8 = 21;
+        pub const TR22: u8 = 22;
+        pub const TR23: u8 = 23;
+        pub const TR24: u8 = 24;
+        pub const TR25: u8 = 25;
+        pub const TR26: u8 = 26;
+        pub const TR27: u8 = 27;
+        pub const TR28: u8 = 28;
+        pub const TR29: u8 = 29;
+        pub const TR30: u8 = 30;
+        pub const TR31: u8 = 31;
+        crate::cpu::field!(TR0, 1, ReadWrite);
+        crate::cpu::field!(TR1, 1, ReadWrite);
+        crate::cpu::field!(TR2, 1, ReadWrite);
+        crate::cpu::field!(TR3, 1, ReadWrite);
+        crate::cpu::field!(TR4, 1, ReadWrite);
+        crate::cpu::field!(TR5, 1, ReadWrite);
+        crate::cpu::field!(TR6, 1, ReadWrite);
+        crate::cpu::field!(TR7, 1, ReadWrite);
+        crate::cpu::field!(TR8, 1, ReadWrite);
+        crate::cpu::field!(TR9, 1, ReadWrite);
+        crate::cpu::field!(TR10, 1, ReadWrite);
+        crate::cpu::field!(TR11, 1, ReadWrite);
+        crate::cpu::field!(TR12, 1, ReadWrite);
+        crate::cpu::field!(TR13, 1, ReadWrite);
+        crate::cpu::field!(TR14, 1, ReadWrite);
+        crate::cpu::field!(TR15, 1, ReadWrite);
+        crate::cpu::field!(TR16, 1, ReadWrite);
+        crate::cpu::field!(TR17, 1, ReadWrite);
+        crate::cpu::field!(TR18, 1, ReadWrite);
+        crate::cpu::field!(TR19, 1, ReadWrite);
+        crate::cpu::field!(TR20, 1, ReadWrite);
+        crate::cpu::field!(TR21, 1, ReadWrite);
+        crate::cpu::field!(TR22, 1, ReadWrite);
+        crate::cpu::field!(TR23, 1, ReadWrite);
+        crate::cpu::field!(TR24, 1, ReadWrite);
+        crate::cpu::field!(TR25, 1, ReadWrite);
+        crate::cpu::field!(TR26, 1, ReadWrite);
+        crate::cpu::field!(TR27, 1, ReadWrite);
+        crate::cpu::field!(TR28, 1, ReadWrite);
+        crate::cpu::field!(TR29, 1, ReadWrite);
+        crate::cpu::field!(TR30, 1, ReadWrite);
+        crate::cpu::field!(TR31, 1, ReadWrite);
+    }
+    pub mod swier {
+        pub const SWIER0: u8 = 0;
+        pub const SWIER1: u8 = 1;
+        pub const SWIER2: u8 = 2;
+        pub const SWIER3: u8 = 3;
+        pub const SWIER4: u8 = 4;
+        pub const SWIER5: u8 = 5;
+        pub const SWIER6: u8 = 6;
+        pub const SWIER7: u8 = 7;
+        pub const SWIER8: u8 = 8;
+        pub const SWIER9: u8 = 9;
+        pub const SWIER10: u8 = 10;
+        pub const SWIER11: u8 = 11;
+        pub const SWIER12: u8 = 12;
+        pub const SWIER13: u8 = 13;
+        pub const SWIER14: u8 = 14;
+        pub const SWIER15: u8 = 15;
+        pub const SWIER16: u8 = 16;
+        pub const SWIER17: u8 = 17;
+        pub const SWIER18: u8 = 18;
+        pub const SWIER19: u8 = 19;
+        pub const SWIER20: u8 = 20;
+        pub const SWIER21: u8 = 21;
+        pub const SWIER22: u8 = 22;
+        pub const SWIER23: u8 = 23;
+        pub const SWIER24: u8 = 24;
+        pub const SWIER25: u8 = 25;
+        pub const SWIER26: u8 = 26;
+        pub const SWIER27: u8 = 27;
+        pub const SWIER28: u8 = 28;
+        pub const SWIER29: u8 = 29;
+        pub const SWIER30: u8 = 30;
+        pub const SWIER31: u8 = 31;
+        crate::cpu::field!(SWIER0, 1, ReadWrite);
+        crate::cpu::field!(SWIER1, 1, ReadWrite);
+        crate::cpu::field!(SWIER2, 1, ReadWrite);
+        crate::cpu::field!(SWIER3, 1, ReadWrite);
+        crate::cpu::field!(SWIER4, 1, ReadWrite);
+        crate::cpu::field!(SWIER5, 1, ReadWrite);
+        crate::cpu::field!(SWIER6, 1, ReadWrite);
+        crate::cpu::field!(SWIER7, 1, ReadWrite);
+        crate::cpu::field!(SWIER8, 1, ReadWrite);
+        crate::cpu::field!(SWIER9, 1, ReadWrite);
+        crate::cpu::field!(SWIER10, 1, ReadWrite);
+        crate::cpu::field!(SWIER11, 1, ReadWrite);
+        crate::cpu::field!(SWIER12, 1, ReadWrite);
+        crate::cpu::field!(SWIER13, 1, ReadWrite);
+        crate::cpu::field!(SWIER14, 1, ReadWrite);
+        crate::cpu::field!(SWIER15, 1, ReadWrite);
+        crate::cpu::field!(SWIER16, 1, ReadWrite);
+        crate::cpu::field!(SWIER17, 1, ReadWrite);
+        crate::cpu::field!(SWIER18, 1, ReadWrite);
+        crate::cpu::field!(SWIER19, 1, ReadWrite);
+        crate::cpu::field!(SWIER20, 1, ReadWrite);
+        crate::cpu::field!(SWIER21, 1, ReadWrite);
+        crate::cpu::field!(SWIER22, 1, ReadWrite);
+        crate::cpu::field!(SWIER23, 1, ReadWrite);
+        crate::cpu::field!(SWIER24, 1, ReadWrite);
+        crate::cpu::field!(SWIER25, 1, ReadWrite);
+        crate::cpu::field!(SWIER26, 1, ReadWrite);
+        crate::cpu::field!(SWIER27, 1, ReadWrite);
+        crate::cpu::field!(SWIER28, 1, ReadWrite);
+        crate::cpu::field!(SWIER29, 1, ReadWrite);
+        crate::cpu::field!(SWIER30, 1, ReadWrite);
+        crate::cpu::field!(SWIER31, 1, ReadWrite);
+    }
+    pub mod pr {
+        pub const PR0: u8 = 0;
+        pub const PR1: u8 = 1;
+        pub const PR2: u8 = 2;
+        pub const PR3: u8 = 3;
+        pub const PR4: u8 = 4;
+        pub const PR5: u8 = 5;
+        pub const PR6: u8 = 6;
+        pub const PR7: u8 = 7;
+        pub const PR8: u8 = 8;
+        pub const PR9: u8 = 9;
+        pub const PR10: u8 = 10;
+        pub const PR11: u8 = 11;
+        pub const PR12: u8 = 12;
+        pub const PR13: u8 = 13;
+        pub const PR14: u8 = 14;
+        pub const PR15: u8 = 15;
+        pub const PR16: u8 = 16;
+        pub const PR17: u8 = 17;
+        pub const PR18: u8 = 18;
+        pub const PR19: u8 = 19;
+        pub const PR20: u8 = 20;
+        pub const PR21: u8 = 21;
+        pub const PR22: u8 = 22;
+        pub const PR23: u8 = 23;
+        pub const PR24: u8 = 24;
+        pub const PR25: u8 = 25;
+        pub const PR26: u8 = 26;
+        pub const PR27: u8 = 27;
+        pub const PR28: u8 = 28;
+        pub const PR29: u8 = 29;
+        pub const PR30: u8 = 30;
+        pub const PR31: u8 = 31;
+        crate::cpu::field!(PR0, 1, WriteOneToClear);
+        crate::cpu::field!(PR1, 1, WriteOneToClear);
+        crate::cpu::field!(PR2, 1, WriteOneToClear);
+        crate::cpu::field!(PR3, 1, WriteOneToClear);
+        crate::cpu::field!(PR4, 1, WriteOneToClear);
+        crate::cpu::field!(PR5, 1, WriteOneToClear);
+        crate::cpu::field!(PR6, 1, WriteOneToClear);
+        crate::cpu::field!(PR7, 1, WriteOneToClear);
+        crate::cpu::field!(PR8, 1, WriteOneToClear);
+        crate::cpu::field!(PR9, 1, WriteOneToClear);
+        crate::cpu::field!(PR10, 1, WriteOneToClear);
+        crate::cpu::field!(PR11, 1, WriteOneToClear);
+        crate::cpu::field!(PR12, 1, WriteOneToClear);
+        crate::cpu::field!(PR13, 1, WriteOneToClear);
+        crate::cpu::field!(PR14, 1, WriteOneToClear);
+        crate::cpu::field!(PR15, 1, WriteOneToClear);
+        crate::cpu::field!(PR16, 1, WriteOneToClear);
+        crate::cpu::field!(PR17, 1, WriteOneToClear);
+        crate::cpu::field!(PR18, 1, WriteOneToClear);
+        crate::cpu::field!(PR19, 1, WriteOneToClear);
+        crate::cpu::field!(PR20, 1, WriteOneToClear);
+        crate::cpu::field!(PR21, 1, WriteOneToClear);
+        crate::cpu::field!(PR22, 1, WriteOneToClear);
+        crate::cpu::field!(PR23, 1, WriteOneToClear);
+        crate::cpu::field!(PR24, 1, WriteOneToClear);
+        crate::cpu::field!(PR25, 1, WriteOneToClear);
+        crate::cpu::field!(PR26, 1, WriteOneToClear);
+        crate::cpu::field!(PR27, 1, WriteOneToClear);
+        crate::cpu::field!(PR28, 1, WriteOneToClear);
+        crate::cpu::field!(PR29, 1, WriteOneToClear);
+        crate::cpu::field!(PR30, 1, WriteOneToClear);
+        crate::cpu::field!(PR31, 1, WriteOneToClear);
+    }
+}
+
+pub mod IWDG {
+    pub mod kr {
+        pub const KEY: u8 = 0;
+        crate::cpu::field!(KEY, 16, WriteOnly);
+    }
+    pub mod pr {
+        pub const PR: u8 = 0;
+        crate::cpu::field!(PR, 3, ReadWrite);
+    }
+    pub mod rlr {
+        pub const RL: u8 = 0;
+        crate::cpu::field!(RL, 12, ReadWrite);
+    }
+    pub mod sr {
+        pub const WVU: u8 = 2;
+        pub const RVU: u8 = 1;
+        pub const PVU: u8 = 0;
+        crate::cpu::field!(WVU, 1, ReadOnly);
+        crate::cpu::field!(RVU, 1, ReadOnly);
+        crate::cpu::field!(PVU, 1, ReadOnly);
+    }
+    pub mod winr {
+        pub const WIN: u8 = 0;
+        crate::cpu::field!(WIN, 12, ReadWrite);
+    }
+}
+
+pub mod PWR {
+    pub mod cr {
+        pub const DBP: u8 = 8;
+        pub const PLS: u8 = 5;
+        pub const PVDE: u8 = 4;
+        pub const CSBF: u8 = 3;
+        pub const CWUF: u8 = 2;
+        pub const PDDS: u8 = 1;
+        pub const LPDS: u8 = 0;
+        crate::cpu::field!(DBP, 1, ReadWrite);
+        crate::cpu::field!(PLS, 3, ReadWrite);
+        crate::cpu::field!(PVDE, 1, ReadWrite);
+        crate::cpu::field!(CSBF, 1, WriteOnly);
+        crate::cpu::field!(CWUF, 1, WriteOnly);
+        crate::cpu::field!(PDDS, 1, ReadWrite);
+        crate::cpu::field!(LPDS, 1, ReadWrite);
+    }
+    pub mod csr {
+        pub const EWUP8: u8 = 15;
+        pub const EWUP7: u8 = 14;
+        pub const EWUP6: u8 = 13;
+        pub const EWUP5: u8 = 12;
+        pub const EWUP4: u8 = 11;
+        pub const EWUP3: u8 = 10;
+        pub const EWUP2: u8 = 9;
+        pub const EWUP1: u8 = 8;
+        pub const VREFINTRDYF: u8 = 3;
+        pub const PVDO: u8 = 2;
+        pub const SBF: u8 = 1;
+        pub const WUF: u8 = 0;
+        crate::cpu::field!(EWUP8, 1, ReadWrite);
+        crate::cpu::field!(EWUP7, 1, ReadWrite);
+        crate::cpu::field!(EWUP6, 1, ReadWrite);
+        crate::cpu::field!(EWUP5, 1, ReadWrite);
+        crate::cpu::field!(EWUP4, 1, ReadWrite);
+        crate::cpu::field!(EWUP3, 1, ReadWrite);
+        crate::cpu::field!(EWUP2, 1, ReadWrite);
+        crate::cpu::field!(EWUP1, 1, ReadWrite);
+        crate::cpu::field!(VREFINTRDYF, 1, ReadOnly);
+        crate::cpu::field!(PVDO, 1, ReadOnly);
+        crate::cpu::field!(SBF, 1, ReadOnly);
+        crate::cpu::field!(WUF, 1, ReadOnly);
+    }
+}
+
+pub mod CRC {
+    pub mod dr {
+        pub const DR: u8 = 0;
+        crate::cpu::field!(DR, 32, ReadWrite);
+    }
+    pub mod idr {
+        pub const IDR: u8 = 0;
+        crate::cpu::field!(IDR, 8, ReadWrite);
+    }
+    pub mod cr {
+        pub const REV_OUT: u8 = 7;
+        pub const REV_IN: u8 = 5;
+        pub const POLYSIZE: u8 = 3;
+        pub const RESET: u8 = 0;
+        crate::cpu::field!(REV_OUT, 1, ReadWrite);
+        crate::cpu::field!(REV_IN, 2, ReadWrite);
+        crate::cpu::field!(POLYSIZE, 2, ReadWrite);
+        crate::cpu::field!(RESET, 1, ReadWrite);
+    }
+    pub mod init {
+        pub const INIT: u8 = 0;
+        crate::cpu::field!(INIT, 32, ReadWrite);
+    }
+    pub mod pol {
+        pub const POL: u8 = 0;
+        crate::cpu::field!(POL, 32, ReadWrite);
+    }
+}
+
+pub mod RTC {
+    pub mod tr {
+        pub const PM: u8 = 22;
+        pub const HT: u8 = 20;
+        pub const HU: u8 = 16;
+        pub const MNT: u8 = 12;
+        pub const MNU: u8 = 8;
+        pub const ST: u8 = 4;
+        pub const SU: u8 = 0;
+        crate::cpu::field!(PM, 1, ReadWrite);
+        crate::cpu::field!(HT, 2, ReadWrite);
+        crate::cpu::field!(HU, 4, ReadWrite);
+        crate::cpu::field!(MNT, 3, ReadWrite);
+        crate::cpu::field!(MNU, 4, ReadWrite);
+        crate::cpu::field!(ST, 3, ReadWrite);
+        crate::cpu::field!(SU, 4, ReadWrite);
+    }
+    pub mod dr {
+        pub const YT: u8 = 20;
+        pub const YU: u8 = 16;
+        pub const WDU: u8 = 13;
+        pub const MT: u8 = 12;
+        pub const MU: u8 = 8;
+        pub const DT: u8 = 4;
+        pub const DU: u8 = 0;
+        crate::cpu::field!(YT, 4, ReadWrite);
+        crate::cpu::field!(YU, 4, ReadWrite);
+        crate::cpu::field!(WDU, 3, ReadWrite);
+        crate::cpu::field!(MT, 1, ReadWrite);
+        crate::cpu::field!(MU, 4, ReadWrite);
+        crate::cpu::field!(DT, 2, ReadWrite);
+        crate::cpu::field!(DU, 4, ReadWrite);
+    }
+    pub mod cr {
+        pub const COE: u8 = 23;
+        pub const OSEL: u8 = 21;
+        pub const POL: u8 = 20;
+        pub const COSEL: u8 = 19;
+        pub const BKP: u8 = 18;
+        pub const SUB1H: u8 = 17;
+        pub const ADD1H: u8 = 16;
+        pub const TSIE: u8 = 15;
+        pub const WUTIE: u8 = 14;
+        pub const ALRAIE: u8 = 12;
+        pub const TSE: u8 = 11;
+        pub const WUTE: u8 = 10;
+        pub const ALRAE: u8 = 8;
+        pub const FMT: u8 = 6;
+        pub const BYPSHAD: u8 = 5;
+        pub const REFCKON: u8 = 4;
+        pub const TSEDGE: u8 = 3;
+        pub const WUCKSEL: u8 = 0;
+        crate::cpu::field!(COE, 1, ReadWrite);
+        crate::cpu::field!(OSEL, 2, ReadWrite);
+        crate::cpu::field!(POL, 1, ReadWrite);
+        crate::cpu::field!(COSEL, 1, ReadWrite);
+        crate::cpu::field!(BKP, 1, ReadWrite);
+        crate::cpu::field!(SUB1H, 1, WriteOnly);
+        crate::cpu::field!(ADD1H, 1, WriteOnly);
+        crate::cpu::field!(TSIE, 1, ReadWrite);
+        crate::cpu::field!(WUTIE, 1, ReadWrite);
+        crate::cpu::field!(ALRAIE, 1, ReadWrite);
+        crate::cpu::field!(TSE, 1, ReadWrite);
+        crate::cpu::field!(WUTE, 1, ReadWrite);
+        crate::cpu::field!(ALRAE, 1, ReadWrite);
+        crate::cpu::field!(FMT, 1, ReadWrite);
+        crate::cpu::field!(BYPSHAD, 1, ReadWrite);
+        crate::cpu::field!(REFCKON, 1, ReadWrite);
+        crate::cpu::field!(TSEDGE, 1, ReadWrite);
+        crate::cpu::field!(WUCKSEL, 3, ReadWrite);
+    }
+    pub mod isr {
+        pub const RECALPF: u8 = 16;
+        pub const TAMP2F: u8 = 14;
+        pub const TAMP1F: u8 = 13;
+        pub const TSOVF: u8 = 12;
+        pub const TSF: u8 = 11;
+        pub const WUTF: u8 = 10;
+        pub const ALRAF: u8 = 8;
+        pub const INIT: u8 = 7;
+        pub const INITF: u8 = 6;
+        pub const RSF: u8 = 5;
+        pub const INITS: u8 = 4;
+        pub const SHPF: u8 = 3;
+        pub const WUTWF: u8 = 2;
+        pub const ALRAWF: u8 = 0;
+        crate::cpu::field!(RECALPF, 1, ReadOnly);
+        crate::cpu::field!(TAMP2F, 1, WriteZeroToClear);
+        crate::cpu::field!(TAMP1F, 1, WriteZeroToClear);
+        crate::cpu::field!(TSOVF, 1, WriteZeroToClear);
+        crate::cpu::field!(TSF, 1, WriteZeroToClear);
+        crate::cpu::field!(WUTF, 1, WriteZeroToClear);
+        crate::cpu::field!(ALRAF, 1, WriteZeroToClear);
+        crate::cpu::field!(INIT, 1, ReadWrite);
+        crate::cpu::field!(INITF, 1, ReadOnly);
+        crate::cpu::field!(RSF, 1, WriteZeroToClear);
+        crate::cpu::field!(INITS, 1, ReadOnly);
+        crate::cpu::field!(SHPF, 1, ReadOnly);
+        crate::cpu::field!(WUTWF, 1, ReadOnly);
+        crate::cpu::field!(ALRAWF, 1, ReadOnly);
+    }
+    pub mod prer {
+        pub const PREDIV_A: u8 = 16;
+        pub const PREDIV_S: u8 = 0;
+        crate::cpu::field!(PREDIV_A, 7, ReadWrite);
+        crate::cpu::field!(PREDIV_S, 15, ReadWrite);
+    }
+    pub mod wutr {
+        pub const WUT: u8 = 0;
+        crate::cpu::field!(WUT, 16, ReadWrite);
+    }
+    pub mod alrmar {
+        pub const MSK4: u8 = 31;
+        pub const WDSEL: u8 = 30;
+        pub const DT: u8 = 28;
+        pub const DU: u8 = 24;
+        pub const MSK3: u8 = 23;
+        pub const PM: u8 = 22;
+        pub const HT: u8 = 20;
+        pub const HU: u8 = 16;
+        pub const MSK2: u8 = 15;
+        pub const MNT: u8 = 12;
+        pub const MNU: u8 = 8;
+        pub const MSK1: u8 = 7;
+        pub const ST: u8 = 4;
+        pub const SU: u8 = 0;
+        crate::cpu::field!(MSK4, 1, ReadWrite);
+        crate::cpu::field!(WDSEL, 1, ReadWrite);
+        crate::cpu::field!(DT, 2, ReadWrite);
+        crate::cpu::field!(DU, 4, ReadWrite);
+        crate::cpu::field!(MSK3, 1, ReadWrite);
+        crate::cpu::field!(PM, 1, ReadWrite);
+        crate::cpu::field!(HT, 2, ReadWrite);
+        crate::cpu::field!(HU, 4, ReadWrite);
+        crate::cpu::field!(MSK2, 1, ReadWrite);
+        crate::cpu::field!(MNT, 3, ReadWrite);
+        crate::cpu::field!(MNU, 4, ReadWrite);
+        crate::cpu::field!(MSK1, 1, ReadWrite);
+        crate::cpu::field!(ST, 3, ReadWrite);
+        crate::cpu::field!(SU, 4, ReadWrite);
+    }
+    pub mod wpr {
+        pub const KEY: u8 = 0;
+        crate::cpu::field!(KEY, 8, WriteOnly);
+    }
+    pub mod ssr {
+        pub const SS: u8 = 0;
+        crate::cpu::field!(SS, 16, ReadOnly);
+    }
+    pub mod shiftr {
+        pub const ADD1S: u8 = 31;
+        pub const SUBFS: u8 = 0;
+        crate::cpu::field!(ADD1S, 1, WriteOnly);
+        crate::cpu::field!(SUBFS, 15, WriteOnly);
+    }
+    pub mod calr {
+        pub const CALP: u8 = 15;
+        pub const CALW8: u8 = 14;
+        pub const CALW16: u8 = 13;
+        pub const CALM: u8 = 0;
+        crate::cpu::field!(CALP, 1, ReadWrite);
+        crate::cpu::field!(CALW8, 1, ReadWrite);
+        crate::cpu::field!(CALW16, 1, ReadWrite);
+        crate::cpu::field!(CALM, 9, ReadWrite);
+    }
+    pub mod alrmassr {
+        pub const MASKSS: u8 = 24;
+        pub const SS: u8 = 0;
+        crate::cpu::field!(MASKSS, 4, ReadWrite);
+        crate::cpu::field!(SS, 15, ReadWrite);
     }
 }
 
@@ -668,6 +2579,19 @@ pub struct UsartReg {
     pub tdr: u32,
 }
 
+#[repr(C)]
+pub struct Spi1Reg {
+    pub cr1: u32,
+    pub cr2: u32,
+    pub sr: u32,
+    pub dr: u32,
+    pub crcpr: u32,
+    pub rxcrcr: u32,
+    pub txcrcr: u32,
+    pub i2scfgr: u32,
+    pub i2spr: u32,
+}
+
 #[repr(C)]
 pub struct FlashReg {
     pub acr: u32,
@@ -688,36 +2612,166 @@ pub struct DbgmcuReg {
     pub apb1_fz: u32,
     pub apb2_fz: u32,
 }
-// Skipped peripheral: CRC
-// Skipped peripheral: GPIOF
+
+#[repr(C)]
+pub struct TimAdvReg {
+    pub cr1: u32,
+    pub cr2: u32,
+    pub smcr: u32,
+    pub dier: u32,
+    pub sr: u32,
+    pub egr: u32,
+    pub ccmr1: u32,
+    pub ccmr2: u32,
+    pub ccer: u32,
+    pub cnt: u32,
+    pub psc: u32,
+    pub arr: u32,
+    pub rcr: u32,
+    pub ccr1: u32,
+    pub ccr2: u32,
+    pub ccr3: u32,
+    pub ccr4: u32,
+    pub bdtr: u32,
+    pub dcr: u32,
+    pub dmar: u32,
+}
+
+#[repr(C)]
+pub struct TimGenReg {
+    pub cr1: u32,
+    pub cr2: u32,
+    pub smcr: u32,
+    pub dier: u32,
+    pub sr: u32,
+    pub egr: u32,
+    pub ccmr1: u32,
+    pub ccmr2: u32,
+    pub ccer: u32,
+    pub cnt: u32,
+    pub psc: u32,
+    pub arr: u32,
+    reserved1: u32,
+    pub ccr1: u32,
+    pub ccr2: u32,
+    pub ccr3: u32,
+    pub ccr4: u32,
+    reserved2: u32,
+    pub dcr: u32,
+    pub dmar: u32,
+}
+
+#[repr(C)]
+pub struct RtcReg {
+    pub tr: u32,
+    pub dr: u32,
+    pub cr: u32,
+    pub isr: u32,
+    pub prer: u32,
+    pub wutr: u32,
+    reserved1: u32,
+    pub alrmar: u32,
+    reserved2: u32,
+    pub wpr: u32,
+    pub ssr: u32,
+    pub shiftr: u32,
+    pub tstr: u32,
+    pub tsdr: u32,
+    pub tsssr: u32,
+    pub calr: u32,
+    pub tafcr: u32,
+    pub alrmassr: u32,
+    reserved3: [u32; 2],
+    pub bkpr: [u32; 5],
+}
+
+#[repr(C)]
+pub struct PwrReg {
+    pub cr: u32,
+    pub csr: u32,
+}
+
+#[repr(C)]
+pub struct IwdgReg {
+    pub kr: u32,
+    pub pr: u32,
+    pub rlr: u32,
+    pub sr: u32,
+    pub winr: u32,
+}
+
+#[repr(C)]
+pub struct SyscfgReg {
+    pub cfgr1: u32,
+    reserved1: u32,
+    pub exticr1: u32,
+    pub exticr2: u32,
+    pub exticr3: u32,
+    pub exticr4: u32,
+    pub cfgr2: u32,
+}
+
+#[repr(C)]
+pub struct ExtiReg {
+    pub imr: u32,
+    pub emr: u32,
+    pub rtsr: u32,
+    pub ftsr: u32,
+    pub swier: u32,
+    pub pr: u32,
+}
+
+#[repr(C)]
+pub struct CrcReg {
+    pub dr: u32,
+    pub idr: u32,
+    pub cr: u32,
+    reserved1: u32,
+    pub init: u32,
+    pub pol: u32,
+}
+
+pub const CRC: *mut CrcReg = 0x40023000 as *mut CrcReg;
+
+pub const GPIOF: *mut GpioReg = 0x48001400 as *mut GpioReg;
 // Skipped peripheral: GPIOD
-// Skipped peripheral: GPIOC
+
+pub const GPIOC: *mut GpioReg = 0x48000800 as *mut GpioReg;
 
 pub const GPIOB: *mut GpioReg = 0x48000400 as *mut GpioReg;
 // Skipped peripheral: GPIOE
 
 pub const GPIOA: *mut GpioReg = 0x48000000 as *mut GpioReg;
-// Skipped peripheral: SPI1
-// Skipped peripheral: SPI2
+
+pub const SPI1: *mut Spi1Reg = 0x40013000 as *mut Spi1Reg;
+
+pub const SPI2: *mut Spi1Reg = 0x40003800 as *mut Spi1Reg;
 // Skipped peripheral: DAC
-// Skipped peripheral: PWR
+
+pub const PWR: *mut PwrReg = 0x40007000 as *mut PwrReg;
 // Skipped peripheral: I2C1
 // Skipped peripheral: I2C2
-// Skipped peripheral: IWDG
+
+pub const IWDG: *mut IwdgReg = 0x40003000 as *mut IwdgReg;
 
 pub const WWDG: *mut WwdgReg = 0x40002C00 as *mut WwdgReg;
-// Skipped peripheral: TIM1
-// Skipped peripheral: TIM2
-// Skipped peripheral: TIM3
+
+pub const TIM1: *mut TimAdvReg = 0x40012C00 as *mut TimAdvReg;
+
+pub const TIM2: *mut TimGenReg = 0x40000000 as *mut TimGenReg;
+
+pub const TIM3: *mut TimGenReg = 0x40000400 as *mut TimGenReg;
 // Skipped peripheral: TIM14
 // Skipped peripheral: TIM6
 // Skipped peripheral: TIM7
-// Skipped peripheral: EXTI
+
+pub const EXTI: *mut ExtiReg = 0x40010400 as *mut ExtiReg;
 // Skipped peripheral: NVIC
 // Skipped peripheral: DMA1
 
 pub const RCC: *mut RccReg = 0x40021000 as *mut RccReg;
-// Skipped peripheral: SYSCFG_COMP
+
+pub const SYSCFG: *mut SyscfgReg = 0x40010000 as *mut SyscfgReg;
 // Skipped peripheral: ADC
 
 pub const USART1: *mut UsartReg = 0x40013800 as *mut UsartReg;
@@ -725,7 +2779,8 @@ pub const USART1: *mut UsartReg = 0x40013800 as *mut UsartReg;
 pub const USART2: *mut UsartReg = 0x40004400 as *mut UsartReg;
 // Skipped peripheral: USART3
 // Skipped peripheral: USART4
-// Skipped peripheral: RTC
+
+pub const RTC: *mut RtcReg = 0x40002800 as *mut RtcReg;
 // Skipped peripheral: TIM15
 // Skipped peripheral: TIM16
 // Skipped peripheral: TIM17
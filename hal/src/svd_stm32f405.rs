@@ -1,14 +1,18 @@
-// DO NOT EDIT. This was generated by svd2rusty
+// Register definitions in the format svd2rusty emits. The vendor SVD files
+// are not in the repository, so these are kept up to date by hand.
 //! This module provides definitions for various hardware registers and their fields.
 
 #![allow(unused)]
 #![allow(non_snake_case)]
 #![allow(non_upper_case_globals)]
+#![allow(non_camel_case_types)]
 
 pub mod DBG {
     pub mod dbgmcu_idcode {
         pub const DEV_ID: u8 = 0;
         pub const REV_ID: u8 = 16;
+        crate::cpu::field!(DEV_ID, 12, ReadOnly);
+        crate::cpu::field!(REV_ID, 16, ReadOnly);
     }
     pub mod dbgmcu_cr {
         pub const DBG_SLEEP: u8 = 0;
@@ -21,6 +25,16 @@ pub mod DBG {
         pub const DBG_TIM5_STOP: u8 = 18;
         pub const DBG_TIM6_STOP: u8 = 19;
         pub const DBG_TIM7_STOP: u8 = 20;
+        crate::cpu::field!(DBG_SLEEP, 1, ReadWrite);
+        crate::cpu::field!(DBG_STOP, 1, ReadWrite);
+        crate::cpu::field!(DBG_STANDBY, 1, ReadWrite);
+        crate::cpu::field!(TRACE_IOEN, 1, ReadWrite);
+        crate::cpu::field!(TRACE_MODE, 2, ReadWrite);
+        crate::cpu::field!(DBG_I2C2_SMBUS_TIMEOUT, 1, ReadWrite);
+        crate::cpu::field!(DBG_TIM8_STOP, 1, ReadWrite);
+        crate::cpu::field!(DBG_TIM5_STOP, 1, ReadWrite);
+        crate::cpu::field!(DBG_TIM6_STOP, 1, ReadWrite);
+        crate::cpu::field!(DBG_TIM7_STOP, 1, ReadWrite);
     }
     pub mod dbgmcu_apb1_fz {
         pub const DBG_TIM2_STOP: u8 = 0;
@@ -39,6 +53,22 @@ pub mod DBG {
         pub const DBG_J2C3SMBUS_TIMEOUT: u8 = 23;
         pub const DBG_CAN1_STOP: u8 = 25;
         pub const DBG_CAN2_STOP: u8 = 26;
+        crate::cpu::field!(DBG_TIM2_STOP, 1, ReadWrite);
+        crate::cpu::field!(DBG_TIM3_STOP, 1, ReadWrite);
+        crate::cpu::field!(DBG_TIM4_STOP, 1, ReadWrite);
+        crate::cpu::field!(DBG_TIM5_STOP, 1, ReadWrite);
+        crate::cpu::field!(DBG_TIM6_STOP, 1, ReadWrite);
+        crate::cpu::field!(DBG_TIM7_STOP, 1, ReadWrite);
+        crate::cpu::field!(DBG_TIM12_STOP, 1, ReadWrite);
+        crate::cpu::field!(DBG_TIM13_STOP, 1, ReadWrite);
+        crate::cpu::field!(DBG_TIM14_STOP, 1, ReadWrite);
+        crate::cpu::field!(DBG_WWDG_STOP, 1, ReadWrite);
+        crate::cpu::field!(DBG_IWDEG_STOP, 1, ReadWrite);
+        crate::cpu::field!(DBG_J2C1_SMBUS_TIMEOUT, 1, ReadWrite);
+        crate::cpu::field!(DBG_J2C2_SMBUS_TIMEOUT, 1, ReadWrite);
+        crate::cpu::field!(DBG_J2C3SMBUS_TIMEOUT, 1, ReadWrite);
+        crate::cpu::field!(DBG_CAN1_STOP, 1, ReadWrite);
+        crate::cpu::field!(DBG_CAN2_STOP, 1, ReadWrite);
     }
     pub mod dbgmcu_apb2_fz {
         pub const DBG_TIM1_STOP: u8 = 0;
@@ -46,6 +76,11 @@ pub mod DBG {
         pub const DBG_TIM9_STOP: u8 = 16;
         pub const DBG_TIM10_STOP: u8 = 17;
         pub const DBG_TIM11_STOP: u8 = 18;
+        crate::cpu::field!(DBG_TIM1_STOP, 1, ReadWrite);
+        crate::cpu::field!(DBG_TIM8_STOP, 1, ReadWrite);
+        crate::cpu::field!(DBG_TIM9_STOP, 1, ReadWrite);
+        crate::cpu::field!(DBG_TIM10_STOP, 1, ReadWrite);
+        crate::cpu::field!(DBG_TIM11_STOP, 1, ReadWrite);
     }
 }
 
@@ -71,6 +106,26 @@ pub mod DMA {
         pub const TEIF0: u8 = 3;
         pub const DMEIF0: u8 = 2;
         pub const FEIF0: u8 = 0;
+        crate::cpu::field!(TCIF3, 1, ReadOnly);
+        crate::cpu::field!(HTIF3, 1, ReadOnly);
+        crate::cpu::field!(TEIF3, 1, ReadOnly);
+        crate::cpu::field!(DMEIF3, 1, ReadOnly);
+        crate::cpu::field!(FEIF3, 1, ReadOnly);
+        crate::cpu::field!(TCIF2, 1, ReadOnly);
+        crate::cpu::field!(HTIF2, 1, ReadOnly);
+        crate::cpu::field!(TEIF2, 1, ReadOnly);
+        crate::cpu::field!(DMEIF2, 1, ReadOnly);
+        crate::cpu::field!(FEIF2, 1, ReadOnly);
+        crate::cpu::field!(TCIF1, 1, ReadOnly);
+        crate::cpu::field!(HTIF1, 1, ReadOnly);
+        crate::cpu::field!(TEIF1, 1, ReadOnly);
+        crate::cpu::field!(DMEIF1, 1, ReadOnly);
+        crate::cpu::field!(FEIF1, 1, ReadOnly);
+        crate::cpu::field!(TCIF0, 1, ReadOnly);
+        crate::cpu::field!(HTIF0, 1, ReadOnly);
+        crate::cpu::field!(TEIF0, 1, ReadOnly);
+        crate::cpu::field!(DMEIF0, 1, ReadOnly);
+        crate::cpu::field!(FEIF0, 1, ReadOnly);
     }
     pub mod hisr {
         pub const TCIF7: u8 = 27;
@@ -93,6 +148,26 @@ pub mod DMA {
         pub const TEIF4: u8 = 3;
         pub const DMEIF4: u8 = 2;
         pub const FEIF4: u8 = 0;
+        crate::cpu::field!(TCIF7, 1, ReadOnly);
+        crate::cpu::field!(HTIF7, 1, ReadOnly);
+        crate::cpu::field!(TEIF7, 1, ReadOnly);
+        crate::cpu::field!(DMEIF7, 1, ReadOnly);
+        crate::cpu::field!(FEIF7, 1, ReadOnly);
+        crate::cpu::field!(TCIF6, 1, ReadOnly);
+        crate::cpu::field!(HTIF6, 1, ReadOnly);
+        crate::cpu::field!(TEIF6, 1, ReadOnly);
+        crate::cpu::field!(DMEIF6, 1, ReadOnly);
+        crate::cpu::field!(FEIF6, 1, ReadOnly);
+        crate::cpu::field!(TCIF5, 1, ReadOnly);
+        crate::cpu::field!(HTIF5, 1, ReadOnly);
+        crate::cpu::field!(TEIF5, 1, ReadOnly);
+        crate::cpu::field!(DMEIF5, 1, ReadOnly);
+        crate::cpu::field!(FEIF5, 1, ReadOnly);
+        crate::cpu::field!(TCIF4, 1, ReadOnly);
+        crate::cpu::field!(HTIF4, 1, ReadOnly);
+        crate::cpu::field!(TEIF4, 1, ReadOnly);
+        crate::cpu::field!(DMEIF4, 1, ReadOnly);
+        crate::cpu::field!(FEIF4, 1, ReadOnly);
     }
     pub mod lifcr {
         pub const CTCIF3: u8 = 27;
@@ -115,6 +190,26 @@ pub mod DMA {
         pub const CTEIF0: u8 = 3;
         pub const CDMEIF0: u8 = 2;
         pub const CFEIF0: u8 = 0;
+        crate::cpu::field!(CTCIF3, 1, WriteOneToClear);
+        crate::cpu::field!(CHTIF3, 1, WriteOneToClear);
+        crate::cpu::field!(CTEIF3, 1, WriteOneToClear);
+        crate::cpu::field!(CDMEIF3, 1, WriteOneToClear);
+        crate::cpu::field!(CFEIF3, 1, WriteOneToClear);
+        crate::cpu::field!(CTCIF2, 1, WriteOneToClear);
+        crate::cpu::field!(CHTIF2, 1, WriteOneToClear);
+        crate::cpu::field!(CTEIF2, 1, WriteOneToClear);
+        crate::cpu::field!(CDMEIF2, 1, WriteOneToClear);
+        crate::cpu::field!(CFEIF2, 1, WriteOneToClear);
+        crate::cpu::field!(CTCIF1, 1, WriteOneToClear);
+        crate::cpu::field!(CHTIF1, 1, WriteOneToClear);
+        crate::cpu::field!(CTEIF1, 1, WriteOneToClear);
+        crate::cpu::field!(CDMEIF1, 1, WriteOneToClear);
+        crate::cpu::field!(CFEIF1, 1, WriteOneToClear);
+        crate::cpu::field!(CTCIF0, 1, WriteOneToClear);
+        crate::cpu::field!(CHTIF0, 1, WriteOneToClear);
+        crate::cpu::field!(CTEIF0, 1, WriteOneToClear);
+        crate::cpu::field!(CDMEIF0, 1, WriteOneToClear);
+        crate::cpu::field!(CFEIF0, 1, WriteOneToClear);
     }
     pub mod hifcr {
         pub const CTCIF7: u8 = 27;
@@ -137,6 +232,26 @@ pub mod DMA {
         pub const CTEIF4: u8 = 3;
         pub const CDMEIF4: u8 = 2;
         pub const CFEIF4: u8 = 0;
+        crate::cpu::field!(CTCIF7, 1, WriteOneToClear);
+        crate::cpu::field!(CHTIF7, 1, WriteOneToClear);
+        crate::cpu::field!(CTEIF7, 1, WriteOneToClear);
+        crate::cpu::field!(CDMEIF7, 1, WriteOneToClear);
+        crate::cpu::field!(CFEIF7, 1, WriteOneToClear);
+        crate::cpu::field!(CTCIF6, 1, WriteOneToClear);
+        crate::cpu::field!(CHTIF6, 1, WriteOneToClear);
+        crate::cpu::field!(CTEIF6, 1, WriteOneToClear);
+        crate::cpu::field!(CDMEIF6, 1, WriteOneToClear);
+        crate::cpu::field!(CFEIF6, 1, WriteOneToClear);
+        crate::cpu::field!(CTCIF5, 1, WriteOneToClear);
+        crate::cpu::field!(CHTIF5, 1, WriteOneToClear);
+        crate::cpu::field!(CTEIF5, 1, WriteOneToClear);
+        crate::cpu::field!(CDMEIF5, 1, WriteOneToClear);
+        crate::cpu::field!(CFEIF5, 1, WriteOneToClear);
+        crate::cpu::field!(CTCIF4, 1, WriteOneToClear);
+        crate::cpu::field!(CHTIF4, 1, WriteOneToClear);
+        crate::cpu::field!(CTEIF4, 1, WriteOneToClear);
+        crate::cpu::field!(CDMEIF4, 1, WriteOneToClear);
+        crate::cpu::field!(CFEIF4, 1, WriteOneToClear);
     }
     pub mod s0cr {
         pub const CHSEL: u8 = 25;
@@ -158,24 +273,51 @@ pub mod DMA {
         pub const TEIE: u8 = 2;
         pub const DMEIE: u8 = 1;
         pub const EN: u8 = 0;
+        crate::cpu::field!(CHSEL, 3, ReadWrite);
+        crate::cpu::field!(MBURST, 2, ReadWrite);
+        crate::cpu::field!(PBURST, 2, ReadWrite);
+        crate::cpu::field!(CT, 1, ReadWrite);
+        crate::cpu::field!(DBM, 1, ReadWrite);
+        crate::cpu::field!(PL, 2, ReadWrite);
+        crate::cpu::field!(PINCOS, 1, ReadWrite);
+        crate::cpu::field!(MSIZE, 2, ReadWrite);
+        crate::cpu::field!(PSIZE, 2, ReadWrite);
+        crate::cpu::field!(MINC, 1, ReadWrite);
+        crate::cpu::field!(PINC, 1, ReadWrite);
+        crate::cpu::field!(CIRC, 1, ReadWrite);
+        crate::cpu::field!(DIR, 2, ReadWrite);
+        crate::cpu::field!(PFCTRL, 1, ReadWrite);
+        crate::cpu::field!(TCIE, 1, ReadWrite);
+        crate::cpu::field!(HTIE, 1, ReadWrite);
+        crate::cpu::field!(TEIE, 1, ReadWrite);
+        crate::cpu::field!(DMEIE, 1, ReadWrite);
+        crate::cpu::field!(EN, 1, ReadWrite);
     }
     pub mod s0ndtr {
         pub const NDT: u8 = 0;
+        crate::cpu::field!(NDT, 16, ReadWrite);
     }
     pub mod s0par {
         pub const PA: u8 = 0;
+        crate::cpu::field!(PA, 32, ReadWrite);
     }
     pub mod s0m0ar {
         pub const M0A: u8 = 0;
+        crate::cpu::field!(M0A, 32, ReadWrite);
     }
     pub mod s0m1ar {
         pub const M1A: u8 = 0;
+        crate::cpu::field!(M1A, 32, ReadWrite);
     }
     pub mod s0fcr {
         pub const FEIE: u8 = 7;
         pub const FS: u8 = 3;
         pub const DMDIS: u8 = 2;
         pub const FTH: u8 = 0;
+        crate::cpu::field!(FEIE, 1, ReadWrite);
+        crate::cpu::field!(FS, 3, ReadOnly);
+        crate::cpu::field!(DMDIS, 1, ReadWrite);
+        crate::cpu::field!(FTH, 2, ReadWrite);
     }
     pub mod s1cr {
         pub const CHSEL: u8 = 25;
@@ -198,24 +340,52 @@ pub mod DMA {
         pub const TEIE: u8 = 2;
         pub const DMEIE: u8 = 1;
         pub const EN: u8 = 0;
+        crate::cpu::field!(CHSEL, 3, ReadWrite);
+        crate::cpu::field!(MBURST, 2, ReadWrite);
+        crate::cpu::field!(PBURST, 2, ReadWrite);
+        crate::cpu::field!(ACK, 1, ReadWrite);
+        crate::cpu::field!(CT, 1, ReadWrite);
+        crate::cpu::field!(DBM, 1, ReadWrite);
+        crate::cpu::field!(PL, 2, ReadWrite);
+        crate::cpu::field!(PINCOS, 1, ReadWrite);
+        crate::cpu::field!(MSIZE, 2, ReadWrite);
+        crate::cpu::field!(PSIZE, 2, ReadWrite);
+        crate::cpu::field!(MINC, 1, ReadWrite);
+        crate::cpu::field!(PINC, 1, ReadWrite);
+        crate::cpu::field!(CIRC, 1, ReadWrite);
+        crate::cpu::field!(DIR, 2, ReadWrite);
+        crate::cpu::field!(PFCTRL, 1, ReadWrite);
+        crate::cpu::field!(TCIE, 1, ReadWrite);
+        crate::cpu::field!(HTIE, 1, ReadWrite);
+        crate::cpu::field!(TEIE, 1, ReadWrite);
+        crate::cpu::field!(DMEIE, 1, ReadWrite);
+        crate::cpu::field!(EN, 1, ReadWrite);
     }
     pub mod s1ndtr {
         pub const NDT: u8 = 0;
+        crate::cpu::field!(NDT, 16, ReadWrite);
     }
     pub mod s1par {
         pub const PA: u8 = 0;
+        crate::cpu::field!(PA, 32, ReadWrite);
     }
     pub mod s1m0ar {
         pub const M0A: u8 = 0;
+        crate::cpu::field!(M0A, 32, ReadWrite);
     }
     pub mod s1m1ar {
         pub const M1A: u8 = 0;
+        crate::cpu::field!(M1A, 32, ReadWrite);
     }
     pub mod s1fcr {
         pub const FEIE: u8 = 7;
         pub const FS: u8 = 3;
         pub const DMDIS: u8 = 2;
         pub const FTH: u8 = 0;
+        crate::cpu::field!(FEIE, 1, ReadWrite);
+        crate::cpu::field!(FS, 3, ReadOnly);
+        crate::cpu::field!(DMDIS, 1, ReadWrite);
+        crate::cpu::field!(FTH, 2, ReadWrite);
     }
     pub mod s2cr {
         pub const CHSEL: u8 = 25;
@@ -238,24 +408,52 @@ pub mod DMA {
         pub const TEIE: u8 = 2;
         pub const DMEIE: u8 = 1;
         pub const EN: u8 = 0;
+        crate::cpu::field!(CHSEL, 3, ReadWrite);
+        crate::cpu::field!(MBURST, 2, ReadWrite);
+        crate::cpu::field!(PBURST, 2, ReadWrite);
+        crate::cpu::field!(ACK, 1, ReadWrite);
+        crate::cpu::field!(CT, 1, ReadWrite);
+        crate::cpu::field!(DBM, 1, ReadWrite);
+        crate::cpu::field!(PL, 2, ReadWrite);
+        crate::cpu::field!(PINCOS, 1, ReadWrite);
+        crate::cpu::field!(MSIZE, 2, ReadWrite);
+        crate::cpu::field!(PSIZE, 2, ReadWrite);
+        crate::cpu::field!(MINC, 1, ReadWrite);
+        crate::cpu::field!(PINC, 1, ReadWrite);
+        crate::cpu::field!(CIRC, 1, ReadWrite);
+        crate::cpu::field!(DIR, 2, ReadWrite);
+        crate::cpu::field!(PFCTRL, 1, ReadWrite);
+        crate::cpu::field!(TCIE, 1, ReadWrite);
+        crate::cpu::field!(HTIE, 1, ReadWrite);
+        crate::cpu::field!(TEIE, 1, ReadWrite);
+        crate::cpu::field!(DMEIE, 1, ReadWrite);
+        crate::cpu::field!(EN, 1, ReadWrite);
     }
     pub mod s2ndtr {
         pub const NDT: u8 = 0;
+        crate::cpu::field!(NDT, 16, ReadWrite);
     }
     pub mod s2par {
         pub const PA: u8 = 0;
+        crate::cpu::field!(PA, 32, ReadWrite);
     }
     pub mod s2m0ar {
         pub const M0A: u8 = 0;
+        crate::cpu::field!(M0A, 32, ReadWrite);
     }
     pub mod s2m1ar {
         pub const M1A: u8 = 0;
+        crate::cpu::field!(M1A, 32, ReadWrite);
     }
     pub mod s2fcr {
         pub const FEIE: u8 = 7;
         pub const FS: u8 = 3;
         pub const DMDIS: u8 = 2;
         pub const FTH: u8 = 0;
+        crate::cpu::field!(FEIE, 1, ReadWrite);
+        crate::cpu::field!(FS, 3, ReadOnly);
+        crate::cpu::field!(DMDIS, 1, ReadWrite);
+        crate::cpu::field!(FTH, 2, ReadWrite);
     }
     pub mod s3cr {
         pub const CHSEL: u8 = 25;
@@ -278,24 +476,52 @@ pub mod DMA {
         pub const TEIE: u8 = 2;
         pub const DMEIE: u8 = 1;
         pub const EN: u8 = 0;
+        crate::cpu::field!(CHSEL, 3, ReadWrite);
+        crate::cpu::field!(MBURST, 2, ReadWrite);
+        crate::cpu::field!(PBURST, 2, ReadWrite);
+        crate::cpu::field!(ACK, 1, ReadWrite);
+        crate::cpu::field!(CT, 1, ReadWrite);
+        crate::cpu::field!(DBM, 1, ReadWrite);
+        crate::cpu::field!(PL, 2, ReadWrite);
+        crate::cpu::field!(PINCOS, 1, ReadWrite);
+        crate::cpu::field!(MSIZE, 2, ReadWrite);
+        crate::cpu::field!(PSIZE, 2, ReadWrite);
+        crate::cpu::field!(MINC, 1, ReadWrite);
+        crate::cpu::field!(PINC, 1, ReadWrite);
+        crate::cpu::field!(CIRC, 1, ReadWrite);
+        crate::cpu::field!(DIR, 2, ReadWrite);
+        crate::cpu::field!(PFCTRL, 1, ReadWrite);
+        crate::cpu::field!(TCIE, 1, ReadWrite);
+        crate::cpu::field!(HTIE, 1, ReadWrite);
+        crate::cpu::field!(TEIE, 1, ReadWrite);
+        crate::cpu::field!(DMEIE, 1, ReadWrite);
+        crate::cpu::field!(EN, 1, ReadWrite);
     }
     pub mod s3ndtr {
         pub const NDT: u8 = 0;
+        crate::cpu::field!(NDT, 16, ReadWrite);
     }
     pub mod s3par {
         pub const PA: u8 = 0;
+        crate::cpu::field!(PA, 32, ReadWrite);
     }
     pub mod s3m0ar {
         pub const M0A: u8 = 0;
+        crate::cpu::field!(M0A, 32, ReadWrite);
     }
     pub mod s3m1ar {
         pub const M1A: u8 = 0;
+        crate::cpu::field!(M1A, 32, ReadWrite);
     }
     pub mod s3fcr {
         pub const FEIE: u8 = 7;
         pub const FS: u8 = 3;
         pub const DMDIS: u8 = 2;
         pub const FTH: u8 = 0;
+        crate::cpu::field!(FEIE, 1, ReadWrite);
+        crate::cpu::field!(FS, 3, ReadOnly);
+        crate::cpu::field!(DMDIS, 1, ReadWrite);
+        crate::cpu::field!(FTH, 2, ReadWrite);
     }
     pub mod s4cr {
         pub const CHSEL: u8 = 25;
@@ -318,24 +544,52 @@ pub mod DMA {
         pub const TEIE: u8 = 2;
         pub const DMEIE: u8 = 1;
         pub const EN: u8 = 0;
+        crate::cpu::field!(CHSEL, 3, ReadWrite);
+        crate::cpu::field!(MBURST, 2, ReadWrite);
+        crate::cpu::field!(PBURST, 2, ReadWrite);
+        crate::cpu::field!(ACK, 1, ReadWrite);
+        crate::cpu::field!(CT, 1, ReadWrite);
+        crate::cpu::field!(DBM, 1, ReadWrite);
+        crate::cpu::field!(PL, 2, ReadWrite);
+        crate::cpu::field!(PINCOS, 1, ReadWrite);
+        crate::cpu::field!(MSIZE, 2, ReadWrite);
+        crate::cpu::field!(PSIZE, 2, ReadWrite);
+        crate::cpu::field!(MINC, 1, ReadWrite);
+        crate::cpu::field!(PINC, 1, ReadWrite);
+        crate::cpu::field!(CIRC, 1, ReadWrite);
+        crate::cpu::field!(DIR, 2, ReadWrite);
+        crate::cpu::field!(PFCTRL, 1, ReadWrite);
+        crate::cpu::field!(TCIE, 1, ReadWrite);
+        crate::cpu::field!(HTIE, 1, ReadWrite);
+        crate::cpu::field!(TEIE, 1, ReadWrite);
+        crate::cpu::field!(DMEIE, 1, ReadWrite);
+        crate::cpu::field!(EN, 1, ReadWrite);
     }
     pub mod s4ndtr {
         pub const NDT: u8 = 0;
+        crate::cpu::field!(NDT, 16, ReadWrite);
     }
     pub mod s4par {
         pub const PA: u8 = 0;
+        crate::cpu::field!(PA, 32, ReadWrite);
     }
     pub mod s4m0ar {
         pub const M0A: u8 = 0;
+        crate::cpu::field!(M0A, 32, ReadWrite);
     }
     pub mod s4m1ar {
         pub const M1A: u8 = 0;
+        crate::cpu::field!(M1A, 32, ReadWrite);
     }
     pub mod s4fcr {
         pub const FEIE: u8 = 7;
         pub const FS: u8 = 3;
         pub const DMDIS: u8 = 2;
         pub const FTH: u8 = 0;
+        crate::cpu::field!(FEIE, 1, ReadWrite);
+        crate::cpu::field!(FS, 3, ReadOnly);
+        crate::cpu::field!(DMDIS, 1, ReadWrite);
+        crate::cpu::field!(FTH, 2, ReadWrite);
     }
     pub mod s5cr {
         pub const CHSEL: u8 = 25;
@@ -358,24 +612,52 @@ pub mod DMA {
         pub const TEIE: u8 = 2;
         pub const DMEIE: u8 = 1;
         pub const EN: u8 = 0;
+        crate::cpu::field!(CHSEL, 3, ReadWrite);
+        crate::cpu::field!(MBURST, 2, ReadWrite);
+        crate::cpu::field!(PBURST, 2, ReadWrite);
+        crate::cpu::field!(ACK, 1, ReadWrite);
+        crate::cpu::field!(CT, 1, ReadWrite);
+        crate::cpu::field!(DBM, 1, ReadWrite);
+        crate::cpu::field!(PL, 2, ReadWrite);
+        crate::cpu::field!(PINCOS, 1, ReadWrite);
+        crate::cpu::field!(MSIZE, 2, ReadWrite);
+        crate::cpu::field!(PSIZE, 2, ReadWrite);
+        crate::cpu::field!(MINC, 1, ReadWrite);
+        crate::cpu::field!(PINC, 1, ReadWrite);
+        crate::cpu::field!(CIRC, 1, ReadWrite);
+        crate::cpu::field!(DIR, 2, ReadWrite);
+        crate::cpu::field!(PFCTRL, 1, ReadWrite);
+        crate::cpu::field!(TCIE, 1, ReadWrite);
+        crate::cpu::field!(HTIE, 1, ReadWrite);
+        crate::cpu::field!(TEIE, 1, ReadWrite);
+        crate::cpu::field!(DMEIE, 1, ReadWrite);
+        crate::cpu::field!(EN, 1, ReadWrite);
     }
     pub mod s5ndtr {
         pub const NDT: u8 = 0;
+        crate::cpu::field!(NDT, 16, ReadWrite);
     }
     pub mod s5par {
         pub const PA: u8 = 0;
+        crate::cpu::field!(PA, 32, ReadWrite);
     }
     pub mod s5m0ar {
         pub const M0A: u8 = 0;
+        crate::cpu::field!(M0A, 32, ReadWrite);
     }
     pub mod s5m1ar {
         pub const M1A: u8 = 0;
+        crate::cpu::field!(M1A, 32, ReadWrite);
     }
     pub mod s5fcr {
         pub const FEIE: u8 = 7;
         pub const FS: u8 = 3;
         pub const DMDIS: u8 = 2;
         pub const FTH: u8 = 0;
+        crate::cpu::field!(FEIE, 1, ReadWrite);
+        crate::cpu::field!(FS, 3, ReadOnly);
+        crate::cpu::field!(DMDIS, 1, ReadWrite);
+        crate::cpu::field!(FTH, 2, ReadWrite);
     }
     pub mod s6cr {
         pub const CHSEL: u8 = 25;
@@ -398,24 +680,52 @@ pub mod DMA {
         pub const TEIE: u8 = 2;
         pub const DMEIE: u8 = 1;
         pub const EN: u8 = 0;
+        crate::cpu::field!(CHSEL, 3, ReadWrite);
+        crate::cpu::field!(MBURST, 2, ReadWrite);
+        crate::cpu::field!(PBURST, 2, ReadWrite);
+        crate::cpu::field!(ACK, 1, ReadWrite);
+        crate::cpu::field!(CT, 1, ReadWrite);
+        crate::cpu::field!(DBM, 1, ReadWrite);
+        crate::cpu::field!(PL, 2, ReadWrite);
+        crate::cpu::field!(PINCOS, 1, ReadWrite);
+        crate::cpu::field!(MSIZE, 2, ReadWrite);
+        crate::cpu::field!(PSIZE, 2, ReadWrite);
+        crate::cpu::field!(MINC, 1, ReadWrite);
+        crate::cpu::field!(PINC, 1, ReadWrite);
+        crate::cpu::field!(CIRC, 1, ReadWrite);
+        crate::cpu::field!(DIR, 2, ReadWrite);
+        crate::cpu::field!(PFCTRL, 1, ReadWrite);
+        crate::cpu::field!(TCIE, 1, ReadWrite);
+        crate::cpu::field!(HTIE, 1, ReadWrite);
+        crate::cpu::field!(TEIE, 1, ReadWrite);
+        crate::cpu::field!(DMEIE, 1, ReadWrite);
+        crate::cpu::field!(EN, 1, ReadWrite);
     }
     pub mod s6ndtr {
         pub const NDT: u8 = 0;
+        crate::cpu::field!(NDT, 16, ReadWrite);
     }
     pub mod s6par {
         pub const PA: u8 = 0;
+        crate::cpu::field!(PA, 32, ReadWrite);
     }
     pub mod s6m0ar {
         pub const M0A: u8 = 0;
+        crate::cpu::field!(M0A, 32, ReadWrite);
     }
     pub mod s6m1ar {
         pub const M1A: u8 = 0;
+        crate::cpu::field!(M1A, 32, ReadWrite);
     }
     pub mod s6fcr {
         pub const FEIE: u8 = 7;
         pub const FS: u8 = 3;
         pub const DMDIS: u8 = 2;
         pub const FTH: u8 = 0;
+        crate::cpu::field!(FEIE, 1, ReadWrite);
+        crate::cpu::field!(FS, 3, ReadOnly);
+        crate::cpu::field!(DMDIS, 1, ReadWrite);
+        crate::cpu::field!(FTH, 2, ReadWrite);
     }
     pub mod s7cr {
         pub const CHSEL: u8 = 25;
@@ -438,24 +748,52 @@ pub mod DMA {
         pub const TEIE: u8 = 2;
         pub const DMEIE: u8 = 1;
         pub const EN: u8 = 0;
+        crate::cpu::field!(CHSEL, 3, ReadWrite);
+        crate::cpu::field!(MBURST, 2, ReadWrite);
+        crate::cpu::field!(PBURST, 2, ReadWrite);
+        crate::cpu::field!(ACK, 1, ReadWrite);
+        crate::cpu::field!(CT, 1, ReadWrite);
+        crate::cpu::field!(DBM, 1, ReadWrite);
+        crate::cpu::field!(PL, 2, ReadWrite);
+        crate::cpu::field!(PINCOS, 1, ReadWrite);
+        crate::cpu::field!(MSIZE, 2, ReadWrite);
+        crate::cpu::field!(PSIZE, 2, ReadWrite);
+        crate::cpu::field!(MINC, 1, ReadWrite);
+        crate::cpu::field!(PINC, 1, ReadWrite);
+        crate::cpu::field!(CIRC, 1, ReadWrite);
+        crate::cpu::field!(DIR, 2, ReadWrite);
+        crate::cpu::field!(PFCTRL, 1, ReadWrite);
+        crate::cpu::field!(TCIE, 1, ReadWrite);
+        crate::cpu::field!(HTIE, 1, ReadWrite);
+        crate::cpu::field!(TEIE, 1, ReadWrite);
+        crate::cpu::field!(DMEIE, 1, ReadWrite);
+        crate::cpu::field!(EN, 1, ReadWrite);
     }
     pub mod s7ndtr {
         pub const NDT: u8 = 0;
+        crate::cpu::field!(NDT, 16, ReadWrite);
     }
     pub mod s7par {
         pub const PA: u8 = 0;
+        crate::cpu::field!(PA, 32, ReadWrite);
     }
     pub mod s7m0ar {
         pub const M0A: u8 = 0;
+        crate::cpu::field!(M0A, 32, ReadWrite);
     }
     pub mod s7m1ar {
         pub const M1A: u8 = 0;
+        crate::cpu::field!(M1A, 32, ReadWrite);
     }
     pub mod s7fcr {
         pub const FEIE: u8 = 7;
         pub const FS: u8 = 3;
         pub const DMDIS: u8 = 2;
         pub const FTH: u8 = 0;
+        crate::cpu::field!(FEIE, 1, ReadWrite);
+        crate::cpu::field!(FS, 3, ReadOnly);
+        crate::cpu::field!(DMDIS, 1, ReadWrite);
+        crate::cpu::field!(FTH, 2, ReadWrite);
     }
 }
 
@@ -473,6 +811,18 @@ pub mod RCC {
         pub const HSITRIM: u8 = 3;
         pub const HSIRDY: u8 = 1;
         pub const HSION: u8 = 0;
+        crate::cpu::field!(PLLI2SRDY, 1, ReadOnly);
+        crate::cpu::field!(PLLI2SON, 1, ReadWrite);
+        crate::cpu::field!(PLLRDY, 1, ReadOnly);
+        crate::cpu::field!(PLLON, 1, ReadWrite);
+        crate::cpu::field!(CSSON, 1, ReadWrite);
+        crate::cpu::field!(HSEBYP, 1, ReadWrite);
+        crate::cpu::field!(HSERDY, 1, ReadOnly);
+        crate::cpu::field!(HSEON, 1, ReadWrite);
+        crate::cpu::field!(HSICAL, 8, ReadOnly);
+        crate::cpu::field!(HSITRIM, 5, ReadWrite);
+        crate::cpu::field!(HSIRDY, 1, ReadOnly);
+        crate::cpu::field!(HSION, 1, ReadWrite);
     }
     pub mod pllcfgr {
         pub const PLLQ3: u8 = 27;
@@ -497,6 +847,11 @@ pub mod RCC {
         pub const PLLM2: u8 = 2;
         pub const PLLM1: u8 = 1;
         pub const PLLM0: u8 = 0;
+        crate::cpu::field!(PLLQ = PLLQ0, 4, ReadWrite);
+        crate::cpu::field!(PLLSRC, 1, ReadWrite);
+        crate::cpu::field!(PLLP = PLLP0, 2, ReadWrite);
+        crate::cpu::field!(PLLN = PLLN0, 9, ReadWrite);
+        crate::cpu::field!(PLLM = PLLM0, 6, ReadWrite);
     }
     pub mod cfgr {
         pub const MCO2: u8 = 30;
@@ -512,6 +867,17 @@ pub mod RCC {
         pub const SWS0: u8 = 2;
         pub const SW1: u8 = 1;
         pub const SW0: u8 = 0;
+        crate::cpu::field!(MCO2, 2, ReadWrite);
+        crate::cpu::field!(MCO2PRE, 3, ReadWrite);
+        crate::cpu::field!(MCO1PRE, 3, ReadWrite);
+        crate::cpu::field!(I2SSRC, 1, ReadWrite);
+        crate::cpu::field!(MCO1, 2, ReadWrite);
+        crate::cpu::field!(RTCPRE, 5, ReadWrite);
+        crate::cpu::field!(PPRE2, 3, ReadWrite);
+        crate::cpu::field!(PPRE1, 3, ReadWrite);
+        crate::cpu::field!(HPRE, 4, ReadWrite);
+        crate::cpu::field!(SWS = SWS0, 2, ReadOnly);
+        crate::cpu::field!(SW = SW0, 2, ReadWrite);
     }
     pub mod cir {
         pub const CSSC: u8 = 23;
@@ -534,6 +900,26 @@ pub mod RCC {
         pub const HSIRDYF: u8 = 2;
         pub const LSERDYF: u8 = 1;
         pub const LSIRDYF: u8 = 0;
+        crate::cpu::field!(CSSC, 1, WriteOnly);
+        crate::cpu::field!(PLLI2SRDYC, 1, WriteOnly);
+        crate::cpu::field!(PLLRDYC, 1, WriteOnly);
+        crate::cpu::field!(HSERDYC, 1, WriteOnly);
+        crate::cpu::field!(HSIRDYC, 1, WriteOnly);
+        crate::cpu::field!(LSERDYC, 1, WriteOnly);
+        crate::cpu::field!(LSIRDYC, 1, WriteOnly);
+        crate::cpu::field!(PLLI2SRDYIE, 1, ReadWrite);
+        crate::cpu::field!(PLLRDYIE, 1, ReadWrite);
+        crate::cpu::field!(HSERDYIE, 1, ReadWrite);
+        crate::cpu::field!(HSIRDYIE, 1, ReadWrite);
+        crate::cpu::field!(LSERDYIE, 1, ReadWrite);
+        crate::cpu::field!(LSIRDYIE, 1, ReadWrite);
+        crate::cpu::field!(CSSF, 1, ReadOnly);
+        crate::cpu::field!(PLLI2SRDYF, 1, ReadOnly);
+        crate::cpu::field!(PLLRDYF, 1, ReadOnly);
+        crate::cpu::field!(HSERDYF, 1, ReadOnly);
+        crate::cpu::field!(HSIRDYF, 1, ReadOnly);
+        crate::cpu::field!(LSERDYF, 1, ReadOnly);
+        crate::cpu::field!(LSIRDYF, 1, ReadOnly);
     }
     pub mod ahb1rstr {
         pub const OTGHSRST: u8 = 29;
@@ -550,14 +936,32 @@ pub mod RCC {
         pub const GPIOCRST: u8 = 2;
         pub const GPIOBRST: u8 = 1;
         pub const GPIOARST: u8 = 0;
+        crate::cpu::field!(OTGHSRST, 1, ReadWrite);
+        crate::cpu::field!(ETHMACRST, 1, ReadWrite);
+        crate::cpu::field!(DMA2RST, 1, ReadWrite);
+        crate::cpu::field!(DMA1RST, 1, ReadWrite);
+        crate::cpu::field!(CRCRST, 1, ReadWrite);
+        crate::cpu::field!(GPIOIRST, 1, ReadWrite);
+        crate::cpu::field!(GPIOHRST, 1, ReadWrite);
+        crate::cpu::field!(GPIOGRST, 1, ReadWrite);
+        crate::cpu::field!(GPIOFRST, 1, ReadWrite);
+        crate::cpu::field!(GPIOERST, 1, ReadWrite);
+        crate::cpu::field!(GPIODRST, 1, ReadWrite);
+        crate::cpu::field!(GPIOCRST, 1, ReadWrite);
+        crate::cpu::field!(GPIOBRST, 1, ReadWrite);
+        crate::cpu::field!(GPIOARST, 1, ReadWrite);
     }
     pub mod ahb2rstr {
         pub const OTGFSRST: u8 = 7;
         pub const RNGRST: u8 = 6;
         pub const DCMIRST: u8 = 0;
+        crate::cpu::field!(OTGFSRST, 1, ReadWrite);
+        crate::cpu::field!(RNGRST, 1, ReadWrite);
+        crate::cpu::field!(DCMIRST, 1, ReadWrite);
     }
     pub mod ahb3rstr {
         pub const FSMCRST: u8 = 0;
+        crate::cpu::field!(FSMCRST, 1, ReadWrite);
     }
     pub mod apb1rstr {
         pub const DACRST: u8 = 29;
@@ -583,6 +987,29 @@ pub mod RCC {
         pub const TIM4RST: u8 = 2;
         pub const TIM3RST: u8 = 1;
         pub const TIM2RST: u8 = 0;
+        crate::cpu::field!(DACRST, 1, ReadWrite);
+        crate::cpu::field!(PWRRST, 1, ReadWrite);
+        crate::cpu::field!(CAN2RST, 1, ReadWrite);
+        crate::cpu::field!(CAN1RST, 1, ReadWrite);
+        crate::cpu::field!(I2C3RST, 1, ReadWrite);
+        crate::cpu::field!(I2C2RST, 1, ReadWrite);
+        crate::cpu::field!(I2C1RST, 1, ReadWrite);
+        crate::cpu::field!(UART5RST, 1, ReadWrite);
+        crate::cpu::field!(UART4RST, 1, ReadWrite);
+        crate::cpu::field!(UART3RST, 1, ReadWrite);
+        crate::cpu::field!(UART2RST, 1, ReadWrite);
+        crate::cpu::field!(SPI3RST, 1, ReadWrite);
+        crate::cpu::field!(SPI2RST, 1, ReadWrite);
+        crate::cpu::field!(WWDGRST, 1, ReadWrite);
+        crate::cpu::field!(TIM14RST, 1, ReadWrite);
+        crate::cpu::field!(TIM13RST, 1, ReadWrite);
+        crate::cpu::field!(TIM12RST, 1, ReadWrite);
+        crate::cpu::field!(TIM7RST, 1, ReadWrite);
+        crate::cpu::field!(TIM6RST, 1, ReadWrite);
+        crate::cpu::field!(TIM5RST, 1, ReadWrite);
+        crate::cpu::field!(TIM4RST, 1, ReadWrite);
+        crate::cpu::field!(TIM3RST, 1, ReadWrite);
+        crate::cpu::field!(TIM2RST, 1, ReadWrite);
     }
     pub mod apb2rstr {
         pub const TIM11RST: u8 = 18;
@@ -596,6 +1023,17 @@ pub mod RCC {
         pub const USART1RST: u8 = 4;
         pub const TIM8RST: u8 = 1;
         pub const TIM1RST: u8 = 0;
+        crate::cpu::field!(TIM11RST, 1, ReadWrite);
+        crate::cpu::field!(TIM10RST, 1, ReadWrite);
+        crate::cpu::field!(TIM9RST, 1, ReadWrite);
+        crate::cpu::field!(SYSCFGRST, 1, ReadWrite);
+        crate::cpu::field!(SPI1RST, 1, ReadWrite);
+        crate::cpu::field!(SDIORST, 1, ReadWrite);
+        crate::cpu::field!(ADCRST, 1, ReadWrite);
+        crate::cpu::field!(USART6RST, 1, ReadWrite);
+        crate::cpu::field!(USART1RST, 1, ReadWrite);
+        crate::cpu::field!(TIM8RST, 1, ReadWrite);
+        crate::cpu::field!(TIM1RST, 1, ReadWrite);
     }
     pub mod ahb1enr {
         pub const OTGHSULPIEN: u8 = 30;
@@ -618,14 +1056,38 @@ pub mod RCC {
         pub const GPIOCEN: u8 = 2;
         pub const GPIOBEN: u8 = 1;
         pub const GPIOAEN: u8 = 0;
+        crate::cpu::field!(OTGHSULPIEN, 1, ReadWrite);
+        crate::cpu::field!(OTGHSEN, 1, ReadWrite);
+        crate::cpu::field!(ETHMACPTPEN, 1, ReadWrite);
+        crate::cpu::field!(ETHMACRXEN, 1, ReadWrite);
+        crate::cpu::field!(ETHMACTXEN, 1, ReadWrite);
+        crate::cpu::field!(ETHMACEN, 1, ReadWrite);
+        crate::cpu::field!(DMA2EN, 1, ReadWrite);
+        crate::cpu::field!(DMA1EN, 1, ReadWrite);
+        crate::cpu::field!(CCMDATARAMEN, 1, ReadWrite);
+        crate::cpu::field!(BKPSRAMEN, 1, ReadWrite);
+        crate::cpu::field!(CRCEN, 1, ReadWrite);
+        crate::cpu::field!(GPIOIEN, 1, ReadWrite);
+        crate::cpu::field!(GPIOHEN, 1, ReadWrite);
+        crate::cpu::field!(GPIOGEN, 1, ReadWrite);
+        crate::cpu::field!(GPIOFEN, 1, ReadWrite);
+        crate::cpu::field!(GPIOEEN, 1, ReadWrite);
+        crate::cpu::field!(GPIODEN, 1, ReadWrite);
+        crate::cpu::field!(GPIOCEN, 1, ReadWrite);
+        crate::cpu::field!(GPIOBEN, 1, ReadWrite);
+        crate::cpu::field!(GPIOAEN, 1, ReadWrite);
     }
     pub mod ahb2enr {
         pub const OTGFSEN: u8 = 7;
         pub const RNGEN: u8 = 6;
         pub const DCMIEN: u8 = 0;
+        crate::cpu::field!(OTGFSEN, 1, ReadWrite);
+        crate::cpu::field!(RNGEN, 1, ReadWrite);
+        crate::cpu::field!(DCMIEN, 1, ReadWrite);
     }
     pub mod ahb3enr {
         pub const FSMCEN: u8 = 0;
+        crate::cpu::field!(FSMCEN, 1, ReadWrite);
     }
     pub mod apb1enr {
         pub const DACEN: u8 = 29;
@@ -651,6 +1113,29 @@ pub mod RCC {
         pub const TIM4EN: u8 = 2;
         pub const TIM3EN: u8 = 1;
         pub const TIM2EN: u8 = 0;
+        crate::cpu::field!(DACEN, 1, ReadWrite);
+        crate::cpu::field!(PWREN, 1, ReadWrite);
+        crate::cpu::field!(CAN2EN, 1, ReadWrite);
+        crate::cpu::field!(CAN1EN, 1, ReadWrite);
+        crate::cpu::field!(I2C3EN, 1, ReadWrite);
+        crate::cpu::field!(I2C2EN, 1, ReadWrite);
+        crate::cpu::field!(I2C1EN, 1, ReadWrite);
+        crate::cpu::field!(UART5EN, 1, ReadWrite);
+        crate::cpu::field!(UART4EN, 1, ReadWrite);
+        crate::cpu::field!(USART3EN, 1, ReadWrite);
+        crate::cpu::field!(USART2EN, 1, ReadWrite);
+        crate::cpu::field!(SPI3EN, 1, ReadWrite);
+        crate::cpu::field!(SPI2EN, 1, ReadWrite);
+        crate::cpu::field!(WWDGEN, 1, ReadWrite);
+        crate::cpu::field!(TIM14EN, 1, ReadWrite);
+        crate::cpu::field!(TIM13EN, 1, ReadWrite);
+        crate::cpu::field!(TIM12EN, 1, ReadWrite);
+        crate::cpu::field!(TIM7EN, 1, ReadWrite);
+        crate::cpu::field!(TIM6EN, 1, ReadWrite);
+        crate::cpu::field!(TIM5EN, 1, ReadWrite);
+        crate::cpu::field!(TIM4EN, 1, ReadWrite);
+        crate::cpu::field!(TIM3EN, 1, ReadWrite);
+        crate::cpu::field!(TIM2EN, 1, ReadWrite);
     }
     pub mod apb2enr {
         pub const TIM11EN: u8 = 18;
@@ -666,6 +1151,19 @@ pub mod RCC {
         pub const USART1EN: u8 = 4;
         pub const TIM8EN: u8 = 1;
         pub const TIM1EN: u8 = 0;
+        crate::cpu::field!(TIM11EN, 1, ReadWrite);
+        crate::cpu::field!(TIM10EN, 1, ReadWrite);
+        crate::cpu::field!(TIM9EN, 1, ReadWrite);
+        crate::cpu::field!(SYSCFGEN, 1, ReadWrite);
+        crate::cpu::field!(SPI1EN, 1, ReadWrite);
+        crate::cpu::field!(SDIOEN, 1, ReadWrite);
+        crate::cpu::field!(ADC3EN, 1, ReadWrite);
+        crate::cpu::field!(ADC2EN, 1, ReadWrite);
+        crate::cpu::field!(ADC1EN, 1, ReadWrite);
+        crate::cpu::field!(USART6EN, 1, ReadWrite);
+        crate::cpu::field!(USART1EN, 1, ReadWrite);
+        crate::cpu::field!(TIM8EN, 1, ReadWrite);
+        crate::cpu::field!(TIM1EN, 1, ReadWrite);
     }
     pub mod ahb1lpenr {
         pub const OTGHSULPILPEN: u8 = 30;
@@ -690,14 +1188,40 @@ pub mod RCC {
         pub const GPIOCLPEN: u8 = 2;
         pub const GPIOBLPEN: u8 = 1;
         pub const GPIOALPEN: u8 = 0;
+        crate::cpu::field!(OTGHSULPILPEN, 1, ReadWrite);
+        crate::cpu::field!(OTGHSLPEN, 1, ReadWrite);
+        crate::cpu::field!(ETHMACPTPLPEN, 1, ReadWrite);
+        crate::cpu::field!(ETHMACRXLPEN, 1, ReadWrite);
+        crate::cpu::field!(ETHMACTXLPEN, 1, ReadWrite);
+        crate::cpu::field!(ETHMACLPEN, 1, ReadWrite);
+        crate::cpu::field!(DMA2LPEN, 1, ReadWrite);
+        crate::cpu::field!(DMA1LPEN, 1, ReadWrite);
+        crate::cpu::field!(BKPSRAMLPEN, 1, ReadWrite);
+        crate::cpu::field!(SRAM2LPEN, 1, ReadWrite);
+        crate::cpu::field!(SRAM1LPEN, 1, ReadWrite);
+        crate::cpu::field!(FLITFLPEN, 1, ReadWrite);
+        crate::cpu::field!(CRCLPEN, 1, ReadWrite);
+        crate::cpu::field!(GPIOILPEN, 1, ReadWrite);
+        crate::cpu::field!(GPIOHLPEN, 1, ReadWrite);
+        crate::cpu::field!(GPIOGLPEN, 1, ReadWrite);
+        crate::cpu::field!(GPIOFLPEN, 1, ReadWrite);
+        crate::cpu::field!(GPIOELPEN, 1, ReadWrite);
+        crate::cpu::field!(GPIODLPEN, 1, ReadWrite);
+        crate::cpu::field!(GPIOCLPEN, 1, ReadWrite);
+        crate::cpu::field!(GPIOBLPEN, 1, ReadWrite);
+        crate::cpu::field!(GPIOALPEN, 1, ReadWrite);
     }
     pub mod ahb2lpenr {
         pub const OTGFSLPEN: u8 = 7;
         pub const RNGLPEN: u8 = 6;
         pub const DCMILPEN: u8 = 0;
+        crate::cpu::field!(OTGFSLPEN, 1, ReadWrite);
+        crate::cpu::field!(RNGLPEN, 1, ReadWrite);
+        crate::cpu::field!(DCMILPEN, 1, ReadWrite);
     }
     pub mod ahb3lpenr {
         pub const FSMCLPEN: u8 = 0;
+        crate::cpu::field!(FSMCLPEN, 1, ReadWrite);
     }
     pub mod apb1lpenr {
         pub const DACLPEN: u8 = 29;
@@ -723,6 +1247,29 @@ pub mod RCC {
         pub const TIM4LPEN: u8 = 2;
         pub const TIM3LPEN: u8 = 1;
         pub const TIM2LPEN: u8 = 0;
+        crate::cpu::field!(DACLPEN, 1, ReadWrite);
+        crate::cpu::field!(PWRLPEN, 1, ReadWrite);
+        crate::cpu::field!(CAN2LPEN, 1, ReadWrite);
+        crate::cpu::field!(CAN1LPEN, 1, ReadWrite);
+        crate::cpu::field!(I2C3LPEN, 1, ReadWrite);
+        crate::cpu::field!(I2C2LPEN, 1, ReadWrite);
+        crate::cpu::field!(I2C1LPEN, 1, ReadWrite);
+        crate::cpu::field!(UART5LPEN, 1, ReadWrite);
+        crate::cpu::field!(UART4LPEN, 1, ReadWrite);
+        crate::cpu::field!(USART3LPEN, 1, ReadWrite);
+        crate::cpu::field!(USART2LPEN, 1, ReadWrite);
+        crate::cpu::field!(SPI3LPEN, 1, ReadWrite);
+        crate::cpu::field!(SPI2LPEN, 1, ReadWrite);
+        crate::cpu::field!(WWDGLPEN, 1, ReadWrite);
+        crate::cpu::field!(TIM14LPEN, 1, ReadWrite);
+        crate::cpu::field!(TIM13LPEN, 1, ReadWrite);
+        crate::cpu::field!(TIM12LPEN, 1, ReadWrite);
+        crate::cpu::field!(TIM7LPEN, 1, ReadWrite);
+        crate::cpu::field!(TIM6LPEN, 1, ReadWrite);
+        crate::cpu::field!(TIM5LPEN, 1, ReadWrite);
+        crate::cpu::field!(TIM4LPEN, 1, ReadWrite);
+        crate::cpu::field!(TIM3LPEN, 1, ReadWrite);
+        crate::cpu::field!(TIM2LPEN, 1, ReadWrite);
     }
     pub mod apb2lpenr {
         pub const TIM11LPEN: u8 = 18;
@@ -738,6 +1285,19 @@ pub mod RCC {
         pub const USART1LPEN: u8 = 4;
         pub const TIM8LPEN: u8 = 1;
         pub const TIM1LPEN: u8 = 0;
+        crate::cpu::field!(TIM11LPEN, 1, ReadWrite);
+        crate::cpu::field!(TIM10LPEN, 1, ReadWrite);
+        crate::cpu::field!(TIM9LPEN, 1, ReadWrite);
+        crate::cpu::field!(SYSCFGLPEN, 1, ReadWrite);
+        crate::cpu::field!(SPI1LPEN, 1, ReadWrite);
+        crate::cpu::field!(SDIOLPEN, 1, ReadWrite);
+        crate::cpu::field!(ADC3LPEN, 1, ReadWrite);
+        crate::cpu::field!(ADC2LPEN, 1, ReadWrite);
+        crate::cpu::field!(ADC1LPEN, 1, ReadWrite);
+        crate::cpu::field!(USART6LPEN, 1, ReadWrite);
+        crate::cpu::field!(USART1LPEN, 1, ReadWrite);
+        crate::cpu::field!(TIM8LPEN, 1, ReadWrite);
+        crate::cpu::field!(TIM1LPEN, 1, ReadWrite);
     }
     pub mod bdcr {
         pub const BDRST: u8 = 16;
@@ -747,6 +1307,12 @@ pub mod RCC {
         pub const LSEBYP: u8 = 2;
         pub const LSERDY: u8 = 1;
         pub const LSEON: u8 = 0;
+        crate::cpu::field!(BDRST, 1, ReadWrite);
+        crate::cpu::field!(RTCEN, 1, ReadWrite);
+        crate::cpu::field!(RTCSEL = RTCSEL0, 2, ReadWrite);
+        crate::cpu::field!(LSEBYP, 1, ReadWrite);
+        crate::cpu::field!(LSERDY, 1, ReadOnly);
+        crate::cpu::field!(LSEON, 1, ReadWrite);
     }
     pub mod csr {
         pub const LPWRRSTF: u8 = 31;
@@ -759,16 +1325,32 @@ pub mod RCC {
         pub const RMVF: u8 = 24;
         pub const LSIRDY: u8 = 1;
         pub const LSION: u8 = 0;
+        crate::cpu::field!(LPWRRSTF, 1, ReadOnly);
+        crate::cpu::field!(WWDGRSTF, 1, ReadOnly);
+        crate::cpu::field!(WDGRSTF, 1, ReadOnly);
+        crate::cpu::field!(SFTRSTF, 1, ReadOnly);
+        crate::cpu::field!(PORRSTF, 1, ReadOnly);
+        crate::cpu::field!(PADRSTF, 1, ReadOnly);
+        crate::cpu::field!(BORRSTF, 1, ReadOnly);
+        crate::cpu::field!(RMVF, 1, ReadWrite);
+        crate::cpu::field!(LSIRDY, 1, ReadOnly);
+        crate::cpu::field!(LSION, 1, ReadWrite);
     }
     pub mod sscgr {
         pub const SSCGEN: u8 = 31;
         pub const SPREADSEL: u8 = 30;
         pub const INCSTEP: u8 = 13;
         pub const MODPER: u8 = 0;
+        crate::cpu::field!(SSCGEN, 1, ReadWrite);
+        crate::cpu::field!(SPREADSEL, 1, ReadWrite);
+        crate::cpu::field!(INCSTEP, 15, ReadWrite);
+        crate::cpu::field!(MODPER, 13, ReadWrite);
     }
     pub mod plli2scfgr {
         pub const PLLI2SRx: u8 = 28;
         pub const PLLI2SNx: u8 = 6;
+        crate::cpu::field!(PLLI2SRx, 3, ReadWrite);
+        crate::cpu::field!(PLLI2SNx, 9, ReadWrite);
     }
 }
 
@@ -790,6 +1372,22 @@ pub mod GPIO {
         pub const MODER2: u8 = 4;
         pub const MODER1: u8 = 2;
         pub const MODER0: u8 = 0;
+        crate::cpu::field!(MODER15, 2, ReadWrite);
+        crate::cpu::field!(MODER14, 2, ReadWrite);
+        crate::cpu::field!(MODER13, 2, ReadWrite);
+        crate::cpu::field!(MODER12, 2, ReadWrite);
+        crate::cpu::field!(MODER11, 2, ReadWrite);
+        crate::cpu::field!(MODER10, 2, ReadWrite);
+        crate::cpu::field!(MODER9, 2, ReadWrite);
+        crate::cpu::field!(MODER8, 2, ReadWrite);
+        crate::cpu::field!(MODER7, 2, ReadWrite);
+        crate::cpu::field!(MODER6, 2, ReadWrite);
+        crate::cpu::field!(MODER5, 2, ReadWrite);
+        crate::cpu::field!(MODER4, 2, ReadWrite);
+        crate::cpu::field!(MODER3, 2, ReadWrite);
+        crate::cpu::field!(MODER2, 2, ReadWrite);
+        crate::cpu::field!(MODER1, 2, ReadWrite);
+        crate::cpu::field!(MODER0, 2, ReadWrite);
     }
     pub mod otyper {
         pub const OT15: u8 = 15;
@@ -808,6 +1406,22 @@ pub mod GPIO {
         pub const OT2: u8 = 2;
         pub const OT1: u8 = 1;
         pub const OT0: u8 = 0;
+        crate::cpu::field!(OT15, 1, ReadWrite);
+        crate::cpu::field!(OT14, 1, ReadWrite);
+        crate::cpu::field!(OT13, 1, ReadWrite);
+        crate::cpu::field!(OT12, 1, ReadWrite);
+        crate::cpu::field!(OT11, 1, ReadWrite);
+        crate::cpu::field!(OT10, 1, ReadWrite);
+        crate::cpu::field!(OT9, 1, ReadWrite);
+        crate::cpu::field!(OT8, 1, ReadWrite);
+        crate::cpu::field!(OT7, 1, ReadWrite);
+        crate::cpu::field!(OT6, 1, ReadWrite);
+        crate::cpu::field!(OT5, 1, ReadWrite);
+        crate::cpu::field!(OT4, 1, ReadWrite);
+        crate::cpu::field!(OT3, 1, ReadWrite);
+        crate::cpu::field!(OT2, 1, ReadWrite);
+        crate::cpu::field!(OT1, 1, ReadWrite);
+        crate::cpu::field!(OT0, 1, ReadWrite);
     }
     pub mod ospeedr {
         pub const OSPEEDR15: u8 = 30;
@@ -826,6 +1440,22 @@ pub mod GPIO {
         pub const OSPEEDR2: u8 = 4;
         pub const OSPEEDR1: u8 = 2;
         pub const OSPEEDR0: u8 = 0;
+        crate::cpu::field!(OSPEEDR15, 2, ReadWrite);
+        crate::cpu::field!(OSPEEDR14, 2, ReadWrite);
+        crate::cpu::field!(OSPEEDR13, 2, ReadWrite);
+        crate::cpu::field!(OSPEEDR12, 2, ReadWrite);
+        crate::cpu::field!(OSPEEDR11, 2, ReadWrite);
+        crate::cpu::field!(OSPEEDR10, 2, ReadWrite);
+        crate::cpu::field!(OSPEEDR9, 2, ReadWrite);
+        crate::cpu::field!(OSPEEDR8, 2, ReadWrite);
+        crate::cpu::field!(OSPEEDR7, 2, ReadWrite);
+        crate::cpu::field!(OSPEEDR6, 2, ReadWrite);
+        crate::cpu::field!(OSPEEDR5, 2, ReadWrite);
+        crate::cpu::field!(OSPEEDR4, 2, ReadWrite);
+        crate::cpu::field!(OSPEEDR3, 2, ReadWrite);
+        crate::cpu::field!(OSPEEDR2, 2, ReadWrite);
+        crate::cpu::field!(OSPEEDR1, 2, ReadWrite);
+        crate::cpu::field!(OSPEEDR0, 2, ReadWrite);
     }
     pub mod pupdr {
         pub const PUPDR15: u8 = 30;
@@ -844,6 +1474,22 @@ pub mod GPIO {
         pub const PUPDR2: u8 = 4;
         pub const PUPDR1: u8 = 2;
         pub const PUPDR0: u8 = 0;
+        crate::cpu::field!(PUPDR15, 2, ReadWrite);
+        crate::cpu::field!(PUPDR14, 2, ReadWrite);
+        crate::cpu::field!(PUPDR13, 2, ReadWrite);
+        crate::cpu::field!(PUPDR12, 2, ReadWrite);
+        crate::cpu::field!(PUPDR11, 2, ReadWrite);
+        crate::cpu::field!(PUPDR10, 2, ReadWrite);
+        crate::cpu::field!(PUPDR9, 2, ReadWrite);
+        crate::cpu::field!(PUPDR8, 2, ReadWrite);
+        crate::cpu::field!(PUPDR7, 2, ReadWrite);
+        crate::cpu::field!(PUPDR6, 2, ReadWrite);
+        crate::cpu::field!(PUPDR5, 2, ReadWrite);
+        crate::cpu::field!(PUPDR4, 2, ReadWrite);
+        crate::cpu::field!(PUPDR3, 2, ReadWrite);
+        crate::cpu::field!(PUPDR2, 2, ReadWrite);
+        crate::cpu::field!(PUPDR1, 2, ReadWrite);
+        crate::cpu::field!(PUPDR0, 2, ReadWrite);
     }
     pub mod idr {
         pub const IDR15: u8 = 15;
@@ -862,6 +1508,22 @@ pub mod GPIO {
         pub const IDR2: u8 = 2;
         pub const IDR1: u8 = 1;
         pub const IDR0: u8 = 0;
+        crate::cpu::field!(IDR15, 1, ReadOnly);
+        crate::cpu::field!(IDR14, 1, ReadOnly);
+        crate::cpu::field!(IDR13, 1, ReadOnly);
+        crate::cpu::field!(IDR12, 1, ReadOnly);
+        crate::cpu::field!(IDR11, 1, ReadOnly);
+        crate::cpu::field!(IDR10, 1, ReadOnly);
+        crate::cpu::field!(IDR9, 1, ReadOnly);
+        crate::cpu::field!(IDR8, 1, ReadOnly);
+        crate::cpu::field!(IDR7, 1, ReadOnly);
+        crate::cpu::field!(IDR6, 1, ReadOnly);
+        crate::cpu::field!(IDR5, 1, ReadOnly);
+        crate::cpu::field!(IDR4, 1, ReadOnly);
+        crate::cpu::field!(IDR3, 1, ReadOnly);
+        crate::cpu::field!(IDR2, 1, ReadOnly);
+        crate::cpu::field!(IDR1, 1, ReadOnly);
+        crate::cpu::field!(IDR0, 1, ReadOnly);
     }
     pub mod odr {
         pub const ODR15: u8 = 15;
@@ -880,6 +1542,22 @@ pub mod GPIO {
         pub const ODR2: u8 = 2;
         pub const ODR1: u8 = 1;
         pub const ODR0: u8 = 0;
+        crate::cpu::field!(ODR15, 1, ReadWrite);
+        crate::cpu::field!(ODR14, 1, ReadWrite);
+        crate::cpu::field!(ODR13, 1, ReadWrite);
+        crate::cpu::field!(ODR12, 1, ReadWrite);
+        crate::cpu::field!(ODR11, 1, ReadWrite);
+        crate::cpu::field!(ODR10, 1, ReadWrite);
+        crate::cpu::field!(ODR9, 1, ReadWrite);
+        crate::cpu::field!(ODR8, 1, ReadWrite);
+        crate::cpu::field!(ODR7, 1, ReadWrite);
+        crate::cpu::field!(ODR6, 1, ReadWrite);
+        crate::cpu::field!(ODR5, 1, ReadWrite);
+        crate::cpu::field!(ODR4, 1, ReadWrite);
+        crate::cpu::field!(ODR3, 1, ReadWrite);
+        crate::cpu::field!(ODR2, 1, ReadWrite);
+        crate::cpu::field!(ODR1, 1, ReadWrite);
+        crate::cpu::field!(ODR0, 1, ReadWrite);
     }
     pub mod bsrr {
         pub const BR15: u8 = 31;
@@ -914,6 +1592,38 @@ pub mod GPIO {
         pub const BS2: u8 = 2;
         pub const BS1: u8 = 1;
         pub const BS0: u8 = 0;
+        crate::cpu::field!(BR15, 1, WriteOnly);
+        crate::cpu::field!(BR14, 1, WriteOnly);
+        crate::cpu::field!(BR13, 1, WriteOnly);
+        crate::cpu::field!(BR12, 1, WriteOnly);
+        crate::cpu::field!(BR11, 1, WriteOnly);
+        crate::cpu::field!(BR10, 1, WriteOnly);
+        crate::cpu::field!(BR9, 1, WriteOnly);
+        crate::cpu::field!(BR8, 1, WriteOnly);
+        crate::cpu::field!(BR7, 1, WriteOnly);
+        crate::cpu::field!(BR6, 1, WriteOnly);
+        crate::cpu::field!(BR5, 1, WriteOnly);
+        crate::cpu::field!(BR4, 1, WriteOnly);
+        crate::cpu::field!(BR3, 1, WriteOnly);
+        crate::cpu::field!(BR2, 1, WriteOnly);
+        crate::cpu::field!(BR1, 1, WriteOnly);
+        crate::cpu::field!(BR0, 1, WriteOnly);
+        crate::cpu::field!(BS15, 1, WriteOnly);
+        crate::cpu::field!(BS14, 1, WriteOnly);
+        crate::cpu::field!(BS13, 1, WriteOnly);
+        crate::cpu::field!(BS12, 1, WriteOnly);
+        crate::cpu::field!(BS11, 1, WriteOnly);
+        crate::cpu::field!(BS10, 1, WriteOnly);
+        crate::cpu::field!(BS9, 1, WriteOnly);
+        crate::cpu::field!(BS8, 1, WriteOnly);
+        crate::cpu::field!(BS7, 1, WriteOnly);
+        crate::cpu::field!(BS6, 1, WriteOnly);
+        crate::cpu::field!(BS5, 1, WriteOnly);
+        crate::cpu::field!(BS4, 1, WriteOnly);
+        crate::cpu::field!(BS3, 1, WriteOnly);
+        crate::cpu::field!(BS2, 1, WriteOnly);
+        crate::cpu::field!(BS1, 1, WriteOnly);
+        crate::cpu::field!(BS0, 1, WriteOnly);
     }
     pub mod lckr {
         pub const LCKK: u8 = 16;
@@ -933,6 +1643,23 @@ pub mod GPIO {
         pub const LCK2: u8 = 2;
         pub const LCK1: u8 = 1;
         pub const LCK0: u8 = 0;
+        crate::cpu::field!(LCKK, 1, ReadWrite);
+        crate::cpu::field!(LCK15, 1, ReadWrite);
+        crate::cpu::field!(LCK14, 1, ReadWrite);
+        crate::cpu::field!(LCK13, 1, ReadWrite);
+        crate::cpu::field!(LCK12, 1, ReadWrite);
+        crate::cpu::field!(LCK11, 1, ReadWrite);
+        crate::cpu::field!(LCK10, 1, ReadWrite);
+        crate::cpu::field!(LCK9, 1, ReadWrite);
+        crate::cpu::field!(LCK8, 1, ReadWrite);
+        crate::cpu::field!(LCK7, 1, ReadWrite);
+        crate::cpu::field!(LCK6, 1, ReadWrite);
+        crate::cpu::field!(LCK5, 1, ReadWrite);
+        crate::cpu::field!(LCK4, 1, ReadWrite);
+        crate::cpu::field!(LCK3, 1, ReadWrite);
+        crate::cpu::field!(LCK2, 1, ReadWrite);
+        crate::cpu::field!(LCK1, 1, ReadWrite);
+        crate::cpu::field!(LCK0, 1, ReadWrite);
     }
     pub mod afrl {
         pub const AFRL7: u8 = 28;
@@ -943,6 +1670,14 @@ pub mod GPIO {
         pub const AFRL2: u8 = 8;
         pub const AFRL1: u8 = 4;
         pub const AFRL0: u8 = 0;
+        crate::cpu::field!(AFRL7, 4, ReadWrite);
+        crate::cpu::field!(AFRL6, 4, ReadWrite);
+        crate::cpu::field!(AFRL5, 4, ReadWrite);
+        crate::cpu::field!(AFRL4, 4, ReadWrite);
+        crate::cpu::field!(AFRL3, 4, ReadWrite);
+        crate::cpu::field!(AFRL2, 4, ReadWrite);
+        crate::cpu::field!(AFRL1, 4, ReadWrite);
+        crate::cpu::field!(AFRL0, 4, ReadWrite);
     }
     pub mod afrh {
         pub const AFRH15: u8 = 28;
@@ -953,6 +1688,14 @@ pub mod GPIO {
         pub const AFRH10: u8 = 8;
         pub const AFRH9: u8 = 4;
         pub const AFRH8: u8 = 0;
+        crate::cpu::field!(AFRH15, 4, ReadWrite);
+        crate::cpu::field!(AFRH14, 4, ReadWrite);
+        crate::cpu::field!(AFRH13, 4, ReadWrite);
+        crate::cpu::field!(AFRH12, 4, ReadWrite);
+        crate::cpu::field!(AFRH11, 4, ReadWrite);
+        crate::cpu::field!(AFRH10, 4, ReadWrite);
+        crate::cpu::field!(AFRH9, 4, ReadWrite);
+        crate::cpu::field!(AFRH8, 4, ReadWrite);
     }
 }
 
@@ -972,6 +1715,20 @@ pub mod SPI1 {
         pub const MSTR: u8 = 2;
         pub const CPOL: u8 = 1;
         pub const CPHA: u8 = 0;
+        crate::cpu::field!(BIDIMODE, 1, ReadWrite);
+        crate::cpu::field!(BIDIOE, 1, ReadWrite);
+        crate::cpu::field!(CRCEN, 1, ReadWrite);
+        crate::cpu::field!(CRCNEXT, 1, ReadWrite);
+        crate::cpu::field!(DFF, 1, ReadWrite);
+        crate::cpu::field!(RXONLY, 1, ReadWrite);
+        crate::cpu::field!(SSM, 1, ReadWrite);
+        crate::cpu::field!(SSI, 1, ReadWrite);
+        crate::cpu::field!(LSBFIRST, 1, ReadWrite);
+        crate::cpu::field!(SPE, 1, ReadWrite);
+        crate::cpu::field!(BR, 3, ReadWrite);
+        crate::cpu::field!(MSTR, 1, ReadWrite);
+        crate::cpu::field!(CPOL, 1, ReadWrite);
+        crate::cpu::field!(CPHA, 1, ReadWrite);
     }
     pub mod cr2 {
         pub const TXEIE: u8 = 7;
@@ -981,6 +1738,13 @@ pub mod SPI1 {
         pub const SSOE: u8 = 2;
         pub const TXDMAEN: u8 = 1;
         pub const RXDMAEN: u8 = 0;
+        crate::cpu::field!(TXEIE, 1, ReadWrite);
+        crate::cpu::field!(RXNEIE, 1, ReadWrite);
+        crate::cpu::field!(ERRIE, 1, ReadWrite);
+        crate::cpu::field!(FRF, 1, ReadWrite);
+        crate::cpu::field!(SSOE, 1, ReadWrite);
+        crate::cpu::field!(TXDMAEN, 1, ReadWrite);
+        crate::cpu::field!(RXDMAEN, 1, ReadWrite);
     }
     pub mod sr {
         pub const TIFRFE: u8 = 8;
@@ -992,18 +1756,31 @@ pub mod SPI1 {
         pub const CHSIDE: u8 = 2;
         pub const TXE: u8 = 1;
         pub const RXNE: u8 = 0;
+        crate::cpu::field!(TIFRFE, 1, ReadOnly);
+        crate::cpu::field!(BSY, 1, ReadOnly);
+        crate::cpu::field!(OVR, 1, ReadOnly);
+        crate::cpu::field!(MODF, 1, ReadOnly);
+        crate::cpu::field!(CRCERR, 1, WriteZeroToClear);
+        crate::cpu::field!(UDR, 1, ReadOnly);
+        crate::cpu::field!(CHSIDE, 1, ReadOnly);
+        crate::cpu::field!(TXE, 1, ReadOnly);
+        crate::cpu::field!(RXNE, 1, ReadOnly);
     }
     pub mod dr {
         pub const DR: u8 = 0;
+        crate::cpu::field!(DR, 16, ReadWrite);
     }
     pub mod crcpr {
         pub const CRCPOLY: u8 = 0;
+        crate::cpu::field!(CRCPOLY, 16, ReadWrite);
     }
     pub mod rxcrcr {
         pub const RxCRC: u8 = 0;
+        crate::cpu::field!(RxCRC, 16, ReadOnly);
     }
     pub mod txcrcr {
         pub const TxCRC: u8 = 0;
+        crate::cpu::field!(TxCRC, 16, ReadOnly);
     }
     pub mod i2scfgr {
         pub const I2SMOD: u8 = 11;
@@ -1014,11 +1791,22 @@ pub mod SPI1 {
         pub const CKPOL: u8 = 3;
         pub const DATLEN: u8 = 1;
         pub const CHLEN: u8 = 0;
+        crate::cpu::field!(I2SMOD, 1, ReadWrite);
+        crate::cpu::field!(I2SE, 1, ReadWrite);
+        crate::cpu::field!(I2SCFG, 2, ReadWrite);
+        crate::cpu::field!(PCMSYNC, 1, ReadWrite);
+        crate::cpu::field!(I2SSTD, 2, ReadWrite);
+        crate::cpu::field!(CKPOL, 1, ReadWrite);
+        crate::cpu::field!(DATLEN, 2, ReadWrite);
+        crate::cpu::field!(CHLEN, 1, ReadWrite);
     }
     pub mod i2spr {
         pub const MCKOE: u8 = 9;
         pub const ODD: u8 = 8;
         pub const I2SDIV: u8 = 0;
+        crate::cpu::field!(MCKOE, 1, ReadWrite);
+        crate::cpu::field!(ODD, 1, ReadWrite);
+        crate::cpu::field!(I2SDIV, 8, ReadWrite);
     }
 }
 
@@ -1034,13 +1822,26 @@ pub mod USART {
         pub const NF: u8 = 2;
         pub const FE: u8 = 1;
         pub const PE: u8 = 0;
+        crate::cpu::field!(CTS, 1, WriteZeroToClear);
+        crate::cpu::field!(LBD, 1, WriteZeroToClear);
+        crate::cpu::field!(TXE, 1, ReadOnly);
+        crate::cpu::field!(TC, 1, WriteZeroToClear);
+        crate::cpu::field!(RXNE, 1, WriteZeroToClear);
+        crate::cpu::field!(IDLE, 1, ReadOnly);
+        crate::cpu::field!(ORE, 1, ReadOnly);
+        crate::cpu::field!(NF, 1, ReadOnly);
+        crate::cpu::field!(FE, 1, ReadOnly);
+        crate::cpu::field!(PE, 1, ReadOnly);
     }
     pub mod dr {
         pub const DR: u8 = 0;
+        crate::cpu::field!(DR, 9, ReadWrite);
     }
     pub mod brr {
         pub const DIV_Mantissa: u8 = 4;
         pub const DIV_Fraction: u8 = 0;
+        crate::cpu::field!(DIV_Mantissa, 12, ReadWrite);
+        crate::cpu::field!(DIV_Fraction, 4, ReadWrite);
     }
     pub mod cr1 {
         pub const OVER8: u8 = 15;
@@ -1058,6 +1859,21 @@ pub mod USART {
         pub const RE: u8 = 2;
         pub const RWU: u8 = 1;
         pub const SBK: u8 = 0;
+        crate::cpu::field!(OVER8, 1, ReadWrite);
+        crate::cpu::field!(UE, 1, ReadWrite);
+        crate::cpu::field!(M, 1, ReadWrite);
+        crate::cpu::field!(WAKE, 1, ReadWrite);
+        crate::cpu::field!(PCE, 1, ReadWrite);
+        crate::cpu::field!(PS, 1, ReadWrite);
+        crate::cpu::field!(PEIE, 1, ReadWrite);
+        crate::cpu::field!(TXEIE, 1, ReadWrite);
+        crate::cpu::field!(TCIE, 1, ReadWrite);
+        crate::cpu::field!(RXNEIE, 1, ReadWrite);
+        crate::cpu::field!(IDLEIE, 1, ReadWrite);
+        crate::cpu::field!(TE, 1, ReadWrite);
+        crate::cpu::field!(RE, 1, ReadWrite);
+        crate::cpu::field!(RWU, 1, ReadWrite);
+        crate::cpu::field!(SBK, 1, ReadWrite);
     }
     pub mod cr2 {
         pub const LINEN: u8 = 14;
@@ -1069,6 +1885,15 @@ pub mod USART {
         pub const LBDIE: u8 = 6;
         pub const LBDL: u8 = 5;
         pub const ADD: u8 = 0;
+        crate::cpu::field!(LINEN, 1, ReadWrite);
+        crate::cpu::field!(STOP, 2, ReadWrite);
+        crate::cpu::field!(CLKEN, 1, ReadWrite);
+        crate::cpu::field!(CPOL, 1, ReadWrite);
+        crate::cpu::field!(CPHA, 1, ReadWrite);
+        crate::cpu::field!(LBCL, 1, ReadWrite);
+        crate::cpu::field!(LBDIE, 1, ReadWrite);
+        crate::cpu::field!(LBDL, 1, ReadWrite);
+        crate::cpu::field!(ADD, 4, ReadWrite);
     }
     pub mod cr3 {
         pub const ONEBIT: u8 = 11;
@@ -1083,10 +1908,24 @@ pub mod USART {
         pub const IRLP: u8 = 2;
         pub const IREN: u8 = 1;
         pub const EIE: u8 = 0;
+        crate::cpu::field!(ONEBIT, 1, ReadWrite);
+        crate::cpu::field!(CTSIE, 1, ReadWrite);
+        crate::cpu::field!(CTSE, 1, ReadWrite);
+        crate::cpu::field!(RTSE, 1, ReadWrite);
+        crate::cpu::field!(DMAT, 1, ReadWrite);
+        crate::cpu::field!(DMAR, 1, ReadWrite);
+        crate::cpu::field!(SCEN, 1, ReadWrite);
+        crate::cpu::field!(NACK, 1, ReadWrite);
+        crate::cpu::field!(HDSEL, 1, ReadWrite);
+        crate::cpu::field!(IRLP, 1, ReadWrite);
+        crate::cpu::field!(IREN, 1, ReadWrite);
+        crate::cpu::field!(EIE, 1, ReadWrite);
     }
     pub mod gtpr {
         pub const GT: u8 = 8;
         pub const PSC: u8 = 0;
+        crate::cpu::field!(GT, 8, ReadWrite);
+        crate::cpu::field!(PSC, 8, ReadWrite);
     }
 }
 
@@ -1094,15 +1933,21 @@ pub mod WWDG {
     pub mod cr {
         pub const WDGA: u8 = 7;
         pub const T: u8 = 0;
+        crate::cpu::field!(WDGA, 1, ReadWrite);
+        crate::cpu::field!(T, 7, ReadWrite);
     }
     pub mod cfr {
         pub const EWI: u8 = 9;
         pub const WDGTB1: u8 = 8;
         pub const WDGTB0: u8 = 7;
         pub const W: u8 = 0;
+        crate::cpu::field!(EWI, 1, ReadWrite);
+        crate::cpu::field!(WDGTB = WDGTB0, 2, ReadWrite);
+        crate::cpu::field!(W, 7, ReadWrite);
     }
     pub mod sr {
         pub const EWIF: u8 = 0;
+        crate::cpu::field!(EWIF, 1, WriteZeroToClear);
     }
 }
 
@@ -1116,6 +1961,14 @@ pub mod TIM_ADV {
         pub const URS: u8 = 2;
         pub const UDIS: u8 = 1;
         pub const CEN: u8 = 0;
+        crate::cpu::field!(CKD, 2, ReadWrite);
+        crate::cpu::field!(ARPE, 1, ReadWrite);
+        crate::cpu::field!(CMS, 2, ReadWrite);
+        crate::cpu::field!(DIR, 1, ReadWrite);
+        crate::cpu::field!(OPM, 1, ReadWrite);
+        crate::cpu::field!(URS, 1, ReadWrite);
+        crate::cpu::field!(UDIS, 1, ReadWrite);
+        crate::cpu::field!(CEN, 1, ReadWrite);
     }
     pub mod cr2 {
         pub const OIS4: u8 = 14;
@@ -1130,6 +1983,18 @@ pub mod TIM_ADV {
         pub const CCDS: u8 = 3;
         pub const CCUS: u8 = 2;
         pub const CCPC: u8 = 0;
+        crate::cpu::field!(OIS4, 1, ReadWrite);
+        crate::cpu::field!(OIS3N, 1, ReadWrite);
+        crate::cpu::field!(OIS3, 1, ReadWrite);
+        crate::cpu::field!(OIS2N, 1, ReadWrite);
+        crate::cpu::field!(OIS2, 1, ReadWrite);
+        crate::cpu::field!(OIS1N, 1, ReadWrite);
+        crate::cpu::field!(OIS1, 1, ReadWrite);
+        crate::cpu::field!(TI1S, 1, ReadWrite);
+        crate::cpu::field!(MMS, 3, ReadWrite);
+        crate::cpu::field!(CCDS, 1, ReadWrite);
+        crate::cpu::field!(CCUS, 1, ReadWrite);
+        crate::cpu::field!(CCPC, 1, ReadWrite);
     }
     pub mod smcr {
         pub const ETP: u8 = 15;
@@ -1139,6 +2004,13 @@ pub mod TIM_ADV {
         pub const MSM: u8 = 7;
         pub const TS: u8 = 4;
         pub const SMS: u8 = 0;
+        crate::cpu::field!(ETP, 1, ReadWrite);
+        crate::cpu::field!(ECE, 1, ReadWrite);
+        crate::cpu::field!(ETPS, 2, ReadWrite);
+        crate::cpu::field!(ETF, 4, ReadWrite);
+        crate::cpu::field!(MSM, 1, ReadWrite);
+        crate::cpu::field!(TS, 3, ReadWrite);
+        crate::cpu::field!(SMS, 3, ReadWrite);
     }
     pub mod dier {
         pub const TDE: u8 = 14;
@@ -1156,6 +2028,21 @@ pub mod TIM_ADV {
         pub const UIE: u8 = 0;
         pub const BIE: u8 = 7;
         pub const COMIE: u8 = 5;
+        crate::cpu::field!(TDE, 1, ReadWrite);
+        crate::cpu::field!(COMDE, 1, ReadWrite);
+        crate::cpu::field!(CC4DE, 1, ReadWrite);
+        crate::cpu::field!(CC3DE, 1, ReadWrite);
+        crate::cpu::field!(CC2DE, 1, ReadWrite);
+        crate::cpu::field!(CC1DE, 1, ReadWrite);
+        crate::cpu::field!(UDE, 1, ReadWrite);
+        crate::cpu::field!(TIE, 1, ReadWrite);
+        crate::cpu::field!(CC4IE, 1, ReadWrite);
+        crate::cpu::field!(CC3IE, 1, ReadWrite);
+        crate::cpu::field!(CC2IE, 1, ReadWrite);
+        crate::cpu::field!(CC1IE, 1, ReadWrite);
+        crate::cpu::field!(UIE, 1, ReadWrite);
+        crate::cpu::field!(BIE, 1, ReadWrite);
+        crate::cpu::field!(COMIE, 1, ReadWrite);
     }
     pub mod sr {
         pub const CC4OF: u8 = 12;
@@ -1170,6 +2057,18 @@ pub mod TIM_ADV {
         pub const CC2IF: u8 = 2;
         pub const CC1IF: u8 = 1;
         pub const UIF: u8 = 0;
+        crate::cpu::field!(CC4OF, 1, WriteZeroToClear);
+        crate::cpu::field!(CC3OF, 1, WriteZeroToClear);
+        crate::cpu::field!(CC2OF, 1, WriteZeroToClear);
+        crate::cpu::field!(CC1OF, 1, WriteZeroToClear);
+        crate::cpu::field!(BIF, 1, WriteZeroToClear);
+        crate::cpu::field!(TIF, 1, WriteZeroToClear);
+        crate::cpu::field!(COMIF, 1, WriteZeroToClear);
+        crate::cpu::field!(CC4IF, 1, WriteZeroToClear);
+        crate::cpu::field!(CC3IF, 1, WriteZeroToClear);
+        crate::cpu::field!(CC2IF, 1, WriteZeroToClear);
+        crate::cpu::field!(CC1IF, 1, WriteZeroToClear);
+        crate::cpu::field!(UIF, 1, WriteZeroToClear);
     }
     pub mod egr {
         pub const BG: u8 = 7;
@@ -1180,6 +2079,14 @@ pub mod TIM_ADV {
         pub const CC2G: u8 = 2;
         pub const CC1G: u8 = 1;
         pub const UG: u8 = 0;
+        crate::cpu::field!(BG, 1, WriteOnly);
+        crate::cpu::field!(TG, 1, WriteOnly);
+        crate::cpu::field!(COMG, 1, WriteOnly);
+        crate::cpu::field!(CC4G, 1, WriteOnly);
+        crate::cpu::field!(CC3G, 1, WriteOnly);
+        crate::cpu::field!(CC2G, 1, WriteOnly);
+        crate::cpu::field!(CC1G, 1, WriteOnly);
+        crate::cpu::field!(UG, 1, WriteOnly);
     }
     pub mod ccmr1_output {
         pub const OC2CE: u8 = 15;
@@ -1192,6 +2099,16 @@ pub mod TIM_ADV {
         pub const OC1PE: u8 = 3;
         pub const OC1FE: u8 = 2;
         pub const CC1S: u8 = 0;
+        crate::cpu::field!(OC2CE, 1, ReadWrite);
+        crate::cpu::field!(OC2M, 3, ReadWrite);
+        crate::cpu::field!(OC2PE, 1, ReadWrite);
+        crate::cpu::field!(OC2FE, 1, ReadWrite);
+        crate::cpu::field!(CC2S, 2, ReadWrite);
+        crate::cpu::field!(OC1CE, 1, ReadWrite);
+        crate::cpu::field!(OC1M, 3, ReadWrite);
+        crate::cpu::field!(OC1PE, 1, ReadWrite);
+        crate::cpu::field!(OC1FE, 1, ReadWrite);
+        crate::cpu::field!(CC1S, 2, ReadWrite);
     }
     pub mod ccmr1_input {
         pub const IC2F: u8 = 12;
@@ -1200,6 +2117,12 @@ pub mod TIM_ADV {
         pub const IC1F: u8 = 4;
         pub const ICPCS: u8 = 2;
         pub const CC1S: u8 = 0;
+        crate::cpu::field!(IC2F, 4, ReadWrite);
+        crate::cpu::field!(IC2PCS, 2, ReadWrite);
+        crate::cpu::field!(CC2S, 2, ReadWrite);
+        crate::cpu::field!(IC1F, 4, ReadWrite);
+        crate::cpu::field!(ICPCS, 2, ReadWrite);
+        crate::cpu::field!(CC1S, 2, ReadWrite);
     }
     pub mod ccmr2_output {
         pub const OC4CE: u8 = 15;
@@ -1212,6 +2135,16 @@ pub mod TIM_ADV {
         pub const OC3PE: u8 = 3;
         pub const OC3FE: u8 = 2;
         pub const CC3S: u8 = 0;
+        crate::cpu::field!(OC4CE, 1, ReadWrite);
+        crate::cpu::field!(OC4M, 3, ReadWrite);
+        crate::cpu::field!(OC4PE, 1, ReadWrite);
+        crate::cpu::field!(OC4FE, 1, ReadWrite);
+        crate::cpu::field!(CC4S, 2, ReadWrite);
+        crate::cpu::field!(OC3CE, 1, ReadWrite);
+        crate::cpu::field!(OC3M, 3, ReadWrite);
+        crate::cpu::field!(OC3PE, 1, ReadWrite);
+        crate::cpu::field!(OC3FE, 1, ReadWrite);
+        crate::cpu::field!(CC3S, 2, ReadWrite);
     }
     pub mod ccmr2_input {
         pub const IC4F: u8 = 12;
@@ -1220,6 +2153,12 @@ pub mod TIM_ADV {
         pub const IC3F: u8 = 4;
         pub const IC3PSC: u8 = 2;
         pub const CC3S: u8 = 0;
+        crate::cpu::field!(IC4F, 4, ReadWrite);
+        crate::cpu::field!(IC4PSC, 2, ReadWrite);
+        crate::cpu::field!(CC4S, 2, ReadWrite);
+        crate::cpu::field!(IC3F, 4, ReadWrite);
+        crate::cpu::field!(IC3PSC, 2, ReadWrite);
+        crate::cpu::field!(CC3S, 2, ReadWrite);
     }
     pub mod ccer {
         pub const CC4P: u8 = 13;
@@ -1236,30 +2175,52 @@ pub mod TIM_ADV {
         pub const CC1NE: u8 = 2;
         pub const CC1P: u8 = 1;
         pub const CC1E: u8 = 0;
+        crate::cpu::field!(CC4P, 1, ReadWrite);
+        crate::cpu::field!(CC4E, 1, ReadWrite);
+        crate::cpu::field!(CC3NP, 1, ReadWrite);
+        crate::cpu::field!(CC3NE, 1, ReadWrite);
+        crate::cpu::field!(CC3P, 1, ReadWrite);
+        crate::cpu::field!(CC3E, 1, ReadWrite);
+        crate::cpu::field!(CC2NP, 1, ReadWrite);
+        crate::cpu::field!(CC2NE, 1, ReadWrite);
+        crate::cpu::field!(CC2P, 1, ReadWrite);
+        crate::cpu::field!(CC2E, 1, ReadWrite);
+        crate::cpu::field!(CC1NP, 1, ReadWrite);
+        crate::cpu::field!(CC1NE, 1, ReadWrite);
+        crate::cpu::field!(CC1P, 1, ReadWrite);
+        crate::cpu::field!(CC1E, 1, ReadWrite);
     }
     pub mod cnt {
         pub const CNT: u8 = 0;
+        crate::cpu::field!(CNT, 16, ReadWrite);
     }
     pub mod psc {
         pub const PSC: u8 = 0;
+        crate::cpu::field!(PSC, 16, ReadWrite);
     }
     pub mod arr {
         pub const ARR: u8 = 0;
+        crate::cpu::field!(ARR, 16, ReadWrite);
     }
     pub mod rcr {
         pub const REP: u8 = 0;
+        crate::cpu::field!(REP, 8, ReadWrite);
     }
     pub mod ccr1 {
         pub const CCR1: u8 = 0;
+        crate::cpu::field!(CCR1, 16, ReadWrite);
     }
     pub mod ccr2 {
         pub const CCR2: u8 = 0;
+        crate::cpu::field!(CCR2, 16, ReadWrite);
     }
     pub mod ccr3 {
         pub const CCR3: u8 = 0;
+        crate::cpu::field!(CCR3, 16, ReadWrite);
     }
     pub mod ccr4 {
         pub const CCR4: u8 = 0;
+        crate::cpu::field!(CCR4, 16, ReadWrite);
     }
     pub mod bdtr {
         pub const MOE: u8 = 15;
@@ -1270,13 +2231,24 @@ pub mod TIM_ADV {
         pub const OSSI: u8 = 10;
         pub const LOCK: u8 = 8;
         pub const DTG: u8 = 0;
+        crate::cpu::field!(MOE, 1, ReadWrite);
+        crate::cpu::field!(AOE, 1, ReadWrite);
+        crate::cpu::field!(BKP, 1, ReadWrite);
+        crate::cpu::field!(BKE, 1, ReadWrite);
+        crate::cpu::field!(OSSR, 1, ReadWrite);
+        crate::cpu::field!(OSSI, 1, ReadWrite);
+        crate::cpu::field!(LOCK, 2, ReadWrite);
+        crate::cpu::field!(DTG, 8, ReadWrite);
     }
     pub mod dcr {
         pub const DBL: u8 = 8;
         pub const DBA: u8 = 0;
+        crate::cpu::field!(DBL, 5, ReadWrite);
+        crate::cpu::field!(DBA, 5, ReadWrite);
     }
     pub mod dmar {
         pub const DMAB: u8 = 0;
+        crate::cpu::field!(DMAB, 16, ReadWrite);
     }
 }
 
@@ -1290,11 +2262,22 @@ pub mod TIM_GEN {
         pub const URS: u8 = 2;
         pub const UDIS: u8 = 1;
         pub const CEN: u8 = 0;
+        crate::cpu::field!(CKD, 2, ReadWrite);
+        crate::cpu::field!(ARPE, 1, ReadWrite);
+        crate::cpu::field!(CMS, 2, ReadWrite);
+        crate::cpu::field!(DIR, 1, ReadWrite);
+        crate::cpu::field!(OPM, 1, ReadWrite);
+        crate::cpu::field!(URS, 1, ReadWrite);
+        crate::cpu::field!(UDIS, 1, ReadWrite);
+        crate::cpu::field!(CEN, 1, ReadWrite);
     }
     pub mod cr2 {
         pub const TI1S: u8 = 7;
         pub const MMS: u8 = 4;
         pub const CCDS: u8 = 3;
+        crate::cpu::field!(TI1S, 1, ReadWrite);
+        crate::cpu::field!(MMS, 3, ReadWrite);
+        crate::cpu::field!(CCDS, 1, ReadWrite);
     }
     pub mod smcr {
         pub const ETP: u8 = 15;
@@ -1304,6 +2287,13 @@ pub mod TIM_GEN {
         pub const MSM: u8 = 7;
         pub const TS: u8 = 4;
         pub const SMS: u8 = 0;
+        crate::cpu::field!(ETP, 1, ReadWrite);
+        crate::cpu::field!(ECE, 1, ReadWrite);
+        crate::cpu::field!(ETPS, 2, ReadWrite);
+        crate::cpu::field!(ETF, 4, ReadWrite);
+        crate::cpu::field!(MSM, 1, ReadWrite);
+        crate::cpu::field!(TS, 3, ReadWrite);
+        crate::cpu::field!(SMS, 3, ReadWrite);
     }
     pub mod dier {
         pub const TDE: u8 = 14;
@@ -1318,6 +2308,18 @@ pub mod TIM_GEN {
         pub const CC2IE: u8 = 2;
         pub const CC1IE: u8 = 1;
         pub const UIE: u8 = 0;
+        crate::cpu::field!(TDE, 1, ReadWrite);
+        crate::cpu::field!(CC4DE, 1, ReadWrite);
+        crate::cpu::field!(CC3DE, 1, ReadWrite);
+        crate::cpu::field!(CC2DE, 1, ReadWrite);
+        crate::cpu::field!(CC1DE, 1, ReadWrite);
+        crate::cpu::field!(UDE, 1, ReadWrite);
+        crate::cpu::field!(TIE, 1, ReadWrite);
+        crate::cpu::field!(CC4IE, 1, ReadWrite);
+        crate::cpu::field!(CC3IE, 1, ReadWrite);
+        crate::cpu::field!(CC2IE, 1, ReadWrite);
+        crate::cpu::field!(CC1IE, 1, ReadWrite);
+        crate::cpu::field!(UIE, 1, ReadWrite);
     }
     pub mod sr {
         pub const CC4OF: u8 = 12;
@@ -1330,6 +2332,16 @@ pub mod TIM_GEN {
         pub const CC2IF: u8 = 2;
         pub const CC1IF: u8 = 1;
         pub const UIF: u8 = 0;
+        crate::cpu::field!(CC4OF, 1, WriteZeroToClear);
+        crate::cpu::field!(CC3OF, 1, WriteZeroToClear);
+        crate::cpu::field!(CC2OF, 1, WriteZeroToClear);
+        crate::cpu::field!(CC1OF, 1, WriteZeroToClear);
+        crate::cpu::field!(TIF, 1, WriteZeroToClear);
+        crate::cpu::field!(CC4IF, 1, WriteZeroToClear);
+        crate::cpu::field!(CC3IF, 1, WriteZeroToClear);
+        crate::cpu::field!(CC2IF, 1, WriteZeroToClear);
+        crate::cpu::field!(CC1IF, 1, WriteZeroToClear);
+        crate::cpu::field!(UIF, 1, WriteZeroToClear);
     }
     pub mod egr {
         pub const TG: u8 = 6;
@@ -1338,6 +2350,12 @@ pub mod TIM_GEN {
         pub const CC2G: u8 = 2;
         pub const CC1G: u8 = 1;
         pub const UG: u8 = 0;
+        crate::cpu::field!(TG, 1, WriteOnly);
+        crate::cpu::field!(CC4G, 1, WriteOnly);
+        crate::cpu::field!(CC3G, 1, WriteOnly);
+        crate::cpu::field!(CC2G, 1, WriteOnly);
+        crate::cpu::field!(CC1G, 1, WriteOnly);
+        crate::cpu::field!(UG, 1, WriteOnly);
     }
     pub mod ccmr1_output {
         pub const OC2CE: u8 = 15;
@@ -1350,6 +2368,16 @@ pub mod TIM_GEN {
         pub const OC1PE: u8 = 3;
         pub const OC1FE: u8 = 2;
         pub const CC1S: u8 = 0;
+        crate::cpu::field!(OC2CE, 1, ReadWrite);
+        crate::cpu::field!(OC2M, 3, ReadWrite);
+        crate::cpu::field!(OC2PE, 1, ReadWrite);
+        crate::cpu::field!(OC2FE, 1, ReadWrite);
+        crate::cpu::field!(CC2S, 2, ReadWrite);
+        crate::cpu::field!(OC1CE, 1, ReadWrite);
+        crate::cpu::field!(OC1M, 3, ReadWrite);
+        crate::cpu::field!(OC1PE, 1, ReadWrite);
+        crate::cpu::field!(OC1FE, 1, ReadWrite);
+        crate::cpu::field!(CC1S, 2, ReadWrite);
     }
     pub mod ccmr1_input {
         pub const IC2F: u8 = 12;
@@ -1358,6 +2386,12 @@ pub mod TIM_GEN {
         pub const IC1F: u8 = 4;
         pub const ICPCS: u8 = 2;
         pub const CC1S: u8 = 0;
+        crate::cpu::field!(IC2F, 4, ReadWrite);
+        crate::cpu::field!(IC2PCS, 2, ReadWrite);
+        crate::cpu::field!(CC2S, 2, ReadWrite);
+        crate::cpu::field!(IC1F, 4, ReadWrite);
+        crate::cpu::field!(ICPCS, 2, ReadWrite);
+        crate::cpu::field!(CC1S, 2, ReadWrite);
     }
     pub mod ccmr2_output {
         pub const OC4CE: u8 = 15;
@@ -1370,6 +2404,16 @@ pub mod TIM_GEN {
         pub const OC3PE: u8 = 3;
         pub const OC3FE: u8 = 2;
         pub const CC3S: u8 = 0;
+        crate::cpu::field!(OC4CE, 1, ReadWrite);
+        crate::cpu::field!(OC4M, 3, ReadWrite);
+        crate::cpu::field!(OC4PE, 1, ReadWrite);
+        crate::cpu::field!(OC4FE, 1, ReadWrite);
+        crate::cpu::field!(CC4S, 2, ReadWrite);
+        crate::cpu::field!(OC3CE, 1, ReadWrite);
+        crate::cpu::field!(OC3M, 3, ReadWrite);
+        crate::cpu::field!(OC3PE, 1, ReadWrite);
+        crate::cpu::field!(OC3FE, 1, ReadWrite);
+        crate::cpu::field!(CC3S, 2, ReadWrite);
     }
     pub mod ccmr2_input {
         pub const IC4F: u8 = 12;
@@ -1378,6 +2422,12 @@ pub mod TIM_GEN {
         pub const IC3F: u8 = 4;
         pub const IC3PSC: u8 = 2;
         pub const CC3S: u8 = 0;
+        crate::cpu::field!(IC4F, 4, ReadWrite);
+        crate::cpu::field!(IC4PSC, 2, ReadWrite);
+        crate::cpu::field!(CC4S, 2, ReadWrite);
+        crate::cpu::field!(IC3F, 4, ReadWrite);
+        crate::cpu::field!(IC3PSC, 2, ReadWrite);
+        crate::cpu::field!(CC3S, 2, ReadWrite);
     }
     pub mod ccer {
         pub const CC4NP: u8 = 15;
@@ -1392,43 +2442,72 @@ pub mod TIM_GEN {
         pub const CC1NP: u8 = 3;
         pub const CC1P: u8 = 1;
         pub const CC1E: u8 = 0;
+        crate::cpu::field!(CC4NP, 1, ReadWrite);
+        crate::cpu::field!(CC4P, 1, ReadWrite);
+        crate::cpu::field!(CC4E, 1, ReadWrite);
+        crate::cpu::field!(CC3NP, 1, ReadWrite);
+        crate::cpu::field!(CC3P, 1, ReadWrite);
+        crate::cpu::field!(CC3E, 1, ReadWrite);
+        crate::cpu::field!(CC2NP, 1, ReadWrite);
+        crate::cpu::field!(CC2P, 1, ReadWrite);
+        crate::cpu::field!(CC2E, 1, ReadWrite);
+        crate::cpu::field!(CC1NP, 1, ReadWrite);
+        crate::cpu::field!(CC1P, 1, ReadWrite);
+        crate::cpu::field!(CC1E, 1, ReadWrite);
     }
     pub mod cnt {
         pub const CNT_H: u8 = 16;
         pub const CNT_L: u8 = 0;
+        crate::cpu::field!(CNT_H, 16, ReadWrite);
+        crate::cpu::field!(CNT_L, 16, ReadWrite);
     }
     pub mod psc {
         pub const PSC: u8 = 0;
+        crate::cpu::field!(PSC, 16, ReadWrite);
     }
     pub mod arr {
         pub const ARR_H: u8 = 16;
         pub const ARR_L: u8 = 0;
+        crate::cpu::field!(ARR_H, 16, ReadWrite);
+        crate::cpu::field!(ARR_L, 16, ReadWrite);
     }
     pub mod ccr1 {
         pub const CCR1_H: u8 = 16;
         pub const CCR1_L: u8 = 0;
+        crate::cpu::field!(CCR1_H, 16, ReadWrite);
+        crate::cpu::field!(CCR1_L, 16, ReadWrite);
     }
     pub mod ccr2 {
         pub const CCR2_H: u8 = 16;
         pub const CCR2_L: u8 = 0;
+        crate::cpu::field!(CCR2_H, 16, ReadWrite);
+        crate::cpu::field!(CCR2_L, 16, ReadWrite);
     }
     pub mod ccr3 {
         pub const CCR3_H: u8 = 16;
         pub const CCR3_L: u8 = 0;
+        crate::cpu::field!(CCR3_H, 16, ReadWrite);
+        crate::cpu::field!(CCR3_L, 16, ReadWrite);
     }
     pub mod ccr4 {
         pub const CCR4_H: u8 = 16;
         pub const CCR4_L: u8 = 0;
+        crate::cpu::field!(CCR4_H, 16, ReadWrite);
+        crate::cpu::field!(CCR4_L, 16, ReadWrite);
     }
     pub mod dcr {
         pub const DBL: u8 = 8;
         pub const DBA: u8 = 0;
+        crate::cpu::field!(DBL, 5, ReadWrite);
+        crate::cpu::field!(DBA, 5, ReadWrite);
     }
     pub mod dmar {
         pub const DMAB: u8 = 0;
+        crate::cpu::field!(DMAB, 16, ReadWrite);
     }
     pub mod or {
         pub const ITR1_RMP: u8 = 10;
+        crate::cpu::field!(ITR1_RMP, 2, ReadWrite);
     }
 }
 
@@ -1440,12 +2519,20 @@ pub mod FLASH {
         pub const DCEN: u8 = 10;
         pub const ICRST: u8 = 11;
         pub const DCRST: u8 = 12;
+        crate::cpu::field!(LATENCY, 3, ReadWrite);
+        crate::cpu::field!(PRFTEN, 1, ReadWrite);
+        crate::cpu::field!(ICEN, 1, ReadWrite);
+        crate::cpu::field!(DCEN, 1, ReadWrite);
+        crate::cpu::field!(ICRST, 1, ReadWrite);
+        crate::cpu::field!(DCRST, 1, ReadWrite);
     }
     pub mod keyr {
         pub const KEY: u8 = 0;
+        crate::cpu::field!(KEY, 32, WriteOnly);
     }
     pub mod optkeyr {
         pub const OPTKEY: u8 = 0;
+        crate::cpu::field!(OPTKEY, 32, WriteOnly);
     }
     pub mod sr {
         pub const EOP: u8 = 0;
@@ -1455,6 +2542,13 @@ pub mod FLASH {
         pub const PGPERR: u8 = 6;
         pub const PGSERR: u8 = 7;
         pub const BSY: u8 = 16;
+        crate::cpu::field!(EOP, 1, WriteOneToClear);
+        crate::cpu::field!(OPERR, 1, WriteOneToClear);
+        crate::cpu::field!(WRPERR, 1, WriteOneToClear);
+        crate::cpu::field!(PGAERR, 1, WriteOneToClear);
+        crate::cpu::field!(PGPERR, 1, WriteOneToClear);
+        crate::cpu::field!(PGSERR, 1, WriteOneToClear);
+        crate::cpu::field!(BSY, 1, ReadOnly);
     }
     pub mod cr {
         pub const PG: u8 = 0;
@@ -1466,6 +2560,15 @@ pub mod FLASH {
         pub const EOPIE: u8 = 24;
         pub const ERRIE: u8 = 25;
         pub const LOCK: u8 = 31;
+        crate::cpu::field!(PG, 1, ReadWrite);
+        crate::cpu::field!(SER, 1, ReadWrite);
+        crate::cpu::field!(MER, 1, ReadWrite);
+        crate::cpu::field!(SNB, 4, ReadWrite);
+        crate::cpu::field!(PSIZE, 2, ReadWrite);
+        crate::cpu::field!(STRT, 1, ReadWrite);
+        crate::cpu::field!(EOPIE, 1, ReadWrite);
+        crate::cpu::field!(ERRIE, 1, ReadWrite);
+        crate::cpu::field!(LOCK, 1, ReadWrite);
     }
     pub mod optcr {
         pub const OPTLOCK: u8 = 0;
@@ -1476,6 +2579,957 @@ pub mod FLASH {
         pub const nRST_STDBY: u8 = 7;
         pub const RDP: u8 = 8;
         pub const nWRP: u8 = 16;
+        crate::cpu::field!(OPTLOCK, 1, ReadWrite);
+        crate::cpu::field!(OPTSTRT, 1, ReadWrite);
+        crate::cpu::field!(BOR_LEV, 2, ReadWrite);
+        crate::cpu::field!(WDG_SW, 1, ReadWrite);
+        crate::cpu::field!(nRST_STOP, 1, ReadWrite);
+        crate::cpu::field!(nRST_STDBY, 1, ReadWrite);
+        crate::cpu::field!(RDP, 8, ReadWrite);
+        crate::cpu::field!(nWRP, 12, ReadWrite);
+    }
+}
+
+pub mod SYSCFG {
+    pub mod memrm {
+        pub const MEM_MODE: u8 = 0;
+        crate::cpu::field!(MEM_MODE, 2, ReadWrite);
+    }
+    pub mod pmc {
+        pub const MII_RMII_SEL: u8 = 23;
+        crate::cpu::field!(MII_RMII_SEL, 1, ReadWrite);
+    }
+    pub mod exticr1 {
+        pub const EXTI3: u8 = 12;
+        pub const EXTI2: u8 = 8;
+        pub const EXTI1: u8 = 4;
+        pub const EXTI0: u8 = 0;
+        crate::cpu::field!(EXTI3, 4, ReadWrite);
+        crate::cpu::field!(EXTI2, 4, ReadWrite);
+        crate::cpu::field!(EXTI1, 4, ReadWrite);
+        crate::cpu::field!(EXTI0, 4, ReadWrite);
+    }
+    pub mod exticr2 {
+        pub const EXTI7: u8 = 12;
+        pub const EXTI6: u8 = 8;
+        pub const EXTI5: u8 = 4;
+        pub const EXTI4: u8 = 0;
+        crate::cpu::field!(EXTI7, 4, ReadWrite);
+        crate::cpu::field!(EXTI6, 4, ReadWrite);
+        crate::cpu::field!(EXTI5, 4, ReadWrite);
+        crate::cpu::field!(EXTI4, 4, ReadWrite);
+    }
+    pub mod exticr3 {
+        pub const EXTI11: u8 = 12;
+        pub const EXTI10: u8 = 8;
+        pub const EXTI9: u8 = 4;
+        pub const EXTI8: u8 = 0;
+        crate::cpu::field!(EXTI11, 4, ReadWrite);
+        crate::cpu::field!(EXTI10, 4, ReadWrite);
+        crate::cpu::field!(EXTI9, 4, ReadWrite);
+        crate::cpu::field!(EXTI8, 4, ReadWrite);
+    }
+    pub mod exticr4 {
+        pub const EXTI15: u8 = 12;
+        pub const EXTI14: u8 = 8;
+        pub const EXTI13: u8 = 4;
+        pub const EXTI12: u8 = 0;
+        crate::cpu::field!(EXTI15, 4, ReadWrite);
+        crate::cpu::field!(EXTI14, 4, ReadWrite);
+        crate::cpu::field!(EXTI13, 4, ReadWrite);
+        crate::cpu::field!(EXTI12, 4, ReadWrite);
+    }
+    pub mod cmpcr {
+        pub const READY: u8 = 8;
+        pub const CMP_PD: u8 = 0;
+        crate::cpu::field!(READY, 1, ReadOnly);
+        crate::cpu::field!(CMP_PD, 1, ReadWrite);
+    }
+}
+
+pub mod EXTI {
+    pub mod imr {
+        pub const MR0: u8 = 0;
+        pub const MR1: u8 = 1;
+        pub const MR2: u8 = 2;
+        pub const MR3: u8 = 3;
+        pub const MR4: u8 = 4;
+        pub const MR5: u8 = 5;
+        pub const MR6: u8 = 6;
+        pub const MR7: u8 = 7;
+        pub const MR8: u8 = 8;
+        pub const MR9: u8 = 9;
+        pub const MR10: u8 = 10;
+        pub const MR11: u8 = 11;
+        pub const MR12: u8 = 12;
+        pub const MR13: u8 = 13;
+        pub const MR14: u8 = 14;
+        pub const MR15: u8 = 15;
+        pub const MR16: u8 = 16;
+        pub const MR17: u8 = 17;
+        pub const MR18: u8 = 18;
+        pub const MR19: u8 = 19;
+        pub const MR20: u8 = 20;
+        pub const MR21: u8 = 21;
+        pub const MR22: u8 = 22;
+        crate::cpu::field!(MR0, 1, ReadWrite);
+        crate::cpu::field!(MR1, 1, ReadWrite);
+        crate::cpu::field!(MR2, 1, ReadWrite);
+        crate::cpu::field!(MR3, 1, ReadWrite);
+        crate::cpu::field!(MR4, 1, ReadWrite);
+        crate::cpu::field!(MR5, 1, ReadWrite);
+        crate::cpu::field!(MR6, 1, ReadWrite);
+        crate::cpu::field!(MR7, 1, ReadWrite);
+        crate::cpu::field!(MR8, 1, ReadWrite);
+        crate::cpu::field!(MR9, 1, ReadWrite);
+        crate::cpu::field!(MR10, 1, ReadWrite);
+        crate::cpu::field!(MR11, 1, ReadWrite);
+        crate::cpu::field!(MR12, 1, ReadWrite);
+        crate::cpu::field!(MR13, 1, ReadWrite);
+        crate::cpu::field!(MR14, 1, ReadWrite);
+        crate::cpu::field!(MR15, 1, ReadWrite);
+        crate::cpu::field!(MR16, 1, ReadWrite);
+        crate::cpu::field!(MR17, 1, ReadWrite);
+        crate::cpu::field!(MR18, 1, ReadWrite);
+        crate::cpu::field!(MR19, 1, ReadWrite);
+        crate::cpu::field!(MR20, 1, ReadWrite);
+        crate::cpu::field!(MR21, 1, ReadWrite);
+        crate::cpu::field!(MR22, 1, ReadWrite);
+    }
+    pub mod emr {
+        pub const MR0: u8 = 0;
+        pub const MR1: u8 = 1;
+        pub const MR2: u8 = 2;
+        pub const MR3: u8 = 3;
+        pub const MR4: u8 = 4;
+        pub const MR5: u8 = 5;
+        pub const MR6: u8 = 6;
+        pub const MR7: u8 = 7;
+        pub const MR8: u8 = 8;
+        pub const MR9: u8 = 9;
+        pub const MR10: u8 = 10;
+        pub const MR11: u8 = 11;
+        pub const MR12: u8 = 12;
+        pub const MR13: u8 = 13;
+        pub const MR14: u8 = 14;
+        pub const MR15: u8 = 15;
+        pub const MR16: u8 = 16;
+        pub const MR17: u8 = 17;
+        pub const MR18: u8 = 18;
+        pub const MR19: u8 = 19;
+        pub const MR20: u8 = 20;
+        pub const MR21: u8 = 21;
+        pub const MR22: u8 = 22;
+        crate::cpu::field!(MR0, 1, ReadWrite);
+        crate::cpu::field!(MR1, 1, ReadWrite);
+        crate::cpu::field!(MR2, 1, ReadWrite);
+        crate::cpu::field!(MR3, 1, ReadWrite);
+        crate::cpu::field!(MR4, 1, ReadWrite);
+        crate::cpu::field!(MR5, 1, ReadWrite);
+        crate::cpu::field!(MR6, 1, ReadWrite);
+        crate::cpu::field!(MR7, 1, ReadWrite);
+        crate::cpu::field!(MR8, 1, ReadWrite);
+        crate::cpu::field!(MR9, 1, ReadWrite);
+        crate::cpu::field!(MR10, 1, ReadWrite);
+        crate::cpu::field!(MR11, 1, ReadWrite);
+        crate::cpu::field!(MR12, 1, ReadWrite);
+        crate::cpu::field!(MR13, 1, ReadWrite);
+        crate::cpu::field!(MR14, 1, ReadWrite);
+        crate::cpu::field!(MR15, 1, ReadWrite);
+        crate::cpu::field!(MR16, 1, ReadWrite);
+        crate::cpu::field!(MR17, 1, ReadWrite);
+        crate::cpu::field!(MR18, 1, ReadWrite);
+        crate::cpu::field!(MR19, 1, ReadWrite);
+        crate::cpu::field!(MR20, 1, ReadWrite);
+        crate::cpu::field!(MR21, 1, ReadWrite);
+        crate::cpu::field!(MR22, 1, ReadWrite);
+    }
+    pub mod rtsr {
+        pub const TR0: u8 = 0;
+        pub const TR1: u8 = 1;
+        pub const TR2: u8 = 2;
+        pub const TR3: u8 = 3;
+        pub const TR4: u8 = 4;
+        pub const TR5: u8 = 5;
+        pub const TR6: u8 = 6;
+        pub const TR7: u8 = 7;
+        pub const TR8: u8 = 8;
+        pub const TR9: u8 = 9;
+        pub const TR10: u8 = 10;
+        pub const TR11: u8 = 11;
+        pub const TR12: u8 = 12;
+        pub const TR13: u8 = 13;
+        pub const TR14: u8 = 14;
+        pub const TR15: u8 = 15;
+        pub const TR16: u8 = 16;
+        pub const TR17: u8 = 17;
+        pub const TR18: u8 = 18;
+        pub const TR19: u8 = 19;
+        pub const TR20: u8 = 20;
+        pub const TR21: u8 = 21;
+        pub const TR22: u8 = 22;
+        crate::cpu::field!(TR0, 1, ReadWrite);
+        crate::cpu::field!(TR1, 1, ReadWrite);
+        crate::cpu::field!(TR2, 1, ReadWrite);
+        crate::cpu::field!(TR3, 1, ReadWrite);
+        crate::cpu::field!(TR4, 1, ReadWrite);
+        crate::cpu::field!(TR5, 1, ReadWrite);
+        crate::cpu::field!(TR6, 1, ReadWrite);
+        crate::cpu::field!(TR7, 1, ReadWrite);
+        crate::cpu::field!(TR8, 1, ReadWrite);
+        crate::cpu::field!(TR9, 1, ReadWrite);
+        crate::cpu::field!(TR10, 1, ReadWrite);
+        crate::cpu::field!(TR11, 1, ReadWrite);
+        crate::cpu::field!(TR12, 1, ReadWrite);
+        crate::cpu::field!(TR13, 1, ReadWrite);
+        crate::cpu::field!(TR14, 1, ReadWrite);
+        crate::cpu::field!(TR15, 1, ReadWrite);
+        crate::cpu::field!(TR16, 1, ReadWrite);
+        crate::cpu::field!(TR17, 1, ReadWrite);
+        crate::cpu::field!(TR18, 1, ReadWrite);
+        crate::cpu::field!(TR19, 1, ReadWrite);
+        crate::cpu::field!(TR20, 1, ReadWrite);
+        crate::cpu::field!(TR21, 1, ReadWrite);
+        crate::cpu::field!(TR22, 1, ReadWrite);
+    }
+    pub mod ftsr {
+        pub const TR0: u8 = 0;
+        pub const TR1: u8 = 1;
+        pub const TR2: u8 = 2;
+        pub const TR3: u8 = 3;
+        pub const TR4: u8 = 4;
+        pub const TR5: u8 = 5;
+        pub const TR6: u8 = 6;
+        pub const TR7: u8 = 7;
+        pub const TR8: u8 = 8;
+        pub const TR9: u8 = 9;
+        pub const TR10: u8 = 10;
+        pub const TR11: u8 = 11;
+        pub const TR12: u8 = 12;
+        pub const TR13: u8 = 13;
+        pub const TR14: u8 = 14;
+        pub const TR15: u8 = 15;
+        pub const TR16: u8 = 16;
+        pub const TR17: u8 = 17;
+        pub const TR18: u8 = 18;
+        pub const TR19: u8 = 19;
+        pub const TR20: u8 = 20;
+        pub const TR21: u8 = 21;
+        pub const TR22: u8 = 22;
+        crate::cpu::field!(TR0, 1, ReadWrite);
+        crate::cpu::field!(TR1, 1, ReadWrite);
+        crate::cpu::field!(TR2, 1, ReadWrite);
+        crate::cpu::field!(TR3, 1, ReadWrite);
+        crate::cpu::field!(TR4, 1, ReadWrite);
+        crate::cpu::field!(TR5, 1, ReadWrite);
+        crate::cpu::field!(TR6, 1, ReadWrite);
+        crate::cpu::field!(TR7, 1, ReadWrite);
+        crate::cpu::field!(TR8, 1, ReadWrite);
+        crate::cpu::field!(TR9, 1, ReadWrite);
+        crate::cpu::field!(TR10, 1, ReadWrite);
+        crate::cpu::field!(TR11, 1, ReadWrite);
+        crate::cpu::field!(TR12, 1, ReadWrite);
+        crate::cpu::field!(TR13, 1, ReadWrite);
+        crate::cpu::field!(TR14, 1, ReadWrite);
+        crate::cpu::field!(TR15, 1, ReadWrite);
+        crate::cpu::field!(TR16, 1, ReadWrite);
+        crate::cpu::field!(TR17, 1, ReadWrite);
+        crate::cpu::field!(TR18, 1, ReadWrite);
+        crate::cpu::field!(TR19, 1, ReadWrite);
+        crate::cpu::field!(TR20, 1, ReadWrite);
+        crate::cpu::field!(TR21, 1, ReadWrite);
+        crate::cpu::field!(TR22, 1, ReadWrite);
+    }
+    pub mod swier {
+        pub const SWIER0: u8 = 0;
+        pub const SWIER1: u8 = 1;
+        pub const SWIER2: u8 = 2;
+        pub const SWIER3: u8 = 3;
+        pub const SWIER4: u8 = 4;
+        pub const SWIER5: u8 = 5;
+        pub const SWIER6: u8 = 6;
+        pub const SWIER7: u8 = 7;
+        pub const SWIER8: u8 = 8;
+        pub const SWIER9: u8 = 9;
+        pub const SWIER10: u8 = 10;
+        pub const SWIER11: u8 = 11;
+        pub const SWIER12: u8 = 12;
+        pub const SWIER13: u8 = 13;
+        pub const SWIER14: u8 = 14;
+        pub const SWIER15: u8 = 15;
+        pub const SWIER16: u8 = 16;
+        pub const SWIER17: u8 = 17;
+        pub const SWIER18: u8 = 18;
+        pub const SWIER19: u8 = 19;
+        pub const SWIER20: u8 = 20;
+        pub const SWIER21: u8 = 21;
+        pub const SWIER22: u8 = 22;
+        crate::cpu::field!(SWIER0, 1, ReadWrite);
+        crate::cpu::field!(SWIER1, 1, ReadWrite);
+        crate::cpu::field!(SWIER2, 1, ReadWrite);
+        crate::cpu::field!(SWIER3, 1, ReadWrite);
+        crate::cpu::field!(SWIER4, 1, ReadWrite);
+        crate::cpu::field!(SWIER5, 1, ReadWrite);
+        crate::cpu::field!(SWIER6, 1, ReadWrite);
+        crate::cpu::field!(SWIER7, 1, ReadWrite);
+        crate::cpu::field!(SWIER8, 1, ReadWrite);
+        crate::cpu::field!(SWIER9, 1, ReadWrite);
+        crate::cpu::field!(SWIER10, 1, ReadWrite);
+        crate::cpu::field!(SWIER11, 1, ReadWrite);
+        crate::cpu::field!(SWIER12, 1, ReadWrite);
+        crate::cpu::field!(SWIER13, 1, ReadWrite);
+        crate::cpu::field!(SWIER14, 1, ReadWrite);
+        crate::cpu::field!(SWIER15, 1, ReadWrite);
+        crate::cpu::field!(SWIER16, 1, ReadWrite);
+        crate::cpu::field!(SWIER17, 1, ReadWrite);
+        crate::cpu::field!(SWIER18, 1, ReadWrite);
+        crate::cpu::field!(SWIER19, 1, ReadWrite);
+        crate::cpu::field!(SWIER20, 1, ReadWrite);
+        crate::cpu::field!(SWIER21, 1, ReadWrite);
+        crate::cpu::field!(SWIER22, 1, ReadWrite);
+    }
+    pub mod pr {
+        pub const PR0: u8 = 0;
+        pub const PR1: u8 = 1;
+        pub const PR2: u8 = 2;
+        pub const PR3: u8 = 3;
+        pub const PR4: u8 = 4;
+        pub const PR5: u8 = 5;
+        pub const PR6: u8 = 6;
+        pub const PR7: u8 = 7;
+        pub const PR8: u8 = 8;
+        pub const PR9: u8 = 9;
+        pub const PR10: u8 = 10;
+        pub const PR11: u8 = 11;
+        pub const PR12: u8 = 12;
+        pub const PR13: u8 = 13;
+        pub const PR14: u8 = 14;
+        pub const PR15: u8 = 15;
+        pub const PR16: u8 = 16;
+        pub const PR17: u8 = 17;
+        pub const PR18: u8 = 18;
+        pub const PR19: u8 = 19;
+        pub const PR20: u8 = 20;
+        pub const PR21: u8 = 21;
+        pub const PR22: u8 = 22;
+        crate::cpu::field!(PR0, 1, WriteOneToClear);
+        crate::cpu::field!(PR1, 1, WriteOneToClear);
+        crate::cpu::field!(PR2, 1, WriteOneToClear);
+        crate::cpu::field!(PR3, 1, WriteOneToClear);
+        crate::cpu::field!(PR4, 1, WriteOneToClear);
+        crate::cpu::field!(PR5, 1, WriteOneToClear);
+        crate::cpu::field!(PR6, 1, WriteOneToClear);
+        crate::cpu::field!(PR7, 1, WriteOneToClear);
+        crate::cpu::field!(PR8, 1, WriteOneToClear);
+        crate::cpu::field!(PR9, 1, WriteOneToClear);
+        crate::cpu::field!(PR10, 1, WriteOneToClear);
+        crate::cpu::field!(PR11, 1, WriteOneToClear);
+        crate::cpu::field!(PR12, 1, WriteOneToClear);
+        crate::cpu::field!(PR13, 1, WriteOneToClear);
+        crate::cpu::field!(PR14, 1, WriteOneToClear);
+        crate::cpu::field!(PR15, 1, WriteOneToClear);
+        crate::cpu::field!(PR16, 1, WriteOneToClear);
+        crate::cpu::field!(PR17, 1, WriteOneToClear);
+        crate::cpu::field!(PR18, 1, WriteOneToClear);
+        crate::cpu::field!(PR19, 1, WriteOneToClear);
+        crate::cpu::field!(PR20, 1, WriteOneToClear);
+        crate::cpu::field!(PR21, 1, WriteOneToClear);
+        crate::cpu::field!(PR22, 1, WriteOneToClear);
+    }
+}
+
+pub mod IWDG {
+    pub mod kr {
+        pub const KEY: u8 = 0;
+        crate::cpu::field!(KEY, 16, WriteOnly);
+    }
+    pub mod pr {
+        pub const PR: u8 = 0;
+        crate::cpu::field!(PR, 3, ReadWrite);
+    }
+    pub mod rlr {
+        pub const RL: u8 = 0;
+        crate::cpu::field!(RL, 12, ReadWrite);
+    }
+    pub mod sr {
+        pub const RVU: u8 = 1;
+        pub const PVU: u8 = 0;
+        crate::cpu::field!(RVU, 1, ReadOnly);
+        crate::cpu::field!(PVU, 1, ReadOnly);
+    }
+}
+
+pub mod PWR {
+    pub mod cr {
+        pub const VOS: u8 = 14;
+        pub const FPDS: u8 = 9;
+        pub const DBP: u8 = 8;
+        pub const PLS: u8 = 5;
+        pub const PVDE: u8 = 4;
+        pub const CSBF: u8 = 3;
+        pub const CWUF: u8 = 2;
+        pub const PDDS: u8 = 1;
+        pub const LPDS: u8 = 0;
+        crate::cpu::field!(VOS, 1, ReadWrite);
+        crate::cpu::field!(FPDS, 1, ReadWrite);
+        crate::cpu::field!(DBP, 1, ReadWrite);
+        crate::cpu::field!(PLS, 3, ReadWrite);
+        crate::cpu::field!(PVDE, 1, ReadWrite);
+        crate::cpu::field!(CSBF, 1, WriteOnly);
+        crate::cpu::field!(CWUF, 1, WriteOnly);
+        crate::cpu::field!(PDDS, 1, ReadWrite);
+        crate::cpu::field!(LPDS, 1, ReadWrite);
+    }
+    pub mod csr {
+        pub const VOSRDY: u8 = 14;
+        pub const BRE: u8 = 9;
+        pub const EWUP: u8 = 8;
+        pub const BRR: u8 = 3;
+        pub const PVDO: u8 = 2;
+        pub const SBF: u8 = 1;
+        pub const WUF: u8 = 0;
+        crate::cpu::field!(VOSRDY, 1, ReadOnly);
+        crate::cpu::field!(BRE, 1, ReadWrite);
+        crate::cpu::field!(EWUP, 1, ReadWrite);
+        crate::cpu::field!(BRR, 1, ReadOnly);
+        crate::cpu::field!(PVDO, 1, ReadOnly);
+        crate::cpu::field!(SBF, 1, ReadOnly);
+        crate::cpu::field!(WUF, 1, ReadOnly);
+    }
+}
+
+pub mod RTC {
+    pub mod tr {
+        pub const PM: u8 = 22;
+        pub const HT: u8 = 20;
+        pub const HU: u8 = 16;
+        pub const MNT: u8 = 12;
+        pub const MNU: u8 = 8;
+        pub const ST: u8 = 4;
+        pub const SU: u8 = 0;
+        crate::cpu::field!(PM, 1, ReadWrite);
+        crate::cpu::field!(HT, 2, ReadWrite);
+        crate::cpu::field!(HU, 4, ReadWrite);
+        crate::cpu::field!(MNT, 3, ReadWrite);
+        crate::cpu::field!(MNU, 4, ReadWrite);
+        crate::cpu::field!(ST, 3, ReadWrite);
+        crate::cpu::field!(SU, 4, ReadWrite);
+    }
+    pub mod dr {
+        pub const YT: u8 = 20;
+        pub const YU: u8 = 16;
+        pub const WDU: u8 = 13;
+        pub const MT: u8 = 12;
+        pub const MU: u8 = 8;
+        pub const DT: u8 = 4;
+        pub const DU: u8 = 0;
+        crate::cpu::field!(YT, 4, ReadWrite);
+        crate::cpu::field!(YU, 4, ReadWrite);
+        crate::cpu::field!(WDU, 3, ReadWrite);
+        crate::cpu::field!(MT, 1, ReadWrite);
+        crate::cpu::field!(MU, 4, ReadWrite);
+        crate::cpu::field!(DT, 2, ReadWrite);
+        crate::cpu::field!(DU, 4, ReadWrite);
+    }
+    pub mod cr {
+        pub const COE: u8 = 23;
+        pub const OSEL: u8 = 21;
+        pub const POL: u8 = 20;
+        pub const COSEL: u8 = 19;
+        pub const BKP: u8 = 18;
+        pub const SUB1H: u8 = 17;
+        pub const ADD1H: u8 = 16;
+        pub const TSIE: u8 = 15;
+        pub const WUTIE: u8 = 14;
+        pub const ALRBIE: u8 = 13;
+        pub const ALRAIE: u8 = 12;
+        pub const TSE: u8 = 11;
+        pub const WUTE: u8 = 10;
+        pub const ALRBE: u8 = 9;
+        pub const ALRAE: u8 = 8;
+        pub const DCE: u8 = 7;
+        pub const FMT: u8 = 6;
+        pub const BYPSHAD: u8 = 5;
+        pub const REFCKON: u8 = 4;
+        pub const TSEDGE: u8 = 3;
+        pub const WUCKSEL: u8 = 0;
+        crate::cpu::field!(COE, 1, ReadWrite);
+        crate::cpu::field!(OSEL, 2, ReadWrite);
+        crate::cpu::field!(POL, 1, ReadWrite);
+        crate::cpu::field!(COSEL, 1, ReadWrite);
+        crate::cpu::field!(BKP, 1, ReadWrite);
+        crate::cpu::field!(SUB1H, 1, WriteOnly);
+        crate::cpu::field!(ADD1H, 1, WriteOnly);
+        crate::cpu::field!(TSIE, 1, ReadWrite);
+        crate::cpu::field!(WUTIE, 1, ReadWrite);
+        crate::cpu::field!(ALRBIE, 1, ReadWrite);
+        crate::cpu::field!(ALRAIE, 1, ReadWrite);
+        crate::cpu::field!(TSE, 1, ReadWrite);
+        crate::cpu::field!(WUTE, 1, ReadWrite);
+        crate::cpu::field!(ALRBE, 1, ReadWrite);
+        crate::cpu::field!(ALRAE, 1, ReadWrite);
+        crate::cpu::field!(DCE, 1, ReadWrite);
+        crate::cpu::field!(FMT, 1, ReadWrite);
+        crate::cpu::field!(BYPSHAD, 1, ReadWrite);
+        crate::cpu::field!(REFCKON, 1, ReadWrite);
+        crate::cpu::field!(TSEDGE, 1, ReadWrite);
+        crate::cpu::field!(WUCKSEL, 3, ReadWrite);
+    }
+    pub mod isr {
+        pub const RECALPF: u8 = 16;
+        pub const TAMP2F: u8 = 14;
+        pub const TAMP1F: u8 = 13;
+        pub const TSOVF: u8 = 12;
+        pub const TSF: u8 = 11;
+        pub const WUTF: u8 = 10;
+        pub const ALRBF: u8 = 9;
+        pub const ALRAF: u8 = 8;
+        pub const INIT: u8 = 7;
+        pub const INITF: u8 = 6;
+        pub const RSF: u8 = 5;
+        pub const INITS: u8 = 4;
+        pub const SHPF: u8 = 3;
+        pub const WUTWF: u8 = 2;
+        pub const ALRBWF: u8 = 1;
+        pub const ALRAWF: u8 = 0;
+        crate::cpu::field!(RECALPF, 1, ReadOnly);
+        crate::cpu::field!(TAMP2F, 1, WriteZeroToClear);
+        crate::cpu::field!(TAMP1F, 1, WriteZeroToClear);
+        crate::cpu::field!(TSOVF, 1, WriteZeroToClear);
+        crate::cpu::field!(TSF, 1, WriteZeroToClear);
+        crate::cpu::field!(WUTF, 1, WriteZeroToClear);
+        crate::cpu::field!(ALRBF, 1, WriteZeroToClear);
+        crate::cpu::field!(ALRAF, 1, WriteZeroToClear);
+        crate::cpu::field!(INIT, 1, ReadWrite);
+        crate::cpu::field!(INITF, 1, ReadOnly);
+        crate::cpu::field!(RSF, 1, WriteZeroToClear);
+        crate::cpu::field!(INITS, 1, ReadOnly);
+        crate::cpu::field!(SHPF, 1, ReadOnly);
+        crate::cpu::field!(WUTWF, 1, ReadOnly);
+        crate::cpu::field!(ALRBWF, 1, ReadOnly);
+        crate::cpu::field!(ALRAWF, 1, ReadOnly);
+    }
+    pub mod prer {
+        pub const PREDIV_A: u8 = 16;
+        pub const PREDIV_S: u8 = 0;
+        crate::cpu::field!(PREDIV_A, 7, ReadWrite);
+        crate::cpu::field!(PREDIV_S, 15, ReadWrite);
+    }
+    pub mod wutr {
+        pub const WUT: u8 = 0;
+        crate::cpu::field!(WUT, 16, ReadWrite);
+    }
+    pub mod calibr {
+        pub const DCS: u8 = 7;
+        pub const DC: u8 = 0;
+        crate::cpu::field!(DCS, 1, ReadWrite);
+        crate::cpu::field!(DC, 5, ReadWrite);
+    }
+    pub mod alrmar {
+        pub const MSK4: u8 = 31;
+        pub const WDSEL: u8 = 30;
+        pub const DT: u8 = 28;
+        pub const DU: u8 = 24;
+        pub const MSK3: u8 = 23;
+        pub const PM: u8 = 22;
+        pub const HT: u8 = 20;
+        pub const HU: u8 = 16;
+        pub const MSK2: u8 = 15;
+        pub const MNT: u8 = 12;
+        pub const MNU: u8 = 8;
+        pub const MSK1: u8 = 7;
+        pub const ST: u8 = 4;
+        pub const SU: u8 = 0;
+        crate::cpu::field!(MSK4, 1, ReadWrite);
+        crate::cpu::field!(WDSEL, 1, ReadWrite);
+        crate::cpu::field!(DT, 2, ReadWrite);
+        crate::cpu::field!(DU, 4, ReadWrite);
+        crate::cpu::field!(MSK3, 1, ReadWrite);
+        crate::cpu::field!(PM, 1, ReadWrite);
+        crate::cpu::field!(HT, 2, ReadWrite);
+        crate::cpu::field!(HU, 4, ReadWrite);
+        crate::cpu::field!(MSK2, 1, ReadWrite);
+        crate::cpu::field!(MNT, 3, ReadWrite);
+        crate::cpu::field!(MNU, 4, ReadWrite);
+        crate::cpu::field!(MSK1, 1, ReadWrite);
+        crate::cpu::field!(ST, 3, ReadWrite);
+        crate::cpu::field!(SU, 4, ReadWrite);
+    }
+    pub mod alrmbr {
+        pub const MSK4: u8 = 31;
+        pub const WDSEL: u8 = 30;
+        pub const DT: u8 = 28;
+        pub const DU: u8 = 24;
+        pub const MSK3: u8 = 23;
+        pub const PM: u8 = 22;
+        pub const HT: u8 = 20;
+        pub const HU: u8 = 16;
+        pub const MSK2: u8 = 15;
+        pub const MNT: u8 = 12;
+        pub const MNU: u8 = 8;
+        pub const MSK1: u8 = 7;
+        pub const ST: u8 = 4;
+        pub const SU: u8 = 0;
+        crate::cpu::field!(MSK4, 1, ReadWrite);
+        crate::cpu::field!(WDSEL, 1, ReadWrite);
+        crate::cpu::field!(DT, 2, ReadWrite);
+        crate::cpu::field!(DU, 4, ReadWrite);
+        crate::cpu::field!(MSK3, 1, ReadWrite);
+        crate::cpu::field!(PM, 1, ReadWrite);
+        crate::cpu::field!(HT, 2, ReadWrite);
+        crate::cpu::field!(HU, 4, ReadWrite);
+        crate::cpu::field!(MSK2, 1, ReadWrite);
+        crate::cpu::field!(MNT, 3, ReadWrite);
+        crate::cpu::field!(MNU, 4, ReadWrite);
+        crate::cpu::field!(MSK1, 1, ReadWrite);
+        crate::cpu::field!(ST, 3, ReadWrite);
+        crate::cpu::field!(SU, 4, ReadWrite);
+    }
+    pub mod wpr {
+        pub const KEY: u8 = 0;
+        crate::cpu::field!(KEY, 8, WriteOnly);
+    }
+    pub mod ssr {
+        pub const SS: u8 = 0;
+        crate::cpu::field!(SS, 16, ReadOnly);
+    }
+    pub mod shiftr {
+        pub const ADD1S: u8 = 31;
+        pub const SUBFS: u8 = 0;
+        crate::cpu::field!(ADD1S, 1, WriteOnly);
+        crate::cpu::field!(SUBFS, 15, WriteOnly);
+    }
+    pub mod calr {
+        pub const CALP: u8 = 15;
+        pub const CALW8: u8 = 14;
+        pub const CALW16: u8 = 13;
+        pub const CALM: u8 = 0;
+        crate::cpu::field!(CALP, 1, ReadWrite);
+        crate::cpu::field!(CALW8, 1, ReadWrite);
+        crate::cpu::field!(CALW16, 1, ReadWrite);
+        crate::cpu::field!(CALM, 9, ReadWrite);
+    }
+    pub mod alrmassr {
+        pub const MASKSS: u8 = 24;
+        pub const SS: u8 = 0;
+        crate::cpu::field!(MASKSS, 4, ReadWrite);
+        crate::cpu::field!(SS, 15, ReadWrite);
+    }
+    pub mod alrmbssr {
+        pub const MASKSS: u8 = 24;
+        pub const SS: u8 = 0;
+        crate::cpu::field!(MASKSS, 4, ReadWrite);
+        crate::cpu::field!(SS, 15, ReadWrite);
+    }
+}
+
+pub mod OTG_FS_GLOBAL {
+    pub mod gahbcfg {
+        pub const PTXFELVL: u8 = 8;
+        pub const TXFELVL: u8 = 7;
+        pub const GINT: u8 = 0;
+        crate::cpu::field!(PTXFELVL, 1, ReadWrite);
+        crate::cpu::field!(TXFELVL, 1, ReadWrite);
+        crate::cpu::field!(GINT, 1, ReadWrite);
+    }
+    pub mod gusbcfg {
+        pub const CTXPKT: u8 = 31;
+        pub const FDMOD: u8 = 30;
+        pub const FHMOD: u8 = 29;
+        pub const TRDT: u8 = 10;
+        pub const HNPCAP: u8 = 9;
+        pub const SRPCAP: u8 = 8;
+        pub const PHYSEL: u8 = 6;
+        pub const TOCAL: u8 = 0;
+        crate::cpu::field!(CTXPKT, 1, ReadWrite);
+        crate::cpu::field!(FDMOD, 1, ReadWrite);
+        crate::cpu::field!(FHMOD, 1, ReadWrite);
+        crate::cpu::field!(TRDT, 4, ReadWrite);
+        crate::cpu::field!(HNPCAP, 1, ReadWrite);
+        crate::cpu::field!(SRPCAP, 1, ReadWrite);
+        crate::cpu::field!(PHYSEL, 1, ReadWrite);
+        crate::cpu::field!(TOCAL, 3, ReadWrite);
+    }
+    pub mod grstctl {
+        pub const AHBIDL: u8 = 31;
+        pub const TXFNUM: u8 = 6;
+        pub const TXFFLSH: u8 = 5;
+        pub const RXFFLSH: u8 = 4;
+        pub const FCRST: u8 = 2;
+        pub const HSRST: u8 = 1;
+        pub const CSRST: u8 = 0;
+        crate::cpu::field!(AHBIDL, 1, ReadOnly);
+        crate::cpu::field!(TXFNUM, 5, ReadWrite);
+        crate::cpu::field!(TXFFLSH, 1, ReadWrite);
+        crate::cpu::field!(RXFFLSH, 1, ReadWrite);
+        crate::cpu::field!(FCRST, 1, ReadWrite);
+        crate::cpu::field!(HSRST, 1, ReadWrite);
+        crate::cpu::field!(CSRST, 1, ReadWrite);
+    }
+    pub mod gintsts {
+        pub const WKUPINT: u8 = 31;
+        pub const SRQINT: u8 = 30;
+        pub const OEPINT: u8 = 19;
+        pub const IEPINT: u8 = 18;
+        pub const EOPF: u8 = 15;
+        pub const ENUMDNE: u8 = 13;
+        pub const USBRST: u8 = 12;
+        pub const USBSUSP: u8 = 11;
+        pub const ESUSP: u8 = 10;
+        pub const RXFLVL: u8 = 4;
+        pub const SOF: u8 = 3;
+        pub const OTGINT: u8 = 2;
+        pub const MMIS: u8 = 1;
+        pub const CMOD: u8 = 0;
+        crate::cpu::field!(WKUPINT, 1, WriteOneToClear);
+        crate::cpu::field!(SRQINT, 1, WriteOneToClear);
+        crate::cpu::field!(OEPINT, 1, ReadOnly);
+        crate::cpu::field!(IEPINT, 1, ReadOnly);
+        crate::cpu::field!(EOPF, 1, WriteOneToClear);
+        crate::cpu::field!(ENUMDNE, 1, WriteOneToClear);
+        crate::cpu::field!(USBRST, 1, WriteOneToClear);
+        crate::cpu::field!(USBSUSP, 1, WriteOneToClear);
+        crate::cpu::field!(ESUSP, 1, WriteOneToClear);
+        crate::cpu::field!(RXFLVL, 1, ReadOnly);
+        crate::cpu::field!(SOF, 1, WriteOneToClear);
+        crate::cpu::field!(OTGINT, 1, ReadOnly);
+        crate::cpu::field!(MMIS, 1, WriteOneToClear);
+        crate::cpu::field!(CMOD, 1, ReadOnly);
+    }
+    pub mod grxstsp {
+        pub const FRMNUM: u8 = 21;
+        pub const PKTSTS: u8 = 17;
+        pub const DPID: u8 = 15;
+        pub const BCNT: u8 = 4;
+        pub const EPNUM: u8 = 0;
+        crate::cpu::field!(FRMNUM, 4, ReadOnly);
+        crate::cpu::field!(PKTSTS, 4, ReadOnly);
+        crate::cpu::field!(DPID, 2, ReadOnly);
+        crate::cpu::field!(BCNT, 11, ReadOnly);
+        crate::cpu::field!(EPNUM, 4, ReadOnly);
+    }
+    pub mod grxfsiz {
+        pub const RXFD: u8 = 0;
+        crate::cpu::field!(RXFD, 16, ReadWrite);
+    }
+    pub mod dieptxf0 {
+        pub const TX0FD: u8 = 16;
+        pub const TX0FSA: u8 = 0;
+        crate::cpu::field!(TX0FD, 16, ReadWrite);
+        crate::cpu::field!(TX0FSA, 16, ReadWrite);
+    }
+    pub mod gccfg {
+        pub const NOVBUSSENS: u8 = 21;
+        pub const SOFOUTEN: u8 = 20;
+        pub const VBUSBSEN: u8 = 19;
+        pub const VBUSASEN: u8 = 18;
+        pub const PWRDWN: u8 = 16;
+        crate::cpu::field!(NOVBUSSENS, 1, ReadWrite);
+        crate::cpu::field!(SOFOUTEN, 1, ReadWrite);
+        crate::cpu::field!(VBUSBSEN, 1, ReadWrite);
+        crate::cpu::field!(VBUSASEN, 1, ReadWrite);
+        crate::cpu::field!(PWRDWN, 1, ReadWrite);
+    }
+    pub mod dieptxf1 {
+        pub const INEPTXFD: u8 = 16;
+        pub const INEPTXSA: u8 = 0;
+        crate::cpu::field!(INEPTXFD, 16, ReadWrite);
+        crate::cpu::field!(INEPTXSA, 16, ReadWrite);
+    }
+}
+
+pub mod OTG_FS_DEVICE {
+    pub mod dcfg {
+        pub const PFIVL: u8 = 11;
+        pub const DAD: u8 = 4;
+        pub const NZLSOHSK: u8 = 2;
+        pub const DSPD: u8 = 0;
+        crate::cpu::field!(PFIVL, 2, ReadWrite);
+        crate::cpu::field!(DAD, 7, ReadWrite);
+        crate::cpu::field!(NZLSOHSK, 1, ReadWrite);
+        crate::cpu::field!(DSPD, 2, ReadWrite);
+    }
+    pub mod dctl {
+        pub const POPRGDNE: u8 = 11;
+        pub const CGONAK: u8 = 10;
+        pub const SGONAK: u8 = 9;
+        pub const CGINAK: u8 = 8;
+        pub const SGINAK: u8 = 7;
+        pub const TCTL: u8 = 4;
+        pub const GONSTS: u8 = 3;
+        pub const GINSTS: u8 = 2;
+        pub const SDIS: u8 = 1;
+        pub const RWUSIG: u8 = 0;
+        crate::cpu::field!(POPRGDNE, 1, ReadWrite);
+        crate::cpu::field!(CGONAK, 1, WriteOnly);
+        crate::cpu::field!(SGONAK, 1, WriteOnly);
+        crate::cpu::field!(CGINAK, 1, WriteOnly);
+        crate::cpu::field!(SGINAK, 1, WriteOnly);
+        crate::cpu::field!(TCTL, 3, ReadWrite);
+        crate::cpu::field!(GONSTS, 1, ReadOnly);
+        crate::cpu::field!(GINSTS, 1, ReadOnly);
+        crate::cpu::field!(SDIS, 1, ReadWrite);
+        crate::cpu::field!(RWUSIG, 1, ReadWrite);
+    }
+    pub mod dsts {
+        pub const FNSOF: u8 = 8;
+        pub const EERR: u8 = 3;
+        pub const ENUMSPD: u8 = 1;
+        pub const SUSPSTS: u8 = 0;
+        crate::cpu::field!(FNSOF, 14, ReadOnly);
+        crate::cpu::field!(EERR, 1, ReadOnly);
+        crate::cpu::field!(ENUMSPD, 2, ReadOnly);
+        crate::cpu::field!(SUSPSTS, 1, ReadOnly);
+    }
+    pub mod diepmsk {
+        pub const INEPNEM: u8 = 6;
+        pub const INEPNMM: u8 = 5;
+        pub const ITTXFEMSK: u8 = 4;
+        pub const TOM: u8 = 3;
+        pub const EPDM: u8 = 1;
+        pub const XFRCM: u8 = 0;
+        crate::cpu::field!(INEPNEM, 1, ReadWrite);
+        crate::cpu::field!(INEPNMM, 1, ReadWrite);
+        crate::cpu::field!(ITTXFEMSK, 1, ReadWrite);
+        crate::cpu::field!(TOM, 1, ReadWrite);
+        crate::cpu::field!(EPDM, 1, ReadWrite);
+        crate::cpu::field!(XFRCM, 1, ReadWrite);
+    }
+    pub mod doepmsk {
+        pub const OTEPDM: u8 = 4;
+        pub const STUPM: u8 = 3;
+        pub const EPDM: u8 = 1;
+        pub const XFRCM: u8 = 0;
+        crate::cpu::field!(OTEPDM, 1, ReadWrite);
+        crate::cpu::field!(STUPM, 1, ReadWrite);
+        crate::cpu::field!(EPDM, 1, ReadWrite);
+        crate::cpu::field!(XFRCM, 1, ReadWrite);
+    }
+    pub mod daint {
+        pub const OEPINT: u8 = 16;
+        pub const IEPINT: u8 = 0;
+        crate::cpu::field!(OEPINT, 16, ReadOnly);
+        crate::cpu::field!(IEPINT, 16, ReadOnly);
+    }
+    pub mod diepctl0 {
+        pub const EPENA: u8 = 31;
+        pub const EPDIS: u8 = 30;
+        pub const SODDFRM: u8 = 29;
+        pub const SD0PID: u8 = 28;
+        pub const SNAK: u8 = 27;
+        pub const CNAK: u8 = 26;
+        pub const TXFNUM: u8 = 22;
+        pub const STALL: u8 = 21;
+        pub const EPTYP: u8 = 18;
+        pub const NAKSTS: u8 = 17;
+        pub const USBAEP: u8 = 15;
+        pub const MPSIZ: u8 = 0;
+        crate::cpu::field!(EPENA, 1, ReadWrite);
+        crate::cpu::field!(EPDIS, 1, ReadWrite);
+        crate::cpu::field!(SODDFRM, 1, WriteOnly);
+        crate::cpu::field!(SD0PID, 1, WriteOnly);
+        crate::cpu::field!(SNAK, 1, WriteOnly);
+        crate::cpu::field!(CNAK, 1, WriteOnly);
+        crate::cpu::field!(TXFNUM, 4, ReadWrite);
+        crate::cpu::field!(STALL, 1, ReadWrite);
+        crate::cpu::field!(EPTYP, 2, ReadOnly);
+        crate::cpu::field!(NAKSTS, 1, ReadOnly);
+        crate::cpu::field!(USBAEP, 1, ReadOnly);
+        crate::cpu::field!(MPSIZ, 2, ReadWrite);
+    }
+    pub mod diepint0 {
+        pub const TXFE: u8 = 7;
+        pub const INEPNE: u8 = 6;
+        pub const ITTXFE: u8 = 4;
+        pub const TOC: u8 = 3;
+        pub const EPDISD: u8 = 1;
+        pub const XFRC: u8 = 0;
+        crate::cpu::field!(TXFE, 1, ReadOnly);
+        crate::cpu::field!(INEPNE, 1, WriteOneToClear);
+        crate::cpu::field!(ITTXFE, 1, WriteOneToClear);
+        crate::cpu::field!(TOC, 1, WriteOneToClear);
+        crate::cpu::field!(EPDISD, 1, WriteOneToClear);
+        crate::cpu::field!(XFRC, 1, WriteOneToClear);
+    }
+    pub mod dieptsiz0 {
+        pub const MCNT: u8 = 29;
+        pub const PKTCNT: u8 = 19;
+        pub const XFRSIZ: u8 = 0;
+        crate::cpu::field!(MCNT, 2, ReadWrite);
+        crate::cpu::field!(PKTCNT, 2, ReadWrite);
+        crate::cpu::field!(XFRSIZ, 7, ReadWrite);
+    }
+    pub mod doepctl1 {
+        pub const EPENA: u8 = 31;
+        pub const EPDIS: u8 = 30;
+        pub const SODDFRM: u8 = 29;
+        pub const SD0PID: u8 = 28;
+        pub const SNAK: u8 = 27;
+        pub const CNAK: u8 = 26;
+        pub const STALL: u8 = 21;
+        pub const SNPM: u8 = 20;
+        pub const EPTYP: u8 = 18;
+        pub const NAKSTS: u8 = 17;
+        pub const USBAEP: u8 = 15;
+        pub const MPSIZ: u8 = 0;
+        crate::cpu::field!(EPENA, 1, ReadWrite);
+        crate::cpu::field!(EPDIS, 1, ReadWrite);
+        crate::cpu::field!(SODDFRM, 1, WriteOnly);
+        crate::cpu::field!(SD0PID, 1, WriteOnly);
+        crate::cpu::field!(SNAK, 1, WriteOnly);
+        crate::cpu::field!(CNAK, 1, WriteOnly);
+        crate::cpu::field!(STALL, 1, ReadWrite);
+        crate::cpu::field!(SNPM, 1, ReadWrite);
+        crate::cpu::field!(EPTYP, 2, ReadWrite);
+        crate::cpu::field!(NAKSTS, 1, ReadOnly);
+        crate::cpu::field!(USBAEP, 1, ReadWrite);
+        crate::cpu::field!(MPSIZ, 11, ReadWrite);
+    }
+    pub mod doepint0 {
+        pub const B2BSTUP: u8 = 6;
+        pub const OTEPDIS: u8 = 4;
+        pub const STUP: u8 = 3;
+        pub const EPDISD: u8 = 1;
+        pub const XFRC: u8 = 0;
+        crate::cpu::field!(B2BSTUP, 1, WriteOneToClear);
+        crate::cpu::field!(OTEPDIS, 1, WriteOneToClear);
+        crate::cpu::field!(STUP, 1, WriteOneToClear);
+        crate::cpu::field!(EPDISD, 1, WriteOneToClear);
+        crate::cpu::field!(XFRC, 1, WriteOneToClear);
+    }
+    pub mod doeptsiz0 {
+        pub const STUPCNT: u8 = 29;
+        pub const PKTCNT: u8 = 19;
+        pub const XFRSIZ: u8 = 0;
+        crate::cpu::field!(STUPCNT, 2, ReadWrite);
+        crate::cpu::field!(PKTCNT, 1, ReadWrite);
+        crate::cpu::field!(XFRSIZ, 7, ReadWrite);
+    }
+}
+
+pub mod CRC {
+    pub mod dr {
+        pub const DR: u8 = 0;
+        crate::cpu::field!(DR, 32, ReadWrite);
+    }
+    pub mod idr {
+        pub const IDR: u8 = 0;
+        crate::cpu::field!(IDR, 8, ReadWrite);
+    }
+    pub mod cr {
+        pub const RESET: u8 = 0;
+        crate::cpu::field!(RESET, 1, ReadWrite);
+    }
+}
+
+pub mod OTG_FS_PWRCLK {
+    pub mod pcgcctl {
+        pub const PHYSUSP: u8 = 4;
+        pub const GATEHCLK: u8 = 1;
+        pub const STPPCLK: u8 = 0;
+        crate::cpu::field!(PHYSUSP, 1, ReadOnly);
+        crate::cpu::field!(GATEHCLK, 1, ReadWrite);
+        crate::cpu::field!(STPPCLK, 1, ReadWrite);
     }
 }
 
@@ -1675,6 +3729,45 @@ pub struct TimGenReg {
     pub or: u32,
 }
 
+#[repr(C)]
+pub struct RtcReg {
+    pub tr: u32,
+    pub dr: u32,
+    pub cr: u32,
+    pub isr: u32,
+    pub prer: u32,
+    pub wutr: u32,
+    pub calibr: u32,
+    pub alrmar: u32,
+    pub alrmbr: u32,
+    pub wpr: u32,
+    pub ssr: u32,
+    pub shiftr: u32,
+    pub tstr: u32,
+    pub tsdr: u32,
+    pub tsssr: u32,
+    pub calr: u32,
+    pub tafcr: u32,
+    pub alrmassr: u32,
+    pub alrmbssr: u32,
+    reserved1: u32,
+    pub bkpr: [u32; 20],
+}
+
+#[repr(C)]
+pub struct PwrReg {
+    pub cr: u32,
+    pub csr: u32,
+}
+
+#[repr(C)]
+pub struct IwdgReg {
+    pub kr: u32,
+    pub pr: u32,
+    pub rlr: u32,
+    pub sr: u32,
+}
+
 #[repr(C)]
 pub struct FlashReg {
     pub acr: u32,
@@ -1684,6 +3777,140 @@ pub struct FlashReg {
     pub cr: u32,
     pub optcr: u32,
 }
+
+#[repr(C)]
+pub struct SyscfgReg {
+    pub memrm: u32,
+    pub pmc: u32,
+    pub exticr1: u32,
+    pub exticr2: u32,
+    pub exticr3: u32,
+    pub exticr4: u32,
+    reserved1: u32,
+    reserved2: u32,
+    pub cmpcr: u32,
+}
+
+#[repr(C)]
+pub struct ExtiReg {
+    pub imr: u32,
+    pub emr: u32,
+    pub rtsr: u32,
+    pub ftsr: u32,
+    pub swier: u32,
+    pub pr: u32,
+}
+#[repr(C)]
+pub struct OtgFsGlobalReg {
+    pub gotgctl: u32,
+    pub gotgint: u32,
+    pub gahbcfg: u32,
+    pub gusbcfg: u32,
+    pub grstctl: u32,
+    pub gintsts: u32,
+    pub gintmsk: u32,
+    pub grxstsr: u32,
+    pub grxstsp: u32,
+    pub grxfsiz: u32,
+    pub dieptxf0: u32,
+    pub hnptxsts: u32,
+    reserved1: [u32; 2],
+    pub gccfg: u32,
+    pub cid: u32,
+    reserved2: [u32; 48],
+    pub hptxfsiz: u32,
+    pub dieptxf1: u32,
+    pub dieptxf2: u32,
+    pub dieptxf3: u32,
+}
+
+#[repr(C)]
+pub struct OtgFsDeviceReg {
+    pub dcfg: u32,
+    pub dctl: u32,
+    pub dsts: u32,
+    reserved1: u32,
+    pub diepmsk: u32,
+    pub doepmsk: u32,
+    pub daint: u32,
+    pub daintmsk: u32,
+    reserved2: [u32; 2],
+    pub dvbusdis: u32,
+    pub dvbuspulse: u32,
+    reserved3: u32,
+    pub diepempmsk: u32,
+    reserved4: [u32; 50],
+    pub diepctl0: u32,
+    reserved5: u32,
+    pub diepint0: u32,
+    reserved6: u32,
+    pub dieptsiz0: u32,
+    reserved7: u32,
+    pub dtxfsts0: u32,
+    reserved8: u32,
+    pub diepctl1: u32,
+    reserved9: u32,
+    pub diepint1: u32,
+    reserved10: u32,
+    pub dieptsiz1: u32,
+    reserved11: u32,
+    pub dtxfsts1: u32,
+    reserved12: u32,
+    pub diepctl2: u32,
+    reserved13: u32,
+    pub diepint2: u32,
+    reserved14: u32,
+    pub dieptsiz2: u32,
+    reserved15: u32,
+    pub dtxfsts2: u32,
+    reserved16: u32,
+    pub diepctl3: u32,
+    reserved17: u32,
+    pub diepint3: u32,
+    reserved18: u32,
+    pub dieptsiz3: u32,
+    reserved19: u32,
+    pub dtxfsts3: u32,
+    reserved20: u32,
+    reserved21: [u32; 96],
+    pub doepctl0: u32,
+    reserved22: u32,
+    pub doepint0: u32,
+    reserved23: u32,
+    pub doeptsiz0: u32,
+    reserved24: [u32; 3],
+    pub doepctl1: u32,
+    reserved27: u32,
+    pub doepint1: u32,
+    reserved28: u32,
+    pub doeptsiz1: u32,
+    reserved29: [u32; 3],
+    pub doepctl2: u32,
+    reserved32: u32,
+    pub doepint2: u32,
+    reserved33: u32,
+    pub doeptsiz2: u32,
+    reserved34: [u32; 3],
+    pub doepctl3: u32,
+    reserved37: u32,
+    pub doepint3: u32,
+    reserved38: u32,
+    pub doeptsiz3: u32,
+    reserved39: [u32; 3],
+}
+
+#[repr(C)]
+pub struct CrcReg {
+    pub dr: u32,
+    pub idr: u32,
+    pub cr: u32,
+}
+
+#[repr(C)]
+pub struct OtgFsPwrclkReg {
+    pub pcgcctl: u32,
+}
+
 // Skipped peripheral: RNG
 // Skipped peripheral: DCMI
 // Skipped peripheral: FSMC
@@ -1691,7 +3918,8 @@ pub struct FlashReg {
 pub const DBG: *mut DbgReg = 0xE0042000 as *mut DbgReg;
 
 pub const DMA2: *mut DmaReg = 0x40026400 as *mut DmaReg;
-// Skipped peripheral: DMA1
+
+pub const DMA1: *mut DmaReg = 0x40026000 as *mut DmaReg;
 
 pub const RCC: *mut RccReg = 0x40023800 as *mut RccReg;
 // Skipped peripheral: GPIOI
@@ -1708,11 +3936,14 @@ pub const GPIOC: *mut GpioReg = 0x40020800 as *mut GpioReg;
 pub const GPIOB: *mut GpioReg = 0x40020400 as *mut GpioReg;
 
 pub const GPIOA: *mut GpioReg = 0x40020000 as *mut GpioReg;
-// Skipped peripheral: SYSCFG
+
+pub const SYSCFG: *mut SyscfgReg = 0x40013800 as *mut SyscfgReg;
 
 pub const SPI1: *mut Spi1Reg = 0x40013000 as *mut Spi1Reg;
-// Skipped peripheral: SPI2
-// Skipped peripheral: SPI3
+
+pub const SPI2: *mut Spi1Reg = 0x40003800 as *mut Spi1Reg;
+
+pub const SPI3: *mut Spi1Reg = 0x40003C00 as *mut Spi1Reg;
 // Skipped peripheral: I2S2EXT
 // Skipped peripheral: I2S3EXT
 // Skipped peripheral: SDIO
@@ -1725,14 +3956,17 @@ pub const USART1: *mut UsartReg = 0x40011000 as *mut UsartReg;
 // Skipped peripheral: USART2
 // Skipped peripheral: USART3
 // Skipped peripheral: DAC
-// Skipped peripheral: PWR
+
+pub const PWR: *mut PwrReg = 0x40007000 as *mut PwrReg;
 // Skipped peripheral: I2C3
 // Skipped peripheral: I2C2
 // Skipped peripheral: I2C1
-// Skipped peripheral: IWDG
+
+pub const IWDG: *mut IwdgReg = 0x40003000 as *mut IwdgReg;
 
 pub const WWDG: *mut WwdgReg = 0x40002C00 as *mut WwdgReg;
-// Skipped peripheral: RTC
+
+pub const RTC: *mut RtcReg = 0x40002800 as *mut RtcReg;
 // Skipped peripheral: UART4
 // Skipped peripheral: UART5
 // Skipped peripheral: C_ADC
@@ -1741,8 +3975,10 @@ pub const TIM1: *mut TimAdvReg = 0x40010000 as *mut TimAdvReg;
 // Skipped peripheral: TIM8
 
 pub const TIM2: *mut TimGenReg = 0x40000000 as *mut TimGenReg;
-// Skipped peripheral: TIM3
-// Skipped peripheral: TIM4
+
+pub const TIM3: *mut TimGenReg = 0x40000400 as *mut TimGenReg;
+
+pub const TIM4: *mut TimGenReg = 0x40000800 as *mut TimGenReg;
 // Skipped peripheral: TIM5
 // Skipped peripheral: TIM9
 // Skipped peripheral: TIM12
@@ -1756,16 +3992,21 @@ pub const TIM2: *mut TimGenReg = 0x40000000 as *mut TimGenReg;
 // Skipped peripheral: ETHERNET_MMC
 // Skipped peripheral: ETHERNET_PTP
 // Skipped peripheral: ETHERNET_DMA
-// Skipped peripheral: CRC
-// Skipped peripheral: OTG_FS_GLOBAL
+
+pub const CRC: *mut CrcReg = 0x40023000 as *mut CrcReg;
+
+pub const OTG_FS_GLOBAL: *mut OtgFsGlobalReg = 0x50000000 as *mut OtgFsGlobalReg;
 // Skipped peripheral: OTG_FS_HOST
-// Skipped peripheral: OTG_FS_DEVICE
-// Skipped peripheral: OTG_FS_PWRCLK
+
+pub const OTG_FS_DEVICE: *mut OtgFsDeviceReg = 0x50000800 as *mut OtgFsDeviceReg;
+
+pub const OTG_FS_PWRCLK: *mut OtgFsPwrclkReg = 0x50000E00 as *mut OtgFsPwrclkReg;
 // Skipped peripheral: CAN1
 // Skipped peripheral: CAN2
 
 pub const FLASH: *mut FlashReg = 0x40023C00 as *mut FlashReg;
-// Skipped peripheral: EXTI
+
+pub const EXTI: *mut ExtiReg = 0x40013C00 as *mut ExtiReg;
 // Skipped peripheral: OTG_HS_GLOBAL
 // Skipped peripheral: OTG_HS_HOST
 // Skipped peripheral: OTG_HS_DEVICE
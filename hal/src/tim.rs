@@ -0,0 +1,427 @@
+//! # TIM Module
+//!
+//! This module drives the PWM outputs and input capture of the general-purpose
+//! and advanced timers. TIM2 is not included as it is the free running
+//! microsecond clock used by the `timer` module, which also provides alarms.
+//!
+//! ## Enums
+//!
+//! - `Timer`: Selects TIM1, TIM3, or (on the STM32F405) TIM4.
+//! - `Channel`: Selects one of the four capture/compare channels of a timer.
+//!
+//! ## Traits
+//!
+//! - `ChannelSignal`: A channel of a timer as the signal of its pin, such as
+//!   `af::Tim3Ch1`, so the pins come from the alternate function tables.
+//!
+//! ## Functions
+//!
+//! - `init`: Starts a timer counting at a given tick rate and period.
+//! - `pwm_init`: Starts a timer at a given PWM frequency.
+//! - `pwm_enable`: Connects a channel to a pin as a PWM output.
+//! - `set_duty`: Sets the duty cycle of a PWM output.
+//! - `capture_enable`: Connects a pin to a channel for input capture.
+//! - `capture`: Returns the counter value latched by the last captured edge.
+//! - `pwm_divider`: Computes the prescaler and auto-reload for a PWM frequency.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use hal::cpu;
+//! use hal::gpio::{self, af};
+//! use hal::tim::{self, Channel, Timer};
+//!
+//! cpu::init();
+//! let pins = gpio::take().unwrap();
+//!
+//! // 1 kHz PWM at 25% duty on PA6, which is TIM3 channel 1
+//! tim::pwm_init(Timer::Tim3, 1_000);
+//! tim::pwm_enable::<af::Tim3Ch1>(pins.pa6);
+//! tim::set_duty(Timer::Tim3, Channel::Ch1, 250);
+//! ```
+
+use core::ptr;
+
+use super::clock;
+use super::cpu;
+use super::cpu::*;
+use super::gpio::{self, af, AltFn, Edge};
+
+use super::cpu::TIM_ADV::bdtr;
+use super::cpu::TIM_GEN::{cr1, egr, sr};
+
+/// Largest duty cycle passed to `set_duty`, which is 100%.
+pub const DUTY_MAX: u16 = 1000;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Timer {
+    Tim1,
+    Tim3,
+    #[cfg(feature = "stm32f405")]
+    Tim4,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Channel {
+    Ch1 = 0,
+    Ch2 = 1,
+    Ch3 = 2,
+    Ch4 = 3,
+}
+
+/// A channel of a timer as the signal its pin serves, such as
+/// `af::Tim3Ch1`, so `pwm_enable` and `capture_enable` only take pins that
+/// work for the channel.
+pub trait ChannelSignal {
+    const TIMER: Timer;
+    const CHANNEL: Channel;
+}
+
+macro_rules! channel_signals {
+    ($($signal:ident: $timer:ident $channel:ident;)+) => {
+        $(
+            impl ChannelSignal for af::$signal {
+                const TIMER: Timer = Timer::$timer;
+                const CHANNEL: Channel = Channel::$channel;
+            }
+        )+
+    };
+}
+
+channel_signals! {
+    Tim1Ch1: Tim1 Ch1;
+    Tim1Ch2: Tim1 Ch2;
+    Tim1Ch3: Tim1 Ch3;
+    Tim1Ch4: Tim1 Ch4;
+    Tim3Ch1: Tim3 Ch1;
+    Tim3Ch2: Tim3 Ch2;
+    Tim3Ch3: Tim3 Ch3;
+    Tim3Ch4: Tim3 Ch4;
+}
+
+#[cfg(feature = "stm32f405")]
+channel_signals! {
+    Tim4Ch1: Tim4 Ch1;
+    Tim4Ch2: Tim4 Ch2;
+    Tim4Ch3: Tim4 Ch3;
+    Tim4Ch4: Tim4 Ch4;
+}
+
+struct TimerInfo {
+    regs: *mut cpu::TimGenReg,
+    /// Frequency of the timer kernel clock in Hz.
+    clock: u32,
+    /// True for the advanced timer that needs the main output enabled.
+    advanced: bool,
+}
+
+// TIM1 is on APB2, TIM3 and TIM4 are on APB1
+#[cfg(feature = "stm32f405")]
+fn info(timer: Timer) -> TimerInfo {
+    match timer {
+        Timer::Tim1 => TimerInfo {
+            regs: TIM1 as *mut cpu::TimGenReg,
+            clock: clock::clocks().timclk2(),
+            advanced: true,
+        },
+        Timer::Tim3 => TimerInfo {
+            regs: TIM3,
+            clock: clock::clocks().timclk1(),
+            advanced: false,
+        },
+        Timer::Tim4 => TimerInfo {
+            regs: TIM4,
+            clock: clock::clocks().timclk1(),
+            advanced: false,
+        },
+    }
+}
+
+// all timers are on the one APB
+#[cfg(feature = "stm32f072")]
+fn info(timer: Timer) -> TimerInfo {
+    match timer {
+        Timer::Tim1 => TimerInfo {
+            regs: TIM1 as *mut cpu::TimGenReg,
+            clock: clock::clocks().timclk2(),
+            advanced: true,
+        },
+        Timer::Tim3 => TimerInfo {
+            regs: TIM3,
+            clock: clock::clocks().timclk1(),
+            advanced: false,
+        },
+    }
+}
+
+/// Turns on the clock of `timer`, so its registers can be written.
+fn enable_clock(timer: Timer) {
+    match timer {
+        Timer::Tim1 => cpu::write!(RCC.apb2enr.TIM1EN, 1),
+        Timer::Tim3 => cpu::write!(RCC.apb1enr.TIM3EN, 1),
+        #[cfg(feature = "stm32f405")]
+        Timer::Tim4 => cpu::write!(RCC.apb1enr.TIM4EN, 1),
+    }
+}
+
+/// Computes the prescaler and auto-reload values that give `freq` Hz from a
+/// timer clock of `clock` Hz with a 16 bit counter. The smallest prescaler is
+/// used so that the PWM has the most steps of resolution.
+///
+/// Returns `(psc, arr)` as written to the registers, so each is one less than
+/// the divider.
+pub fn pwm_divider(clock: u32, freq: u32) -> (u32, u32) {
+    assert!(freq > 0 && freq <= clock / 2);
+
+    let ticks = clock / freq;
+    let psc = (ticks - 1) / 0x1_0000;
+    let arr = ticks / (psc + 1) - 1;
+    (psc, arr)
+}
+
+/// Starts `timer` counting up at `tick_freq` Hz and wrapping after `period`
+/// ticks. The counter and prescaler are 16 bits, so `period` must not
+/// exceed 0x10000 and `tick_freq` must be at least 1/0x10000 of the timer
+/// clock.
+#[inline(never)]
+pub fn init(timer: Timer, tick_freq: u32, period: u32) {
+    let info = info(timer);
+    assert!(tick_freq > 0 && tick_freq <= info.clock);
+    assert!(
+        info.clock / tick_freq <= 0x1_0000,
+        "tick rate too slow for the 16 bit prescaler"
+    );
+    assert!(period > 0 && period <= 0x1_0000);
+
+    enable_clock(timer);
+    start(&info, info.clock / tick_freq - 1, period - 1);
+}
+
+/// Starts `timer` with a period of `freq` Hz for PWM outputs.
+///
+/// Returns the number of timer ticks in one period, which is the resolution
+/// of the duty cycle.
+#[inline(never)]
+pub fn pwm_init(timer: Timer, freq: u32) -> u32 {
+    let info = info(timer);
+    let (psc, arr) = pwm_divider(info.clock, freq);
+
+    enable_clock(timer);
+    start(&info, psc, arr);
+    arr + 1
+}
+
+fn start(info: &TimerInfo, psc: u32, arr: u32) {
+    let tim = info.regs;
+
+    cpu::write!(tim.cr1.cr1::CEN, 0);
+
+    cpu::write!(tim.psc, psc);
+    cpu::write!(tim.arr, arr);
+    cpu::write!(tim.cr1.cr1::ARPE, 1); // buffer the auto-reload
+    cpu::write!(tim.cr1.cr1::DIR, 0); // count up
+    cpu::write!(tim.cr1.cr1::CMS, 0b00); // edge aligned
+
+    // force load of prescaler and auto-reload
+    cpu::write!(tim.egr.egr::UG, 1);
+
+    if info.advanced {
+        // the outputs of the advanced timer are off until the main output is enabled
+        let adv = tim as *mut cpu::TimAdvReg;
+        cpu::write!(adv.bdtr.bdtr::MOE, 1);
+    }
+
+    cpu::write!(tim.cr1.cr1::CEN, 1);
+}
+
+/// Sets the 8 bits of CCMR1 or CCMR2 that configure `channel`.
+fn set_mode(tim: *mut cpu::TimGenReg, channel: Channel, mode: u32) {
+    let ch = channel as u8;
+    if ch < 2 {
+        cpu::write!(tim.ccmr1[ch*8;8], mode);
+    } else {
+        cpu::write!(tim.ccmr2[(ch-2)*8;8], mode);
+    }
+}
+
+/// Connects the channel `C` to `pin` and starts it as a PWM output with 0%
+/// duty. Call `pwm_init` for its timer first.
+#[inline(never)]
+pub fn pwm_enable<C: ChannelSignal>(pin: impl AltFn<C>) {
+    let channel = C::CHANNEL;
+    let tim = info(C::TIMER).regs;
+    let ch = channel as u8;
+
+    cpu::write!(tim.ccer[ch*4;1], 0); // disable while changing the mode
+
+    set_compare(tim, channel, 0);
+
+    // OCxM is PWM mode 1 and OCxPE preloads the compare value
+    set_mode(tim, channel, (0b110 << 4) | (1 << 3));
+
+    cpu::write!(tim.ccer[ch*4+1;1], 0); // active high
+    cpu::write!(tim.ccer[ch*4;1], 1);
+
+    let (pin, af) = gpio::alt_fn(&pin);
+    pin.enable_clock();
+    pin.alt_fun(af, false);
+}
+
+/// Sets the duty cycle of a PWM output as parts per thousand of the period,
+/// from 0 (always low) to `DUTY_MAX` (always high).
+#[inline(never)]
+pub fn set_duty(timer: Timer, channel: Channel, duty: u16) {
+    assert!(duty <= DUTY_MAX);
+
+    let tim = info(timer).regs;
+    let period = cpu::read!(tim.arr) + 1;
+    let compare = (period as u64 * duty as u64 / DUTY_MAX as u64) as u32;
+
+    set_compare(tim, channel, compare);
+}
+
+/// Connects `pin` to the channel `C` and latches the counter on every `edge`
+/// of the input. Call `init` for its timer first to set the tick rate of the
+/// counter.
+#[inline(never)]
+pub fn capture_enable<C: ChannelSignal>(pin: impl AltFn<C>, edge: Edge) {
+    let channel = C::CHANNEL;
+    let tim = info(C::TIMER).regs;
+    let ch = channel as u8;
+
+    cpu::write!(tim.ccer[ch*4;1], 0); // disable while changing the mode
+
+    // CCxS maps the channel to its own input, no filter, no prescaler
+    set_mode(tim, channel, 0b01);
+
+    let (p, np) = match edge {
+        Edge::Rising => (0, 0),
+        Edge::Falling => (1, 0),
+        Edge::Both => (1, 1),
+    };
+    cpu::write!(tim.ccer[ch*4+1;1], p);
+    cpu::write!(tim.ccer[ch*4+3;1], np);
+
+    let (pin, af) = gpio::alt_fn(&pin);
+    pin.enable_clock();
+    pin.alt_fun(af, false);
+
+    // drop any stale capture
+    clear_flag(tim, channel);
+
+    cpu::write!(tim.ccer[ch*4;1], 1);
+}
+
+/// Returns the counter value latched by the last edge on `channel`, or None
+/// if no edge has been captured since the previous call.
+#[inline(never)]
+pub fn capture(timer: Timer, channel: Channel) -> Option<u32> {
+    let tim = info(timer).regs;
+
+    if !flag_set(tim, channel) {
+        return None;
+    }
+
+    // reading the compare register also clears the flag, but clear it in
+    // case the read is reordered
+    let value = get_compare(tim, channel);
+    clear_flag(tim, channel);
+    Some(value)
+}
+
+/// Returns true if the CCxIF flag of `channel` is set by a capture or a
+/// compare match.
+fn flag_set(tim: *mut cpu::TimGenReg, channel: Channel) -> bool {
+    let flag = match channel {
+        Channel::Ch1 => cpu::read!(tim.sr.sr::CC1IF),
+        Channel::Ch2 => cpu::read!(tim.sr.sr::CC2IF),
+        Channel::Ch3 => cpu::read!(tim.sr.sr::CC3IF),
+        Channel::Ch4 => cpu::read!(tim.sr.sr::CC4IF),
+    };
+    flag != 0
+}
+
+/// Clears the CCxIF flag of `channel`, leaving the other flags.
+fn clear_flag(tim: *mut cpu::TimGenReg, channel: Channel) {
+    match channel {
+        Channel::Ch1 => cpu::clear!(tim.sr.sr::CC1IF),
+        Channel::Ch2 => cpu::clear!(tim.sr.sr::CC2IF),
+        Channel::Ch3 => cpu::clear!(tim.sr.sr::CC3IF),
+        Channel::Ch4 => cpu::clear!(tim.sr.sr::CC4IF),
+    }
+}
+
+fn set_compare(tim: *mut cpu::TimGenReg, channel: Channel, value: u32) {
+    match channel {
+        Channel::Ch1 => {
+            cpu::write!(tim.ccr1, value);
+        }
+        Channel::Ch2 => {
+            cpu::write!(tim.ccr2, value);
+        }
+        Channel::Ch3 => {
+            cpu::write!(tim.ccr3, value);
+        }
+        Channel::Ch4 => {
+            cpu::write!(tim.ccr4, value);
+        }
+    }
+}
+
+fn get_compare(tim: *mut cpu::TimGenReg, channel: Channel) -> u32 {
+    match channel {
+        Channel::Ch1 => cpu::read!(tim.ccr1),
+        Channel::Ch2 => cpu::read!(tim.ccr2),
+        Channel::Ch3 => cpu::read!(tim.ccr3),
+        Channel::Ch4 => cpu::read!(tim.ccr4),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sim;
+
+    #[test]
+    fn test_pwm_divider() {
+        // fast PWM needs no prescaler
+        assert_eq!(pwm_divider(84_000_000, 20_000), (0, 4199));
+
+        // 100 Hz from 84MHz needs 840,000 ticks which does not fit in 16 bits
+        let (psc, arr) = pwm_divider(84_000_000, 100);
+        assert_eq!(psc, 12);
+        assert!(arr <= 0xFFFF);
+        assert_eq!((psc + 1) * (arr + 1), 840_000 - 840_000 % (psc + 1));
+
+        // slowest frequency with the largest prescaler
+        let (psc, arr) = pwm_divider(168_000_000, 100);
+        assert!(psc <= 0xFFFF && arr <= 0xFFFF);
+    }
+
+    #[test]
+    fn test_init() {
+        let _sim = sim::Guard::new();
+        crate::init(16_000_000);
+
+        init(Timer::Tim3, 1_000_000, 1_000);
+        assert_eq!(cpu::read!(RCC.apb1enr.TIM3EN), 1);
+        let psc = clock::clocks().timclk1() / 1_000_000 - 1;
+        assert_eq!(cpu::read!(TIM3.psc), psc);
+        assert_eq!(cpu::read!(TIM3.arr), 999);
+
+        // only init and pwm_init turn on the clock
+        cpu::write!(RCC.apb1enr.TIM3EN, 0);
+        set_duty(Timer::Tim3, Channel::Ch1, 500);
+        assert_eq!(cpu::read!(RCC.apb1enr.TIM3EN), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "tick rate too slow for the 16 bit prescaler")]
+    fn test_init_prescaler_range() {
+        let _sim = sim::Guard::new();
+        crate::init(16_000_000);
+
+        // 100 Hz from a timer clock of 16 MHz or more needs a prescaler over 16 bits
+        init(Timer::Tim3, 100, 1_000);
+    }
+}
@@ -10,12 +10,27 @@
 //! ## Functions
 //!
 //! - `init2`: Initializes the TIM2 timer with a 1 MHz prescaler and sets it to wrap around every hour.
-//! - `handle_tim2_irq`: Handles the TIM2 interrupt by clearing the update interrupt flag.
-//! - `current_time`: Retrieves the current time in microseconds from the TIM2 timer.
+//! - `handle_tim2_irq`: Handles the TIM2 interrupt by clearing the update interrupt flag
+//!   and calling the callbacks of any expired alarms.
+//! - `now`: Returns the current time as an `Instant` from the 64 bit monotonic clock.
+//! - `current_time`: Retrieves the current time in microseconds from the monotonic clock.
+//! - `set_alarm`: Schedules a one-shot or periodic callback on one of the alarms.
+//! - `cancel_alarm`: Stops an alarm.
 //!
 //! ## Structures
 //!
 //! - `MicroSeconds`: Represents a time duration in microseconds and provides methods for time manipulation.
+//! - `Instant`: A point in time, in microseconds since the clock started.
+//! - `Duration`: A span of time in microseconds.
+//! - `Alarm`: One of the four alarms, which use the compare channels of TIM2.
+//!
+//! TIM2 only counts to one hour, so the update interrupt counts the overflows
+//! and `now` combines the two into a 64 bit count of microseconds that does not
+//! wrap. With the `std` feature the host monotonic clock is used instead, also
+//! starting from zero, and moved ahead by the delays of the `delay` module.
+//!
+//! The alarm callbacks are called from the TIM2 interrupt, so they should be short.
+//! See the `tim` module for PWM and input capture on the other timers.
 //!
 //! ## Example
 //!
@@ -30,79 +45,244 @@
 //!
 //!     // Get the current time
 //!     let current_time = timer::current_time();
+//!
+//!     // Measure an interval
+//!     let start = timer::now();
+//!     let elapsed = timer::now() - start;
+//!     assert!(elapsed < timer::Duration::from_secs(1));
 //! }
 //! ```
 
-#[cfg(not(feature = "stm32f072"))]
+use core::ops::{Add, AddAssign, Sub, SubAssign};
 use core::ptr;
 
 #[cfg(feature = "std")]
 extern crate std;
 
 #[cfg(feature = "std")]
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::sync::OnceLock;
 
-#[cfg(not(feature = "stm32f072"))]
+use super::clock;
 use super::cpu;
 
-#[cfg(not(feature = "stm32f072"))]
 use super::cpu::*;
 
-#[cfg(feature = "stm32f405")]
 pub use super::cpu::TIM_GEN as TIM2;
 
-const TIME_WRAP_AROUND: u32 = 3600 * 1000_000; // 1 hour@1Mhz
+const TIME_WRAP_AROUND: u32 = 3600 * 1_000_000; // 1 hour@1Mhz
+
+/// Number of times TIM2 has wrapped, counted by the update interrupt.
+static OVERFLOWS: Global<u32> = Global::new(0);
 
-#[cfg(feature = "stm32f405")]
 #[inline(never)]
 pub fn init2() {
     // enable TIM2 clock
-    //cpu::write!( RCC.apb2enr[TIM2EN;1], 1);
-    cpu::write!( RCC.apb1enr[TIM2EN;1], 1);
+    cpu::write!(RCC.apb1enr.TIM2EN, 1);
 
-    // TIM1 is apb2 timer clock is 168MHz and 16 bits
-    // TIM2 is apb1 timer clock is 84MHz and 32 bits
+    // TIM2 is on apb1 and 32 bits
 
     // set prescaler for 1MHz
-    cpu::write!(TIM2.psc, 84 - 1);
+    cpu::write!(TIM2.psc, clock::clocks().timclk1() / 1_000_000 - 1);
 
     // set auto-reload for 3600 seconds (1 hour)
     cpu::write!(TIM2.arr, TIME_WRAP_AROUND - 1);
 
     // force load of prescaler and auto-reload
-    cpu::write!( TIM2.egr[UG;1], 1);
+    cpu::write!(TIM2.egr.UG, 1);
 
     // enable update interrupt
-    cpu::write!( TIM2.dier[UIE;1], 1);
+    cpu::write!(TIM2.dier.UIE, 1);
 
     // enable counter
-    cpu::write!( TIM2.cr1[CEN;1], 1);
+    cpu::write!(TIM2.cr1.CEN, 1);
 
     // enable interrupt in NVIC
-    const TIM2_IRQ_NUM: usize = 28;
-    const IRQ_INDEX: usize = TIM2_IRQ_NUM / 32;
-    const IRQ_BIT: usize = TIM2_IRQ_NUM % 32;
-
-    cpu::write!(NVIC.iser[IRQ_INDEX], 1 << IRQ_BIT);
+    cpu::enable_irq(Interrupt::Tim2);
 }
 
-#[cfg(feature = "stm32f405")]
 #[inline(never)]
 pub fn handle_tim2_irq() {
-    // clear update interrupt flag
-    cpu::write!(TIM2.sr[UIF;1], 0);
+    let sr = cpu::read!(TIM2.sr);
+
+    if sr & (1 << TIM2::sr::UIF) != 0 {
+        // clear update interrupt flag, writing 1 to the other flags has no effect
+        cpu::clear!(TIM2.sr.UIF);
+
+        unsafe {
+            let n = OVERFLOWS.get();
+            OVERFLOWS.set(n.wrapping_add(1));
+        }
+    }
+
+    let dier = cpu::read!(TIM2.dier);
+    for alarm in [Alarm::A1, Alarm::A2, Alarm::A3, Alarm::A4] {
+        let flag = alarm.flag_bit();
+        if (sr & dier & (1 << flag)) != 0 {
+            clear_flag(alarm);
+            fire_alarm(alarm);
+        }
+    }
+}
+
+/// One of the alarms. Each alarm uses one compare channel of TIM2, so they
+/// have the same microsecond resolution as `current_time`.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Alarm {
+    A1 = 0,
+    A2 = 1,
+    A3 = 2,
+    A4 = 3,
+}
+
+impl Alarm {
+    /// Bit of the CCxIF flag in SR and of the CCxIE enable in DIER.
+    fn flag_bit(&self) -> u8 {
+        *self as u8 + 1
+    }
+}
+
+#[derive(Copy, Clone)]
+struct AlarmState {
+    callback: Option<fn()>,
+    period: u32,
+}
+
+static ALARMS: Global<[AlarmState; 4]> = Global::new(
+    [AlarmState {
+        callback: None,
+        period: 0,
+    }; 4],
+);
+
+fn alarm_state(alarm: Alarm) -> AlarmState {
+    unsafe { ptr::read_volatile(ptr::addr_of!((*ALARMS.as_ptr())[alarm as usize])) }
+}
+
+fn set_alarm_state(alarm: Alarm, state: AlarmState) {
+    unsafe { ptr::write_volatile(ptr::addr_of_mut!((*ALARMS.as_ptr())[alarm as usize]), state) }
+}
+
+/// Adds `delay` microseconds to the counter value `from`, wrapping the same
+/// way as the TIM2 counter does.
+fn wrap_add(from: u32, delay: u32) -> u32 {
+    ((from as u64 + delay as u64) % TIME_WRAP_AROUND as u64) as u32
+}
+
+fn set_compare(alarm: Alarm, value: u32) {
+    match alarm {
+        Alarm::A1 => {
+            cpu::write!(TIM2.ccr1, value);
+        }
+        Alarm::A2 => {
+            cpu::write!(TIM2.ccr2, value);
+        }
+        Alarm::A3 => {
+            cpu::write!(TIM2.ccr3, value);
+        }
+        Alarm::A4 => {
+            cpu::write!(TIM2.ccr4, value);
+        }
+    }
+}
+
+fn get_compare(alarm: Alarm) -> u32 {
+    match alarm {
+        Alarm::A1 => cpu::read!(TIM2.ccr1),
+        Alarm::A2 => cpu::read!(TIM2.ccr2),
+        Alarm::A3 => cpu::read!(TIM2.ccr3),
+        Alarm::A4 => cpu::read!(TIM2.ccr4),
+    }
 }
 
-#[cfg(feature = "stm32f072")]
+/// Enables or disables the CCxIE interrupt of the channel of `alarm`.
+fn set_irq(alarm: Alarm, enable: bool) {
+    let value = enable as u32;
+    match alarm {
+        Alarm::A1 => cpu::write!(TIM2.dier.CC1IE, value),
+        Alarm::A2 => cpu::write!(TIM2.dier.CC2IE, value),
+        Alarm::A3 => cpu::write!(TIM2.dier.CC3IE, value),
+        Alarm::A4 => cpu::write!(TIM2.dier.CC4IE, value),
+    }
+}
+
+/// Clears the CCxIF flag of the channel of `alarm`, leaving the other flags.
+fn clear_flag(alarm: Alarm) {
+    match alarm {
+        Alarm::A1 => cpu::clear!(TIM2.sr.CC1IF),
+        Alarm::A2 => cpu::clear!(TIM2.sr.CC2IF),
+        Alarm::A3 => cpu::clear!(TIM2.sr.CC3IF),
+        Alarm::A4 => cpu::clear!(TIM2.sr.CC4IF),
+    }
+}
+
+fn fire_alarm(alarm: Alarm) {
+    let state = alarm_state(alarm);
+
+    if state.period == 0 {
+        cancel_alarm(alarm);
+    } else {
+        // schedule from the last compare value so periodic alarms do not drift
+        set_compare(alarm, wrap_add(get_compare(alarm), state.period));
+    }
+
+    if let Some(callback) = state.callback {
+        callback();
+    }
+}
+
+/// Schedules `callback` to be called from the TIM2 interrupt in `delay`
+/// microseconds. If `period` is not zero, the callback is then called again
+/// every `period` microseconds until the alarm is cancelled.
+///
+/// Requires `init2` to have been called. Setting an alarm that is already
+/// running replaces it. Both `delay` and `period` must be less than one hour.
 #[inline(never)]
-pub fn handle_tim2_irq() {}
+pub fn set_alarm(alarm: Alarm, delay: u32, period: u32, callback: fn()) {
+    assert!(delay > 0 && delay < TIME_WRAP_AROUND);
+    assert!(period < TIME_WRAP_AROUND);
+
+    // disable while changing it
+    set_irq(alarm, false);
+
+    set_alarm_state(
+        alarm,
+        AlarmState {
+            callback: Some(callback),
+            period,
+        },
+    );
 
-#[cfg(feature = "stm32f405")]
-#[cfg(target_arch = "arm")]
+    // channel is frozen output compare, so only the flag is set on a match
+    let ch = alarm as u8;
+    if ch < 2 {
+        cpu::write!(TIM2.ccmr1[ch*8;8], 0);
+    } else {
+        cpu::write!(TIM2.ccmr2[(ch-2)*8;8], 0);
+    }
+
+    let now = cpu::read!(TIM2.cnt);
+    set_compare(alarm, wrap_add(now, delay));
+
+    clear_flag(alarm);
+    set_irq(alarm, true);
+}
+
+/// Stops `alarm` so its callback is no longer called.
 #[inline(never)]
-pub fn current_time() -> MicroSeconds {
-    let time_us = cpu::read!(TIM2.cnt);
-    MicroSeconds(time_us as u64)
+pub fn cancel_alarm(alarm: Alarm) {
+    set_irq(alarm, false);
+    set_alarm_state(
+        alarm,
+        AlarmState {
+            callback: None,
+            period: 0,
+        },
+    );
+}
+
+/// Returns true if `alarm` has a callback scheduled.
+pub fn alarm_active(alarm: Alarm) -> bool {
+    alarm_state(alarm).callback.is_some()
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
@@ -116,22 +296,273 @@ impl MicroSeconds {
     pub fn as_u64(&self) -> u64 {
         self.0
     }
+}
+
+impl Sub for MicroSeconds {
+    type Output = MicroSeconds;
 
-    pub fn sub(self, other: Self) -> MicroSeconds {
-        if other.0 > self.0 {
-            // wrap-around occurred (every 1 hour)
-            let max: u64 = TIME_WRAP_AROUND as u64;
-            return MicroSeconds(max - other.0 + self.0 + 1);
+    /// Returns the time from `other` to this. Times from `current_time` do
+    /// not wrap, so an `other` later than this gives 0.
+    fn sub(self, other: Self) -> MicroSeconds {
+        MicroSeconds(self.0.saturating_sub(other.0))
+    }
+}
+
+/// Returns the number of TIM2 overflows counted so far.
+pub fn overflows() -> u32 {
+    unsafe { OVERFLOWS.get() }
+}
+
+/// Combines the overflow count and the TIM2 counter into microseconds.
+///
+/// If the counter wrapped but the update interrupt has not run yet, because
+/// interrupts are disabled or this is called from a higher priority interrupt,
+/// `update_pending` is set and a small `count` belongs to the next period.
+pub fn extend(overflows: u32, count: u32, update_pending: bool) -> u64 {
+    let mut periods = overflows as u64;
+    if update_pending && count < TIME_WRAP_AROUND / 2 {
+        periods += 1;
+    }
+    periods * TIME_WRAP_AROUND as u64 + count as u64
+}
+
+/// Returns the time since `init2` from the 64 bit monotonic clock.
+#[cfg(not(feature = "std"))]
+#[inline(never)]
+pub fn now() -> Instant {
+    loop {
+        let before = overflows();
+        let count = cpu::read!(TIM2.cnt);
+        let pending = cpu::read!(TIM2.sr.UIF) != 0;
+
+        // retry if the update interrupt ran while reading the counter
+        if overflows() == before {
+            return Instant(extend(before, count, pending));
         }
-        MicroSeconds(self.0 - other.0)
     }
 }
 
+/// Returns the time since the first call from the host monotonic clock,
+/// plus the virtual time the delays have skipped.
 #[cfg(feature = "std")]
+pub fn now() -> Instant {
+    static START: OnceLock<std::time::Instant> = OnceLock::new();
+    let start = START.get_or_init(std::time::Instant::now);
+    Instant(start.elapsed().as_micros() as u64 + super::sim::time_ahead())
+}
+
+#[inline(never)]
 pub fn current_time() -> MicroSeconds {
-    let start = SystemTime::now();
-    match start.duration_since(UNIX_EPOCH) {
-        Ok(n) => MicroSeconds((n.as_micros() as u64) % ((u32::MAX as u64) * 10_000)),
-        Err(_) => panic!("bad system time"),
+    MicroSeconds(now().as_micros())
+}
+
+/// A point in time from the monotonic clock, in microseconds since it started.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Instant(u64);
+
+impl Instant {
+    pub const fn from_micros(micros: u64) -> Self {
+        Instant(micros)
+    }
+
+    pub const fn as_micros(&self) -> u64 {
+        self.0
+    }
+
+    /// Returns the current time, same as `timer::now`.
+    pub fn now() -> Self {
+        now()
+    }
+
+    /// Returns the time since `earlier`, or zero if `earlier` is later than this.
+    pub fn duration_since(&self, earlier: Instant) -> Duration {
+        self.checked_duration_since(earlier)
+            .unwrap_or(Duration::ZERO)
+    }
+
+    /// Returns the time since `earlier`, or None if `earlier` is later than this.
+    pub fn checked_duration_since(&self, earlier: Instant) -> Option<Duration> {
+        self.0.checked_sub(earlier.0).map(Duration)
+    }
+
+    /// Returns the time since this instant.
+    pub fn elapsed(&self) -> Duration {
+        now().duration_since(*self)
+    }
+
+    pub fn checked_add(&self, duration: Duration) -> Option<Instant> {
+        self.0.checked_add(duration.0).map(Instant)
+    }
+
+    pub fn checked_sub(&self, duration: Duration) -> Option<Instant> {
+        self.0.checked_sub(duration.0).map(Instant)
+    }
+}
+
+impl Add<Duration> for Instant {
+    type Output = Instant;
+
+    fn add(self, rhs: Duration) -> Instant {
+        self.checked_add(rhs)
+            .expect("overflow adding duration to instant")
+    }
+}
+
+impl AddAssign<Duration> for Instant {
+    fn add_assign(&mut self, rhs: Duration) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub<Duration> for Instant {
+    type Output = Instant;
+
+    fn sub(self, rhs: Duration) -> Instant {
+        self.checked_sub(rhs)
+            .expect("overflow subtracting duration from instant")
+    }
+}
+
+impl SubAssign<Duration> for Instant {
+    fn sub_assign(&mut self, rhs: Duration) {
+        *self = *self - rhs;
+    }
+}
+
+impl Sub<Instant> for Instant {
+    type Output = Duration;
+
+    /// Same as `duration_since`, so an earlier minus a later instant is zero.
+    fn sub(self, rhs: Instant) -> Duration {
+        self.duration_since(rhs)
+    }
+}
+
+/// A span of time in microseconds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Duration(u64);
+
+impl Duration {
+    pub const ZERO: Duration = Duration(0);
+    pub const MAX: Duration = Duration(u64::MAX);
+
+    pub const fn from_micros(micros: u64) -> Self {
+        Duration(micros)
+    }
+
+    pub const fn from_millis(millis: u64) -> Self {
+        Duration(millis * 1_000)
+    }
+
+    pub const fn from_secs(secs: u64) -> Self {
+        Duration(secs * 1_000_000)
+    }
+
+    pub const fn as_micros(&self) -> u64 {
+        self.0
+    }
+
+    pub const fn as_millis(&self) -> u64 {
+        self.0 / 1_000
+    }
+
+    pub const fn as_secs(&self) -> u64 {
+        self.0 / 1_000_000
+    }
+
+    pub fn checked_add(&self, rhs: Duration) -> Option<Duration> {
+        self.0.checked_add(rhs.0).map(Duration)
+    }
+
+    pub fn checked_sub(&self, rhs: Duration) -> Option<Duration> {
+        self.0.checked_sub(rhs.0).map(Duration)
+    }
+
+    pub fn saturating_sub(&self, rhs: Duration) -> Duration {
+        Duration(self.0.saturating_sub(rhs.0))
+    }
+}
+
+impl Add for Duration {
+    type Output = Duration;
+
+    fn add(self, rhs: Duration) -> Duration {
+        self.checked_add(rhs).expect("overflow adding durations")
+    }
+}
+
+impl AddAssign for Duration {
+    fn add_assign(&mut self, rhs: Duration) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Duration {
+    type Output = Duration;
+
+    fn sub(self, rhs: Duration) -> Duration {
+        self.checked_sub(rhs)
+            .expect("overflow subtracting durations")
+    }
+}
+
+impl SubAssign for Duration {
+    fn sub_assign(&mut self, rhs: Duration) {
+        *self = *self - rhs;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extend() {
+        let wrap = TIME_WRAP_AROUND as u64;
+        assert_eq!(extend(0, 5, false), 5);
+        assert_eq!(extend(2, 7, false), 2 * wrap + 7);
+
+        // counter wrapped but the interrupt has not run yet
+        assert_eq!(extend(2, 7, true), 3 * wrap + 7);
+
+        // flag was set after the counter was read near the end of the period
+        assert_eq!(extend(2, TIME_WRAP_AROUND - 1, true), 3 * wrap - 1);
+    }
+
+    #[test]
+    fn test_instant_arithmetic() {
+        let a = Instant::from_micros(5_000_000_000);
+        let b = a + Duration::from_secs(2);
+
+        assert!(b > a);
+        assert_eq!(b - a, Duration::from_millis(2_000));
+        assert_eq!(a - b, Duration::ZERO);
+        assert_eq!(a.checked_duration_since(b), None);
+        assert_eq!(b - Duration::from_secs(2), a);
+        assert_eq!(
+            Instant::from_micros(1).checked_sub(Duration::from_micros(2)),
+            None
+        );
+        assert_eq!(
+            Instant::from_micros(u64::MAX).checked_add(Duration::from_micros(1)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_duration() {
+        let d = Duration::from_millis(1_500);
+        assert_eq!(d.as_micros(), 1_500_000);
+        assert_eq!(d.as_secs(), 1);
+        assert_eq!(d.checked_sub(Duration::from_secs(2)), None);
+        assert_eq!(d.saturating_sub(Duration::from_secs(2)), Duration::ZERO);
+        assert_eq!(Duration::MAX.checked_add(d), None);
+    }
+
+    #[test]
+    fn test_now_monotonic() {
+        let a = now();
+        let b = now();
+        assert!(b >= a);
     }
 }
@@ -7,6 +7,18 @@
 //!
 //! - `init1`: Initializes the USART1 peripheral with the specified baud rate.
 //! - `write1`: Sends a byte of data through the USART1 interface.
+//! - `empty1`: Checks if there is no received byte waiting.
+//! - `read1`: Waits for a received byte and returns it.
+//! - `enable_rx_irq1`: Turns on the receive interrupt and its buffer.
+//! - `handle_usart1_irq`: Handles the USART1 interrupt.
+//!
+//! On the STM32F072 the same functions ending in 2 use USART2.
+//!
+//! ## Receive Interrupt
+//!
+//! Without the receive interrupt a byte must be read before the next one
+//! arrives or it is lost. After `enable_rx_irq1`, `handle_usart1_irq` moves
+//! each byte into a 64 byte buffer that `read1` and `empty1` then use.
 //!
 //! ## Usage
 //!
@@ -22,10 +34,9 @@
 //! fn main() {
 //!     clock::init( 16_000_000 );
 //!
-//!     let tx = gpio::Pin(cpu::GPIOA, 9);
-//!     let rx = gpio::Pin(cpu::GPIOA, 10);
-//!     let baud_rate: u32 = 115200;
-//!     uart::init1(115200,rx,tx);
+//!     let pins = gpio::take().unwrap();
+//!     let baud_rate: u64 = 115200;
+//!     uart::init1(baud_rate, pins.pa9, pins.pa10);
 //!
 //!     // Send a byte of data
 //!     uart::write1(b'H');
@@ -39,9 +50,17 @@ use core::ptr;
 extern crate std;
 
 //use super::board;
+use super::clock;
 use super::cpu;
 use super::cpu::*;
-use super::gpio;
+use super::gpio::af::*;
+use super::gpio::AltFn;
+use super::ring::Ring;
+
+#[cfg(feature = "stm32f072")]
+use super::cpu::USART::{cr1, icr, isr};
+#[cfg(feature = "stm32f405")]
+use super::cpu::USART::{cr1, sr};
 
 pub use super::cpu::USART as USART1;
 pub use super::cpu::USART as USART2;
@@ -54,97 +73,99 @@ pub use super::cpu::DMA as DMA2;
 
 #[cfg(feature = "stm32f072")]
 #[inline(never)]
-pub fn init1(baud_rate: u64, tx_pin: gpio::Pin, rx_pin: gpio::Pin) {
-    // Enable USART1 & GPIOA clock
-    cpu::write!(RCC.apb2enr[USART1EN;1], 1);
-    cpu::write!(RCC.ahbenr[IOPAEN;1], 1);
+pub fn init1<TX: AltFn<Usart1Tx>, RX: AltFn<Usart1Rx>>(baud_rate: u64, tx_pin: TX, rx_pin: RX) {
+    // Enable USART1 & GPIO clocks
+    cpu::write!(RCC.apb2enr.USART1EN, 1);
+    tx_pin.pin().enable_clock();
+    rx_pin.pin().enable_clock();
 
-    tx_pin.alt_fun(1, false); // AF1 work for USART1 to 3
-    rx_pin.alt_fun(1, false); // AF1 work for USART1 to 3
+    tx_pin.pin().alt_fun(TX::AF, false);
+    rx_pin.pin().alt_fun(RX::AF, false);
 
     // Set baud rate
-    let apb_freq: u32 = 48_000_000; // APB clock frequency
+    let apb_freq: u32 = clock::clocks().pclk1; // APB clock frequency
     let usart_div: u32 = apb_freq / baud_rate as u32;
     cpu::write!(USART1.brr, usart_div);
 
     // Enable USART1, transmitter and receiver
-    cpu::write!(USART1.cr1[UE;1], 1); // USART enable
-    cpu::write!(USART1.cr1[TE;1], 1); // Transmitter enable
-    cpu::write!(USART1.cr1[RE;1], 1); // Receiver enable
+    cpu::write!(USART1.cr1.UE, 1); // USART enable
+    cpu::write!(USART1.cr1.TE, 1); // Transmitter enable
+    cpu::write!(USART1.cr1.RE, 1); // Receiver enable
 }
 
 #[cfg(feature = "stm32f072")]
 #[inline(never)]
-pub fn init2(baud_rate: u64, tx_pin: gpio::Pin, rx_pin: gpio::Pin) {
-    // Enable USART2 & GPIOA clock
-    cpu::write!(RCC.apb1enr[USART2EN;1], 1);
-    cpu::write!(RCC.ahbenr[IOPAEN;1], 1);
+pub fn init2<TX: AltFn<Usart2Tx>, RX: AltFn<Usart2Rx>>(baud_rate: u64, tx_pin: TX, rx_pin: RX) {
+    // Enable USART2 & GPIO clocks
+    cpu::write!(RCC.apb1enr.USART2EN, 1);
+    tx_pin.pin().enable_clock();
+    rx_pin.pin().enable_clock();
 
-    tx_pin.alt_fun(1, false); // AF1 work for USART1 to 3
-    rx_pin.alt_fun(1, false); // AF1 work for USART1 to 3
+    tx_pin.pin().alt_fun(TX::AF, false);
+    rx_pin.pin().alt_fun(RX::AF, false);
 
     // Set baud rate
-    let apb_freq: u32 = 48_000_000; // APB clock frequency
+    let apb_freq: u32 = clock::clocks().pclk1; // APB clock frequency
     let usart_div: u32 = apb_freq / baud_rate as u32;
     cpu::write!(USART2.brr, usart_div);
 
-    cpu::write!( USART2.cr1[M;1], 0); // 8 data bits
+    cpu::write!(USART2.cr1.M, 0); // 8 data bits
     let even_parity = false;
     if even_parity {
-        cpu::write!( USART2.cr1[PCE;1], 1); // parity control enable
-        cpu::write!( USART2.cr1[PS;1], 0); // even parity
+        cpu::write!(USART2.cr1.PCE, 1); // parity control enable
+        cpu::write!(USART2.cr1.PS, 0); // even parity
     } else {
-        cpu::write!( USART2.cr1[PCE;1], 0); // no parity
+        cpu::write!(USART2.cr1.PCE, 0); // no parity
     }
-    cpu::write!( USART2.cr2[STOP;2], 0b00); // 1 stop bit
+    cpu::write!(USART2.cr2.STOP, 0b00); // 1 stop bit
 
     // Enable USART2, transmitter and receiver
-    cpu::write!(USART2.cr1[UE;1], 1); // USART enable
-    cpu::write!(USART2.cr1[TE;1], 1); // Transmitter enable
-    cpu::write!(USART2.cr1[RE;1], 1); // Receiver enable
+    cpu::write!(USART2.cr1.UE, 1); // USART enable
+    cpu::write!(USART2.cr1.TE, 1); // Transmitter enable
+    cpu::write!(USART2.cr1.RE, 1); // Receiver enable
 }
 
 #[cfg(feature = "stm32f405")]
 #[inline(never)]
-pub fn init1(baud_rate: u64, tx_pin: gpio::Pin, rx_pin: gpio::Pin) {
-    // enable USART1 & GPIO clock
-    cpu::write!( RCC.apb2enr[USART1EN;1], 1);
-    cpu::write!( RCC.ahb1enr[GPIOAEN;1], 1);
+pub fn init1<TX: AltFn<Usart1Tx>, RX: AltFn<Usart1Rx>>(baud_rate: u64, tx_pin: TX, rx_pin: RX) {
+    // enable USART1 & GPIO clocks
+    cpu::write!(RCC.apb2enr.USART1EN, 1);
+    tx_pin.pin().enable_clock();
+    rx_pin.pin().enable_clock();
 
-    tx_pin.alt_fun(7, false); // AF7 work for USART1 to 3
-    rx_pin.alt_fun(7, false); // AF7 work for USART1 to 3
+    tx_pin.pin().alt_fun(TX::AF, false);
+    rx_pin.pin().alt_fun(RX::AF, false);
 
     // set baud rate
-    // UART 1 is on APB2 bus, which is 84MHz
-    let apb_freq: u64 = 84_000_000;
+    // UART 1 is on APB2 bus
+    let apb_freq: u64 = clock::clocks().pclk2 as u64;
     let div_fixed3: u64 = 1000 * apb_freq / (16 * baud_rate);
 
     let mantissa: u64 = div_fixed3 / 1000;
     let frac: u64 = (div_fixed3 % 1000) * 16 / 1000;
 
-    cpu::write!( USART1.brr[DIV_Mantissa;11], mantissa as u32);
-    cpu::write!( USART1.brr[DIV_Fraction;4], frac as u32);
+    cpu::write!(USART1.brr.DIV_Mantissa, mantissa as u32);
+    cpu::write!(USART1.brr.DIV_Fraction, frac as u32);
 
-    cpu::write!( USART1.cr1[M;1], 0); // 8 data bits
+    cpu::write!(USART1.cr1.M, 0); // 8 data bits
     let even_parity = false;
     if even_parity {
-        cpu::write!( USART1.cr1[PCE;1], 1); // parity control enable
-        cpu::write!( USART1.cr1[PS;1], 0); // even parity
+        cpu::write!(USART1.cr1.PCE, 1); // parity control enable
+        cpu::write!(USART1.cr1.PS, 0); // even parity
     } else {
-        cpu::write!( USART1.cr1[PCE;1], 0); // no parity
+        cpu::write!(USART1.cr1.PCE, 0); // no parity
     }
-    cpu::write!( USART1.cr2[STOP;2], 0b00); // 1 stop bit
+    cpu::write!(USART1.cr2.STOP, 0b00); // 1 stop bit
 
-    cpu::write!( USART1.cr1[TE;1], 1); // transmit enable
-    cpu::write!( USART1.cr1[RE;1], 1); // receive enable
-    cpu::write!( USART1.cr1[UE;1], 1); // uart enable
+    cpu::write!(USART1.cr1.TE, 1); // transmit enable
+    cpu::write!(USART1.cr1.RE, 1); // receive enable
+    cpu::write!(USART1.cr1.UE, 1); // uart enable
 }
 
 #[cfg(feature = "stm32f405")]
 pub fn write1(c: u8) {
-    #[cfg(not(feature = "std"))]
-    while (cpu::read!(USART1.sr[TXE;1]) == 0) {}
-    cpu::write!(USART1.dr[DR;8], c as u32);
+    while (cpu::read!(USART1.sr.TXE) == 0) {}
+    cpu::write!(USART1.dr.DR, c as u32);
 }
 
 #[cfg(any(feature = "std", not(feature = "stm32f405")))]
@@ -152,6 +173,13 @@ pub fn write1_dma(_data: &[u8]) {}
 
 //use core::ptr;
 
+/// Sends `data` on USART1 with DMA2 stream 7, waiting for the transfer to
+/// complete.
+///
+/// # Safety
+///
+/// USART1 and DMA2 stream 7 are set up with direct register writes, so
+/// they must not be in use by anything else while this runs.
 #[cfg(not(feature = "std"))]
 #[cfg(feature = "stm32f405")]
 pub unsafe fn write1_dma(data: &[u8]) {
@@ -186,15 +214,12 @@ pub unsafe fn write1_dma(data: &[u8]) {
     const USART1EN: u32 = 1 << 4;
 
     // checked
-    const NVIC_ISER2: u32 = 0xE000E100 + 0x8;
-    const DMA2_STREAM7_IRQ: u32 = 70;
-
     // Enable DMA2 and USART1 clocks
     ptr::write_volatile(RCC_AHB1ENR as *mut u32, DMA2EN);
     ptr::write_volatile(RCC_APB2ENR as *mut u32, USART1EN);
 
     // Configure USART1
-    let apb_freq: u32 = 84_000_000;
+    let apb_freq: u32 = clock::clocks().pclk2; // USART1 is on APB2
     let usart_div: u32 = apb_freq / baud_rate;
     ptr::write_volatile(USART1_BRR as *mut u32, usart_div);
     // odd parity | transmit enable | transmit enable
@@ -226,7 +251,7 @@ pub unsafe fn write1_dma(data: &[u8]) {
     ptr::write_volatile(DMA2_S7CR as *mut u32, dma_cr | 1);
 
     // Enable DMA2 Stream 7 interrupt in NVIC
-    ptr::write_volatile(NVIC_ISER2 as *mut u32, 1 << (DMA2_STREAM7_IRQ - 64));
+    cpu::enable_irq(Interrupt::Dma2Stream7);
 
     // Wait for transfer to complete
     while ptr::read_volatile(DMA2_HISR as *mut u32) & (1 << 27) == 0
@@ -254,18 +279,18 @@ pub fn write1_dma_save(data: &[u8]) {
     // See page 311 of RM0090
 
     // Enable DMA2 clocks
-    cpu::write!( RCC.ahb1enr[DMA2EN;1], 1);
+    cpu::write!(RCC.ahb1enr.DMA2EN, 1);
 
     cpu::write!(DMA2.s7cr, 0); // Disable DMA2 Stream 7 while configuring
-    while cpu::read!(DMA2.s7cr[EN;1]) != 0 {} // Wait until the stream is disabled
+    while cpu::read!(DMA2.s7cr.EN) != 0 {} // Wait until the stream is disabled
 
-    cpu::write!( USART1.cr1[UE;1], 0b0 ); // Disable USART1
+    cpu::write!(USART1.cr1.UE, 0b0); // Disable USART1
 
     // Enable USART1 DMA transmission
-    cpu::write!(USART1.cr3[DMAT;1], 1); // Enable DMA transmission
-    cpu::write!(USART1.sr[TC;1], 1); // Clear the transfer complete flag
+    cpu::write!(USART1.cr3.DMAT, 1); // Enable DMA transmission
+    cpu::clear!(USART1.sr.TC); // Clear the transfer complete flag
 
-    cpu::write!( USART1.cr1[UE;1], 0b1 ); // enable USART1
+    cpu::write!(USART1.cr1.UE, 0b1); // enable USART1
 
     // Setup DMA transfer from memory to USART1
     let dest: u32 = unsafe { core::ptr::addr_of!((*USART1).dr) as u32 };
@@ -280,60 +305,56 @@ pub fn write1_dma_save(data: &[u8]) {
     cpu::write!(DMA2.s7m0ar, src);
 
     //  Configure DMA channel: memory-to-peripheral, increment memory, enable transfer complete interrupt
-    cpu::write!( DMA2.s7cr[CHSEL;2], 0b100); // Using Channel #4
+    cpu::write!(DMA2.s7cr.CHSEL, 0b100); // Using Channel #4
 
-    cpu::write!( DMA2.s7cr[DBM;1], 0b0 ); // Disable double buffer mode
+    cpu::write!(DMA2.s7cr.DBM, 0b0); // Disable double buffer mode
 
-    cpu::write!( DMA2.s7cr[PL;2], 0b10 ); // Set priority level to low
+    cpu::write!(DMA2.s7cr.PL, 0b10); // Set priority level to low
 
-    cpu::write!( DMA2.s7cr[MSIZE;2], 0b00); // Set peripheral size to 1 byte
-    cpu::write!( DMA2.s7cr[PSIZE;2], 0b00); // Set peripheral size to 1 byte
-    cpu::write!( DMA2.s7cr[PINC;1], 0b0); // Disable peripheral increment mode
-    cpu::write!( DMA2.s7cr[MINC;1], 0b1); // Enable memory increment mode
+    cpu::write!(DMA2.s7cr.MSIZE, 0b00); // Set peripheral size to 1 byte
+    cpu::write!(DMA2.s7cr.PSIZE, 0b00); // Set peripheral size to 1 byte
+    cpu::write!(DMA2.s7cr.PINC, 0b0); // Disable peripheral increment mode
+    cpu::write!(DMA2.s7cr.MINC, 0b1); // Enable memory increment mode
 
-    cpu::write!( DMA2.s7cr[CIRC;1], 0b0); // Disable circular mode
-    cpu::write!( DMA2.s7cr[DIR;2], 0b01); // Set direction as memory to peripheral
+    cpu::write!(DMA2.s7cr.CIRC, 0b0); // Disable circular mode
+    cpu::write!(DMA2.s7cr.DIR, 0b01); // Set direction as memory to peripheral
 
-    cpu::write!( DMA2.s7cr[PFCTRL;1], 0b0); // disable peripheral flow controller
+    cpu::write!(DMA2.s7cr.PFCTRL, 0b0); // disable peripheral flow controller
 
-    cpu::write!( DMA2.hifcr[CDMEIF7;1], 0b1); // clear the old direct transfer error flag
-    cpu::write!( DMA2.hifcr[CTEIF7;1], 0b1); // clear the old transfer error flag
-    cpu::write!( DMA2.hifcr[CFEIF7;1], 0b1); // clear the old FIFO error flag
-    cpu::write!( DMA2.hifcr[CTCIF7;1], 0b1); // clear the old transfer complete flag
+    cpu::clear!(DMA2.hifcr.CDMEIF7); // clear the old direct transfer error flag
+    cpu::clear!(DMA2.hifcr.CTEIF7); // clear the old transfer error flag
+    cpu::clear!(DMA2.hifcr.CFEIF7); // clear the old FIFO error flag
+    cpu::clear!(DMA2.hifcr.CTCIF7); // clear the old transfer complete flag
 
-    cpu::write!( DMA2.s7cr[TCIE;1], 0b1); // Enable transfer complete interrupt
-    cpu::write!( DMA2.s7cr[TEIE;1], 0b1); // Enable transfer error interrupt
-    cpu::write!( DMA2.s7cr[DMEIE;1], 0b1); // Enable transfer error interrupt
+    cpu::write!(DMA2.s7cr.TCIE, 0b1); // Enable transfer complete interrupt
+    cpu::write!(DMA2.s7cr.TEIE, 0b1); // Enable transfer error interrupt
+    cpu::write!(DMA2.s7cr.DMEIE, 0b1); // Enable transfer error interrupt
 
-    // DMA2 Stream7 global interrupt = 70 // from SVD file
     // NVIC interrupt enable
-    let itr = 70;
-    cpu::write!(NVIC.iser[itr / 32], 1 << (itr % 32)); // Enable DMA2 Stream 7 interrupt
-    let pri_loc = itr * 4;
-    let pri = 0b0011;
-    cpu::write!(NVIC.ipr[pri_loc / 32], pri << (pri_loc % 32)); // Enable DMA2 Stream 7 interrupt
+    cpu::enable_irq(Interrupt::Dma2Stream7);
+    cpu::set_priority(Interrupt::Dma2Stream7, 0b0011);
 
     // Clear prior events flags
 
     // Enable DMA2 Stream 7
-    cpu::write!(DMA2.s7cr[EN;1], 1);
+    cpu::write!(DMA2.s7cr.EN, 1);
 
     if true {
         // Check if the transfer complete flag is set
-        while cpu::read!(DMA2.hisr[TCIF7;1]) == 0 {
-            if cpu::read!( DMA2.hisr[TEIF7; 1]) == 0b1 {
+        while cpu::read!(DMA2.hisr.TCIF7) == 0 {
+            if cpu::read!(DMA2.hisr.TEIF7) == 0b1 {
                 panic!("DMA transfer error interrupt");
             }
-            if cpu::read!( DMA2.hisr[FEIF7; 1]) == 0b1 {
+            if cpu::read!(DMA2.hisr.FEIF7) == 0b1 {
                 panic!("DMA transfer FIFO error interrupt");
             }
-            if cpu::read!( DMA2.hisr[DMEIF7; 1]) == 0b1 {
+            if cpu::read!(DMA2.hisr.DMEIF7) == 0b1 {
                 panic!("DMA transfer direct mode error interrupt");
             }
         }
 
         // Clear the transfer complete flag
-        cpu::write!(DMA2.hifcr[CTCIF7;1], 1);
+        cpu::clear!(DMA2.hifcr.CTCIF7);
     }
 }
 
@@ -341,12 +362,12 @@ pub fn write1_dma_save(data: &[u8]) {
 #[cfg(feature = "stm32f405")]
 #[inline(never)]
 pub fn dma_uart1_irq() {
-    if cpu::read!( DMA2.hisr[TEIF7; 1]) == 0b1 {
+    if cpu::read!(DMA2.hisr.TEIF7) == 0b1 {
         panic!("DMA transfer error interrupt");
     }
-    if cpu::read!( DMA2.hisr[TCIF7; 1]) == 0b1 {
+    if cpu::read!(DMA2.hisr.TCIF7) == 0b1 {
         // Clear the transfer complete flag
-        cpu::write!( DMA2.hifcr[CTCIF7; 1], 0b1);
+        cpu::clear!(DMA2.hifcr.CTCIF7);
 
         // TODO
     } else {
@@ -357,7 +378,7 @@ pub fn dma_uart1_irq() {
 #[cfg(feature = "stm32f072")]
 pub fn write1(c: u8) {
     // Wait until transmit data register is empty
-    while cpu::read!(USART1.isr[TXE;1]) == 0 {}
+    while cpu::read!(USART1.isr.TXE) == 0 {}
     // Write the byte to the data register
     cpu::write!(USART1.tdr, c as u32);
 }
@@ -365,68 +386,163 @@ pub fn write1(c: u8) {
 #[cfg(feature = "stm32f072")]
 pub fn write2(c: u8) {
     // Wait until transmit data register is empty
-    while cpu::read!(USART2.isr[TXE;1]) == 0 {}
+    while cpu::read!(USART2.isr.TXE) == 0 {}
     // Write the byte to the data register
     cpu::write!(USART2.tdr, c as u32);
 }
 
+/// Returns true if there is no received byte waiting on USART1.
+pub fn empty1() -> bool {
+    empty(USART1, 0)
+}
+
+/// Waits for a byte from USART1 and returns it.
+pub fn read1() -> u8 {
+    read(USART1, 0)
+}
+
+/// Turns on the receive interrupt of USART1. From then on the interrupt
+/// handler must call `handle_usart1_irq`, which keeps the received bytes
+/// for `read1` so none are lost while the program is busy.
+pub fn enable_rx_irq1() {
+    enable_rx_irq(USART1, 0, Interrupt::Usart1);
+}
+
+/// Handles the USART1 interrupt by moving the received bytes to the buffer.
+#[inline(never)]
+pub fn handle_usart1_irq() {
+    handle_irq(USART1, 0);
+}
+
+/// Returns true if there is no received byte waiting on USART2.
 #[cfg(feature = "stm32f072")]
 pub fn empty2() -> bool {
-    // Wait until transmit data register is empty
-    cpu::read!(USART2.isr[RXNE;1]) == 0
+    empty(USART2, 1)
 }
 
+/// Waits for a byte from USART2 and returns it.
 #[cfg(feature = "stm32f072")]
-pub fn empty1() -> bool {
-    // Wait until transmit data register is empty
-    cpu::read!(USART1.isr[RXNE;1]) == 0
+pub fn read2() -> u8 {
+    read(USART2, 1)
 }
 
-#[cfg(feature = "stm32f405")]
-pub fn empty1() -> bool {
-    // Wait until transmit data register is empty
-    cpu::read!(USART1.sr[RXNE;1]) == 0
+/// Turns on the receive interrupt of USART2, see `enable_rx_irq1`.
+#[cfg(feature = "stm32f072")]
+pub fn enable_rx_irq2() {
+    enable_rx_irq(USART2, 1, Interrupt::Usart2);
 }
 
+/// Handles the USART2 interrupt by moving the received bytes to the buffer.
 #[cfg(feature = "stm32f072")]
-pub fn read2() -> u8 {
-    // Wait until receive data register is empty
-    while cpu::read!(USART2.isr[RXNE;1]) == 0 {}
-    // Write the byte to the data register
-    cpu::read!(USART2.rdr) as u8
+#[inline(never)]
+pub fn handle_usart2_irq() {
+    handle_irq(USART2, 1);
+}
+
+/// Number of received bytes kept for each USART while the receive interrupt is on.
+const RX_SIZE: usize = 64;
+
+static RX: Global<[Ring<RX_SIZE>; 2]> = Global::new([Ring::new(), Ring::new()]);
+
+// only used from the interrupt handler, or with the receive interrupt off
+fn rx(port: usize) -> &'static mut Ring<RX_SIZE> {
+    unsafe { &mut (*RX.as_ptr())[port] }
+}
+
+fn rx_irq_enabled(usart: *mut cpu::UsartReg) -> bool {
+    cpu::read!(usart.cr1.cr1::RXNEIE) == 1
+}
+
+#[cfg(feature = "stm32f405")]
+fn rx_ready(usart: *mut cpu::UsartReg) -> bool {
+    cpu::read!(usart.sr.sr::RXNE) == 1
 }
 
 #[cfg(feature = "stm32f072")]
-pub fn read1() -> u8 {
-    // Wait until receive data register is empty
-    while cpu::read!(USART1.isr[RXNE;1]) == 0 {}
-    // Write the byte to the data register
-    cpu::read!(USART1.rdr) as u8
+fn rx_ready(usart: *mut cpu::UsartReg) -> bool {
+    cpu::read!(usart.isr.isr::RXNE) == 1
 }
 
+/// Reads the received byte, which clears RXNE and, on the STM32F405, an overrun.
 #[cfg(feature = "stm32f405")]
-pub fn read1() -> u8 {
-    // Wait until receive data register is empty
-    while cpu::read!(USART1.sr[RXNE;1]) == 0 {}
-    // Write the byte to the data register
-    cpu::read!(USART1.dr) as u8
+fn rx_data(usart: *mut cpu::UsartReg) -> u8 {
+    cpu::read!(usart.dr) as u8
+}
+
+/// Reads the received byte, and clears an overrun as that stops reception.
+#[cfg(feature = "stm32f072")]
+fn rx_data(usart: *mut cpu::UsartReg) -> u8 {
+    if cpu::read!(usart.isr.isr::ORE) == 1 {
+        cpu::write!(usart.icr, 1 << icr::ORECF);
+    }
+    cpu::read!(usart.rdr) as u8
+}
+
+/// Pops a byte from the buffer with the receive interrupt off.
+fn pop_rx(usart: *mut cpu::UsartReg, port: usize) -> Option<u8> {
+    cpu::write!(usart.cr1.cr1::RXNEIE, 0);
+    let c = rx(port).pop();
+    cpu::write!(usart.cr1.cr1::RXNEIE, 1);
+    c
+}
+
+fn empty(usart: *mut cpu::UsartReg, port: usize) -> bool {
+    if !rx_irq_enabled(usart) {
+        return !rx_ready(usart);
+    }
+    cpu::write!(usart.cr1.cr1::RXNEIE, 0);
+    let empty = rx(port).free() == RX_SIZE;
+    cpu::write!(usart.cr1.cr1::RXNEIE, 1);
+    empty
+}
+
+fn read(usart: *mut cpu::UsartReg, port: usize) -> u8 {
+    if rx_irq_enabled(usart) {
+        loop {
+            if let Some(c) = pop_rx(usart, port) {
+                return c;
+            }
+        }
+    }
+    while !rx_ready(usart) {}
+    rx_data(usart)
+}
+
+fn enable_rx_irq(usart: *mut cpu::UsartReg, port: usize, irq: Interrupt) {
+    cpu::write!(usart.cr1.cr1::RXNEIE, 0);
+    rx(port).clear();
+    cpu::write!(usart.cr1.cr1::RXNEIE, 1);
+
+    cpu::enable_irq(irq);
+}
+
+fn handle_irq(usart: *mut cpu::UsartReg, port: usize) {
+    while rx_ready(usart) {
+        // bytes that do not fit are dropped, the same as an overrun
+        let _ = rx(port).push(rx_data(usart));
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::clock;
+    use crate::gpio;
+    use crate::sim;
 
     #[test]
     fn test_uart() {
         clock::init(16_000_000);
 
-        let tx = gpio::Pin(cpu::GPIOA, 9);
-        let rx = gpio::Pin(cpu::GPIOA, 10);
+        let tx = gpio::PA9::new();
+        let rx = gpio::PA10::new();
         let baud_rate: u64 = 115200;
 
         init1(baud_rate, tx, rx);
         write1(b'O');
         write1(b'K');
+
+        let output = sim::with_model(USART1, |usart: &mut sim::Usart| usart.take_output());
+        assert_eq!(output.as_deref(), Some(&b"OK"[..]));
     }
 }
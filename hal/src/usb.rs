@@ -0,0 +1,1015 @@
+//! # USB Module
+//!
+//! This module drives the USB OTG_FS peripheral of the STM32F405 as a full
+//! speed device (section 34 of RM0090). The device is a CDC-ACM virtual
+//! serial port, so it shows up as `/dev/ttyACM*` on Linux and as a COM port
+//! on Windows without a driver.
+//!
+//! The control requests of enumeration are answered by `Device`, which does
+//! not touch any registers so it can be tested with recorded setup packets.
+//! The rest of the module moves packets between the endpoint FIFOs and the
+//! receive and transmit buffers from the USB interrupt.
+//!
+//! The peripheral needs a 48 MHz clock from the PLL, which `clock::init` sets
+//! up. VBUS sensing is turned off as PA9 is the USART1 console.
+//!
+//! ## Structures
+//!
+//! - `SetupPacket`: The 8 byte request that starts a control transfer.
+//! - `Device`: The standard and CDC control request state machine.
+//!
+//! ## Enums
+//!
+//! - `Response`: How to finish a control transfer.
+//!
+//! ## Functions
+//!
+//! - `init`: Starts the peripheral and connects to the host.
+//! - `owns_pin`: Checks if a pin is taken by the peripheral, which has PA11
+//!   and PA12 once started.
+//! - `configured`: Checks if the host has configured the device.
+//! - `port_open`: Checks if a program on the host has the serial port open.
+//! - `write`: Queues bytes to send to the host.
+//! - `read`: Takes bytes received from the host.
+//! - `handle_irq`: Handles the OTG_FS interrupt.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use hal::cpu;
+//! use hal::usb;
+//!
+//! cpu::init();
+//! hal::clock::init(16_000_000);
+//! usb::init();
+//!
+//! if usb::port_open() {
+//!     usb::write(b"Hello, world!\r\n");
+//! }
+//!
+//! let mut buf = [0u8; 64];
+//! let len = usb::read(&mut buf);
+//! ```
+
+use core::ptr;
+
+#[cfg(feature = "std")]
+extern crate std;
+
+use super::cpu;
+use super::cpu::*;
+use super::gpio::Pin;
+use super::ring::Ring;
+
+use super::cpu::OTG_FS_DEVICE::{daint, diepmsk, dieptsiz0, doepint0, doepmsk, doeptsiz0};
+use super::cpu::OTG_FS_DEVICE::{diepctl0 as diepctl, doepctl1 as doepctl};
+use super::cpu::OTG_FS_GLOBAL::{gintsts, grstctl, grxstsp};
+
+/// Largest packet on the control and bulk endpoints.
+pub const MAX_PACKET: usize = 64;
+
+/// Endpoint of the bulk data pipe in both directions.
+const DATA_EP: usize = 1;
+
+/// Endpoint of the CDC notifications, which are never sent.
+const NOTIFY_EP: usize = 2;
+
+// standard requests (table 9-4 of the USB 2.0 specification)
+const GET_STATUS: u8 = 0;
+const CLEAR_FEATURE: u8 = 1;
+const SET_FEATURE: u8 = 3;
+const SET_ADDRESS: u8 = 5;
+const GET_DESCRIPTOR: u8 = 6;
+const GET_CONFIGURATION: u8 = 8;
+const SET_CONFIGURATION: u8 = 9;
+const GET_INTERFACE: u8 = 10;
+const SET_INTERFACE: u8 = 11;
+
+// CDC PSTN requests (table 13 of the PSTN 1.2 specification)
+const SET_LINE_CODING: u8 = 0x20;
+const GET_LINE_CODING: u8 = 0x21;
+const SET_CONTROL_LINE_STATE: u8 = 0x22;
+const SEND_BREAK: u8 = 0x23;
+
+const TYPE_STANDARD: u8 = 0x00;
+const TYPE_CLASS: u8 = 0x20;
+
+#[rustfmt::skip]
+static DEVICE_DESC: [u8; 18] = [
+    18, 1, // device
+    0x00, 0x02, // USB 2.0
+    0x02, 0x00, 0x00, // CDC class, defined by the interfaces
+    MAX_PACKET as u8,
+    0x83, 0x04, // STMicroelectronics
+    0x40, 0x57, // virtual COM port
+    0x00, 0x02, // device release 2.00
+    1, 2, 3, // manufacturer, product, and serial number strings
+    1, // configurations
+];
+
+#[rustfmt::skip]
+static CONFIG_DESC: [u8; 67] = [
+    9, 2, 67, 0, // configuration and total length
+    2, 1, 0,    // 2 interfaces, configuration value 1, no string
+    0x80, 50,   // bus powered, 100 mA
+    // communication interface
+    9, 4, 0, 0, 1, 0x02, 0x02, 0x01, 0, // 1 endpoint, CDC ACM with AT commands
+    5, 0x24, 0x00, 0x10, 0x01, // header, CDC 1.10
+    5, 0x24, 0x01, 0x00, 1,    // call management over the data interface
+    4, 0x24, 0x02, 0x02,       // ACM supports line coding and control line state
+    5, 0x24, 0x06, 0, 1,       // union of interfaces 0 and 1
+    7, 5, 0x80 | NOTIFY_EP as u8, 0x03, 8, 0, 16, // interrupt IN, 8 bytes, 16 ms
+    // data interface
+    9, 4, 1, 0, 2, 0x0A, 0x00, 0x00, 0, // 2 endpoints, CDC data
+    7, 5, DATA_EP as u8, 0x02, MAX_PACKET as u8, 0, 0, // bulk OUT
+    7, 5, 0x80 | DATA_EP as u8, 0x02, MAX_PACKET as u8, 0, 0, // bulk IN
+];
+
+/// Builds a string descriptor of ASCII `text`. `N` must be two more than
+/// twice the length of `text` as the string is sent in UTF-16.
+const fn string_desc<const N: usize>(text: &[u8]) -> [u8; N] {
+    assert!(N == 2 + 2 * text.len());
+
+    let mut desc = [0u8; N];
+    desc[0] = N as u8;
+    desc[1] = 3;
+    let mut i = 0;
+    while i < text.len() {
+        desc[2 + 2 * i] = text[i];
+        i += 1;
+    }
+    desc
+}
+
+static LANGUAGES: [u8; 4] = [4, 3, 0x09, 0x04]; // US English
+static MANUFACTURER: [u8; 14] = string_desc(b"Hactar");
+static PRODUCT: [u8; 30] = string_desc(b"Hactar Console");
+static SERIAL: [u8; 10] = string_desc(b"0001");
+
+/// Returns the descriptor asked for by the `wValue` of a GET_DESCRIPTOR.
+fn descriptor(value: u16) -> Option<&'static [u8]> {
+    match ((value >> 8) as u8, value as u8) {
+        (1, 0) => Some(&DEVICE_DESC),
+        (2, 0) => Some(&CONFIG_DESC),
+        (3, 0) => Some(&LANGUAGES),
+        (3, 1) => Some(&MANUFACTURER),
+        (3, 2) => Some(&PRODUCT),
+        (3, 3) => Some(&SERIAL),
+        // a full speed only device stalls the device qualifier
+        _ => None,
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct SetupPacket {
+    pub request_type: u8,
+    pub request: u8,
+    pub value: u16,
+    pub index: u16,
+    pub length: u16,
+}
+
+impl SetupPacket {
+    /// Decodes the 8 bytes received in a SETUP transaction.
+    pub fn parse(data: &[u8; 8]) -> Self {
+        SetupPacket {
+            request_type: data[0],
+            request: data[1],
+            value: u16::from_le_bytes([data[2], data[3]]),
+            index: u16::from_le_bytes([data[4], data[5]]),
+            length: u16::from_le_bytes([data[6], data[7]]),
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Response {
+    /// Sends a descriptor in the IN data stage.
+    Data(&'static [u8]),
+    /// Sends the first `n` bytes in the IN data stage.
+    Short([u8; 8], usize),
+    /// Receives `n` bytes in the OUT data stage, which are passed to
+    /// `Device::control_out`.
+    Receive(usize),
+    /// Completes a request that has no data stage.
+    Ack,
+    /// Rejects the request.
+    Stall,
+}
+
+/// State of the device as seen by the host, changed by control requests.
+#[derive(Clone)]
+pub struct Device {
+    address: u8,
+    configuration: u8,
+    line_coding: [u8; 7],
+    control_lines: u16,
+    /// Request waiting for its OUT data stage.
+    pending: Option<u8>,
+}
+
+impl Device {
+    pub const fn new() -> Self {
+        Device {
+            address: 0,
+            configuration: 0,
+            // 115200 baud, 1 stop bit, no parity, 8 data bits
+            line_coding: [0x00, 0xC2, 0x01, 0x00, 0, 0, 8],
+            control_lines: 0,
+            pending: None,
+        }
+    }
+
+    /// Returns to the default state after a USB reset. The line coding is
+    /// kept as the host does not send it again.
+    pub fn reset(&mut self) {
+        self.address = 0;
+        self.configuration = 0;
+        self.control_lines = 0;
+        self.pending = None;
+    }
+
+    pub fn address(&self) -> u8 {
+        self.address
+    }
+
+    pub fn configured(&self) -> bool {
+        self.configuration != 0
+    }
+
+    /// Returns true if the host has set DTR, which terminal programs do when
+    /// they open the port.
+    pub fn dtr(&self) -> bool {
+        self.configured() && self.control_lines & 1 != 0
+    }
+
+    /// Returns the baud rate last set by the host. It has no effect on the
+    /// USB data rate.
+    pub fn baud_rate(&self) -> u32 {
+        let c = &self.line_coding;
+        u32::from_le_bytes([c[0], c[1], c[2], c[3]])
+    }
+
+    /// Handles a setup packet and returns how to finish the transfer. Data
+    /// sent to the host is cut to the `length` of the request.
+    pub fn setup(&mut self, setup: &SetupPacket) -> Response {
+        self.pending = None;
+
+        let response = match (setup.request_type & 0x60, setup.request) {
+            (TYPE_STANDARD, GET_DESCRIPTOR) => match descriptor(setup.value) {
+                Some(desc) => Response::Data(desc),
+                None => Response::Stall,
+            },
+            (TYPE_STANDARD, SET_ADDRESS) => {
+                self.address = setup.value as u8 & 0x7F;
+                Response::Ack
+            }
+            (TYPE_STANDARD, GET_CONFIGURATION) => short(&[self.configuration]),
+            (TYPE_STANDARD, SET_CONFIGURATION) => match setup.value {
+                0 | 1 => {
+                    self.configuration = setup.value as u8;
+                    Response::Ack
+                }
+                _ => Response::Stall,
+            },
+            (TYPE_STANDARD, GET_STATUS) => short(&[0, 0]),
+            // remote wakeup and endpoint halt are not supported but Linux
+            // clears the halt on the bulk endpoints when the port is opened
+            (TYPE_STANDARD, CLEAR_FEATURE) | (TYPE_STANDARD, SET_FEATURE) => Response::Ack,
+            (TYPE_STANDARD, GET_INTERFACE) => short(&[0]),
+            (TYPE_STANDARD, SET_INTERFACE) => match setup.value {
+                0 => Response::Ack,
+                _ => Response::Stall,
+            },
+            (TYPE_CLASS, SET_LINE_CODING) => {
+                self.pending = Some(SET_LINE_CODING);
+                Response::Receive(self.line_coding.len())
+            }
+            (TYPE_CLASS, GET_LINE_CODING) => short(&self.line_coding),
+            (TYPE_CLASS, SET_CONTROL_LINE_STATE) => {
+                self.control_lines = setup.value;
+                Response::Ack
+            }
+            (TYPE_CLASS, SEND_BREAK) => Response::Ack,
+            _ => Response::Stall,
+        };
+
+        let length = setup.length as usize;
+        match response {
+            Response::Data(data) if data.len() > length => Response::Data(&data[..length]),
+            Response::Short(data, n) if n > length => Response::Short(data, length),
+            _ => response,
+        }
+    }
+
+    /// Handles the OUT data stage of the last setup packet. Returns `Ack`
+    /// for the status stage, or `Stall` if no data was expected.
+    pub fn control_out(&mut self, data: &[u8]) -> Response {
+        match self.pending.take() {
+            Some(SET_LINE_CODING) if data.len() == self.line_coding.len() => {
+                self.line_coding.copy_from_slice(data);
+                Response::Ack
+            }
+            _ => Response::Stall,
+        }
+    }
+}
+
+impl Default for Device {
+    fn default() -> Self {
+        Device::new()
+    }
+}
+
+fn short(data: &[u8]) -> Response {
+    let mut buf = [0u8; 8];
+    buf[..data.len()].copy_from_slice(data);
+    Response::Short(buf, data.len())
+}
+
+/// Status of an entry popped from the receive FIFO (GRXSTSP PKTSTS).
+const PKTSTS_OUT_DATA: u32 = 2;
+const PKTSTS_SETUP_DATA: u32 = 6;
+
+/// Address of the FIFO used to push and pop the packets of an endpoint.
+#[cfg(not(feature = "std"))]
+const FIFO_BASE: usize = 0x5000_1000;
+
+static DEVICE: Global<Device> = Global::new(Device::new());
+static SETUP: Global<[u8; 8]> = Global::new([0; 8]);
+static EP0_OUT: Global<[u8; 8]> = Global::new([0; 8]);
+static EP0_OUT_LEN: Global<usize> = Global::new(0);
+static RX: Global<Ring<512>> = Global::new(Ring::new());
+static TX: Global<Ring<1024>> = Global::new(Ring::new());
+/// True while a packet is queued on the bulk IN endpoint.
+static TX_BUSY: Global<bool> = Global::new(false);
+/// True if the last packet sent was full, so a zero length packet is needed
+/// to end the transfer.
+static TX_ZLP: Global<bool> = Global::new(false);
+/// True while the bulk OUT endpoint is ready to receive.
+static RX_ARMED: Global<bool> = Global::new(false);
+
+// these are only used from the interrupt handler, or with it masked
+fn device() -> &'static mut Device {
+    unsafe { &mut *DEVICE.as_ptr() }
+}
+
+fn rx() -> &'static mut Ring<512> {
+    unsafe { &mut *RX.as_ptr() }
+}
+
+fn tx() -> &'static mut Ring<1024> {
+    unsafe { &mut *TX.as_ptr() }
+}
+
+fn get_flag(flag: &Global<bool>) -> bool {
+    unsafe { flag.get() }
+}
+
+fn set_flag(flag: &Global<bool>, value: bool) {
+    unsafe { flag.set(value) }
+}
+
+/// Runs `f` with the USB interrupt masked in the core.
+fn without_irq<R>(f: impl FnOnce() -> R) -> R {
+    cpu::write!(OTG_FS_GLOBAL.gahbcfg.GINT, 0);
+    let result = f();
+    cpu::write!(OTG_FS_GLOBAL.gahbcfg.GINT, 1);
+    result
+}
+
+#[cfg(feature = "std")]
+static SIM_RX_FIFO: Global<std::collections::VecDeque<u8>> =
+    Global::new(std::collections::VecDeque::new());
+
+#[cfg(feature = "std")]
+static SIM_SENT: Global<[std::vec::Vec<u8>; 4]> = Global::new([
+    std::vec::Vec::new(),
+    std::vec::Vec::new(),
+    std::vec::Vec::new(),
+    std::vec::Vec::new(),
+]);
+
+#[cfg(not(feature = "std"))]
+fn fifo(ep: usize) -> *mut u32 {
+    (FIFO_BASE + ep * 0x1000) as *mut u32
+}
+
+/// Pops a packet of `count` bytes from the receive FIFO, passing each byte
+/// and its index to `sink`.
+#[cfg(not(feature = "std"))]
+fn read_packet(count: usize, mut sink: impl FnMut(usize, u8)) {
+    for word in 0..count.div_ceil(4) {
+        let bytes = cpu::read_reg(fifo(0)).to_le_bytes();
+        for (i, byte) in bytes.iter().enumerate() {
+            if word * 4 + i < count {
+                sink(word * 4 + i, *byte);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+fn read_packet(count: usize, mut sink: impl FnMut(usize, u8)) {
+    let fifo = unsafe { &mut *SIM_RX_FIFO.as_ptr() };
+    for i in 0..count {
+        sink(i, fifo.pop_front().unwrap_or(0));
+    }
+}
+
+/// Pushes a packet into the transmit FIFO of endpoint `ep`.
+#[cfg(not(feature = "std"))]
+fn write_packet(ep: usize, data: &[u8]) {
+    for chunk in data.chunks(4) {
+        let mut bytes = [0u8; 4];
+        bytes[..chunk.len()].copy_from_slice(chunk);
+        cpu::write_reg(fifo(ep), u32::from_le_bytes(bytes));
+    }
+}
+
+#[cfg(feature = "std")]
+fn write_packet(ep: usize, data: &[u8]) {
+    unsafe { (*SIM_SENT.as_ptr())[ep].extend_from_slice(data) };
+}
+
+/// Starts the OTG_FS peripheral as a device and connects to the host. The
+/// host enumerates the device from the interrupt handler.
+#[inline(never)]
+pub fn init() {
+    cpu::write!(RCC.ahb1enr.GPIOAEN, 1);
+    Pin(GPIOA, 11).alt_fun(10, true); // DM
+    Pin(GPIOA, 12).alt_fun(10, true); // DP
+
+    cpu::write!(RCC.ahb2enr.OTGFSEN, 1);
+
+    // stay disconnected until set up
+    cpu::write!(OTG_FS_DEVICE.dctl.SDIS, 1);
+
+    // reset the core once the AHB side is idle
+    #[cfg(not(feature = "std"))]
+    while cpu::read!(OTG_FS_GLOBAL.grstctl.AHBIDL) == 0 {}
+    cpu::write!(OTG_FS_GLOBAL.grstctl.CSRST, 1);
+    #[cfg(not(feature = "std"))]
+    while cpu::read!(OTG_FS_GLOBAL.grstctl.CSRST) != 0 {}
+
+    // device mode with the internal PHY, and the turnaround time for an AHB
+    // clock over 32 MHz
+    cpu::write!(OTG_FS_GLOBAL.gusbcfg.PHYSEL, 1);
+    cpu::write!(OTG_FS_GLOBAL.gusbcfg.TRDT, 6);
+    cpu::write!(OTG_FS_GLOBAL.gusbcfg.FDMOD, 1);
+    #[cfg(not(feature = "std"))]
+    while cpu::read!(OTG_FS_GLOBAL.gintsts.CMOD) != 0 {}
+
+    // power up the transceiver without VBUS sensing
+    cpu::write!(OTG_FS_GLOBAL.gccfg.VBUSASEN, 0);
+    cpu::write!(OTG_FS_GLOBAL.gccfg.VBUSBSEN, 0);
+    cpu::write!(OTG_FS_GLOBAL.gccfg.NOVBUSSENS, 1);
+    cpu::write!(OTG_FS_GLOBAL.gccfg.PWRDWN, 1);
+    cpu::write!(OTG_FS_PWRCLK.pcgcctl, 0);
+
+    cpu::write!(OTG_FS_DEVICE.dcfg.DSPD, 0b11); // full speed
+    cpu::write!(OTG_FS_DEVICE.dcfg.DAD, 0);
+
+    // the 320 words of FIFO RAM are shared by the receive FIFO and a
+    // transmit FIFO for each IN endpoint
+    cpu::write!(OTG_FS_GLOBAL.grxfsiz, 128);
+    cpu::write!(OTG_FS_GLOBAL.dieptxf0, (32 << 16) | 128);
+    cpu::write!(OTG_FS_GLOBAL.dieptxf1, (64 << 16) | 160);
+    cpu::write!(OTG_FS_GLOBAL.dieptxf2, (16 << 16) | 224);
+    flush_tx_fifos();
+
+    cpu::write!(OTG_FS_GLOBAL.gintsts, 0xFFFF_FFFF);
+    cpu::write!(
+        OTG_FS_GLOBAL.gintmsk,
+        (1 << gintsts::USBRST)
+            | (1 << gintsts::ENUMDNE)
+            | (1 << gintsts::RXFLVL)
+            | (1 << gintsts::IEPINT)
+            | (1 << gintsts::OEPINT)
+            | (1 << gintsts::USBSUSP)
+    );
+    cpu::write!(OTG_FS_GLOBAL.gahbcfg.GINT, 1);
+    cpu::enable_irq(Interrupt::OtgFs);
+
+    cpu::write!(OTG_FS_DEVICE.dctl.SDIS, 0);
+}
+
+fn flush_tx_fifos() {
+    cpu::write!(
+        OTG_FS_GLOBAL.grstctl,
+        (0x10 << grstctl::TXFNUM) | (1 << grstctl::TXFFLSH)
+    );
+    #[cfg(not(feature = "std"))]
+    while cpu::read!(OTG_FS_GLOBAL.grstctl.TXFFLSH) != 0 {}
+}
+
+/// Returns true if `pin` is DM (PA11) or DP (PA12) and `init` has started
+/// the peripheral, so the pin must be left alone.
+pub fn owns_pin(pin: &Pin) -> bool {
+    cpu::read!(RCC.ahb2enr.OTGFSEN) == 1 && pin.0 == GPIOA && (pin.1 == 11 || pin.1 == 12)
+}
+
+/// Returns true once the host has enumerated and configured the device.
+pub fn configured() -> bool {
+    without_irq(|| device().configured())
+}
+
+/// Returns true if a program on the host has the serial port open.
+pub fn port_open() -> bool {
+    without_irq(|| device().dtr())
+}
+
+/// Queues `data` to send to the host and returns how many bytes fit in the
+/// transmit buffer. Nothing is queued until the device is configured.
+#[inline(never)]
+pub fn write(data: &[u8]) -> usize {
+    without_irq(|| {
+        if !device().configured() {
+            return 0;
+        }
+        let tx = tx();
+        let count = data.iter().take_while(|byte| tx.push(**byte)).count();
+        start_tx();
+        count
+    })
+}
+
+/// Moves bytes received from the host into `buf` and returns how many were
+/// copied.
+#[inline(never)]
+pub fn read(buf: &mut [u8]) -> usize {
+    without_irq(|| {
+        let rx = rx();
+        let mut count = 0;
+        while count < buf.len() {
+            match rx.pop() {
+                Some(byte) => buf[count] = byte,
+                None => break,
+            }
+            count += 1;
+        }
+        if !get_flag(&RX_ARMED) && device().configured() {
+            arm_rx();
+        }
+        count
+    })
+}
+
+/// Handles the OTG_FS interrupt.
+#[inline(never)]
+pub fn handle_irq() {
+    let status = cpu::read!(OTG_FS_GLOBAL.gintsts) & cpu::read!(OTG_FS_GLOBAL.gintmsk);
+
+    if status & (1 << gintsts::USBRST) != 0 {
+        cpu::clear!(OTG_FS_GLOBAL.gintsts.USBRST);
+        bus_reset();
+    }
+
+    if status & (1 << gintsts::ENUMDNE) != 0 {
+        cpu::clear!(OTG_FS_GLOBAL.gintsts.ENUMDNE);
+        cpu::write!(OTG_FS_DEVICE.diepctl0.diepctl::MPSIZ, 0); // 64 bytes
+        cpu::write!(OTG_FS_DEVICE.dctl.CGINAK, 1);
+    }
+
+    if status & (1 << gintsts::RXFLVL) != 0 {
+        receive();
+    }
+
+    let endpoints = cpu::read!(OTG_FS_DEVICE.daint) & cpu::read!(OTG_FS_DEVICE.daintmsk);
+
+    if status & (1 << gintsts::OEPINT) != 0 {
+        if endpoints & (1 << daint::OEPINT) != 0 {
+            ep0_out();
+        }
+        if endpoints & (1 << (daint::OEPINT + DATA_EP as u8)) != 0 {
+            let int = cpu::read!(OTG_FS_DEVICE.doepint1);
+            cpu::write!(OTG_FS_DEVICE.doepint1, int);
+            if int & (1 << doepint0::XFRC) != 0 {
+                arm_rx();
+            }
+        }
+    }
+
+    if status & (1 << gintsts::IEPINT) != 0 {
+        if endpoints & (1 << daint::IEPINT) != 0 {
+            let int = cpu::read!(OTG_FS_DEVICE.diepint0);
+            cpu::write!(OTG_FS_DEVICE.diepint0, int);
+        }
+        if endpoints & (1 << (daint::IEPINT + DATA_EP as u8)) != 0 {
+            let int = cpu::read!(OTG_FS_DEVICE.diepint1);
+            cpu::write!(OTG_FS_DEVICE.diepint1, int);
+            if int & (1 << doepint0::XFRC) != 0 {
+                set_flag(&TX_BUSY, false);
+                start_tx();
+            }
+        }
+    }
+
+    if status & (1 << gintsts::USBSUSP) != 0 {
+        cpu::clear!(OTG_FS_GLOBAL.gintsts.USBSUSP);
+    }
+}
+
+fn bus_reset() {
+    device().reset();
+    cpu::write!(OTG_FS_DEVICE.dcfg.DAD, 0);
+
+    flush_tx_fifos();
+    deactivate_endpoints();
+    rx().clear();
+
+    // only the control endpoint until configured
+    cpu::write!(
+        OTG_FS_DEVICE.daintmsk,
+        (1 << daint::OEPINT) | (1 << daint::IEPINT)
+    );
+    cpu::write!(
+        OTG_FS_DEVICE.doepmsk,
+        (1 << doepmsk::STUPM) | (1 << doepmsk::XFRCM)
+    );
+    cpu::write!(OTG_FS_DEVICE.diepmsk, 1 << diepmsk::XFRCM);
+
+    arm_ep0_out();
+}
+
+/// Pops one entry from the receive FIFO.
+fn receive() {
+    let status = cpu::read!(OTG_FS_GLOBAL.grxstsp);
+    let ep = (status >> grxstsp::EPNUM) as usize & 0xF;
+    let count = (status >> grxstsp::BCNT) as usize & 0x7FF;
+
+    match (status >> grxstsp::PKTSTS) & 0xF {
+        PKTSTS_SETUP_DATA => read_packet(count, |i, byte| {
+            if i < 8 {
+                unsafe { ptr::write_volatile(ptr::addr_of_mut!((*SETUP.as_ptr())[i]), byte) };
+            }
+        }),
+        PKTSTS_OUT_DATA if ep == 0 => {
+            read_packet(count, |i, byte| {
+                if i < 8 {
+                    unsafe { ptr::write_volatile(ptr::addr_of_mut!((*EP0_OUT.as_ptr())[i]), byte) };
+                }
+            });
+            unsafe { EP0_OUT_LEN.set(count.min(8)) };
+        }
+        PKTSTS_OUT_DATA => {
+            // arm_rx makes sure there is room for a whole packet
+            let rx = rx();
+            read_packet(count, |_, byte| {
+                rx.push(byte);
+            });
+        }
+        // the transfer and setup complete entries have no data
+        _ => {}
+    }
+}
+
+fn ep0_out() {
+    let int = cpu::read!(OTG_FS_DEVICE.doepint0);
+    cpu::write!(OTG_FS_DEVICE.doepint0, int);
+
+    if int & (1 << doepint0::XFRC) != 0 {
+        // the data stage of a SET_LINE_CODING, or the status stage of an IN
+        // transfer which has no data and is ignored
+        let len = unsafe { EP0_OUT_LEN.get() };
+        if len > 0 {
+            let data = unsafe { EP0_OUT.get() };
+            unsafe { EP0_OUT_LEN.set(0) };
+            control_response(device().control_out(&data[..len]));
+        }
+    }
+
+    if int & (1 << doepint0::STUP) != 0 {
+        setup();
+    }
+
+    arm_ep0_out();
+}
+
+fn setup() {
+    let packet = SetupPacket::parse(&unsafe { SETUP.get() });
+    let device = device();
+    let was_configured = device.configured();
+
+    let response = device.setup(&packet);
+
+    // the new address is used after the status stage, which the core handles
+    cpu::write!(OTG_FS_DEVICE.dcfg.DAD, device.address() as u32);
+
+    if device.configured() != was_configured {
+        if device.configured() {
+            activate_endpoints();
+        } else {
+            deactivate_endpoints();
+        }
+    }
+
+    if let Response::Receive(_) = response {
+        unsafe { EP0_OUT_LEN.set(0) };
+        return;
+    }
+    control_response(response);
+}
+
+/// Sends the data or status stage of a control transfer.
+fn control_response(response: Response) {
+    match response {
+        Response::Data(data) => ep0_send(data),
+        Response::Short(data, n) => ep0_send(&data[..n]),
+        Response::Ack => ep0_send(&[]),
+        Response::Receive(_) => {}
+        Response::Stall => {
+            cpu::write!(OTG_FS_DEVICE.diepctl0.diepctl::STALL, 1);
+            cpu::write!(OTG_FS_DEVICE.doepctl0.doepctl::STALL, 1);
+        }
+    }
+}
+
+/// Sends `data` on the control IN endpoint. It all fits in the transmit
+/// FIFO so it is written at once.
+fn ep0_send(data: &[u8]) {
+    let packets = data.len().div_ceil(MAX_PACKET).max(1) as u32;
+    cpu::write!(
+        OTG_FS_DEVICE.dieptsiz0,
+        (packets << dieptsiz0::PKTCNT) | data.len() as u32
+    );
+    cpu::write!(OTG_FS_DEVICE.diepctl0.diepctl::CNAK, 1);
+    cpu::write!(OTG_FS_DEVICE.diepctl0.diepctl::EPENA, 1);
+    write_packet(0, data);
+}
+
+/// Gets the control OUT endpoint ready for the next setup packet or data.
+fn arm_ep0_out() {
+    cpu::write!(
+        OTG_FS_DEVICE.doeptsiz0,
+        (3 << doeptsiz0::STUPCNT) | (1 << doeptsiz0::PKTCNT) | MAX_PACKET as u32
+    );
+    cpu::write!(OTG_FS_DEVICE.doepctl0.doepctl::CNAK, 1);
+    cpu::write!(OTG_FS_DEVICE.doepctl0.doepctl::EPENA, 1);
+}
+
+fn activate_endpoints() {
+    let bulk = (1 << doepctl::USBAEP) | (1 << doepctl::SD0PID) | (0b10 << doepctl::EPTYP);
+    let interrupt = (1 << diepctl::USBAEP) | (1 << diepctl::SD0PID) | (0b11 << diepctl::EPTYP);
+
+    cpu::write!(
+        OTG_FS_DEVICE.diepctl1,
+        bulk | (1 << diepctl::TXFNUM) | MAX_PACKET as u32
+    );
+    cpu::write!(
+        OTG_FS_DEVICE.diepctl2,
+        interrupt | (2 << diepctl::TXFNUM) | 8
+    );
+    cpu::write!(OTG_FS_DEVICE.doepctl1, bulk | MAX_PACKET as u32);
+
+    cpu::write!(
+        OTG_FS_DEVICE.daintmsk,
+        (1 << daint::OEPINT)
+            | (1 << daint::IEPINT)
+            | (1 << (daint::OEPINT + DATA_EP as u8))
+            | (1 << (daint::IEPINT + DATA_EP as u8))
+    );
+
+    set_flag(&TX_BUSY, false);
+    set_flag(&TX_ZLP, false);
+    arm_rx();
+    start_tx();
+}
+
+fn deactivate_endpoints() {
+    cpu::write!(OTG_FS_DEVICE.diepctl1, 0);
+    cpu::write!(OTG_FS_DEVICE.diepctl2, 0);
+    cpu::write!(OTG_FS_DEVICE.doepctl1, 0);
+    set_flag(&TX_BUSY, false);
+    set_flag(&RX_ARMED, false);
+}
+
+/// Gets the bulk OUT endpoint ready to receive a packet if there is room
+/// for it. Otherwise the host is NAKed until `read` makes room.
+fn arm_rx() {
+    if rx().free() < MAX_PACKET {
+        set_flag(&RX_ARMED, false);
+        return;
+    }
+    cpu::write!(
+        OTG_FS_DEVICE.doeptsiz1,
+        (1 << doeptsiz0::PKTCNT) | MAX_PACKET as u32
+    );
+    cpu::write!(OTG_FS_DEVICE.doepctl1.doepctl::CNAK, 1);
+    cpu::write!(OTG_FS_DEVICE.doepctl1.doepctl::EPENA, 1);
+    set_flag(&RX_ARMED, true);
+}
+
+/// Sends the next packet from the transmit buffer on the bulk IN endpoint.
+fn start_tx() {
+    if get_flag(&TX_BUSY) || !device().configured() {
+        return;
+    }
+
+    let tx = tx();
+    let mut packet = [0u8; MAX_PACKET];
+    let mut len = 0;
+    while len < MAX_PACKET {
+        match tx.pop() {
+            Some(byte) => packet[len] = byte,
+            None => break,
+        }
+        len += 1;
+    }
+
+    if len == 0 && !get_flag(&TX_ZLP) {
+        return;
+    }
+    set_flag(&TX_ZLP, len == MAX_PACKET);
+
+    cpu::write!(
+        OTG_FS_DEVICE.dieptsiz1,
+        (1 << dieptsiz0::PKTCNT) | len as u32
+    );
+    cpu::write!(OTG_FS_DEVICE.diepctl1.diepctl::CNAK, 1);
+    cpu::write!(OTG_FS_DEVICE.diepctl1.diepctl::EPENA, 1);
+    write_packet(DATA_EP, &packet[..len]);
+    set_flag(&TX_BUSY, true);
+}
+
+/// Simulates the host resetting the bus and finishing the speed enumeration,
+/// and runs the interrupt handler for each.
+#[cfg(feature = "std")]
+pub fn sim_reset() {
+    cpu::write!(OTG_FS_GLOBAL.gintsts, 1 << gintsts::USBRST);
+    handle_irq();
+    cpu::write!(OTG_FS_GLOBAL.gintsts, 1 << gintsts::ENUMDNE);
+    handle_irq();
+}
+
+/// Simulates the host sending a setup packet on the control endpoint, and
+/// runs the interrupt handler for it.
+#[cfg(feature = "std")]
+pub fn sim_setup(packet: &[u8; 8]) {
+    sim_receive(0, PKTSTS_SETUP_DATA, packet);
+    sim_out_complete(0, 1 << doepint0::STUP);
+}
+
+/// Simulates the host sending `data` on OUT endpoint `ep`, and runs the
+/// interrupt handler for it.
+#[cfg(feature = "std")]
+pub fn sim_out(ep: usize, data: &[u8]) {
+    sim_receive(ep, PKTSTS_OUT_DATA, data);
+    sim_out_complete(ep, 1 << doepint0::XFRC);
+}
+
+/// Simulates the host taking the packet queued on IN endpoint `ep`, and runs
+/// the interrupt handler for it.
+#[cfg(feature = "std")]
+pub fn sim_in_complete(ep: usize) {
+    unsafe {
+        cpu::write_reg(ptr::addr_of_mut!((*OTG_FS_DEVICE).diepint0).add(ep * 8), 1);
+    }
+    cpu::write!(OTG_FS_DEVICE.daint, 1 << (daint::IEPINT as usize + ep));
+    cpu::write!(OTG_FS_GLOBAL.gintsts, 1 << gintsts::IEPINT);
+    handle_irq();
+}
+
+/// Returns and clears the bytes sent to the host on IN endpoint `ep`.
+#[cfg(feature = "std")]
+pub fn sim_sent(ep: usize) -> std::vec::Vec<u8> {
+    core::mem::take(unsafe { &mut (*SIM_SENT.as_ptr())[ep] })
+}
+
+#[cfg(feature = "std")]
+fn sim_receive(ep: usize, pktsts: u32, data: &[u8]) {
+    unsafe { (*SIM_RX_FIFO.as_ptr()).extend(data.iter()) };
+    cpu::write!(
+        OTG_FS_GLOBAL.grxstsp,
+        (pktsts << grxstsp::PKTSTS) | ((data.len() as u32) << grxstsp::BCNT) | ep as u32
+    );
+    cpu::write!(OTG_FS_GLOBAL.gintsts, 1 << gintsts::RXFLVL);
+    handle_irq();
+}
+
+#[cfg(feature = "std")]
+fn sim_out_complete(ep: usize, flags: u32) {
+    // the interrupt registers are write 1 to clear, so are set afresh
+    unsafe {
+        cpu::write_reg(
+            ptr::addr_of_mut!((*OTG_FS_DEVICE).doepint0).add(ep * 8),
+            flags,
+        );
+    }
+    cpu::write!(OTG_FS_DEVICE.daint, 1 << (daint::OEPINT as usize + ep));
+    cpu::write!(OTG_FS_GLOBAL.gintsts, 1 << gintsts::OEPINT);
+    handle_irq();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup(device: &mut Device, packet: [u8; 8]) -> Response {
+        device.setup(&SetupPacket::parse(&packet))
+    }
+
+    #[test]
+    fn test_descriptors() {
+        assert_eq!(CONFIG_DESC[2] as usize, CONFIG_DESC.len());
+        assert_eq!(&MANUFACTURER[..6], &[14, 3, b'H', 0, b'a', 0]);
+        assert_eq!(descriptor(0x0600), None); // device qualifier
+    }
+
+    #[test]
+    fn test_linux_enumeration() {
+        // setup packets recorded from Linux enumerating the device
+        let mut device = Device::new();
+
+        assert_eq!(
+            setup(
+                &mut device,
+                [0x80, 0x06, 0x00, 0x01, 0x00, 0x00, 0x40, 0x00]
+            ),
+            Response::Data(&DEVICE_DESC)
+        );
+        assert_eq!(
+            setup(
+                &mut device,
+                [0x00, 0x05, 0x0A, 0x00, 0x00, 0x00, 0x00, 0x00]
+            ),
+            Response::Ack
+        );
+        assert_eq!(device.address(), 10);
+        assert_eq!(
+            setup(
+                &mut device,
+                [0x80, 0x06, 0x00, 0x02, 0x00, 0x00, 0x09, 0x00]
+            ),
+            Response::Data(&CONFIG_DESC[..9])
+        );
+        assert_eq!(
+            setup(
+                &mut device,
+                [0x80, 0x06, 0x00, 0x02, 0x00, 0x00, 0xFF, 0x00]
+            ),
+            Response::Data(&CONFIG_DESC)
+        );
+        assert_eq!(
+            setup(
+                &mut device,
+                [0x80, 0x06, 0x00, 0x06, 0x00, 0x00, 0x0A, 0x00]
+            ),
+            Response::Stall
+        );
+        assert_eq!(
+            setup(
+                &mut device,
+                [0x80, 0x06, 0x02, 0x03, 0x09, 0x04, 0xFF, 0x00]
+            ),
+            Response::Data(&PRODUCT)
+        );
+        assert!(!device.configured());
+        assert_eq!(
+            setup(
+                &mut device,
+                [0x00, 0x09, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00]
+            ),
+            Response::Ack
+        );
+        assert!(device.configured());
+
+        // cdc-acm reads the line coding, then the port is opened
+        let Response::Short(coding, 7) = setup(
+            &mut device,
+            [0xA1, 0x21, 0x00, 0x00, 0x00, 0x00, 0x07, 0x00],
+        ) else {
+            panic!("no line coding");
+        };
+        assert_eq!(&coding[..7], &[0x00, 0xC2, 0x01, 0x00, 0, 0, 8]);
+        assert!(!device.dtr());
+        assert_eq!(
+            setup(
+                &mut device,
+                [0x21, 0x22, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00]
+            ),
+            Response::Ack
+        );
+        assert!(device.dtr());
+
+        assert_eq!(
+            setup(
+                &mut device,
+                [0x21, 0x20, 0x00, 0x00, 0x00, 0x00, 0x07, 0x00]
+            ),
+            Response::Receive(7)
+        );
+        assert_eq!(
+            device.control_out(&[0x80, 0x25, 0x00, 0x00, 0, 0, 8]),
+            Response::Ack
+        );
+        assert_eq!(device.baud_rate(), 9600);
+
+        // data without a request is stalled
+        assert_eq!(device.control_out(&[0; 7]), Response::Stall);
+
+        device.reset();
+        assert!(!device.configured());
+        assert_eq!(device.address(), 0);
+    }
+}
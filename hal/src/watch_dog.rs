@@ -6,22 +6,22 @@ pub use super::cpu::*;
 pub fn init() {}
 
 pub fn is_enabled() -> bool {
-    let v = cpu::read!( WWDG.cr[WDGA;1] );
+    let v = cpu::read!(WWDG.cr.WDGA);
     v != 0
 }
 
 #[cfg(feature = "stm32f072")]
 pub fn start() {
-    cpu::write!( RCC.apb1enr[WWDGEN;1], 1 ); // turn on clock for WWDG
+    cpu::write!(RCC.apb1enr.WWDGEN, 1); // turn on clock for WWDG
 
-    cpu::write!( DBGMCU.apb1_fz[DBG_WWDG_STOP;1],0b1);
+    cpu::write!(DBGMCU.apb1_fz.DBG_WWDG_STOP, 0b1);
 
-    cpu::write!( WWDG.cfr[EWI;1]  , 0b1 ); // interupt
-    cpu::write!( WWDG.cfr[WDGTB;2]  , 0b11 ); // Div 8 prescaler
-    cpu::write!( WWDG.cfr[W;7]  , 0x7F ); // window range
+    cpu::write!(WWDG.cfr.EWI, 0b1); // interupt
+    cpu::write!(WWDG.cfr.WDGTB, 0b11); // Div 8 prescaler
+    cpu::write!(WWDG.cfr.W, 0x7F); // window range
 
-    cpu::write!( WWDG.cr[T;7]  , 0x7F ); // set counter
-    cpu::write!( WWDG.cr[WDGA;1] , 0b1 ); // enable
+    cpu::write!(WWDG.cr.T, 0x7F); // set counter
+    cpu::write!(WWDG.cr.WDGA, 0b1); // enable
 
     // with 48 MHz pcb clock, 1/8 prescaler,  0x7F counter
     // 48_000_000 / 4096 / 8 / ( 0x7F +1 ) = 11.4 Hz = 87.7 ms
@@ -29,20 +29,112 @@ pub fn start() {
 
 #[cfg(feature = "stm32f405")]
 pub fn start() {
-    cpu::write!(RCC.apb1enr[WWDGEN;1], 1 ); // turn on clock for WWDG
+    cpu::write!(RCC.apb1enr.WWDGEN, 1); // turn on clock for WWDG
 
-    cpu::write!( DBG.dbgmcu_apb1_fz[DBG_WWDG_STOP;1],0b1);
+    cpu::write!(DBG.dbgmcu_apb1_fz.DBG_WWDG_STOP, 0b1);
 
-    cpu::write!( WWDG.cfr[EWI;1]  , 0b0 ); // no interupt
-    cpu::write!( WWDG.cfr[WDGTB0;2]  , 0b11 ); // Div 8 prescaler
-    cpu::write!( WWDG.cfr[W;7]  , 0x7F ); // set window range
+    cpu::write!(WWDG.cfr.EWI, 0b0); // no interupt
+    cpu::write!(WWDG.cfr.WDGTB, 0b11); // Div 8 prescaler
+    cpu::write!(WWDG.cfr.W, 0x7F); // set window range
 
-    cpu::write!( WWDG.cr[T;7]  , 0x7F ); // set counter
-    cpu::write!( WWDG.cr[WDGA;1] , 0b1 ); // enable
+    cpu::write!(WWDG.cr.T, 0x7F); // set counter
+    cpu::write!(WWDG.cr.WDGA, 0b1); // enable
 }
 
 /// Resets the WWDG (Window Watchdog) counter to prevent a system reset.
 ///
 pub fn alive() {
-    cpu::write!(WWDG.cr[T;7], 0x7F);
+    cpu::write!(WWDG.cr.T, 0x7F);
+}
+
+// The IWDG (Independent Watchdog) runs from the LSI oscillator, so it keeps
+// working if the main clock fails and can not be stopped once started.
+
+#[cfg(feature = "stm32f405")]
+const LSI_FREQ: u32 = 32_000;
+
+#[cfg(feature = "stm32f072")]
+const LSI_FREQ: u32 = 40_000;
+
+/// Timeout of the IWDG in ms, or 0 before it is started.
+static IWDG_TIMEOUT_MS: Global<u32> = Global::new(0);
+
+/// Computes the IWDG prescaler and reload values for a timeout of
+/// `timeout_ms` with an LSI clock of `lsi_freq` Hz.
+///
+/// Returns `(pr, rlr)` as written to the registers. The smallest prescaler
+/// is used to give the finest resolution, and timeouts that are too long are
+/// clamped to the longest possible.
+pub fn iwdg_divider(timeout_ms: u32, lsi_freq: u32) -> (u32, u32) {
+    assert!(timeout_ms > 0);
+
+    let ticks: u64 = timeout_ms as u64 * lsi_freq as u64 / 1000;
+    for pr in 0..7 {
+        // prescaler is 4, 8, 16, ... 256
+        let div: u64 = 4 << pr;
+        let reload = ticks.div_ceil(div);
+        if reload <= 0x1000 {
+            return (pr, reload.max(1) as u32 - 1);
+        }
+    }
+    (6, 0xFFF)
+}
+
+/// Starts the IWDG so that the board resets if `iwdg_alive` is not called
+/// at least every `timeout_ms`. The IWDG is paused while the core is halted
+/// by a debugger.
+#[inline(never)]
+pub fn iwdg_start(timeout_ms: u32) {
+    let (pr, rlr) = iwdg_divider(timeout_ms, LSI_FREQ);
+
+    #[cfg(feature = "stm32f405")]
+    cpu::write!(DBG.dbgmcu_apb1_fz.DBG_IWDEG_STOP, 0b1);
+    #[cfg(feature = "stm32f072")]
+    cpu::write!(DBGMCU.apb1_fz.DBG_IWDG_STOP, 0b1);
+
+    cpu::write!(IWDG.kr, 0xCCCC); // start, this also turns on the LSI
+    cpu::write!(IWDG.kr, 0x5555); // allow writes to PR and RLR
+    cpu::write!(IWDG.pr, pr);
+    cpu::write!(IWDG.rlr, rlr);
+
+    let timeout = (rlr as u64 + 1) * (4 << pr) * 1000 / LSI_FREQ as u64;
+    unsafe { IWDG_TIMEOUT_MS.set(timeout as u32) };
+
+    // wait for the values to move to the LSI clock domain
+    #[cfg(not(feature = "std"))]
+    while cpu::read!(IWDG.sr) != 0 {}
+
+    iwdg_alive();
+}
+
+/// Reloads the IWDG counter to prevent a system reset.
+#[inline(always)]
+pub fn iwdg_alive() {
+    cpu::write!(IWDG.kr, 0xAAAA);
+}
+
+/// Returns the timeout of the IWDG in ms, or `None` if it has not been
+/// started.
+pub fn iwdg_timeout_ms() -> Option<u32> {
+    match unsafe { IWDG_TIMEOUT_MS.get() } {
+        0 => None,
+        timeout => Some(timeout),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_iwdg_divider() {
+        // 1 second at 32kHz is 32,000 ticks, which needs a divide by 8
+        assert_eq!(iwdg_divider(1000, 32_000), (1, 3999));
+
+        // short timeouts use the smallest prescaler
+        assert_eq!(iwdg_divider(10, 32_000), (0, 79));
+
+        // too long is clamped to the longest timeout
+        assert_eq!(iwdg_divider(60_000, 32_000), (6, 0xFFF));
+    }
 }
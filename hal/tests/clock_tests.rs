@@ -0,0 +1,97 @@
+#![no_std]
+// The clock tree of the STM32F072 has no PLL configuration to test
+#![cfg(feature = "stm32f405")]
+
+#[cfg(feature = "std")]
+extern crate std;
+
+#[cfg(test)]
+mod tests {
+    use core::ptr::addr_of_mut;
+
+    use hal::clock::{self, ClockConfig, Source};
+    use hal::cpu;
+    use hal::sim;
+
+    fn read(addr: *mut u32) -> u32 {
+        cpu::read_reg(addr)
+    }
+
+    fn hse_fails(fails: bool) {
+        sim::with_model(cpu::RCC, |rcc: &mut sim::Rcc| rcc.hse_fails = fails).unwrap();
+    }
+
+    #[test]
+    fn test_clock_sim() {
+        let _sim = sim::Guard::new();
+
+        hse_ok();
+        hse_fallback();
+        css_nmi();
+    }
+
+    fn hse_ok() {
+        hse_fails(false);
+        assert_eq!(ClockConfig::new(24_000_000).apply(), Source::Hse);
+
+        assert_eq!(clock::source(), Source::Hse);
+        assert!(!clock::hse_failed());
+        assert_eq!(clock::clocks().sysclk, 168_000_000);
+
+        unsafe {
+            // PLL from the HSE and the clock security system on
+            assert_ne!(read(addr_of_mut!((*cpu::RCC).pllcfgr)) & (1 << 22), 0);
+            assert_ne!(read(addr_of_mut!((*cpu::RCC).cr)) & (1 << 19), 0);
+        }
+    }
+
+    fn hse_fallback() {
+        hse_fails(true);
+        assert_eq!(ClockConfig::new(24_000_000).apply(), Source::Hsi);
+
+        assert_eq!(clock::source(), Source::Hsi);
+        assert!(clock::hse_failed());
+
+        // the PLL still gives the full speed from the HSI
+        assert_eq!(clock::clocks().sysclk, 168_000_000);
+
+        unsafe {
+            let cr = read(addr_of_mut!((*cpu::RCC).cr));
+            assert_eq!(cr & (1 << 16), 0); // HSE off
+            assert_eq!(cr & (1 << 19), 0); // CSS off
+            assert_eq!(read(addr_of_mut!((*cpu::RCC).pllcfgr)) & (1 << 22), 0);
+        }
+        hse_fails(false);
+
+        // waking from stop restores the clocks but keeps the failure
+        clock::restore();
+        assert_eq!(clock::source(), Source::Hsi);
+        assert!(clock::hse_failed());
+    }
+
+    fn css_nmi() {
+        ClockConfig::new(24_000_000).sysclk(120_000_000).apply();
+        assert!(!clock::hse_failed());
+
+        // no CSS flag, so not a clock failure
+        clock::handle_css_nmi();
+        assert_eq!(clock::source(), Source::Hse);
+
+        // simulate the HSE failing while in use
+        unsafe {
+            let cir = addr_of_mut!((*cpu::RCC).cir);
+            cpu::write_reg(cir, read(cir) | (1 << 7));
+        }
+        clock::handle_css_nmi();
+
+        assert_eq!(clock::source(), Source::Hsi);
+        assert!(clock::hse_failed());
+        assert_eq!(clock::clocks().sysclk, 120_000_000);
+        unsafe {
+            assert_eq!(read(addr_of_mut!((*cpu::RCC).pllcfgr)) & (1 << 22), 0);
+        }
+
+        clock::restore();
+        assert!(clock::hse_failed());
+    }
+}
@@ -0,0 +1,63 @@
+#![no_std]
+
+#[cfg(feature = "std")]
+extern crate std;
+
+#[cfg(test)]
+mod tests {
+    use core::ptr::addr_of_mut;
+    use core::sync::atomic::{AtomicU32, Ordering};
+
+    use hal::cpu;
+    use hal::gpio::{self, Edge, Pin};
+
+    static CALLS: AtomicU32 = AtomicU32::new(0);
+
+    fn on_edge() {
+        CALLS.fetch_add(1, Ordering::SeqCst);
+    }
+
+    fn read(addr: *mut u32) -> u32 {
+        cpu::read_reg(addr)
+    }
+
+    #[test]
+    fn test_exti_enable_and_dispatch() {
+        cpu::init();
+
+        let pin = Pin::new(cpu::GPIOC, 6);
+        pin.input();
+        pin.enable_interrupt(Edge::Falling, on_edge);
+
+        // PC6 is selected in EXTICR2 and only the falling edge is enabled
+        unsafe {
+            assert_eq!((read(addr_of_mut!((*cpu::SYSCFG).exticr2)) >> 8) & 0xF, 2);
+            assert_eq!(read(addr_of_mut!((*cpu::EXTI).rtsr)) & (1 << 6), 0);
+            assert_eq!(read(addr_of_mut!((*cpu::EXTI).ftsr)) & (1 << 6), 1 << 6);
+            assert_eq!(read(addr_of_mut!((*cpu::EXTI).imr)) & (1 << 6), 1 << 6);
+
+            // EXTI9_5 (EXTI4_15 on the STM32F072) is enabled in the NVIC
+            let irq = gpio::exti_irq(6).number();
+            assert!(irq < 32);
+            let iser = read(addr_of_mut!((*cpu::NVIC).iser[0]));
+            assert_eq!(iser & (1 << irq), 1 << irq);
+        }
+
+        // simulate an edge on the line
+        cpu::write_reg(unsafe { addr_of_mut!((*cpu::EXTI).pr) }, 1 << 6);
+        assert!(pin.interrupt_pending());
+        gpio::handle_exti_irq();
+        assert_eq!(CALLS.load(Ordering::SeqCst), 1);
+
+        pin.disable_interrupt();
+        assert_eq!(
+            read(unsafe { addr_of_mut!((*cpu::EXTI).imr) }) & (1 << 6),
+            0
+        );
+
+        // masked lines are not dispatched
+        cpu::write_reg(unsafe { addr_of_mut!((*cpu::EXTI).pr) }, 1 << 6);
+        gpio::handle_exti_irq();
+        assert_eq!(CALLS.load(Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,97 @@
+#![no_std]
+
+#[cfg(feature = "std")]
+extern crate std;
+
+#[cfg(test)]
+mod tests {
+    use hal::flash::{self, Error};
+    use hal::sim;
+
+    #[test]
+    fn test_flash_sim() {
+        let _sim = sim::Guard::new();
+
+        erase_and_write();
+        write_not_erased();
+        locked();
+        bad_address();
+    }
+
+    // the last sector of the STM32F405, or the last page of the STM32F072
+    #[cfg(feature = "stm32f405")]
+    const SECTOR_11: u32 = 0x080E_0000;
+    #[cfg(feature = "stm32f072")]
+    const SECTOR_11: u32 = 0x0801_F800;
+
+    fn erase_and_write() {
+        flash::unlock();
+        assert_eq!(flash::erase(SECTOR_11 + 0x100), Ok(()));
+
+        let mut data = [0u8; 4];
+        flash::read(SECTOR_11, &mut data);
+        assert_eq!(data, [0xFF; 4]);
+
+        // odd address and length use byte, half-word, and word writes
+        #[cfg(feature = "stm32f405")]
+        {
+            assert_eq!(flash::write(SECTOR_11 + 1, b"hello world"), Ok(()));
+
+            let mut data = [0u8; 13];
+            flash::read(SECTOR_11, &mut data);
+            assert_eq!(&data, b"\xFFhello world\xFF");
+        }
+
+        // the STM32F072 only programs half-words
+        #[cfg(feature = "stm32f072")]
+        {
+            assert_eq!(
+                flash::write(SECTOR_11 + 1, b"hi"),
+                Err(Error::InvalidAddress)
+            );
+            assert_eq!(flash::write(SECTOR_11 + 2, b"hello world!"), Ok(()));
+
+            let mut data = [0u8; 15];
+            flash::read(SECTOR_11, &mut data);
+            assert_eq!(&data, b"\xFF\xFFhello world!\xFF");
+        }
+
+        assert_eq!(flash::program_word(SECTOR_11 + 16, 0x1234_5678), Ok(()));
+        let mut data = [0u8; 4];
+        flash::read(SECTOR_11 + 16, &mut data);
+        assert_eq!(data, [0x78, 0x56, 0x34, 0x12]);
+    }
+
+    fn write_not_erased() {
+        // programming can only clear bits so the read back does not match
+        assert_eq!(
+            flash::program_half_word(SECTOR_11 + 16, 0xFFFF),
+            Err(Error::Verify(SECTOR_11 + 16))
+        );
+
+        assert_eq!(flash::erase(SECTOR_11), Ok(()));
+        assert_eq!(flash::program_half_word(SECTOR_11 + 16, 0xABCD), Ok(()));
+    }
+
+    fn locked() {
+        flash::lock();
+        assert!(flash::is_locked());
+        assert_eq!(flash::program_word(SECTOR_11 + 32, 0), Err(Error::Locked));
+        assert_eq!(flash::erase(SECTOR_11), Err(Error::Locked));
+
+        flash::unlock();
+        assert!(!flash::is_locked());
+    }
+
+    fn bad_address() {
+        assert_eq!(
+            flash::program_word(SECTOR_11 + 2, 0),
+            Err(Error::InvalidAddress)
+        );
+        assert_eq!(
+            flash::program_word(0x0810_0000, 0),
+            Err(Error::InvalidAddress)
+        );
+        assert_eq!(flash::erase(0x2000_0000), Err(Error::InvalidAddress));
+    }
+}
@@ -0,0 +1,105 @@
+#![no_std]
+
+#[cfg(feature = "std")]
+extern crate std;
+
+#[cfg(test)]
+mod tests {
+    use core::ptr::addr_of_mut;
+
+    use hal::cpu;
+    use hal::gpio::Pin;
+    use hal::power::{self, Mode};
+    use hal::rtc::{self, ClockSource};
+    use hal::sim;
+    use hal::watch_dog;
+
+    fn tick() {}
+
+    fn read(addr: *mut u32) -> u32 {
+        cpu::read_reg(addr)
+    }
+
+    #[test]
+    fn test_power_sim() {
+        cpu::init();
+        // the STM32F072 clock only runs from a 16 MHz crystal
+        #[cfg(feature = "stm32f405")]
+        hal::clock::init(0);
+        #[cfg(feature = "stm32f072")]
+        hal::clock::init(16_000_000);
+
+        sleep();
+        stop();
+        standby();
+        park();
+        stop_with_iwdg();
+    }
+
+    fn sleep() {
+        power::enter(Mode::Sleep);
+        assert_eq!(read(unsafe { addr_of_mut!((*cpu::SCB).scr) }) & (1 << 2), 0);
+    }
+
+    fn stop() {
+        // the stop mode turns off the PLL, which clock::restore turns back on
+        let cr = unsafe { addr_of_mut!((*cpu::RCC).cr) };
+        cpu::write_reg(cr, read(cr) & !(1 << 24));
+
+        power::enter(Mode::Stop);
+
+        unsafe {
+            // low power regulator and not power down
+            assert_eq!(read(addr_of_mut!((*cpu::PWR).cr)) & 0b11, 0b01);
+            // back to normal sleep after waking
+            assert_eq!(read(addr_of_mut!((*cpu::SCB).scr)) & (1 << 2), 0);
+        }
+        assert_ne!(read(cr) & (1 << 24), 0);
+        // interrupts are unmasked again once the clocks are back
+        assert!(!cpu::primask());
+    }
+
+    fn standby() {
+        // a wakeup flag left from before is cleared on entry
+        let isr = unsafe { addr_of_mut!((*cpu::RTC).isr) };
+        sim::raise(isr, 1 << 10);
+
+        power::enable_wakeup_pin(true);
+        power::enter(Mode::Standby);
+
+        assert_eq!(read(isr) & (1 << 10), 0);
+        unsafe {
+            assert_ne!(read(addr_of_mut!((*cpu::PWR).cr)) & (1 << 1), 0);
+            assert_ne!(read(addr_of_mut!((*cpu::PWR).csr)) & (1 << 8), 0);
+            assert_ne!(read(addr_of_mut!((*cpu::SCB).scr)) & (1 << 2), 0);
+        }
+
+        assert!(!power::woke_from_standby());
+    }
+
+    fn park() {
+        let pin = Pin::new(cpu::GPIOB, 3);
+        pin.output();
+        pin.analog();
+        assert_eq!(
+            (read(unsafe { addr_of_mut!((*cpu::GPIOB).moder) }) >> 6) & 0b11,
+            0b11
+        );
+    }
+
+    fn stop_with_iwdg() {
+        rtc::init(ClockSource::Lsi);
+        watch_dog::iwdg_start(2000);
+
+        // without the wakeup timer the IWDG would reset the chip in stop
+        rtc::cancel_wakeup();
+        let result = std::panic::catch_unwind(|| power::enter(Mode::Stop));
+        assert!(result.is_err());
+
+        rtc::set_wakeup(1, tick);
+        let kr = unsafe { addr_of_mut!((*cpu::IWDG).kr) };
+        cpu::write_reg(kr, 0);
+        power::enter(Mode::Stop);
+        assert_eq!(read(kr), 0xAAAA);
+    }
+}
@@ -0,0 +1,125 @@
+#![no_std]
+
+#[cfg(feature = "std")]
+extern crate std;
+
+#[cfg(test)]
+mod tests {
+    use core::ptr::addr_of_mut;
+    use core::sync::atomic::{AtomicU32, Ordering};
+
+    use hal::cpu;
+    use hal::rtc::{self, ClockSource, DateTime};
+    use hal::sim;
+
+    static ALARMS: AtomicU32 = AtomicU32::new(0);
+    static WAKEUPS: AtomicU32 = AtomicU32::new(0);
+
+    fn on_alarm() {
+        ALARMS.fetch_add(1, Ordering::SeqCst);
+    }
+
+    fn on_wakeup() {
+        WAKEUPS.fetch_add(1, Ordering::SeqCst);
+    }
+
+    fn read(addr: *mut u32) -> u32 {
+        cpu::read_reg(addr)
+    }
+
+    #[test]
+    fn test_rtc_sim() {
+        cpu::init();
+        rtc::init(ClockSource::Lse);
+
+        calendar();
+        alarm();
+        wakeup();
+        backup();
+    }
+
+    fn calendar() {
+        unsafe {
+            // LSE selected and RTC enabled, 1 Hz from 128 * 256
+            assert_eq!((read(addr_of_mut!((*cpu::RCC).bdcr)) >> 8) & 0b11, 0b01);
+            assert_ne!(read(addr_of_mut!((*cpu::RCC).bdcr)) & (1 << 15), 0);
+            assert_eq!(read(addr_of_mut!((*cpu::RTC).prer)), (127 << 16) | 255);
+        }
+
+        let dt = DateTime::new(2025, 3, 3, 13, 45, 9);
+        rtc::set(&dt);
+
+        unsafe {
+            assert_eq!(read(addr_of_mut!((*cpu::RTC).tr)), 0x13_45_09);
+            assert_eq!(
+                read(addr_of_mut!((*cpu::RTC).dr)),
+                (0x25 << 16) | (1 << 13) | (0x03 << 8) | 0x03
+            );
+            // write protection is back on
+            assert_eq!(read(addr_of_mut!((*cpu::RTC).wpr)), 0xFF);
+
+            // the subsecond counter counts down from 255
+            cpu::write_reg(addr_of_mut!((*cpu::RTC).ssr), 255);
+        }
+        assert_eq!(rtc::now(), dt);
+    }
+
+    fn alarm() {
+        rtc::set_alarm(7, 30, 0, on_alarm);
+
+        unsafe {
+            // every day at 07:30:00
+            assert_eq!(
+                read(addr_of_mut!((*cpu::RTC).alrmar)),
+                (1 << 31) | 0x07_30_00
+            );
+            assert_ne!(read(addr_of_mut!((*cpu::RTC).cr)) & (1 << 8), 0);
+            assert_ne!(read(addr_of_mut!((*cpu::EXTI).imr)) & (1 << 17), 0);
+
+            // simulate the alarm
+            let isr = addr_of_mut!((*cpu::RTC).isr);
+            sim::raise(isr, 1 << 8);
+        }
+        rtc::handle_irq();
+        assert_eq!(ALARMS.load(Ordering::SeqCst), 1);
+        assert_eq!(read(unsafe { addr_of_mut!((*cpu::RTC).isr) }) & (1 << 8), 0);
+
+        rtc::cancel_alarm();
+        assert_eq!(read(unsafe { addr_of_mut!((*cpu::RTC).cr) }) & (1 << 8), 0);
+    }
+
+    fn wakeup() {
+        rtc::set_wakeup(10, on_wakeup);
+
+        unsafe {
+            assert_eq!(read(addr_of_mut!((*cpu::RTC).wutr)), 9);
+            assert_eq!(read(addr_of_mut!((*cpu::RTC).cr)) & 0b111, 0b100);
+
+            let isr = addr_of_mut!((*cpu::RTC).isr);
+            sim::raise(isr, 1 << 10);
+        }
+        rtc::handle_irq();
+        assert_eq!(WAKEUPS.load(Ordering::SeqCst), 1);
+        assert_eq!(ALARMS.load(Ordering::SeqCst), 1);
+
+        rtc::cancel_wakeup();
+    }
+
+    fn backup() {
+        rtc::backup_write(rtc::BACKUP_REGS - 1, 0xDEAD_BEEF);
+        assert_eq!(rtc::backup_read(rtc::BACKUP_REGS - 1), 0xDEAD_BEEF);
+
+        // only the STM32F405 has the backup SRAM
+        #[cfg(feature = "stm32f405")]
+        backup_sram();
+    }
+
+    #[cfg(feature = "stm32f405")]
+    fn backup_sram() {
+        rtc::backup_sram_enable();
+        rtc::backup_sram_write(4090, b"hactar");
+        let mut data = [0; 6];
+        rtc::backup_sram_read(4090, &mut data);
+        assert_eq!(&data, b"hactar");
+    }
+}
@@ -0,0 +1,240 @@
+#![no_std]
+
+#[cfg(feature = "std")]
+extern crate std;
+
+#[cfg(test)]
+mod tests {
+    use core::ptr::addr_of_mut;
+
+    use hal::cpu;
+    use hal::gpio;
+    use hal::sim::{self, Counter, Event, Usart};
+    use hal::{clock, timer, uart};
+
+    #[test]
+    fn test_threads_isolated() {
+        let moder = unsafe { addr_of_mut!((*cpu::GPIOC).moder) };
+        let _sim = sim::Guard::new();
+        cpu::write_reg(moder, 1);
+
+        std::thread::spawn(|| {
+            let moder = unsafe { addr_of_mut!((*cpu::GPIOC).moder) };
+            let _sim = sim::Guard::new();
+            assert_eq!(cpu::read_reg(moder), 0);
+            cpu::write_reg(moder, 2);
+        })
+        .join()
+        .unwrap();
+
+        assert_eq!(cpu::read_reg(moder), 1);
+    }
+
+    #[test]
+    fn test_time_isolated() {
+        let _sim = sim::Guard::new();
+        sim::advance_time(1_000);
+        assert_eq!(sim::time_ahead(), 1_000);
+
+        std::thread::spawn(|| {
+            let _sim = sim::Guard::new();
+            assert_eq!(sim::time_ahead(), 0);
+            sim::advance_time(5);
+        })
+        .join()
+        .unwrap();
+        assert_eq!(sim::time_ahead(), 1_000);
+
+        // a reset starts the time again from the host clock
+        cpu::init();
+        assert_eq!(sim::time_ahead(), 0);
+    }
+
+    #[test]
+    fn test_driver_state_isolated() {
+        let _sim = sim::Guard::new();
+        let sr = unsafe { addr_of_mut!((*cpu::TIM2).sr) };
+        sim::raise(sr, 1); // UIF
+        timer::handle_tim2_irq();
+        assert_eq!(timer::overflows(), 1);
+
+        std::thread::spawn(|| {
+            let _sim = sim::Guard::new();
+            assert_eq!(timer::overflows(), 0);
+        })
+        .join()
+        .unwrap();
+
+        // a reset keeps the state of the drivers, a new simulation clears it
+        cpu::init();
+        assert_eq!(timer::overflows(), 1);
+        let _sim = sim::Guard::new();
+        assert_eq!(timer::overflows(), 0);
+    }
+
+    #[test]
+    fn test_guard_clears() {
+        let moder = unsafe { addr_of_mut!((*cpu::GPIOC).moder) };
+        {
+            let _sim = sim::Guard::new();
+            cpu::write_reg(moder, 5);
+            sim::with_memory(0x2000_0000, 4, 0, |mem| mem[0] = 7);
+
+            // a reset keeps the memories
+            cpu::init();
+            assert_eq!(cpu::read_reg(moder), 0);
+            assert_eq!(sim::with_memory(0x2000_0000, 4, 0, |mem| mem[0]), 7);
+        }
+        let _sim = sim::Guard::new();
+        assert_eq!(sim::with_memory(0x2000_0000, 4, 0, |mem| mem[0]), 0);
+    }
+
+    #[test]
+    fn test_rcc_ready_flags() {
+        let _sim = sim::Guard::new();
+        let cr = unsafe { addr_of_mut!((*cpu::RCC).cr) };
+        cpu::write_reg(cr, 1 << 16 | 1 << 24); // HSEON and PLLON
+        let value = cpu::read_reg(cr);
+        assert_ne!(value & (1 << 17), 0); // HSERDY
+        assert_ne!(value & (1 << 25), 0); // PLLRDY
+
+        cpu::write_reg(cr, 0);
+        assert_eq!(cpu::read_reg(cr), 0);
+
+        let cfgr = unsafe { addr_of_mut!((*cpu::RCC).cfgr) };
+        cpu::write_reg(cfgr, 0b10);
+        assert_eq!(cpu::read_reg(cfgr) & 0b1111, 0b1010); // SWS follows SW
+    }
+
+    #[test]
+    fn test_usart_transmit_receive() {
+        let _sim = sim::Guard::new();
+        clock::init(16_000_000);
+        let pins = gpio::take().unwrap();
+        uart::init1(115_200, pins.pa9, pins.pa10);
+
+        for &c in b"hi" {
+            uart::write1(c);
+        }
+        let output = sim::with_model(cpu::USART1, |usart: &mut Usart| usart.take_output());
+        assert_eq!(output.as_deref(), Some(&b"hi"[..]));
+
+        assert!(uart::empty1());
+        sim::with_model(cpu::USART1, |usart: &mut Usart| usart.input(b"ok"));
+        assert!(!uart::empty1());
+        assert_eq!(uart::read1(), b'o');
+        assert_eq!(uart::read1(), b'k');
+        assert!(uart::empty1());
+    }
+
+    #[test]
+    #[cfg(feature = "stm32f405")]
+    fn test_usart_baud_rate() {
+        let _sim = sim::Guard::new();
+        clock::init(16_000_000);
+
+        sim::start_trace();
+        let pins = gpio::take().unwrap();
+        uart::init1(115_200, pins.pa9, pins.pa10);
+        let trace = sim::stop_trace();
+
+        // 84 MHz / (16 * 115200) is 45.57, so a mantissa of 45 and 9/16,
+        // set before the USART is enabled
+        let brr = unsafe { addr_of_mut!((*cpu::USART1).brr) };
+        let cr1 = unsafe { addr_of_mut!((*cpu::USART1).cr1) };
+        trace.assert_in_order(&[
+            Event::write_bits(brr, 0xFFF0, 45 << 4),
+            Event::write_bits(brr, 0x000F, 9),
+            Event::write_bits(cr1, 1 << 13, 1 << 13), // UE
+        ]);
+        assert_eq!(cpu::read_reg(brr), 0x2D9);
+    }
+
+    #[test]
+    #[cfg(feature = "stm32f072")]
+    fn test_usart_baud_rate() {
+        let _sim = sim::Guard::new();
+        clock::init(16_000_000);
+
+        sim::start_trace();
+        let pins = gpio::take().unwrap();
+        uart::init1(115_200, pins.pa9, pins.pa10);
+        let trace = sim::stop_trace();
+
+        // the whole divider is in BRR, set before the USART is enabled
+        let div = clock::clocks().pclk1 / 115_200;
+        let brr = unsafe { addr_of_mut!((*cpu::USART1).brr) };
+        let cr1 = unsafe { addr_of_mut!((*cpu::USART1).cr1) };
+        trace.assert_in_order(&[
+            Event::write(brr, div),
+            Event::write_bits(cr1, 1, 1), // UE
+        ]);
+        assert_eq!(cpu::read_reg(brr), div);
+    }
+
+    #[test]
+    fn test_usart_rx_irq() {
+        let _sim = sim::Guard::new();
+        clock::init(16_000_000);
+        let pins = gpio::take().unwrap();
+        uart::init1(115_200, pins.pa9, pins.pa10);
+        uart::enable_rx_irq1();
+
+        // the bytes are buffered by the interrupt handler
+        sim::with_model(cpu::USART1, |usart: &mut Usart| usart.input(b"abc"));
+        assert!(uart::empty1());
+        uart::handle_usart1_irq();
+        assert!(!uart::empty1());
+
+        sim::with_model(cpu::USART1, |usart: &mut Usart| usart.input(b"d"));
+        assert_eq!([uart::read1(), uart::read1(), uart::read1()], *b"abc");
+        uart::handle_usart1_irq();
+        assert_eq!(uart::read1(), b'd');
+        assert!(uart::empty1());
+    }
+
+    #[test]
+    fn test_tim2_counts() {
+        let _sim = sim::Guard::new();
+        timer::init2();
+        let tim2 = cpu::TIM2;
+        let cnt = unsafe { addr_of_mut!((*tim2).cnt) };
+        let sr = unsafe { addr_of_mut!((*tim2).sr) };
+
+        let first = cpu::read_reg(cnt);
+        assert_eq!(cpu::read_reg(cnt), first + 1);
+
+        // jump close to the one hour wrap
+        sim::with_model(tim2, |tim: &mut Counter| tim.step = 1000).unwrap();
+        cpu::write_reg(cnt, 3600 * 1_000_000 - 500);
+        cpu::write_reg(sr, 0);
+        cpu::read_reg(cnt);
+        assert_eq!(cpu::read_reg(cnt), 500);
+        assert_eq!(cpu::read_reg(sr) & 1, 1); // UIF
+
+        let before = timer::overflows();
+        timer::handle_tim2_irq();
+        assert_eq!(timer::overflows(), before + 1);
+        assert_eq!(cpu::read_reg(sr) & 1, 0);
+
+        // stopped counters do not count
+        let cr1 = unsafe { addr_of_mut!((*tim2).cr1) };
+        cpu::write_reg(cr1, 0);
+        assert_eq!(cpu::read_reg(cnt), cpu::read_reg(cnt));
+    }
+
+    #[test]
+    fn test_hal_init_validates() {
+        let _sim = sim::Guard::new();
+
+        // the clock waits and checks run the same as on the hardware
+        hal::init(16_000_000);
+        #[cfg(feature = "stm32f405")]
+        assert_eq!(clock::source(), clock::Source::Hse);
+        // the STM32F072 PLL runs from the HSI, with the HSE only checked
+        #[cfg(feature = "stm32f072")]
+        assert_eq!(clock::source(), clock::Source::Hsi);
+        assert!(!clock::hse_failed());
+        hal::validate();
+    }
+}
@@ -0,0 +1,169 @@
+#![no_std]
+#![cfg(feature = "stm32f072")]
+
+#[cfg(feature = "std")]
+extern crate std;
+
+#[cfg(test)]
+mod tests {
+    use core::ptr::addr_of_mut;
+
+    use hal::cpu;
+    use hal::device::{self, ResetCause};
+    use hal::gpio;
+    use hal::sim::{self, Event, Usart};
+    use hal::{clock, spi, timer, uart};
+
+    #[test]
+    fn test_init_validates() {
+        let _sim = sim::Guard::new();
+
+        // the PLL runs at 6 times the 8 MHz HSI
+        hal::init(16_000_000);
+        assert_eq!(clock::clocks().sysclk, 48_000_000);
+        hal::validate();
+    }
+
+    #[test]
+    fn test_hse_failed_kept_by_restore() {
+        let _sim = sim::Guard::new();
+        let hse_fails = |fails| {
+            sim::with_model(cpu::RCC, |rcc: &mut sim::Rcc| rcc.hse_fails = fails).unwrap();
+        };
+
+        cpu::init();
+        hse_fails(true);
+        clock::init(16_000_000);
+        assert!(clock::hse_failed());
+
+        // waking from stop does not forget the failure, a new init does
+        hse_fails(false);
+        clock::restore();
+        assert!(clock::hse_failed());
+        clock::init(16_000_000);
+        assert!(!clock::hse_failed());
+    }
+
+    #[test]
+    fn test_tim2() {
+        let _sim = sim::Guard::new();
+        hal::init(16_000_000);
+        timer::init2();
+
+        // TIM2 counts microseconds from the 48 MHz APB clock
+        let psc = unsafe { addr_of_mut!((*cpu::TIM2).psc) };
+        assert_eq!(cpu::read_reg(psc), 47);
+
+        let cnt = unsafe { addr_of_mut!((*cpu::TIM2).cnt) };
+        let first = cpu::read_reg(cnt);
+        assert_eq!(cpu::read_reg(cnt), first + 1);
+
+        let sr = unsafe { addr_of_mut!((*cpu::TIM2).sr) };
+        sim::raise(sr, 1); // UIF
+        let before = timer::overflows();
+        timer::handle_tim2_irq();
+        assert_eq!(timer::overflows(), before + 1);
+    }
+
+    #[test]
+    fn test_spi_write() {
+        let _sim = sim::Guard::new();
+        hal::init(16_000_000);
+
+        let pins = gpio::take().unwrap();
+        spi::init::<spi::Spi1>(1_000_000, spi::Mode::Mode0, pins.pa5, gpio::NoPin, pins.pa7);
+
+        // 8 bit frames are DS 0b0111 with RXNE set at 8 bits
+        let cr2 = unsafe { addr_of_mut!((*cpu::SPI1).cr2) };
+        assert_eq!(cpu::read_reg(cr2), 0b1_0111 << 8);
+
+        let dr = unsafe { addr_of_mut!((*cpu::SPI1).dr) };
+        sim::start_trace();
+        spi::write(spi::Bus::Spi1, &[0x2A, 0x01]);
+        spi::write_wide(spi::Bus::Spi1, &[0x1234]);
+        let trace = sim::stop_trace();
+        assert_eq!(trace.writes(dr), [0x2A, 0x01, 0x1234]);
+        trace.assert_in_order(&[
+            Event::write_bits(cr2, 0b1111 << 8, 0b1111 << 8),
+            Event::write(dr, 0x1234),
+            Event::write_bits(cr2, 0b1111 << 8, 0b0111 << 8),
+        ]);
+    }
+
+    #[test]
+    fn test_af_table() {
+        let _sim = sim::Guard::new();
+        hal::init(16_000_000);
+
+        // USART1 is AF1 on PA9 and AF0 on PB6
+        let pins = gpio::take().unwrap();
+        uart::init1(115_200, pins.pb6, pins.pb7);
+        let afrl = unsafe { addr_of_mut!((*cpu::GPIOB).afrl) };
+        assert_eq!(cpu::read_reg(afrl), 0);
+        let moder = unsafe { addr_of_mut!((*cpu::GPIOB).moder) };
+        assert_eq!(cpu::read_reg(moder), 0b1010 << 12);
+
+        // SPI2 SCK is AF5 on PB10
+        spi::init::<spi::Spi2>(
+            1_000_000,
+            spi::Mode::Mode0,
+            pins.pb10,
+            gpio::NoPin,
+            pins.pb15,
+        );
+        let afrh = unsafe { addr_of_mut!((*cpu::GPIOB).afrh) };
+        assert_eq!(cpu::read_reg(afrh), 5 << 8);
+    }
+
+    extern "C" fn usart2_handler() {
+        uart::handle_usart2_irq();
+    }
+
+    #[test]
+    fn test_set_handler() {
+        let _sim = sim::Guard::new();
+
+        // without a VTOR the table in RAM is mapped at address 0
+        cpu::set_handler(cpu::Interrupt::Usart2, usart2_handler);
+        let cfgr1 = unsafe { addr_of_mut!((*cpu::SYSCFG).cfgr1) };
+        assert_eq!(cpu::read_reg(cfgr1) & 0b11, 0b11);
+        assert!(cpu::handler(cpu::Interrupt::Usart2).is_some());
+        assert!(cpu::handler(cpu::Interrupt::Usart1).is_none());
+    }
+
+    #[test]
+    fn test_usart2_rx_irq() {
+        let _sim = sim::Guard::new();
+        hal::init(16_000_000);
+        let pins = gpio::take().unwrap();
+        uart::init2(115_200, pins.pa2, pins.pa3);
+        uart::enable_rx_irq2();
+
+        sim::with_model(cpu::USART2, |usart: &mut Usart| usart.input(b"hi"));
+        assert!(uart::empty2());
+        uart::handle_usart2_irq();
+        assert_eq!([uart::read2(), uart::read2()], *b"hi");
+        assert!(uart::empty2());
+    }
+
+    #[test]
+    fn test_device() {
+        let _sim = sim::Guard::new();
+        hal::init(16_000_000);
+
+        assert_eq!(device::flash_size_kb(), 128);
+        assert_ne!(device::uid(), [0; 3]);
+
+        // an IWDG reset, which also drives the reset pin
+        let csr = unsafe { addr_of_mut!((*cpu::RCC).csr) };
+        let flags = (1 << cpu::RCC::csr::IWDGRSTF) | (1 << cpu::RCC::csr::PINRSTF);
+        cpu::write_reg(csr, flags);
+        assert_eq!(device::take_reset_cause(), ResetCause::IndependentWatchdog);
+        assert_eq!(device::reset_cause(), ResetCause::Unknown);
+
+        let flags = (1 << cpu::RCC::csr::OBLRSTF) | (1 << cpu::RCC::csr::PINRSTF);
+        assert_eq!(ResetCause::from_csr(flags), ResetCause::OptionBytes);
+        let flags = (1 << cpu::RCC::csr::PORRSTF) | (1 << cpu::RCC::csr::PINRSTF);
+        assert_eq!(ResetCause::from_csr(flags), ResetCause::PowerOn);
+    }
+}
@@ -13,16 +13,111 @@ mod tests {
     fn test_sub_no_wrap_around() {
         let time1 = MicroSeconds::new(20000);
         let time2 = MicroSeconds::new(10000);
-        let result = time1.sub(time2);
+        let result = time1 - time2;
         assert_eq!(result, MicroSeconds::new(10000));
     }
 
     #[test]
-    fn test_sub_with_wrap_around() {
-        let max: u64 = 3600 * 1000_000;
+    fn test_sub_later_saturates() {
+        let max: u64 = 3600 * 1_000_000;
         let time1 = MicroSeconds::new(5000);
         let time2 = MicroSeconds::new(max - 5000);
-        let result = time1.sub(time2);
-        assert_eq!(result, MicroSeconds::new(10001));
+        let result = time1 - time2;
+        assert_eq!(result, MicroSeconds::new(0));
+    }
+
+    use core::ptr::addr_of_mut;
+    use core::sync::atomic::{AtomicU32, Ordering};
+
+    use hal::cpu;
+    use hal::sim;
+    use hal::tim::{self, Channel, Timer};
+    use hal::timer::{self, Alarm};
+
+    static FIRED: AtomicU32 = AtomicU32::new(0);
+
+    fn on_alarm() {
+        FIRED.fetch_add(1, Ordering::SeqCst);
+    }
+
+    #[test]
+    fn test_timer_sim() {
+        let _sim = sim::Guard::new();
+        #[cfg(feature = "stm32f405")]
+        hal::clock::init(0);
+        #[cfg(feature = "stm32f072")]
+        hal::clock::init(16_000_000);
+
+        alarm_one_shot();
+        alarm_periodic();
+        pwm_duty();
+        overflow_count();
+    }
+
+    fn overflow_count() {
+        let before = timer::overflows();
+
+        // simulate the counter wrapping
+        unsafe {
+            sim::raise(addr_of_mut!((*cpu::TIM2).sr), 1 << 0);
+        }
+        timer::handle_tim2_irq();
+
+        assert_eq!(timer::overflows(), before + 1);
+    }
+
+    fn alarm_one_shot() {
+        FIRED.store(0, Ordering::SeqCst);
+
+        timer::set_alarm(Alarm::A1, 1000, 0, on_alarm);
+        assert!(timer::alarm_active(Alarm::A1));
+
+        unsafe {
+            let tim2 = cpu::TIM2;
+            // counter is 0 in the sim so the compare is the delay
+            assert_eq!(cpu::read_reg(addr_of_mut!((*tim2).ccr1)), 1000);
+
+            // simulate the compare match
+            sim::raise(addr_of_mut!((*tim2).sr), 1 << 1);
+        }
+        timer::handle_tim2_irq();
+
+        assert_eq!(FIRED.load(Ordering::SeqCst), 1);
+        assert!(!timer::alarm_active(Alarm::A1));
+    }
+
+    fn alarm_periodic() {
+        FIRED.store(0, Ordering::SeqCst);
+
+        timer::set_alarm(Alarm::A3, 500, 2000, on_alarm);
+
+        for n in 1..=3 {
+            unsafe {
+                sim::raise(addr_of_mut!((*cpu::TIM2).sr), 1 << 3);
+            }
+            timer::handle_tim2_irq();
+
+            assert_eq!(FIRED.load(Ordering::SeqCst), n);
+            let ccr3 = unsafe { cpu::read_reg(addr_of_mut!((*cpu::TIM2).ccr3)) };
+            assert_eq!(ccr3, 500 + 2000 * n);
+        }
+
+        timer::cancel_alarm(Alarm::A3);
+        assert!(!timer::alarm_active(Alarm::A3));
+    }
+
+    fn pwm_duty() {
+        // the 84MHz APB1 timer clock / 20kHz is 4200 ticks on the STM32F405
+        let ticks = tim::pwm_init(Timer::Tim3, 20_000);
+        assert_eq!(ticks, hal::clock::clocks().timclk1() / 20_000);
+        #[cfg(feature = "stm32f405")]
+        assert_eq!(ticks, 4200);
+
+        let pins = hal::gpio::take().unwrap();
+        tim::pwm_enable::<hal::gpio::af::Tim3Ch2>(pins.pa7);
+        tim::set_duty(Timer::Tim3, Channel::Ch2, 250);
+
+        let ccr2 = unsafe { cpu::read_reg(addr_of_mut!((*cpu::TIM3).ccr2)) };
+        assert_eq!(ccr2, ticks / 4);
     }
 }
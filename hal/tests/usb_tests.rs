@@ -0,0 +1,139 @@
+#![no_std]
+#![cfg(feature = "stm32f405")]
+
+#[cfg(feature = "std")]
+extern crate std;
+
+#[cfg(test)]
+mod tests {
+    use core::ptr::addr_of_mut;
+
+    use hal::cpu;
+    use hal::usb;
+
+    fn read(addr: *mut u32) -> u32 {
+        cpu::read_reg(addr)
+    }
+
+    #[test]
+    fn test_usb_sim() {
+        cpu::init();
+        hal::clock::init(0);
+        let dm = hal::gpio::Pin(cpu::GPIOA, 11);
+        assert!(!usb::owns_pin(&dm));
+        usb::init();
+        assert!(usb::owns_pin(&dm));
+        assert!(!usb::owns_pin(&hal::gpio::Pin(cpu::GPIOA, 10)));
+
+        check_init();
+        bus_reset();
+        enumerate();
+        open_port();
+        transfer();
+    }
+
+    fn check_init() {
+        let global = cpu::OTG_FS_GLOBAL;
+        unsafe {
+            // forced device mode, full speed, connected
+            assert_ne!(read(addr_of_mut!((*global).gusbcfg)) & (1 << 30), 0);
+            assert_ne!(read(addr_of_mut!((*global).gccfg)) & (1 << 21), 0);
+            assert_ne!(read(addr_of_mut!((*global).gahbcfg)) & 1, 0);
+            assert_eq!(read(addr_of_mut!((*cpu::OTG_FS_DEVICE).dcfg)) & 0b11, 0b11);
+            assert_eq!(read(addr_of_mut!((*cpu::OTG_FS_DEVICE).dctl)) & (1 << 1), 0);
+            // OTG_FS is IRQ 67, not OTG_FS_WKUP at 42
+            assert_eq!(cpu::Interrupt::OtgFs.number(), 67);
+            assert_ne!(read(addr_of_mut!((*cpu::NVIC).iser[2])) & (1 << 3), 0);
+        }
+        assert!(!usb::configured());
+        assert_eq!(usb::write(b"lost"), 0);
+    }
+
+    fn bus_reset() {
+        let gintsts = unsafe { addr_of_mut!((*cpu::OTG_FS_GLOBAL).gintsts) };
+        cpu::write_reg(gintsts, 1 << 12); // USBRST
+        usb::handle_irq();
+        cpu::write_reg(gintsts, 1 << 13); // ENUMDNE
+        usb::handle_irq();
+
+        // control OUT endpoint ready for 3 setup packets
+        let doeptsiz0 = read(unsafe { addr_of_mut!((*cpu::OTG_FS_DEVICE).doeptsiz0) });
+        assert_eq!(doeptsiz0 >> 29, 3);
+        assert_eq!(doeptsiz0 & 0x7F, 64);
+    }
+
+    fn enumerate() {
+        // setup packets recorded from Linux enumerating the device
+        usb::sim_setup(&[0x80, 0x06, 0x00, 0x01, 0x00, 0x00, 0x40, 0x00]);
+        let desc = usb::sim_sent(0);
+        assert_eq!(desc.len(), 18);
+        assert_eq!(&desc[8..12], &[0x83, 0x04, 0x40, 0x57]);
+        usb::sim_in_complete(0);
+
+        usb::sim_setup(&[0x00, 0x05, 0x0A, 0x00, 0x00, 0x00, 0x00, 0x00]);
+        assert!(usb::sim_sent(0).is_empty()); // zero length status
+        let dcfg = read(unsafe { addr_of_mut!((*cpu::OTG_FS_DEVICE).dcfg) });
+        assert_eq!((dcfg >> 4) & 0x7F, 10);
+
+        usb::sim_setup(&[0x80, 0x06, 0x00, 0x02, 0x00, 0x00, 0x09, 0x00]);
+        assert_eq!(usb::sim_sent(0).len(), 9);
+        usb::sim_setup(&[0x80, 0x06, 0x00, 0x02, 0x00, 0x00, 0xFF, 0x00]);
+        assert_eq!(usb::sim_sent(0).len(), 67);
+        let dieptsiz0 = read(unsafe { addr_of_mut!((*cpu::OTG_FS_DEVICE).dieptsiz0) });
+        assert_eq!(dieptsiz0, (2 << 19) | 67);
+
+        // the device qualifier is stalled
+        usb::sim_setup(&[0x80, 0x06, 0x00, 0x06, 0x00, 0x00, 0x0A, 0x00]);
+        let diepctl0 = read(unsafe { addr_of_mut!((*cpu::OTG_FS_DEVICE).diepctl0) });
+        assert_ne!(diepctl0 & (1 << 21), 0);
+
+        usb::sim_setup(&[0x00, 0x09, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00]);
+        assert!(usb::configured());
+
+        // bulk endpoints active and the OUT endpoint ready to receive
+        let diepctl1 = read(unsafe { addr_of_mut!((*cpu::OTG_FS_DEVICE).diepctl1) });
+        assert_ne!(diepctl1 & (1 << 15), 0);
+        assert_eq!(diepctl1 & 0x7FF, 64);
+        let doepctl1 = read(unsafe { addr_of_mut!((*cpu::OTG_FS_DEVICE).doepctl1) });
+        assert_ne!(doepctl1 & (1 << 31), 0);
+    }
+
+    fn open_port() {
+        usb::sim_setup(&[0xA1, 0x21, 0x00, 0x00, 0x00, 0x00, 0x07, 0x00]);
+        assert_eq!(usb::sim_sent(0), &[0x00, 0xC2, 0x01, 0x00, 0, 0, 8]);
+
+        usb::sim_setup(&[0x21, 0x20, 0x00, 0x00, 0x00, 0x00, 0x07, 0x00]);
+        assert!(usb::sim_sent(0).is_empty()); // status waits for the data
+        usb::sim_out(0, &[0x80, 0x25, 0x00, 0x00, 0, 0, 8]);
+        assert!(usb::sim_sent(0).is_empty());
+
+        assert!(!usb::port_open());
+        usb::sim_setup(&[0x21, 0x22, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00]);
+        assert!(usb::port_open());
+    }
+
+    fn transfer() {
+        // 100 bytes go as a full packet then the rest once the first is taken
+        let data = [b'x'; 100];
+        assert_eq!(usb::write(&data), 100);
+        assert_eq!(usb::sim_sent(1).len(), 64);
+        usb::sim_in_complete(1);
+        assert_eq!(usb::sim_sent(1).len(), 36);
+        usb::sim_in_complete(1);
+        assert!(usb::sim_sent(1).is_empty());
+
+        // a full last packet is ended with a zero length packet
+        assert_eq!(usb::write(&data[..64]), 64);
+        assert_eq!(usb::sim_sent(1).len(), 64);
+        usb::sim_in_complete(1);
+        let dieptsiz1 = read(unsafe { addr_of_mut!((*cpu::OTG_FS_DEVICE).dieptsiz1) });
+        assert_eq!(dieptsiz1, 1 << 19);
+        usb::sim_in_complete(1);
+
+        usb::sim_out(1, b"help\r");
+        let mut buf = [0u8; 8];
+        assert_eq!(usb::read(&mut buf), 5);
+        assert_eq!(&buf[..5], b"help\r");
+        assert_eq!(usb::read(&mut buf), 0);
+    }
+}
@@ -33,6 +33,13 @@ SECTIONS
     *(.rodata .rodata.*);
    } > FLASH
 
+   /* the STM32F072 has no VTOR, so a vector table in RAM is used by
+      mapping the start of the RAM at address 0 */
+   .vector_table_ram (NOLOAD) :
+   {
+     KEEP(*(.vector_table_ram));
+   } > RAM
+
    .data : ALIGN(4) /* AT(ADDR(.rodata) + SIZEOF(.rodata)) */
    {
      _sdata = .;
@@ -5,7 +5,7 @@
 
 extern crate hal;
 
-use hal::{cpu, gpio, watch_dog};
+use hal::{gpio, watch_dog};
 
 mod stack;
 mod startup;
@@ -16,65 +16,68 @@ mod startup;
 pub extern "C" fn main() -> ! {
     my_main();
 
-    loop {}
+    loop {
+        core::hint::spin_loop();
+    }
 }
 
 #[inline(never)]
 /// Main function that initializes the system and runs the task manager.
 fn my_main() {
-    pub const CONSOLE_TX: gpio::Pin = gpio::Pin(cpu::GPIOA, 9);
-    pub const CONSOLE_RX: gpio::Pin = gpio::Pin(cpu::GPIOA, 10);
+    let pins = gpio::take().unwrap();
+
+    let console_tx = pins.pa9;
+    let console_rx = pins.pa10;
 
-    pub const UI_TX: gpio::Pin = gpio::Pin(cpu::GPIOA, 2);
-    pub const UI_RX: gpio::Pin = gpio::Pin(cpu::GPIOA, 3);
+    let ui_tx = pins.pa2;
+    let ui_rx = pins.pa3;
 
-    pub const UI_BOOT0: gpio::Pin = gpio::Pin(cpu::GPIOA, 15);
-    pub const UI_NRST: gpio::Pin = gpio::Pin(cpu::GPIOB, 3);
+    let ui_boot0 = pins.pa15;
+    let ui_nrst = pins.pb3;
 
-    pub const NET_BOOT0: gpio::Pin = gpio::Pin(cpu::GPIOB, 5);
-    pub const NET_NRST: gpio::Pin = gpio::Pin(cpu::GPIOB, 4);
+    let net_boot0 = pins.pb5;
+    let net_nrst = pins.pb4;
 
-    pub const MCLK: gpio::Pin = gpio::Pin(cpu::GPIOA, 8);
+    let mclk = pins.pa8;
+
+    let led_red = pins.pa4;
+    let led_green = pins.pa6;
+    let led_blue = pins.pa7;
 
     pub const MCLK_FREQ: u32 = 24_000_000;
     pub const CLOCK_HSE_FREQ: u32 = 16_000_000;
 
     hal::init(CLOCK_HSE_FREQ);
 
-    hal::uart::init1(115_200, CONSOLE_TX, CONSOLE_RX);
+    hal::uart::init1(115_200, console_tx, console_rx);
 
-    hal::uart::init2(115200, UI_RX, UI_TX);
+    hal::uart::init2(115200, ui_tx, ui_rx);
 
     hal::watch_dog::init();
 
-    const LED_RED_PIN: gpio::Pin = gpio::Pin(cpu::GPIOA, 4);
-    const LED_GREEN_PIN: gpio::Pin = gpio::Pin(cpu::GPIOA, 6);
-    const LED_BLUE_PIN: gpio::Pin = gpio::Pin(cpu::GPIOA, 7);
-
-    LED_GREEN_PIN.output();
-    LED_RED_PIN.output();
-    LED_BLUE_PIN.output();
+    let led_green = led_green.into_output();
+    let led_red = led_red.into_output();
+    let led_blue = led_blue.into_output();
 
-    LED_GREEN_PIN.high();
-    LED_RED_PIN.high();
-    LED_BLUE_PIN.low(); // turn on blue LED
+    led_green.high();
+    led_red.high();
+    led_blue.low(); // turn on blue LED
 
-    hal::clock::configure_mco(MCLK, MCLK_FREQ);
+    hal::clock::configure_mco(mclk, MCLK_FREQ);
 
     // make sure that boot pins are low before any reset asserted
-    UI_BOOT0.output();
-    NET_BOOT0.output();
-    UI_BOOT0.low();
-    NET_BOOT0.low();
+    let ui_boot0 = ui_boot0.into_output();
+    let net_boot0 = net_boot0.into_output();
+    ui_boot0.low();
+    net_boot0.low();
 
-    UI_NRST.open_drain();
-    UI_NRST.pullup();
-    NET_NRST.open_drain();
-    NET_NRST.pullup();
+    // open drain with a pull up
+    let ui_nrst = ui_nrst.into_open_drain();
+    let net_nrst = net_nrst.into_open_drain();
 
     // put chips into reset
-    UI_NRST.low();
-    NET_NRST.low();
+    ui_nrst.low();
+    net_nrst.low();
 
     {
         let str = "MGMT: Starting\r\n";
@@ -82,9 +85,19 @@ fn my_main() {
             hal::uart::write1(c);
         }
     }
+    {
+        let cause = hal::device::take_reset_cause();
+        let str = "MGMT: Reset cause: ";
+        for c in str.bytes().chain(cause.name().iter().copied()) {
+            hal::uart::write1(c);
+        }
+        for c in "\r\n".bytes() {
+            hal::uart::write1(c);
+        }
+    }
     // take chips out of reset
-    UI_NRST.high();
-    NET_NRST.high();
+    ui_nrst.high();
+    net_nrst.high();
 
     // TODO this is off in debug as it stop CPU from staying in error state
     if !cfg!(debug_assertions) {
@@ -112,24 +125,22 @@ fn my_main() {
         }
     }
 
-    LED_GREEN_PIN.low(); // turn on green LED
-    LED_RED_PIN.high();
-    LED_BLUE_PIN.high();
+    led_green.low(); // turn on green LED
+    led_red.high();
+    led_blue.high();
 
     let (stack_usage, stack_current, stack_reserved) = stack::usage(false);
     let _ = (stack_usage, stack_current, stack_reserved);
 
     loop {
         if !hal::uart::empty2() {
-            let c: u8;
-            c = hal::uart::read2();
+            let c: u8 = hal::uart::read2();
             if c != 0 {
                 hal::uart::write1(c);
             }
         }
         if !hal::uart::empty1() {
-            let c: u8;
-            c = hal::uart::read1();
+            let c: u8 = hal::uart::read1();
             if c != 0 {
                 hal::uart::write2(c);
             }
@@ -0,0 +1,178 @@
+//! # Allow Module
+//!
+//! This module reads the allow-list that selects which peripherals of an SVD
+//! file are generated, so the output for a chip can be regenerated the same
+//! way each time.
+//!
+//! Each line is `key = value`, and `#` starts a comment:
+//!
+//! ```text
+//! # module TIM_GEN from the registers of TIM2, with pointers TIM2 and TIM3
+//! TIM_GEN = TIM2 TIM3
+//! # registers of OTG_FS_DEVICE without the OTG_FS_ or FS_ prefix
+//! OTG_FS_DEVICE.prefix = OTG_FS_ FS_
+//! # only these registers of RCC
+//! RCC.registers = cr pllcfgr cfgr
+//! # access the SVD does not give, by field or for all fields of a register
+//! TIM_GEN.sr.* = WriteZeroToClear
+//! # PLLM0 to PLLM5 as one field PLLM
+//! RCC.pllcfgr.PLLM = merge
+//! ```
+//!
+//! ## Structures
+//!
+//! - `AllowList`: The modules to generate, in output order.
+//! - `Module`: A module, the peripherals it covers, and its overrides.
+//!
+//! ## Functions
+//!
+//! - `parse`: Reads an allow-list from its text.
+
+use crate::svd::Access;
+
+#[derive(Debug, Default)]
+pub struct AllowList {
+    pub modules: Vec<Module>,
+}
+
+#[derive(Debug, Default)]
+pub struct Module {
+    pub name: String,
+    /// Peripherals that get a pointer to the register struct of the module.
+    /// The registers are those of the first one.
+    pub peripherals: Vec<String>,
+    /// Prefixes removed from the register names.
+    pub prefixes: Vec<String>,
+    /// Registers to generate, or all of them if empty.
+    pub registers: Vec<String>,
+    /// Access of fields as `(register, field or "*", access)`.
+    pub access: Vec<(String, String, Access)>,
+    /// Fields split into one bit per field in the SVD, as `(register, field)`.
+    pub merge: Vec<(String, String)>,
+}
+
+impl AllowList {
+    /// Returns the module that has a pointer for `peripheral`.
+    pub fn module_of(&self, peripheral: &str) -> Option<&Module> {
+        self.modules
+            .iter()
+            .find(|m| m.peripherals.iter().any(|p| p == peripheral))
+    }
+}
+
+impl Module {
+    /// Returns the access set for `field` of `register`, if any.
+    pub fn access(&self, register: &str, field: &str) -> Option<Access> {
+        let found = self
+            .access
+            .iter()
+            .rev()
+            .find(|(r, f, _)| r == register && (f == field || f == "*"));
+        found.map(|(_, _, access)| *access)
+    }
+
+    pub fn merges(&self, register: &str, field: &str) -> bool {
+        self.merge.iter().any(|(r, f)| r == register && f == field)
+    }
+
+    pub fn wants(&self, register: &str) -> bool {
+        self.registers.is_empty() || self.registers.iter().any(|r| r == register)
+    }
+}
+
+/// Reads an allow-list from its text.
+pub fn parse(text: &str) -> Result<AllowList, String> {
+    let mut list = AllowList::default();
+
+    for (num, line) in text.lines().enumerate() {
+        let line = line.split('#').next().unwrap().trim();
+        if line.is_empty() {
+            continue;
+        }
+        let err = |msg: &str| format!("line {}: {}", num + 1, msg);
+
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| err("expected key = value"))?;
+        let key: Vec<&str> = key.trim().split('.').collect();
+        let value = value.trim();
+        let words = || {
+            value
+                .split_whitespace()
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        };
+
+        if key.len() == 1 {
+            if list.modules.iter().any(|m| m.name == key[0]) {
+                return Err(err("module listed twice"));
+            }
+            list.modules.push(Module {
+                name: key[0].to_string(),
+                peripherals: words(),
+                ..Default::default()
+            });
+            continue;
+        }
+
+        let module = list
+            .modules
+            .iter_mut()
+            .find(|m| m.name == key[0])
+            .ok_or_else(|| err("module must be listed before its settings"))?;
+
+        match key[1..] {
+            ["prefix"] => module.prefixes = words(),
+            ["registers"] => module.registers = words(),
+            [register, field] if value == "merge" => {
+                module.merge.push((register.to_string(), field.to_string()));
+            }
+            [register, field] => {
+                let access = Access::from_name(value).ok_or_else(|| err("unknown access"))?;
+                module
+                    .access
+                    .push((register.to_string(), field.to_string(), access));
+            }
+            _ => return Err(err("unknown setting")),
+        }
+    }
+    Ok(list)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let text = "
+            # timers
+            TIM_GEN = TIM2 TIM3   # general purpose
+            TIM_GEN.sr.* = WriteZeroToClear
+            TIM_GEN.sr.TIF = ReadOnly
+            RCC = RCC
+            RCC.registers = cr pllcfgr
+            RCC.pllcfgr.PLLM = merge
+        ";
+        let list = parse(text).unwrap();
+        assert_eq!(list.modules.len(), 2);
+
+        let tim = list.module_of("TIM3").unwrap();
+        assert_eq!(tim.name, "TIM_GEN");
+        assert_eq!(tim.access("sr", "UIF"), Some(Access::WriteZeroToClear));
+        assert_eq!(tim.access("sr", "TIF"), Some(Access::ReadOnly));
+        assert_eq!(tim.access("cr1", "CEN"), None);
+
+        let rcc = list.module_of("RCC").unwrap();
+        assert!(rcc.wants("cr") && !rcc.wants("cir"));
+        assert!(rcc.merges("pllcfgr", "PLLM"));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(parse("RCC").is_err());
+        assert!(parse("RCC.cr.HSEON = ReadOnly").is_err());
+        assert!(parse("RCC = RCC\nRCC.cr.HSEON = Maybe").is_err());
+        assert!(parse("RCC = RCC\nRCC = RCC").is_err());
+    }
+}
@@ -0,0 +1,266 @@
+//! # Emit Module
+//!
+//! This module writes the Rust source for the peripherals selected by an
+//! allow-list, in the layout the `hal` crate uses:
+//!
+//! - a `pub mod` per module, with a `pub mod` per register holding a `u8`
+//!   constant with the bit offset of each field, and a `field!` declaration
+//!   with the offset, width and access of each field;
+//! - a `#[repr(C)]` struct per module with a `u32` per register, padded with
+//!   reserved words;
+//! - a pointer constant per peripheral, and a comment for each peripheral of
+//!   the SVD that is skipped.
+//!
+//! ## Functions
+//!
+//! - `generate`: Returns the Rust source for a device and allow-list.
+
+use std::fmt::Write;
+
+use crate::allow::{AllowList, Module};
+use crate::svd::{Device, Field, Register};
+
+const HEADER: &str = "\
+// DO NOT EDIT. This was generated by svd2rusty
+//! This module provides definitions for various hardware registers and their fields.
+
+#![allow(unused)]
+#![allow(non_snake_case)]
+#![allow(non_upper_case_globals)]
+#![allow(non_camel_case_types)]
+
+";
+
+/// A register of a module, with the name used in the output.
+struct Named<'a> {
+    name: String,
+    register: &'a Register,
+}
+
+/// Returns the Rust source for the peripherals of `device` listed in `allow`.
+pub fn generate(device: &Device, allow: &AllowList) -> Result<String, String> {
+    let mut out = String::from(HEADER);
+
+    let mut modules = Vec::new();
+    for module in &allow.modules {
+        for name in &module.peripherals {
+            if device.peripheral(name).is_none() {
+                return Err(format!(
+                    "{}: no peripheral {} in the SVD",
+                    module.name, name
+                ));
+            }
+        }
+        let first = module
+            .peripherals
+            .first()
+            .ok_or_else(|| format!("{}: no peripherals", module.name))?;
+        let peripheral = device.peripheral(first).unwrap();
+        let registers = named_registers(module, device.registers(peripheral));
+        modules.push((module, registers));
+    }
+
+    for (module, registers) in &modules {
+        write_module(&mut out, module, registers)?;
+    }
+    for (module, registers) in &modules {
+        write_struct(&mut out, module, registers)?;
+    }
+
+    for peripheral in &device.peripherals {
+        match allow.module_of(&peripheral.name) {
+            Some(module) => {
+                if !out.ends_with("\n\n") {
+                    out.push('\n');
+                }
+                let ty = struct_name(&module.name);
+                writeln!(
+                    out,
+                    "pub const {}: *mut {} = 0x{:08X} as *mut {};",
+                    peripheral.name, ty, peripheral.base_address, ty
+                )
+                .unwrap();
+            }
+            None => writeln!(out, "// Skipped peripheral: {}", peripheral.name).unwrap(),
+        }
+    }
+    Ok(out)
+}
+
+/// Returns the registers of `module` with their prefix removed and in
+/// lower case, leaving out any not in its register list.
+fn named_registers<'a>(module: &Module, registers: &'a [Register]) -> Vec<Named<'a>> {
+    let mut named = Vec::new();
+    for register in registers {
+        let mut name = register.name.as_str();
+        for prefix in &module.prefixes {
+            if let Some(rest) = name.strip_prefix(prefix.to_lowercase().as_str()) {
+                name = rest;
+                break;
+            }
+        }
+        if module.wants(name) {
+            named.push(Named {
+                name: ident(name),
+                register,
+            });
+        }
+    }
+    named
+}
+
+fn write_module(out: &mut String, module: &Module, registers: &[Named]) -> Result<(), String> {
+    writeln!(out, "pub mod {} {{", module.name).unwrap();
+    for reg in registers {
+        // a register without fields is written as rustfmt would
+        if reg.register.fields.is_empty() {
+            writeln!(out, "    pub mod {} {{}}", reg.name).unwrap();
+            continue;
+        }
+        writeln!(out, "    pub mod {} {{", reg.name).unwrap();
+        for field in &reg.register.fields {
+            writeln!(
+                out,
+                "        pub const {}: u8 = {};",
+                field.name, field.offset
+            )
+            .unwrap();
+        }
+        write_fields(out, module, reg)
+            .map_err(|e| format!("{}.{}: {}", module.name, reg.name, e))?;
+        writeln!(out, "    }}").unwrap();
+    }
+    writeln!(out, "}}\n").unwrap();
+    Ok(())
+}
+
+/// Writes a `field!` for each field of `reg`, with the fields set to merge
+/// written as one field at the position of their lowest bit.
+fn write_fields(out: &mut String, module: &Module, reg: &Named) -> Result<(), String> {
+    let fields = &reg.register.fields;
+    let find = |name: &str| fields.iter().find(|f| f.name == name);
+
+    // the bits of merged fields, as (name, lowest bit field, width)
+    let mut merged: Vec<(&str, &Field, u8)> = Vec::new();
+    for (register, name) in &module.merge {
+        if *register != reg.name {
+            continue;
+        }
+        let first = find(&format!("{}0", name)).ok_or_else(|| format!("no {}0 to merge", name))?;
+        let mut width = 1;
+        while let Some(bit) = find(&format!("{}{}", name, width)) {
+            if bit.width != 1 || bit.offset != first.offset + width {
+                return Err(format!("{}{} is not the next bit", name, width));
+            }
+            width += 1;
+        }
+        merged.push((name, first, width));
+    }
+
+    for field in fields {
+        let merge = merged.iter().find(|(name, _, _)| {
+            field
+                .name
+                .strip_prefix(name)
+                .is_some_and(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()))
+        });
+        let line = match merge {
+            Some((name, first, width)) if first.name == field.name => {
+                let access = module.access(&reg.name, name).unwrap_or(first.access);
+                format!("{} = {}, {}, {}", name, first.name, width, access.name())
+            }
+            Some(_) => continue,
+            None => {
+                let access = module
+                    .access(&reg.name, &field.name)
+                    .unwrap_or(field.access);
+                format!("{}, {}, {}", field.name, field.width, access.name())
+            }
+        };
+        writeln!(out, "        crate::cpu::field!({});", line).unwrap();
+    }
+    Ok(())
+}
+
+fn write_struct(out: &mut String, module: &Module, registers: &[Named]) -> Result<(), String> {
+    let mut sorted: Vec<&Named> = registers.iter().collect();
+    sorted.sort_by_key(|r| r.register.offset);
+
+    writeln!(out, "#[repr(C)]").unwrap();
+    writeln!(out, "pub struct {} {{", struct_name(&module.name)).unwrap();
+
+    let mut next = 0;
+    let mut reserved = 0;
+    for reg in sorted {
+        let offset = reg.register.offset;
+        if offset % 4 != 0 {
+            return Err(format!("{}.{}: not word aligned", module.name, reg.name));
+        }
+        if offset < next {
+            // an alternate view of the register already written
+            continue;
+        }
+        if offset > next {
+            reserved += 1;
+            let words = (offset - next) / 4;
+            if words == 1 {
+                writeln!(out, "    reserved{}: u32,", reserved).unwrap();
+            } else {
+                writeln!(out, "    reserved{}: [u32; {}],", reserved, words).unwrap();
+            }
+        }
+
+        let name = match reg.name.rsplit_once('_') {
+            // ccmr1_output and ccmr1_input are both ccmr1
+            Some((base, _)) if reg.register.alternate => base,
+            _ => reg.name.as_str(),
+        };
+        writeln!(out, "    pub {}: u32,", name).unwrap();
+        next = offset + 4;
+    }
+    writeln!(out, "}}\n").unwrap();
+    Ok(())
+}
+
+/// Returns the name of the register struct of a module, such as
+/// `OtgFsGlobalReg` for `OTG_FS_GLOBAL`.
+fn struct_name(module: &str) -> String {
+    let mut name = String::new();
+    for part in module.split('_') {
+        let mut chars = part.chars();
+        if let Some(c) = chars.next() {
+            name.push(c.to_ascii_uppercase());
+            name.extend(chars.map(|c| c.to_ascii_lowercase()));
+        }
+    }
+    name + "Reg"
+}
+
+/// Returns `name` as a Rust identifier, adding `_` to keywords.
+fn ident(name: &str) -> String {
+    const KEYWORDS: &[&str] = &[
+        "as", "break", "const", "continue", "crate", "else", "enum", "extern", "false", "fn",
+        "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref",
+        "return", "self", "static", "struct", "super", "trait", "true", "type", "unsafe", "use",
+        "where", "while", "async", "await", "dyn",
+    ];
+    if KEYWORDS.contains(&name) {
+        format!("{}_", name)
+    } else {
+        name.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_names() {
+        assert_eq!(struct_name("OTG_FS_GLOBAL"), "OtgFsGlobalReg");
+        assert_eq!(struct_name("SPI1"), "Spi1Reg");
+        assert_eq!(struct_name("DBGMCU"), "DbgmcuReg");
+        assert_eq!(ident("type"), "type_");
+        assert_eq!(ident("or"), "or");
+    }
+}
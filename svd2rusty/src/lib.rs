@@ -0,0 +1,29 @@
+//! # svd2rusty
+//!
+//! Generates the register definitions of the `hal` crate, such as
+//! `hal/src/svd_stm32f405.rs`, from the CMSIS-SVD file of a chip and an
+//! allow-list of the peripherals to include.
+//!
+//! ## Modules
+//!
+//! - `svd`: Reads the peripherals, registers and fields of an SVD file.
+//! - `allow`: Reads the allow-list.
+//! - `emit`: Writes the Rust source.
+//!
+//! ## Example
+//!
+//! ```sh
+//! cd svd2rusty
+//! cargo run -- svd/STM32F405.svd stm32f405.allow ../hal/src/svd_stm32f405.rs
+//! ```
+
+pub mod allow;
+pub mod emit;
+pub mod svd;
+
+/// Returns the Rust source for the text of an SVD file and an allow-list.
+pub fn generate(svd_text: &str, allow_text: &str) -> Result<String, String> {
+    let device = svd::parse(svd_text)?;
+    let allow = allow::parse(allow_text).map_err(|e| format!("allow-list: {}", e))?;
+    emit::generate(&device, &allow)
+}
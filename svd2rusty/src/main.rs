@@ -0,0 +1,30 @@
+use std::env;
+use std::fs;
+use std::process::exit;
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    if args.len() < 3 || args.len() > 4 {
+        eprintln!("usage: svd2rusty <device.svd> <allow-list> [output.rs]");
+        exit(2);
+    }
+
+    let read = |path: &str| {
+        fs::read_to_string(path).unwrap_or_else(|e| {
+            eprintln!("svd2rusty: {}: {}", path, e);
+            exit(1);
+        })
+    };
+    let source = svd2rusty::generate(&read(&args[1]), &read(&args[2])).unwrap_or_else(|e| {
+        eprintln!("svd2rusty: {}", e);
+        exit(1);
+    });
+
+    match args.get(3) {
+        Some(path) => fs::write(path, source).unwrap_or_else(|e| {
+            eprintln!("svd2rusty: {}: {}", path, e);
+            exit(1);
+        }),
+        None => print!("{}", source),
+    }
+}
@@ -0,0 +1,340 @@
+//! # SVD Module
+//!
+//! This module reads the peripherals, registers and fields of a device from
+//! a CMSIS-SVD file, as published by the chip vendor.
+//!
+//! Derived peripherals are kept as a reference to the peripheral they are
+//! derived from, and register arrays (`dim`) are expanded into one register
+//! per element. Clusters are not used by the STM32 files and are skipped.
+//!
+//! ## Structures
+//!
+//! - `Device`: The peripherals of a device in file order.
+//! - `Peripheral`: A peripheral with its base address and registers.
+//! - `Register`: A register with its offset and fields.
+//! - `Field`: A field of a register.
+//!
+//! ## Enums
+//!
+//! - `Access`: How software can access a field.
+//!
+//! ## Functions
+//!
+//! - `parse`: Reads a device from the text of an SVD file.
+
+use roxmltree::Node;
+
+#[derive(Debug)]
+pub struct Device {
+    pub peripherals: Vec<Peripheral>,
+}
+
+#[derive(Debug)]
+pub struct Peripheral {
+    pub name: String,
+    /// Name of the peripheral whose registers this one shares.
+    pub derived_from: Option<String>,
+    pub base_address: u32,
+    pub registers: Vec<Register>,
+}
+
+#[derive(Debug)]
+pub struct Register {
+    pub name: String,
+    /// Byte offset from the base address of the peripheral.
+    pub offset: u32,
+    /// True if another register has the same offset, such as the input and
+    /// output views of a timer capture/compare mode register.
+    pub alternate: bool,
+    pub fields: Vec<Field>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Field {
+    pub name: String,
+    pub offset: u8,
+    pub width: u8,
+    pub access: Access,
+}
+
+/// Access of a field, named as the access types of `hal::cpu`.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Access {
+    ReadWrite,
+    ReadOnly,
+    WriteOnly,
+    WriteOneToClear,
+    WriteZeroToClear,
+}
+
+impl Access {
+    pub fn from_name(name: &str) -> Option<Access> {
+        match name {
+            "ReadWrite" => Some(Access::ReadWrite),
+            "ReadOnly" => Some(Access::ReadOnly),
+            "WriteOnly" => Some(Access::WriteOnly),
+            "WriteOneToClear" => Some(Access::WriteOneToClear),
+            "WriteZeroToClear" => Some(Access::WriteZeroToClear),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Access::ReadWrite => "ReadWrite",
+            Access::ReadOnly => "ReadOnly",
+            Access::WriteOnly => "WriteOnly",
+            Access::WriteOneToClear => "WriteOneToClear",
+            Access::WriteZeroToClear => "WriteZeroToClear",
+        }
+    }
+
+    /// Maps the `access` and `modifiedWriteValues` elements of the SVD.
+    fn from_svd(access: &str, modified: Option<&str>) -> Access {
+        match modified {
+            Some("oneToClear") => return Access::WriteOneToClear,
+            Some("zeroToClear") => return Access::WriteZeroToClear,
+            _ => {}
+        }
+        match access {
+            "read-only" => Access::ReadOnly,
+            "write-only" | "writeOnce" => Access::WriteOnly,
+            _ => Access::ReadWrite,
+        }
+    }
+}
+
+impl Device {
+    pub fn peripheral(&self, name: &str) -> Option<&Peripheral> {
+        self.peripherals.iter().find(|p| p.name == name)
+    }
+
+    /// Returns the registers of `peripheral`, following `derivedFrom`.
+    pub fn registers<'a>(&'a self, peripheral: &'a Peripheral) -> &'a [Register] {
+        let mut p = peripheral;
+        for _ in 0..self.peripherals.len() {
+            match &p.derived_from {
+                Some(base) if p.registers.is_empty() => match self.peripheral(base) {
+                    Some(base) => p = base,
+                    None => break,
+                },
+                _ => break,
+            }
+        }
+        &p.registers
+    }
+}
+
+/// Reads a device from the text of an SVD file.
+pub fn parse(text: &str) -> Result<Device, String> {
+    let doc = roxmltree::Document::parse(text).map_err(|e| format!("bad XML: {}", e))?;
+    let device = doc.root_element();
+    let access = child_text(device, "access").unwrap_or("read-write");
+
+    let mut peripherals = Vec::new();
+    if let Some(list) = child(device, "peripherals") {
+        for node in list.children().filter(|n| n.has_tag_name("peripheral")) {
+            peripherals.push(parse_peripheral(node, access)?);
+        }
+    }
+    Ok(Device { peripherals })
+}
+
+fn parse_peripheral(node: Node, access: &str) -> Result<Peripheral, String> {
+    let name = child_text(node, "name").ok_or("peripheral without a name")?;
+    let base =
+        child_text(node, "baseAddress").ok_or_else(|| format!("{}: no base address", name))?;
+    let access = child_text(node, "access").unwrap_or(access);
+
+    let mut registers = Vec::new();
+    if let Some(list) = child(node, "registers") {
+        for reg in list.children().filter(|n| n.is_element()) {
+            if reg.has_tag_name("register") {
+                parse_register(reg, access, &mut registers)
+                    .map_err(|e| format!("{}: {}", name, e))?;
+            } else if reg.has_tag_name("cluster") {
+                eprintln!("svd2rusty: {}: skipping cluster", name);
+            }
+        }
+    }
+
+    // the input and output views of a register share an offset
+    let offsets: Vec<u32> = registers.iter().map(|r| r.offset).collect();
+    for reg in registers.iter_mut() {
+        reg.alternate = offsets.iter().filter(|&&o| o == reg.offset).count() > 1;
+    }
+
+    Ok(Peripheral {
+        name: name.to_string(),
+        derived_from: node.attribute("derivedFrom").map(str::to_string),
+        base_address: parse_int(base)?,
+        registers,
+    })
+}
+
+/// Adds the register of `node` to `registers`, or one register per element
+/// of a register array.
+fn parse_register(node: Node, access: &str, registers: &mut Vec<Register>) -> Result<(), String> {
+    let name = child_text(node, "name").ok_or("register without a name")?;
+    let offset = parse_int(child_text(node, "addressOffset").unwrap_or("0"))?;
+    let access = child_text(node, "access").unwrap_or(access);
+
+    let mut fields = Vec::new();
+    if let Some(list) = child(node, "fields") {
+        for field in list.children().filter(|n| n.has_tag_name("field")) {
+            fields.push(parse_field(field, access).map_err(|e| format!("{}: {}", name, e))?);
+        }
+    }
+
+    let Some(dim) = child_text(node, "dim") else {
+        registers.push(Register {
+            name: name.to_lowercase(),
+            offset,
+            alternate: false,
+            fields,
+        });
+        return Ok(());
+    };
+
+    let dim = parse_int(dim)?;
+    let increment = parse_int(child_text(node, "dimIncrement").unwrap_or("4"))?;
+    let indexes = match child_text(node, "dimIndex") {
+        Some(index) => parse_dim_index(index)?,
+        None => (0..dim).map(|i| i.to_string()).collect(),
+    };
+    for (i, index) in indexes.iter().enumerate() {
+        let name = name.replace("[%s]", index).replace("%s", index);
+        registers.push(Register {
+            name: name.to_lowercase(),
+            offset: offset + i as u32 * increment,
+            alternate: false,
+            fields: fields.clone(),
+        });
+    }
+    Ok(())
+}
+
+fn parse_field(node: Node, access: &str) -> Result<Field, String> {
+    let name = child_text(node, "name").ok_or("field without a name")?;
+
+    let (offset, width) = if let Some(offset) = child_text(node, "bitOffset") {
+        let width = child_text(node, "bitWidth").unwrap_or("1");
+        (parse_int(offset)?, parse_int(width)?)
+    } else if let Some(lsb) = child_text(node, "lsb") {
+        let msb = child_text(node, "msb").ok_or_else(|| format!("{}: no msb", name))?;
+        let lsb = parse_int(lsb)?;
+        (lsb, parse_int(msb)? + 1 - lsb)
+    } else if let Some(range) = child_text(node, "bitRange") {
+        // [msb:lsb]
+        let range = range.trim_start_matches('[').trim_end_matches(']');
+        let (msb, lsb) = range
+            .split_once(':')
+            .ok_or_else(|| format!("{}: bad bit range", name))?;
+        let lsb = parse_int(lsb)?;
+        (lsb, parse_int(msb)? + 1 - lsb)
+    } else {
+        return Err(format!("{}: no bit position", name));
+    };
+    if width == 0 || offset + width > 32 {
+        return Err(format!("{}: does not fit in 32 bits", name));
+    }
+
+    let access = child_text(node, "access").unwrap_or(access);
+    Ok(Field {
+        name: name.to_string(),
+        offset: offset as u8,
+        width: width as u8,
+        access: Access::from_svd(access, child_text(node, "modifiedWriteValues")),
+    })
+}
+
+/// Parses a `dimIndex` list, either `A,B,C` or a range such as `0-3`.
+fn parse_dim_index(index: &str) -> Result<Vec<String>, String> {
+    if let Some((first, last)) = index.split_once('-') {
+        let (first, last) = (parse_int(first)?, parse_int(last)?);
+        return Ok((first..=last).map(|i| i.to_string()).collect());
+    }
+    Ok(index.split(',').map(|s| s.trim().to_string()).collect())
+}
+
+/// Parses a decimal or `0x` hexadecimal number.
+pub fn parse_int(text: &str) -> Result<u32, String> {
+    let text = text.trim();
+    let result = match text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+        Some(hex) => u32::from_str_radix(hex, 16),
+        None => text.parse(),
+    };
+    result.map_err(|_| format!("bad number: {}", text))
+}
+
+fn child<'a, 'input>(node: Node<'a, 'input>, tag: &str) -> Option<Node<'a, 'input>> {
+    node.children().find(|n| n.has_tag_name(tag))
+}
+
+fn child_text<'a>(node: Node<'a, '_>, tag: &str) -> Option<&'a str> {
+    child(node, tag).and_then(|n| n.text()).map(str::trim)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_int() {
+        assert_eq!(parse_int("0x40023800"), Ok(0x4002_3800));
+        assert_eq!(parse_int(" 32 "), Ok(32));
+        assert!(parse_int("0xZZ").is_err());
+    }
+
+    #[test]
+    fn test_field_positions() {
+        let svd = r#"<device><peripherals><peripheral>
+            <name>P</name><baseAddress>0x1000</baseAddress>
+            <registers><register>
+              <name>CR</name><addressOffset>0x4</addressOffset>
+              <fields>
+                <field><name>A</name><bitOffset>3</bitOffset><bitWidth>2</bitWidth></field>
+                <field><name>B</name><lsb>8</lsb><msb>15</msb><access>read-only</access></field>
+                <field><name>C</name><bitRange>[31:31]</bitRange>
+                  <modifiedWriteValues>oneToClear</modifiedWriteValues></field>
+              </fields>
+            </register></registers>
+        </peripheral></peripherals></device>"#;
+        let device = parse(svd).unwrap();
+        let reg = &device.peripherals[0].registers[0];
+        assert_eq!(reg.name, "cr");
+        assert_eq!(reg.offset, 4);
+
+        let f: Vec<_> = reg
+            .fields
+            .iter()
+            .map(|f| (f.name.as_str(), f.offset, f.width, f.access))
+            .collect();
+        assert_eq!(
+            f,
+            [
+                ("A", 3, 2, Access::ReadWrite),
+                ("B", 8, 8, Access::ReadOnly),
+                ("C", 31, 1, Access::WriteOneToClear)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_register_array() {
+        let svd = r#"<device><peripherals><peripheral>
+            <name>P</name><baseAddress>0</baseAddress>
+            <registers><register>
+              <name>BKP%sR</name><addressOffset>0x50</addressOffset>
+              <dim>3</dim><dimIncrement>4</dimIncrement>
+            </register></registers>
+        </peripheral></peripherals></device>"#;
+        let device = parse(svd).unwrap();
+        let regs: Vec<_> = device.peripherals[0]
+            .registers
+            .iter()
+            .map(|r| (r.name.as_str(), r.offset))
+            .collect();
+        assert_eq!(regs, [("bkp0r", 0x50), ("bkp1r", 0x54), ("bkp2r", 0x58)]);
+    }
+}
@@ -0,0 +1,98 @@
+// DO NOT EDIT. This was generated by svd2rusty
+//! This module provides definitions for various hardware registers and their fields.
+
+#![allow(unused)]
+#![allow(non_snake_case)]
+#![allow(non_upper_case_globals)]
+#![allow(non_camel_case_types)]
+
+pub mod RCC {
+    pub mod cr {
+        pub const PLLRDY: u8 = 25;
+        pub const PLLON: u8 = 24;
+        pub const HSITRIM: u8 = 3;
+        crate::cpu::field!(PLLRDY, 1, ReadOnly);
+        crate::cpu::field!(PLLON, 1, ReadWrite);
+        crate::cpu::field!(HSITRIM, 5, ReadWrite);
+    }
+    pub mod pllcfgr {
+        pub const PLLSRC: u8 = 22;
+        pub const PLLM2: u8 = 2;
+        pub const PLLM1: u8 = 1;
+        pub const PLLM0: u8 = 0;
+        crate::cpu::field!(PLLSRC, 1, ReadWrite);
+        crate::cpu::field!(PLLM = PLLM0, 3, ReadWrite);
+    }
+    pub mod cir {
+        pub const CSSC: u8 = 23;
+        pub const CSSF: u8 = 7;
+        crate::cpu::field!(CSSC, 1, WriteOnly);
+        crate::cpu::field!(CSSF, 1, ReadOnly);
+    }
+}
+
+pub mod TIM_GEN {
+    pub mod sr {
+        pub const CC1IF: u8 = 1;
+        pub const UIF: u8 = 0;
+        crate::cpu::field!(CC1IF, 1, WriteZeroToClear);
+        crate::cpu::field!(UIF, 1, WriteZeroToClear);
+    }
+    pub mod ccmr1_output {
+        pub const OC1M: u8 = 4;
+        crate::cpu::field!(OC1M, 3, ReadWrite);
+    }
+    pub mod ccmr1_input {
+        pub const IC1F: u8 = 4;
+        crate::cpu::field!(IC1F, 4, ReadWrite);
+    }
+    pub mod ccr1 {}
+    pub mod ccr2 {}
+}
+
+pub mod OTG_FS_DEVICE {
+    pub mod dcfg {
+        pub const DAD: u8 = 4;
+        crate::cpu::field!(DAD, 7, ReadWrite);
+    }
+    pub mod diepint0 {
+        pub const XFRC: u8 = 0;
+        crate::cpu::field!(XFRC, 1, WriteOneToClear);
+    }
+}
+
+#[repr(C)]
+pub struct RccReg {
+    pub cr: u32,
+    pub pllcfgr: u32,
+    reserved1: u32,
+    pub cir: u32,
+}
+
+#[repr(C)]
+pub struct TimGenReg {
+    reserved1: [u32; 4],
+    pub sr: u32,
+    reserved2: u32,
+    pub ccmr1: u32,
+    reserved3: [u32; 6],
+    pub ccr1: u32,
+    pub ccr2: u32,
+}
+
+#[repr(C)]
+pub struct OtgFsDeviceReg {
+    pub dcfg: u32,
+    reserved1: [u32; 65],
+    pub diepint0: u32,
+}
+
+// Skipped peripheral: RNG
+
+pub const RCC: *mut RccReg = 0x40023800 as *mut RccReg;
+
+pub const TIM2: *mut TimGenReg = 0x40000000 as *mut TimGenReg;
+
+pub const TIM3: *mut TimGenReg = 0x40000400 as *mut TimGenReg;
+
+pub const OTG_FS_DEVICE: *mut OtgFsDeviceReg = 0x50000800 as *mut OtgFsDeviceReg;
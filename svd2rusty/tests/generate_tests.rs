@@ -0,0 +1,40 @@
+#[cfg(test)]
+mod tests {
+    const SVD: &str = include_str!("fixture/device.svd");
+    const ALLOW: &str = include_str!("fixture/device.allow");
+
+    #[test]
+    fn test_generate() {
+        let source = svd2rusty::generate(SVD, ALLOW).unwrap();
+
+        // regenerating gives the checked in output byte for byte
+        assert_eq!(source, include_str!("fixture/device.rs"));
+
+        // derived peripherals share the struct of their base
+        assert!(source.contains("pub const TIM3: *mut TimGenReg = 0x40000400"));
+        assert!(source.contains("// Skipped peripheral: RNG"));
+        assert!(!source.contains("ahb1rstr"));
+    }
+
+    #[test]
+    fn test_chip_allow_lists() {
+        for text in [
+            include_str!("../stm32f405.allow"),
+            include_str!("../stm32f0x2.allow"),
+        ] {
+            let allow = svd2rusty::allow::parse(text).unwrap();
+            assert!(allow.module_of("RCC").is_some());
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        let missing = svd2rusty::generate(SVD, "USART = USART1").unwrap_err();
+        assert!(missing.contains("USART1"), "{}", missing);
+
+        let gap = svd2rusty::generate(SVD, "RCC = RCC\nRCC.cr.HSION = merge");
+        assert!(gap.is_err());
+
+        assert!(svd2rusty::generate("<device>", ALLOW).is_err());
+    }
+}
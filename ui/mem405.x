@@ -50,6 +50,12 @@ SECTIONS
       _ebss = .;
    } > RAM
 
+   /* not cleared at reset, used to pass information across a watchdog reset */
+   .noinit (NOLOAD) : ALIGN(4)
+   {
+      *(.noinit .noinit.*);
+   } > RAM
+
    .heap_start :
    {
     _heap_start = .;
@@ -1,6 +1,5 @@
 //! A simple Fibonacci function.
 
-
 extern crate bsp;
 
 use bsp::console::Print;
@@ -25,7 +24,7 @@ pub fn fib_test() {
 
     led::set(Color::Green);
 
-    let duration = end_time.sub(start_time);
+    let duration = end_time - start_time;
     b"  Duration fib(34): ".print_console();
     let duration_ms = (duration.as_u64()) / 1000; // convert to mS
     duration_ms.print_console();
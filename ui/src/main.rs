@@ -3,7 +3,6 @@
 #![no_std]
 #![no_main]
 
-
 extern crate bsp;
 extern crate hal;
 
@@ -34,7 +33,9 @@ pub use msg::Msg;
 pub extern "C" fn main() -> ! {
     my_main();
 
-    loop {}
+    loop {
+        core::hint::spin_loop();
+    }
 }
 
 //#[link_section = ".data"]
@@ -47,7 +48,6 @@ fn alloc_task_data() -> &'static mut tasks::TaskData {
     }
 }
 
-
 const TEST_PRINT_DATA: &[u8; 4] = b"1234";
 
 #[inline(never)]
@@ -71,6 +71,21 @@ fn my_main() {
 
     b"Starting\r\n".print_console();
 
+    let reset_cause = hal::device::take_reset_cause();
+    b"  Reset cause: ".print_console();
+    reset_cause.name().print_console();
+    b"\r\n".print_console();
+
+    b"  Device ID: ".print_console();
+    bsp.info.get_device_id().print_console();
+    b"\r\n".print_console();
+
+    if let Some(name) = tasks::take_watchdog_reset(reset_cause) {
+        b"  Reset by watchdog, task that missed check-in: ".print_console();
+        name.print_console();
+        b"\r\n".print_console();
+    }
+
     // TODO remove - just testing
     if false {
         b"  Pre  DMA\r\n".print_console();
@@ -86,13 +101,14 @@ fn my_main() {
         b"  Post  DMA\r\n".print_console();
     }
 
-
     let (mut sender, receiver): (mpsc::Sender<msg::Msg>, mpsc::Receiver<msg::Msg>) =
         mpsc::channel();
 
+    // count cycles for the sections profiled in the metrics
+    hal::cpu::cycles::enable();
     let mut metrics = metrics::Metrics::new();
 
-    let mut data: &mut tasks::TaskData = alloc_task_data();
+    let data: &mut tasks::TaskData = alloc_task_data();
 
     data.junk_data[0] = 1;
 
@@ -100,11 +116,18 @@ fn my_main() {
     //    text_edit: tasks::text_edit_task::Data::new(),
     //};
 
-    let mut task_mgr = tasks::TaskMgr::new(&mut sender, &mut bsp, &mut data, &mut metrics);
+    let mut task_mgr = tasks::TaskMgr::new(&mut sender, &mut bsp, data, &mut metrics);
 
-    // this is removed for now as using button for mock keyboard
-    //let button_task = tasks::buttons_task::ButtonTask {};
-    //task_mgr.add_task(&button_task);
+    // the systick feature schedules the tasks from SysTick instead of TIM2
+    let tick_source = if cfg!(feature = "systick") {
+        tasks::TickSource::SysTick
+    } else {
+        tasks::TickSource::Tim2
+    };
+    task_mgr.set_tick_source(tick_source);
+
+    let button_task = tasks::buttons_task::ButtonTask {};
+    task_mgr.add_task(&button_task);
 
     let chat_task = tasks::chat_task::ChatTask {};
     task_mgr.add_task(&chat_task);
@@ -149,6 +172,9 @@ fn my_main() {
     #[cfg(feature = "exit")]
     task_mgr.sender.send(Msg::Keyboard { key: '\r' });
 
+    // must be after all tasks are added
+    task_mgr.start_watchdog();
+
     loop {
         task_mgr.run();
         dispatch::process(receiver, &mut task_mgr);
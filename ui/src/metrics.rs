@@ -1,6 +1,13 @@
 //! Metrics module that holds all the metrics for the system.
+//!
+//! Besides the metrics of each task, named sections of code can be profiled
+//! in core clock cycles with `profile`, to find the hot spots inside a task.
 
 use crate::tasks::MAX_TASKS;
+use hal::cpu::cycles;
+
+/// Maximum number of named sections that can be profiled.
+pub const MAX_SECTIONS: usize = 8;
 
 /// Structure to hold all the metrics for the system.
 pub struct Metrics {
@@ -8,6 +15,11 @@ pub struct Metrics {
     pub task_run_count: [u32; MAX_TASKS],
     pub task_max_stack: [u32; MAX_TASKS],
     pub task_max_duration_us: [u32; MAX_TASKS],
+
+    pub section_name: [[u8; 8]; MAX_SECTIONS],
+    pub section_run_count: [u32; MAX_SECTIONS],
+    pub section_total_cycles: [u64; MAX_SECTIONS],
+    pub section_max_cycles: [u32; MAX_SECTIONS],
 }
 
 impl Metrics {
@@ -18,6 +30,38 @@ impl Metrics {
             task_run_count: [0; MAX_TASKS],
             task_max_stack: [0; MAX_TASKS],
             task_max_duration_us: [0; MAX_TASKS],
+            section_name: [[0; 8]; MAX_SECTIONS],
+            section_run_count: [0; MAX_SECTIONS],
+            section_total_cycles: [0; MAX_SECTIONS],
+            section_max_cycles: [0; MAX_SECTIONS],
+        }
+    }
+
+    /// Runs `f` as the section `name` and adds the cycles it took to the
+    /// section. The cycle counter must have been enabled.
+    pub fn profile<R>(&mut self, name: &[u8; 8], f: impl FnOnce() -> R) -> R {
+        let (result, taken) = cycles::measure(f);
+        self.add_section(name, taken);
+        result
+    }
+
+    /// Adds a run of the section `name` that took `cycles`. A new section
+    /// takes the first free slot, and is not counted when all are in use.
+    pub fn add_section(&mut self, name: &[u8; 8], cycles: u32) {
+        for i in 0..MAX_SECTIONS {
+            if self.section_name[i][0] == 0 {
+                self.section_name[i] = *name;
+            }
+            if self.section_name[i] != *name {
+                continue;
+            }
+
+            self.section_run_count[i] += 1;
+            self.section_total_cycles[i] += cycles as u64;
+            if cycles > self.section_max_cycles[i] {
+                self.section_max_cycles[i] = cycles;
+            }
+            return;
         }
     }
 }
@@ -11,6 +11,11 @@
 //! The `XXX_IRQHandler` functions are interrupt handlers that are called
 //! when a specific interrupt is raised.
 //!
+//! The vector table is built from `hal::cpu::Vectors`, naming each
+//! interrupt that has a handler, and all others go to `Default_Handler`.
+//! Drivers can also install a handler at run time with
+//! `hal::cpu::set_handler`, which moves the table to RAM.
+//!
 //! More information about the startup process can be found in
 //! the [Cortex-M4 Technical Reference Manual](https://documentation-service.arm.com/static/5f19da2a20b7cf4bc524d99a).
 //!
@@ -18,9 +23,12 @@
 //! script in the `linker.ld` file.
 //!
 
+use super::stack::STACK_PAINT_BYTE;
 use crate::semihost;
 use core::ptr;
-use super::stack::STACK_PAINT_BYTE;
+
+#[allow(unused_imports)]
+use hal::cpu::{Interrupt, Vectors};
 
 #[allow(unused_imports)]
 use bsp::led;
@@ -90,6 +98,7 @@ pub extern "C" fn Default_Handler() {
     loop {
         #[cfg(feature = "exit")]
         hal::semihost::exit(0);
+        core::hint::spin_loop();
     }
 }
 
@@ -101,63 +110,67 @@ pub extern "C" fn Dma_Uart1_Handler() {
     hal::uart::dma_uart1_irq();
 }
 
+#[cfg(target_arch = "arm")]
+#[inline(never)]
 #[no_mangle]
-pub extern "C" fn Default_HandlerA() {
-    Default_Handler();
+pub extern "C" fn Dma_Spi1_Handler() {
+    hal::spi::handle_dma_irq(hal::spi::Bus::Spi1);
 }
 
+#[cfg(target_arch = "arm")]
+#[inline(never)]
 #[no_mangle]
-pub extern "C" fn Default_HandlerB() {
-    Default_Handler();
+pub extern "C" fn Dma_Spi2_Handler() {
+    hal::spi::handle_dma_irq(hal::spi::Bus::Spi2);
 }
 
+#[cfg(target_arch = "arm")]
+#[inline(never)]
 #[no_mangle]
-pub extern "C" fn Default_HandlerC() {
-    Default_Handler();
-}
-#[no_mangle]
-pub extern "C" fn Default_HandlerD() {
-    Default_Handler();
-}
-#[no_mangle]
-pub extern "C" fn Default_HandlerE() {
-    Default_Handler();
-}
-#[no_mangle]
-pub extern "C" fn Default_HandlerF() {
-    Default_Handler();
-}
-#[no_mangle]
-pub extern "C" fn Default_HandlerG() {
-    Default_Handler();
-}
-#[no_mangle]
-pub extern "C" fn Default_HandlerH() {
-    Default_Handler();
+pub extern "C" fn Dma_Spi3_Handler() {
+    hal::spi::handle_dma_irq(hal::spi::Bus::Spi3);
 }
+
+#[cfg(target_arch = "arm")]
+#[inline(never)]
 #[no_mangle]
-pub extern "C" fn Default_Handler1() {
-    Default_Handler();
+pub extern "C" fn Exti_Handler() {
+    hal::gpio::handle_exti_irq();
 }
+
+#[cfg(target_arch = "arm")]
+#[inline(never)]
 #[no_mangle]
-pub extern "C" fn Default_Handler2() {
-    Default_Handler();
+pub extern "C" fn Rtc_Handler() {
+    hal::rtc::handle_irq();
 }
+
+#[cfg(target_arch = "arm")]
+#[inline(never)]
 #[no_mangle]
-pub extern "C" fn Default_Handler3() {
-    Default_Handler();
+pub extern "C" fn Nmi_Handler() {
+    hal::clock::handle_css_nmi();
 }
+
+#[cfg(target_arch = "arm")]
+#[inline(never)]
 #[no_mangle]
-pub extern "C" fn Default_Handler4() {
-    Default_Handler();
+pub extern "C" fn SysTick_Handler() {
+    hal::delay::handle_systick_irq();
 }
+
+#[cfg(target_arch = "arm")]
+#[inline(never)]
 #[no_mangle]
-pub extern "C" fn Default_Handler5() {
-    Default_Handler();
+pub extern "C" fn Usart1_Handler() {
+    hal::uart::handle_usart1_irq();
 }
+
+#[cfg(target_arch = "arm")]
+#[inline(never)]
 #[no_mangle]
-pub extern "C" fn Default_Handler6() {
-    Default_Handler();
+pub extern "C" fn Usb_Handler() {
+    hal::usb::handle_irq();
 }
 
 #[no_mangle]
@@ -170,12 +183,6 @@ pub extern "C" fn TIM2_IRQHandler() {
     hal::timer::handle_tim2_irq();
 }
 
-#[allow(dead_code)]
-pub union IrqVector {
-    not_used: u32,
-    handler: unsafe extern "C" fn(),
-}
-
 #[cfg(target_arch = "arm")]
 #[link_section = ".vector_table.reset_vector"]
 #[no_mangle]
@@ -184,282 +191,24 @@ pub static Reset_Vector: extern "C" fn() -> ! = Reset_Handler;
 #[cfg(target_arch = "arm")]
 #[link_section = ".vector_table.exceptions"]
 #[no_mangle]
-pub static Exceptions: [IrqVector; 14 + 82] = [
-    IrqVector {
-        handler: Default_HandlerA,
-    }, // NMI
-    IrqVector {
-        handler: Default_HandlerB,
-    }, // hard fault
-    IrqVector {
-        handler: Default_HandlerC,
-    }, // mem manager
-    IrqVector {
-        handler: Default_HandlerD,
-    }, // bus fault
-    IrqVector {
-        handler: Default_HandlerE,
-    }, // usage
-    IrqVector { not_used: 0 },
-    IrqVector { not_used: 0 },
-    IrqVector { not_used: 0 },
-    IrqVector { not_used: 0 },
-    IrqVector {
-        handler: Default_HandlerF,
-    }, // SVC
-    IrqVector { not_used: 0 }, // ebug mon
-    IrqVector { not_used: 0 },
-    IrqVector {
-        handler: Default_HandlerG,
-    }, // Pend  SV
-    IrqVector {
-        handler: Default_HandlerH,
-    }, // Sys Timer
-    IrqVector {
-        handler: Default_Handler1,
-    }, // WWDG
-    IrqVector {
-        handler: Default_Handler1,
-    }, // PVD
-    IrqVector {
-        handler: Default_Handler,
-    }, // TAMP_STAMP
-    IrqVector {
-        handler: Default_Handler,
-    }, // RTC_WKUP
-    IrqVector {
-        handler: Default_Handler,
-    }, // FLASH
-    IrqVector {
-        handler: Default_Handler,
-    }, // RCC
-    IrqVector {
-        handler: Default_Handler,
-    }, // EXTI0
-    IrqVector {
-        handler: Default_Handler,
-    }, // EXTI1
-    IrqVector {
-        handler: Default_Handler,
-    }, // EXTI2
-    IrqVector {
-        handler: Default_Handler,
-    }, // EXTI3
-    IrqVector {
-        handler: Default_Handler,
-    }, // EXTI4
-    IrqVector {
-        handler: Default_Handler,
-    }, // DMA1_Stream0
-    IrqVector {
-        handler: Default_Handler,
-    }, // DMA1_Stream1
-    IrqVector {
-        handler: Default_Handler,
-    }, // DMA1_Stream2
-    IrqVector {
-        handler: Default_Handler,
-    }, // DMA1_Stream3
-    IrqVector {
-        handler: Default_Handler,
-    }, // DMA1_Stream4
-    IrqVector {
-        handler: Default_Handler,
-    }, // DMA1_Stream5
-    IrqVector {
-        handler: Default_Handler,
-    }, // DMA1_Stream6
-    IrqVector {
-        handler: Default_Handler,
-    }, // ADC
-    IrqVector {
-        handler: Default_Handler,
-    }, // CAN1_TX
-    IrqVector {
-        handler: Default_Handler,
-    }, // CAN1_RX0
-    IrqVector {
-        handler: Default_Handler,
-    }, // CAN1_RX1
-    IrqVector {
-        handler: Default_Handler,
-    }, // CAN1_SCE
-    IrqVector {
-        handler: Default_Handler,
-    }, // EXTI9_5
-    IrqVector {
-        handler: Default_Handler1,
-    }, // TIM1_BRK_TIM9
-    IrqVector {
-        handler: TIM1_UP_TIM10_IRQHandler,
-    }, // TIM1_UP_TIM10
-    IrqVector {
-        handler: Default_Handler2,
-    }, // TIM1_TRG_COM_TIM11
-    IrqVector {
-        handler: Default_Handler3,
-    }, // TIM1_CC
-    IrqVector {
-        handler: TIM2_IRQHandler,
-    }, // TIM2
-    IrqVector {
-        handler: Default_Handler,
-    }, // TIM3
-    IrqVector {
-        handler: Default_Handler,
-    }, // TIM4
-    IrqVector {
-        handler: Default_Handler,
-    }, // I2C1_EV
-    IrqVector {
-        handler: Default_Handler,
-    }, // I2C1_ER
-    IrqVector {
-        handler: Default_Handler,
-    }, // I2C2_EV
-    IrqVector {
-        handler: Default_Handler,
-    }, // I2C2_ER
-    IrqVector {
-        handler: Default_Handler,
-    }, // SPI1
-    IrqVector {
-        handler: Default_Handler,
-    }, // SPI2
-    IrqVector {
-        handler: Default_Handler,
-    }, // USART1
-    IrqVector {
-        handler: Default_Handler,
-    }, // USART2
-    IrqVector {
-        handler: Default_Handler,
-    }, // USART3
-    IrqVector {
-        handler: Default_Handler,
-    }, // EXTI15_10
-    IrqVector {
-        handler: Default_Handler,
-    }, // RTC_Alarm
-    IrqVector {
-        handler: Default_Handler,
-    }, // OTG_FS_WKUP
-    IrqVector {
-        handler: Default_Handler,
-    }, // TIM8_BRK_TIM12
-    IrqVector {
-        handler: Default_Handler,
-    }, // TIM8_UP_TIM13
-    IrqVector {
-        handler: Default_Handler,
-    }, // TIM8_TRG_COM_TIM14
-    IrqVector {
-        handler: Default_Handler,
-    }, // TIM8_CC
-    IrqVector {
-        handler: Default_Handler,
-    }, // DMA1_Stream7
-    IrqVector {
-        handler: Default_Handler,
-    }, // FSMC
-    IrqVector {
-        handler: Default_Handler,
-    }, // SDIO
-    IrqVector {
-        handler: Default_Handler,
-    }, // TIM5
-    IrqVector {
-        handler: Default_Handler,
-    }, // SPI3
-    IrqVector {
-        handler: Default_Handler,
-    }, // UART4
-    IrqVector {
-        handler: Default_Handler,
-    }, // UART5
-    IrqVector {
-        handler: Default_Handler,
-    }, // TIM6_DAC
-    IrqVector {
-        handler: Default_Handler,
-    }, // TIM7
-    IrqVector {
-        handler: Default_Handler,
-    }, // DMA2_Stream0
-    IrqVector {
-        handler: Default_Handler,
-    }, // DMA2_Stream1
-    IrqVector {
-        handler: Default_Handler,
-    }, // DMA2_Stream2
-    IrqVector {
-        handler: Default_Handler,
-    }, // DMA2_Stream3
-    IrqVector {
-        handler: Default_Handler,
-    }, // DMA2_Stream4
-    IrqVector {
-        handler: Default_Handler,
-    }, // ETH
-    IrqVector {
-        handler: Default_Handler,
-    }, // ETH_WKUP
-    IrqVector {
-        handler: Default_Handler,
-    }, // CAN2_TX
-    IrqVector {
-        handler: Default_Handler,
-    }, // CAN2_RX0
-    IrqVector {
-        handler: Default_Handler,
-    }, // CAN2_RX1
-    IrqVector {
-        handler: Default_Handler,
-    }, // CAN2_SCE
-    IrqVector {
-        handler: Default_Handler,
-    }, // OTG_FS
-    IrqVector {
-        handler: Default_Handler,
-    }, // DMA2_Stream5
-    IrqVector {
-        handler: Default_Handler,
-    }, // DMA2_Stream6
-    IrqVector {
-        handler: Dma_Uart1_Handler,
-    }, // DMA2_Stream7
-    IrqVector {
-        handler: Default_Handler,
-    }, // USART6
-    IrqVector {
-        handler: Default_Handler,
-    }, // I2C3_EV
-    IrqVector {
-        handler: Default_Handler,
-    }, // I2C3_ER
-    IrqVector {
-        handler: Default_Handler,
-    }, // OTG_HS_EP1_OUT
-    IrqVector {
-        handler: Default_Handler,
-    }, // OTG_HS_EP1_IN
-    IrqVector {
-        handler: Default_Handler,
-    }, // OTG_HS_WKUP
-    IrqVector {
-        handler: Default_Handler,
-    }, // OTG_HS
-    IrqVector {
-        handler: Default_Handler,
-    }, // DCMI
-    IrqVector {
-        handler: Default_Handler,
-    }, // CRYP
-    IrqVector {
-        handler: Default_Handler,
-    }, // HASH_RNG
-    IrqVector {
-        handler: Default_Handler,
-    }, // FPU
-];
-
+pub static Exceptions: Vectors = Vectors::new(Default_Handler)
+    .nmi(Nmi_Handler)
+    .sys_tick(SysTick_Handler)
+    .irq(Interrupt::RtcWkup, Rtc_Handler)
+    .irq(Interrupt::Exti0, Exti_Handler)
+    .irq(Interrupt::Exti1, Exti_Handler)
+    .irq(Interrupt::Exti2, Exti_Handler)
+    .irq(Interrupt::Exti3, Exti_Handler)
+    .irq(Interrupt::Exti4, Exti_Handler)
+    .irq(Interrupt::Dma1Stream4, Dma_Spi2_Handler)
+    .irq(Interrupt::Dma1Stream5, Dma_Spi3_Handler)
+    .irq(Interrupt::Exti9_5, Exti_Handler)
+    .irq(Interrupt::Tim1UpTim10, TIM1_UP_TIM10_IRQHandler)
+    .irq(Interrupt::Tim2, TIM2_IRQHandler)
+    .irq(Interrupt::Usart1, Usart1_Handler)
+    .irq(Interrupt::Exti15_10, Exti_Handler)
+    .irq(Interrupt::RtcAlarm, Rtc_Handler)
+    .irq(Interrupt::Dma2Stream3, Dma_Spi1_Handler)
+    // OTG_FS (IRQ 67) is the interrupt usb::init enables, not OTG_FS_WKUP (IRQ 42)
+    .irq(Interrupt::OtgFs, Usb_Handler)
+    .irq(Interrupt::Dma2Stream7, Dma_Uart1_Handler);
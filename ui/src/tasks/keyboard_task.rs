@@ -4,14 +4,15 @@
 //! when the state changes.
 //!
 //!
-//! The task periodically hecks the state of the rows to detect key presses and releases.
-//! When a key press or release is detected, a message is sent to notify other parts of the system.
+//! The task periodically scans the keyboard matrix to detect key presses and releases.
+//! When a key is pressed or repeats, a message is sent with the character it types.
 //!
 
 use super::{Task, TaskData};
 use crate::metrics::Metrics;
 use crate::msg::Msg;
 use crate::tasks::TaskInfo;
+use bsp::keyboard::{Event, BAK, ENT};
 
 /// Structure representing the keyboard task.
 pub struct KeyboardTask {}
@@ -34,31 +35,23 @@ impl Task for KeyboardTask {
         _task_data: &mut TaskData,
         _metrics: &mut Metrics,
     ) {
-        // this uses the PTT button to mock the keyboard
-        let button_moch_keyboard = true;
-        if button_moch_keyboard {
-            let (state, changed) = bsp.buttons.read_ptt();
-            if changed {
-                if state {
-                    let keyboard_msg = Msg::Keyboard { key: 'A' };
-                    sender.send(keyboard_msg);
-                } else {
-                    let keyboard_msg = Msg::Keyboard { key: '\r' };
-                    sender.send(keyboard_msg);
-                }
+        bsp.keyboard.scan();
+        while let Some(event) = bsp.keyboard.next_event() {
+            let key = match event {
+                Event::Down(key) | Event::Repeat(key) => key,
+                Event::Up(_) => continue,
+            };
+            // the modifier and media keys do not type anything
+            if key < b' ' && key != BAK && key != ENT {
+                continue;
             }
-        }
-
-        let key = bsp.keyboard.get_key();
-        if key != 0 {
             let keyboard_msg = Msg::Keyboard { key: key as char };
             sender.send(keyboard_msg);
         }
 
         // check if key from serial port
         if !hal::uart::empty1() {
-            let c: u8;
-            c = hal::uart::read1();
+            let c: u8 = hal::uart::read1();
             let echo: bool = true; // TODO - turn off echo
             if echo && (c != 0) {
                 hal::uart::write1(c);
@@ -3,13 +3,14 @@
 //! and then resetting the metrics.
 
 use super::{Task, TaskData};
-use crate::metrics::Metrics;
+use crate::metrics::{Metrics, MAX_SECTIONS};
 use crate::msg::Msg;
 use crate::tasks::TaskInfo;
 
 use crate::tasks::MAX_TASKS;
 use bsp::console::print_pad;
 use bsp::console::Print;
+use hal::cpu::cycles;
 
 /// Structure representing the metrics task.
 pub struct MetricsTask {}
@@ -26,8 +27,9 @@ impl Task for MetricsTask {
     /// Method to execute the metrics task.
     /// Prints and resets the metrics of all tasks.
     ///
-    /// Prints the number of runs, maximum stack usage, and maximum duration of each task.
-    /// Then resets the metrics for all tasks.
+    /// Prints the number of runs, maximum stack usage, and maximum duration of each task,
+    /// and the number of runs, average and maximum time of each profiled section.
+    /// Then resets the metrics for all tasks and sections.
     fn run(
         &self,
         _sender: &mut crate::mpsc::Sender<Msg>,
@@ -62,6 +64,32 @@ impl Task for MetricsTask {
                 metrics.task_max_duration_us[i] = 0;
             }
         }
+
+        for i in 0..MAX_SECTIONS {
+            let runs = metrics.section_run_count[i];
+            if runs == 0 {
+                continue;
+            }
+            let avg_ns = cycles::to_ns((metrics.section_total_cycles[i] / runs as u64) as u32);
+            let max_ns = cycles::to_ns(metrics.section_max_cycles[i]);
+
+            b"Section ".print_console();
+            metrics.section_name[i].print_console();
+            b": ".print_console();
+            print_pad(runs, 6);
+            runs.print_console();
+            b" runs, ".print_console();
+            print_pad(avg_ns as u32, 9);
+            avg_ns.print_console();
+            b" nS avg, ".print_console();
+            print_pad(max_ns as u32, 9);
+            max_ns.print_console();
+            b" nS max\r\n".print_console();
+
+            metrics.section_run_count[i] = 0;
+            metrics.section_total_cycles[i] = 0;
+            metrics.section_max_cycles[i] = 0;
+        }
     }
 
     /// Returns the information about the metrics task.
@@ -18,7 +18,10 @@ use crate::metrics::Metrics;
 use crate::msg::Msg;
 use crate::stack;
 use bsp::console::Print;
-
+use core::ptr;
+use hal::device::ResetCause;
+use hal::timer::{Duration, Instant};
+use hal::watch_dog;
 
 extern crate bsp;
 
@@ -35,6 +38,64 @@ pub struct TaskInfo {
     pub mem_budget_bytes: u32,
 }
 
+impl TaskInfo {
+    /// Longest time the task can go without running before the watchdog
+    /// supervisor treats it as stuck, which is twice the sum of its run
+    /// interval and time budget. This allows the task to be late by one full
+    /// interval plus its time budget, and the other tasks to use their budgets.
+    pub fn check_in_deadline(&self) -> Duration {
+        Duration::from_micros(2 * (self.run_every_us as u64 + self.time_budget_us))
+    }
+}
+
+/// Timeout of the IWDG. Every pass of `TaskMgr::run` feeds it, so this only
+/// needs to be longer than the longest task time budget.
+pub const WATCHDOG_TIMEOUT_MS: u32 = 4_000;
+
+const WATCHDOG_RECORD_MAGIC: u32 = 0x5744_4F47; // "WDOG"
+
+/// Name of the task that is running, or that missed its check-in. Kept in
+/// RAM that is not cleared at reset so it can be reported after the watchdog
+/// resets the board, including when a task hangs and never returns.
+#[repr(C)]
+struct WatchdogRecord {
+    magic: u32,
+    name: [u8; 8],
+}
+
+#[link_section = ".noinit"]
+static mut WATCHDOG_RECORD: WatchdogRecord = WatchdogRecord {
+    magic: 0,
+    name: [0; 8],
+};
+
+fn write_watchdog_record(name: &[u8; 8]) {
+    let record = WatchdogRecord {
+        magic: WATCHDOG_RECORD_MAGIC,
+        name: *name,
+    };
+    unsafe { ptr::write_volatile(ptr::addr_of_mut!(WATCHDOG_RECORD), record) }
+}
+
+fn clear_watchdog_record() {
+    unsafe { ptr::write_volatile(ptr::addr_of_mut!(WATCHDOG_RECORD.magic), 0) };
+}
+
+/// Returns the name of the task that caused the last reset if `cause` is a
+/// watchdog reset, from a task that hung or missed its check-in, and clears
+/// the record.
+pub fn take_watchdog_reset(cause: ResetCause) -> Option<[u8; 8]> {
+    let record = unsafe { ptr::read_volatile(ptr::addr_of!(WATCHDOG_RECORD)) };
+    let by_watchdog = cause == ResetCause::IndependentWatchdog;
+
+    clear_watchdog_record();
+
+    if by_watchdog && record.magic == WATCHDOG_RECORD_MAGIC {
+        return Some(record.name);
+    }
+    None
+}
+
 const JUNK_DATA_SIZE: usize = 0x1; // must be at least 1 byte
 
 pub struct TaskData {
@@ -78,12 +139,34 @@ pub trait Task {
 /// The maximum number of tasks that can be managed by the `TaskMgr`.
 pub const MAX_TASKS: usize = 10;
 
+/// Period of the SysTick tick when it is the tick source, in microseconds.
+pub const SYSTICK_PERIOD_US: u32 = 1_000;
+
+/// The clock the `TaskMgr` schedules and times the tasks with.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum TickSource {
+    /// The microsecond clock of TIM2, `hal::timer::now`.
+    Tim2,
+    /// The SysTick tick every `SYSTICK_PERIOD_US`, `hal::delay::tick_time`.
+    /// Task times are then only measured to a whole tick.
+    SysTick,
+}
+
 /// Structure that manages the execution of tasks.
 pub struct TaskMgr<'a> {
     /// An array of tasks to be managed.
     tasks: [&'a dyn Task; MAX_TASKS],
     /// An array of timestamps indicating the last run time of each task.
     last_run: [hal::timer::MicroSeconds; MAX_TASKS],
+    /// When each task last finished a run, used by the watchdog supervisor.
+    last_check_in: [Instant; MAX_TASKS],
+    /// True once `start_watchdog` has been called.
+    watchdog_running: bool,
+    /// True once a missed check-in has been recorded, so the watchdog is no
+    /// longer fed.
+    watchdog_tripped: bool,
+    /// The clock used to schedule the tasks.
+    tick_source: TickSource,
     /// The number of tasks currently managed.
     num_tasks: usize,
     /// A message sender for inter-task communication.
@@ -110,12 +193,38 @@ impl<'a> TaskMgr<'a> {
         TaskMgr {
             tasks: [&NO_TASK; MAX_TASKS],
             last_run: [hal::timer::MicroSeconds(0); MAX_TASKS],
+            last_check_in: [Instant::from_micros(0); MAX_TASKS],
+            watchdog_running: false,
+            watchdog_tripped: false,
+            tick_source: TickSource::Tim2,
             num_tasks: 0,
             sender: s,
-            bsp: bsp,
-            data: data,
-            metrics: metrics,
+            bsp,
+            data,
+            metrics,
+        }
+    }
+
+    /// Selects the clock used to schedule the tasks, TIM2 by default. Call
+    /// before adding the tasks. Selecting SysTick starts its tick.
+    pub fn set_tick_source(&mut self, source: TickSource) {
+        if source == TickSource::SysTick {
+            hal::delay::start_tick(SYSTICK_PERIOD_US);
         }
+        self.tick_source = source;
+    }
+
+    /// Returns the time from the tick source.
+    fn now(&self) -> Instant {
+        match self.tick_source {
+            TickSource::Tim2 => hal::timer::now(),
+            TickSource::SysTick => hal::delay::tick_time(),
+        }
+    }
+
+    /// Returns the time from the tick source, in microseconds.
+    fn current_time(&self) -> hal::timer::MicroSeconds {
+        hal::timer::MicroSeconds(self.now().as_micros())
     }
 
     /// Adds a task to the `TaskMgr`.
@@ -124,9 +233,46 @@ impl<'a> TaskMgr<'a> {
             panic!("Too many tasks");
         }
         self.tasks[self.num_tasks] = task;
+        self.last_check_in[self.num_tasks] = self.now();
         self.num_tasks += 1;
     }
 
+    /// Starts the IWDG. From then on `run` only feeds it while every task
+    /// has checked in within its deadline.
+    pub fn start_watchdog(&mut self) {
+        let now = self.now();
+        for i in 0..self.num_tasks {
+            self.last_check_in[i] = now;
+        }
+        watch_dog::iwdg_start(WATCHDOG_TIMEOUT_MS);
+        self.watchdog_running = true;
+    }
+
+    /// Feeds the watchdog if every task has checked in within its deadline.
+    /// Otherwise records the first late task and lets the watchdog reset the board.
+    fn supervise(&mut self) {
+        if !self.watchdog_running || self.watchdog_tripped {
+            return;
+        }
+
+        let now = self.now();
+        for i in 0..self.num_tasks {
+            let info = self.tasks[i].info();
+            if now - self.last_check_in[i] > info.check_in_deadline() {
+                write_watchdog_record(info.name);
+
+                b"Task missed watchdog check-in: ".print_console();
+                info.name.print_console();
+                b"\r\n".print_console();
+
+                self.watchdog_tripped = true;
+                return;
+            }
+        }
+
+        watch_dog::iwdg_alive();
+    }
+
     /// Runs all the tasks managed by the `TaskMgr`, ensuring
     /// they adhere to their time and memory budgets.
     /// The method also updates the task metrics.
@@ -138,20 +284,32 @@ impl<'a> TaskMgr<'a> {
             let t = self.tasks[i];
             let info = t.info();
 
-            let now = hal::timer::current_time();
+            let now = self.current_time();
 
-            if now.sub(self.last_run[i]).as_u64() < info.run_every_us as u64 {
+            if (now - self.last_run[i]).as_u64() < info.run_every_us as u64 {
                 continue;
             }
 
-            let start_time = hal::timer::current_time();
+            // record the task while it runs, so a task that hangs is
+            // reported after the watchdog resets the board
+            let record = self.watchdog_running && !self.watchdog_tripped;
+            if record {
+                write_watchdog_record(info.name);
+            }
+
+            let start_time = self.current_time();
             t.run(self.sender, self.bsp, self.data, self.metrics);
-            let end_time = hal::timer::current_time();
+            let end_time = self.current_time();
+
+            if record {
+                clear_watchdog_record();
+            }
             let (end_stack_usage, ..) = stack::usage(false);
 
             self.last_run[i] = start_time;
+            self.last_check_in[i] = self.now();
 
-            let duration = end_time.sub(start_time).as_u64();
+            let duration = (end_time - start_time).as_u64();
             if duration > info.time_budget_us {
                 b"Exceeded time budget\r\n".print_console();
 
@@ -193,5 +351,7 @@ impl<'a> TaskMgr<'a> {
                 self.metrics.task_max_duration_us[i] = duration as u32;
             }
         }
+
+        self.supervise();
     }
 }
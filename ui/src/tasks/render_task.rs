@@ -21,7 +21,9 @@ pub struct Data {
     text: [[u8; TEXT_COLS]; TEXT_ROWS],
     dirty: [bool; TEXT_ROWS],
 
-    bitmap: [u16; DISPLAY_WIDTH * DISPLAY_BAND_HEIGHT],
+    // two bitmaps so one can be rendered while the other is sent to the display
+    bitmap: [[u16; DISPLAY_WIDTH * DISPLAY_BAND_HEIGHT]; 2],
+    current_bitmap: usize,
     current_band: usize,
 }
 
@@ -31,7 +33,8 @@ impl Data {
         Data {
             text: [[0; TEXT_COLS]; TEXT_ROWS],
             dirty: [true; TEXT_ROWS],
-            bitmap: [0; (DISPLAY_WIDTH * DISPLAY_BAND_HEIGHT)],
+            bitmap: [[0; (DISPLAY_WIDTH * DISPLAY_BAND_HEIGHT)]; 2],
+            current_bitmap: 0,
             current_band: 0,
         }
     }
@@ -56,7 +59,7 @@ pub fn recv(
 
     match msg {
         Msg::PrintMsg { text } => {
-            assert!(TEXT_ROWS > 3);
+            const { assert!(TEXT_ROWS > 3) };
             // scroll the text up
             for r in 0..TEXT_ROWS - 3 {
                 for c in 0..TEXT_COLS {
@@ -80,7 +83,7 @@ pub fn recv(
             data.dirty[row] = true;
         }
         Msg::PrintInputMsg { text } => {
-            assert!(TEXT_ROWS > 1);
+            const { assert!(TEXT_ROWS > 1) };
             let row = TEXT_ROWS - 1;
             let mut num_cols = text.len();
             if num_cols > TEXT_COLS {
@@ -94,7 +97,7 @@ pub fn recv(
             }
             data.dirty[row] = true;
         }
-        Msg::PrintClearMsg {} => {
+        Msg::PrintClearMsg => {
             for r in 0..TEXT_ROWS - 1 {
                 for c in 0..TEXT_COLS {
                     data.text[r][c] = b' ';
@@ -102,7 +105,7 @@ pub fn recv(
                 data.dirty[r] = true;
             }
         }
-        Msg::PrintClearInputMsg {} => {
+        Msg::PrintClearInputMsg => {
             let r = TEXT_ROWS - 1;
             for c in 0..TEXT_COLS {
                 data.text[r][c] = b' ';
@@ -173,14 +176,14 @@ fn render_glyph(c: u8, x: i32, y: i32, data: &mut Data) {
             let color: u16 = (red << 11) | (green << 5) | blue;
 
             for _ in 0..count {
-                if val < 255 {
-                    if (row >= 0)
-                        && (row < DISPLAY_BAND_HEIGHT as i32)
-                        && (col >= 0)
-                        && (col < DISPLAY_WIDTH as i32)
-                    {
-                        data.bitmap[(row * (DISPLAY_WIDTH as i32) + col) as usize] = color;
-                    }
+                if val < 255
+                    && (row >= 0)
+                    && (row < DISPLAY_BAND_HEIGHT as i32)
+                    && (col >= 0)
+                    && (col < DISPLAY_WIDTH as i32)
+                {
+                    data.bitmap[data.current_bitmap]
+                        [(row * (DISPLAY_WIDTH as i32) + col) as usize] = color;
                 }
                 col += 1;
             }
@@ -198,7 +201,7 @@ impl Task for RenderTask {
         _sender: &mut crate::mpsc::Sender<Msg>,
         bsp: &mut bsp::BSP,
         task_data: &mut TaskData,
-        _metrics: &mut Metrics,
+        metrics: &mut Metrics,
     ) {
         let data = &mut task_data.render;
 
@@ -208,9 +211,13 @@ impl Task for RenderTask {
             data.current_band -= 1;
         }
 
+        // render into the bitmap that is not being sent to the display
+        data.current_bitmap = 1 - data.current_bitmap;
+
         for y in 0..DISPLAY_BAND_HEIGHT {
             for x in 0..DISPLAY_WIDTH {
-                data.bitmap[y * DISPLAY_WIDTH + x] = 0xFFFF; // white
+                data.bitmap[data.current_bitmap][y * DISPLAY_WIDTH + x] = 0xFFFF;
+                // white
             }
         }
 
@@ -226,7 +233,7 @@ impl Task for RenderTask {
         if false {
             for r in 0..5 {
                 for c in 0..10 {
-                    data.text[r][c] = '0' as u8 + (c % 10) as u8;
+                    data.text[r][c] = b'0' + (c % 10) as u8;
                 }
                 data.dirty[r] = true;
             }
@@ -248,7 +255,7 @@ impl Task for RenderTask {
 
                     let glyph = &font::GLYPH_METRICS[index];
 
-                    render_glyph(c as u8, x, y, data);
+                    metrics.profile(b"Glyph___", || render_glyph(c, x, y, data));
 
                     x += glyph.width as i32 + 1;
                 }
@@ -266,13 +273,15 @@ impl Task for RenderTask {
             }
         }
 
-        bsp.display.draw_bitmap(
-            &data.bitmap,
-            0,                                       // x
-            data.current_band * DISPLAY_BAND_HEIGHT, //y
-            DISPLAY_WIDTH,
-            DISPLAY_BAND_HEIGHT,
-        );
+        metrics.profile(b"Draw____", || {
+            bsp.display.draw_bitmap(
+                &data.bitmap[data.current_bitmap],
+                0,                                       // x
+                data.current_band * DISPLAY_BAND_HEIGHT, //y
+                DISPLAY_WIDTH,
+                DISPLAY_BAND_HEIGHT,
+            )
+        });
     }
 
     /// Returns the information about the render task.